@@ -0,0 +1,40 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Build an `INSERT ... ON DUPLICATE KEY UPDATE` without parsing any SQL:
+//!
+//!     cargo run --example build_insert
+
+use sqlparser::ast::*;
+
+fn main() {
+    let counters = ObjectName(vec![Ident::new("counters")]);
+    let column = |name: &str| Expr::Identifier(Ident::new(name));
+    let number = |n: u64| Expr::Value(Value::Number(n.to_string()));
+
+    let insert = InsertBuilder::new(counters)
+        .columns(vec![Ident::new("id"), Ident::new("hits")])
+        .values(vec![number(1), number(1)])
+        .values(vec![number(2), number(1)])
+        .on_duplicate_key_update(
+            Ident::new("hits"),
+            Expr::BinaryOp {
+                left: Box::new(column("hits")),
+                op: BinaryOperator::Plus,
+                right: Box::new(number(1)),
+            },
+        )
+        .build();
+
+    // INSERT INTO counters (id, hits) VALUES (1, 1), (2, 1) ON DUPLICATE KEY UPDATE hits = hits + 1
+    println!("{}", insert);
+}
@@ -0,0 +1,68 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tokenizing a statement with many identifiers, which each go through the
+//! keyword lookup. Also prints the number of allocations per tokenization.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use sqlparser::dialect::MySqlDialect;
+use sqlparser::tokenizer::Tokenizer;
+
+/// The system allocator, counting the allocations
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn wide_insert(c: &mut Criterion) {
+    let dialect = MySqlDialect {};
+    let columns: Vec<String> = (0..10_000).map(|i| format!("column_{}", i)).collect();
+    let values: Vec<String> = (0..10_000).map(|i| i.to_string()).collect();
+    let insert = format!(
+        "INSERT INTO wide ({}) VALUES ({})",
+        columns.join(", "),
+        values.join(", ")
+    );
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let tokens = Tokenizer::new(&dialect, &insert).tokenize().unwrap();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "tokenizing a 10k-column INSERT: {} tokens, {} allocations",
+        tokens.len(),
+        allocations
+    );
+
+    let mut group = c.benchmark_group("sqlparser-rs keyword lookup benchmark");
+    group.bench_function("sqlparser::tokenize_wide_insert", |b| {
+        b.iter(|| Tokenizer::new(&dialect, &insert).tokenize());
+    });
+}
+
+criterion_group!(benches, wide_insert);
+criterion_main!(benches);
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use criterion::{criterion_group, criterion_main, Criterion};
-use sqlparser::dialect::GenericDialect;
+use sqlparser::dialect::{GenericDialect, MySqlDialect};
 use sqlparser::parser::Parser;
 
 fn basic_queries(c: &mut Criterion) {
@@ -39,5 +39,54 @@ fn basic_queries(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, basic_queries);
+/// A multi-row INSERT like the ones mysqldump writes
+fn insert_rows(count: usize) -> String {
+    let rows: Vec<String> = (0..count)
+        .map(|i| {
+            format!(
+                "({}, 'customer {}', 'a note with \\'quotes\\'', {}.{:02}, '2020-01-01 10:00:00', NULL)",
+                i,
+                i,
+                i * 3,
+                i % 100
+            )
+        })
+        .collect();
+    format!(
+        "INSERT INTO `orders` (`id`, `customer`, `note`, `amount`, `created_at`, `deleted_at`) VALUES {}",
+        rows.join(",")
+    )
+}
+
+fn insert_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sqlparser-rs insert batch benchmark");
+    let dialect = MySqlDialect {};
+
+    let insert = insert_rows(10_000);
+    group.bench_function("sqlparser::insert_batch", |b| {
+        b.iter(|| Parser::parse_sql(&dialect, &insert));
+    });
+
+    // about 5 MB, run fewer times
+    group.sample_size(10);
+    let insert = insert_rows(50_000);
+    group.bench_function("sqlparser::insert_batch_50k", |b| {
+        b.iter(|| Parser::parse_sql(&dialect, &insert));
+    });
+}
+
+/// A `WHERE id IN (...)` with many literals, as ORMs write for a batch of
+/// ids
+fn in_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sqlparser-rs in list benchmark");
+    let dialect = MySqlDialect {};
+
+    let ids: Vec<String> = (0..10_000).map(|i| (i * 7).to_string()).collect();
+    let select = format!("SELECT * FROM `orders` WHERE `id` IN ({})", ids.join(", "));
+    group.bench_function("sqlparser::in_list_10k", |b| {
+        b.iter(|| Parser::parse_sql(&dialect, &select));
+    });
+}
+
+criterion_group!(benches, basic_queries, insert_batch, in_list);
 criterion_main!(benches);
@@ -0,0 +1,363 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shortcuts to the fields of a statement and the `SELECT` of a query
+
+use super::*;
+
+/// Defines a view borrowing the fields of each struct variant of
+/// `Statement`, and the `Statement::as_*` and `as_*_mut` methods giving it
+macro_rules! statement_views {
+    ($(
+        $(#[$attr:meta])*
+        $variant:ident => $as_ref:ident, $as_mut:ident, $view:ident, $view_mut:ident {
+            $($field:ident: $ty:ty,)*
+        }
+    )*) => {
+        $(
+            #[doc = concat!("The fields of `Statement::", stringify!($variant), "`")]
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct $view<'a> {
+                $(pub $field: &'a $ty,)*
+            }
+
+            #[doc = concat!(
+                "The fields of `Statement::", stringify!($variant), "`, borrowed mutably"
+            )]
+            #[derive(Debug)]
+            pub struct $view_mut<'a> {
+                $(pub $field: &'a mut $ty,)*
+            }
+        )*
+
+        impl Statement {
+            $(
+                #[doc = concat!("The fields, if this is a `Statement::", stringify!($variant), "`")]
+                $(#[$attr])*
+                pub fn $as_ref(&self) -> Option<$view<'_>> {
+                    match self {
+                        Statement::$variant { $($field,)* } => Some($view { $($field,)* }),
+                        _ => None,
+                    }
+                }
+
+                #[doc = concat!("Like `", stringify!($as_ref), "`, but borrows the fields mutably")]
+                pub fn $as_mut(&mut self) -> Option<$view_mut<'_>> {
+                    match self {
+                        Statement::$variant { $($field,)* } => Some($view_mut { $($field,)* }),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+    };
+}
+
+statement_views! {
+    Explain => as_explain, as_explain_mut, ExplainRef, ExplainMut {
+        analyze: Option<bool>,
+        modifier: Option<ExplainModifier>,
+        format_type: Option<ExplainFormat>,
+        body: ExplainStmt,
+    }
+    ///
+    /// ```
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::parser::Parser;
+    /// let sql = "INSERT INTO t (a, b) VALUES (1, 2)";
+    /// let statements = Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    /// let insert = statements[0].as_insert().unwrap();
+    /// assert_eq!("t", insert.table_name.to_string());
+    /// assert_eq!(2, insert.columns.len());
+    /// ```
+    Insert => as_insert, as_insert_mut, InsertRef, InsertMut {
+        hints: Option<String>,
+        comments: Vec<String>,
+        priority: Option<Priority>,
+        ignore: bool,
+        into: bool,
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        set: bool,
+        source: Query,
+        update: Option<Vec<Assignment>>,
+    }
+    ///
+    /// ```
+    /// # use sqlparser::ast::Priority;
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::parser::Parser;
+    /// let sql = "REPLACE DELAYED t SET a = 1";
+    /// let statements = Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    /// let replace = statements[0].as_replace().unwrap();
+    /// assert_eq!(&Some(Priority::DELAYED), replace.priority);
+    /// assert!(!*replace.into && *replace.set);
+    /// ```
+    Replace => as_replace, as_replace_mut, ReplaceRef, ReplaceMut {
+        hints: Option<String>,
+        comments: Vec<String>,
+        priority: Option<Priority>,
+        into: bool,
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        set: bool,
+        source: Query,
+    }
+    Copy => as_copy, as_copy_mut, CopyRef, CopyMut {
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        values: Vec<Option<String>>,
+    }
+    ///
+    /// ```
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::parser::Parser;
+    /// let sql = "UPDATE t SET a = 1 WHERE b = 2";
+    /// let mut statements = Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    /// let update = statements[0].as_update_mut().unwrap();
+    /// *update.selection = None;
+    /// assert_eq!("UPDATE t SET a = 1", statements[0].to_string());
+    /// ```
+    Update => as_update, as_update_mut, UpdateRef, UpdateMut {
+        hints: Option<String>,
+        comments: Vec<String>,
+        table_name: ObjectName,
+        assignments: Vec<Assignment>,
+        selection: Option<Expr>,
+        limit: Option<Expr>,
+    }
+    ///
+    /// ```
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::parser::Parser;
+    /// let sql = "DELETE FROM t WHERE a = 1";
+    /// let statements = Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    /// let delete = statements[0].as_delete().unwrap();
+    /// assert_eq!("a = 1", delete.selection.as_ref().unwrap().to_string());
+    /// assert!(statements[0].as_update().is_none());
+    /// ```
+    Delete => as_delete, as_delete_mut, DeleteRef, DeleteMut {
+        hints: Option<String>,
+        comments: Vec<String>,
+        table_name: ObjectName,
+        selection: Option<Expr>,
+    }
+    CreateView => as_create_view, as_create_view_mut, CreateViewRef, CreateViewMut {
+        name: ObjectName,
+        columns: Vec<Ident>,
+        query: Query,
+        materialized: bool,
+        with_options: Vec<SqlOption>,
+    }
+    CreateTable => as_create_table, as_create_table_mut, CreateTableRef, CreateTableMut {
+        name: ObjectName,
+        columns: Vec<ColumnDef>,
+        index: Vec<IndexInfo>,
+        constraints: Vec<TableConstraint>,
+        with_options: Vec<SqlOption>,
+        table_options: Vec<TableOptionDef>,
+        partition: Option<PartitionDefinition>,
+        on_duplicate: Option<CreateTableOnDuplicate>,
+        if_not_exists: bool,
+        external: bool,
+        file_format: Option<FileFormat>,
+        location: Option<String>,
+        query: Option<Box<Query>>,
+        without_rowid: bool,
+    }
+    CreateVirtualTable => as_create_virtual_table, as_create_virtual_table_mut,
+        CreateVirtualTableRef, CreateVirtualTableMut {
+        name: ObjectName,
+        if_not_exists: bool,
+        module_name: Ident,
+        module_args: Vec<Ident>,
+    }
+    CreateIndex => as_create_index, as_create_index_mut, CreateIndexRef, CreateIndexMut {
+        name: ObjectName,
+        table_name: ObjectName,
+        columns: Vec<IndexKeyPart>,
+        unique: bool,
+        kind: Option<MysqlIndexStorageType>,
+        using: Option<MysqlIndexType>,
+        options: Vec<IndexOptions>,
+        algorithm: Option<Ident>,
+        lock: Option<Ident>,
+        if_not_exists: bool,
+    }
+    AlterTable => as_alter_table, as_alter_table_mut, AlterTableRef, AlterTableMut {
+        name: ObjectName,
+        operation: Vec<AlterTableOperation>,
+    }
+    Drop => as_drop, as_drop_mut, DropRef, DropMut {
+        object_type: ObjectType,
+        if_exists: bool,
+        names: Vec<ObjectName>,
+        on_info: ObjectName,
+        algorithm: Option<Ident>,
+        lock: Option<Ident>,
+        cascade: bool,
+        restrict: bool,
+    }
+    SetVariable => as_set_variable, as_set_variable_mut, SetVariableRef, SetVariableMut {
+        local: bool,
+        variable: Ident,
+        value: SetVariableValue,
+    }
+    AdminSetVariable => as_admin_set_variable, as_admin_set_variable_mut,
+        AdminSetVariableRef, AdminSetVariableMut {
+        variable: Ident,
+        value: SetVariableValue,
+        selection: Option<Expr>,
+    }
+    ReLoad => as_reload, as_reload_mut, ReloadRef, ReloadMut {
+        variable: Ident,
+        selection: Option<Expr>,
+    }
+    ShowVariable => as_show_variable, as_show_variable_mut, ShowVariableRef, ShowVariableMut {
+        variable: Ident,
+        global: bool,
+        filter: Option<ShowStatementFilter>,
+    }
+    ShowColumns => as_show_columns, as_show_columns_mut, ShowColumnsRef, ShowColumnsMut {
+        extended: bool,
+        full: bool,
+        fields: bool,
+        table_in: bool,
+        table_name: ObjectName,
+        database: Option<Ident>,
+        database_in: bool,
+        filter: Option<ShowStatementFilter>,
+    }
+    ShowObjects => as_show_objects, as_show_objects_mut, ShowObjectsRef, ShowObjectsMut {
+        kind: ShowObjectKind,
+        database: Option<Ident>,
+        filter: Option<ShowStatementFilter>,
+    }
+    ShowCreate => as_show_create, as_show_create_mut, ShowCreateRef, ShowCreateMut {
+        table_name: ObjectName,
+    }
+    StartTransaction => as_start_transaction, as_start_transaction_mut,
+        StartTransactionRef, StartTransactionMut {
+        modes: Vec<TransactionMode>,
+    }
+    SetTransaction => as_set_transaction, as_set_transaction_mut,
+        SetTransactionRef, SetTransactionMut {
+        modes: Vec<TransactionMode>,
+    }
+    Commit => as_commit, as_commit_mut, CommitRef, CommitMut {
+        chain: bool,
+    }
+    Rollback => as_rollback, as_rollback_mut, RollbackRef, RollbackMut {
+        chain: bool,
+    }
+    CreateSchema => as_create_schema, as_create_schema_mut, CreateSchemaRef, CreateSchemaMut {
+        schema_name: ObjectName,
+        if_not_exists: bool,
+    }
+    Assert => as_assert, as_assert_mut, AssertRef, AssertMut {
+        condition: Expr,
+        message: Option<Expr>,
+    }
+    Lock => as_lock, as_lock_mut, LockRef, LockMut {
+        lock_tables: Vec<LockInfo>,
+    }
+    UNLock => as_unlock, as_unlock_mut, UnlockRef, UnlockMut {
+        chain: bool,
+    }
+    Call => as_call, as_call_mut, CallRef, CallMut {
+        name: ObjectName,
+        parameter: Option<Vec<Expr>>,
+    }
+    ChangeDatabase => as_change_database, as_change_database_mut,
+        ChangeDatabaseRef, ChangeDatabaseMut {
+        database: Ident,
+    }
+    Desc => as_desc, as_desc_mut, DescRef, DescMut {
+        table_name: ObjectName,
+    }
+    VersionedComment => as_versioned_comment, as_versioned_comment_mut,
+        VersionedCommentRef, VersionedCommentMut {
+        version: Option<u64>,
+        statement: Statement,
+    }
+    ClientDelimiter => as_client_delimiter, as_client_delimiter_mut,
+        ClientDelimiterRef, ClientDelimiterMut {
+        delimiter: String,
+    }
+}
+
+impl Statement {
+    /// The query, if this is a query statement:
+    ///
+    /// ```
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::parser::Parser;
+    /// let statements = Parser::parse_sql(&MySqlDialect {}, "SELECT a FROM t").unwrap();
+    /// let select = statements[0].as_query().and_then(|query| query.as_select());
+    /// assert_eq!("SELECT a FROM t", select.unwrap().to_string());
+    /// ```
+    pub fn as_query(&self) -> Option<&Query> {
+        match self {
+            Statement::Query(query) => Some(query),
+            _ => None,
+        }
+    }
+
+    /// Like `as_query`, but borrows the query mutably
+    pub fn as_query_mut(&mut self) -> Option<&mut Query> {
+        match self {
+            Statement::Query(query) => Some(query),
+            _ => None,
+        }
+    }
+}
+
+impl Query {
+    /// The `SELECT` of the query, looking through parentheses, or `None`
+    /// for `UNION` and the other set operations and for `VALUES`:
+    ///
+    /// ```
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::parser::Parser;
+    /// let sql = "SELECT a FROM t UNION SELECT b FROM u";
+    /// let statements = Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    /// assert!(statements[0].as_query().unwrap().as_select().is_none());
+    /// ```
+    pub fn as_select(&self) -> Option<&Select> {
+        self.body.as_select()
+    }
+
+    /// Like `as_select`, but borrows the `SELECT` mutably
+    pub fn as_select_mut(&mut self) -> Option<&mut Select> {
+        self.body.as_select_mut()
+    }
+}
+
+impl SetExpr {
+    /// The `SELECT`, looking through parentheses, see `Query::as_select`
+    pub fn as_select(&self) -> Option<&Select> {
+        match self {
+            SetExpr::Select(select) => Some(select),
+            SetExpr::Query(query) => query.as_select(),
+            _ => None,
+        }
+    }
+
+    /// Like `as_select`, but borrows the `SELECT` mutably
+    pub fn as_select_mut(&mut self) -> Option<&mut Select> {
+        match self {
+            SetExpr::Select(select) => Some(select),
+            SetExpr::Query(query) => query.as_select_mut(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,411 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builders for the common statements, which fill in the fields that are
+//! rarely needed. Their fields are private, so that new AST fields only add
+//! builder methods.
+
+use super::*;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// `a AND b`, or just `b` when there's no `a` yet. An `OR` on either side
+/// is put in parentheses, as is an `AND` on the right, so that the result
+/// prints as SQL that parses back to it.
+fn and(left: Option<Expr>, right: Expr) -> Expr {
+    let nested = |expr: Expr, and_too: bool| match expr {
+        Expr::BinaryOp { op: BinaryOperator::Or, .. } => Expr::Nested(Box::new(expr)),
+        Expr::BinaryOp { op: BinaryOperator::And, .. } if and_too => Expr::Nested(Box::new(expr)),
+        expr => expr,
+    };
+    match left {
+        Some(left) => Expr::BinaryOp {
+            left: Box::new(nested(left, false)),
+            op: BinaryOperator::And,
+            right: Box::new(nested(right, true)),
+        },
+        None => right,
+    }
+}
+
+/// Builds a `SELECT` query:
+///
+/// ```
+/// # use sqlparser::ast::*;
+/// let query = SelectBuilder::new()
+///     .column(Expr::Identifier(Ident::new("a")))
+///     .from(ObjectName(vec![Ident::new("t")]))
+///     .filter(Expr::IsNotNull(Box::new(Expr::Identifier(Ident::new("b")))))
+///     .build();
+/// assert_eq!("SELECT a FROM t WHERE b IS NOT NULL", query.to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SelectBuilder {
+    select: Select,
+    order_by: Vec<OrderByExpr>,
+    limit: Option<Expr>,
+    offset: Option<Expr>,
+    for_update: bool,
+}
+
+impl Default for SelectBuilder {
+    fn default() -> Self {
+        SelectBuilder {
+            select: Select {
+                hints: None,
+                comments: vec![],
+                distinct: None,
+                modifiers: vec![],
+                top: None,
+                projection: vec![],
+                wildcard_except: vec![],
+                from: vec![],
+                selection: None,
+                group_by: vec![],
+                having: None,
+            },
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            for_update: false,
+        }
+    }
+}
+
+impl SelectBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `SELECT DISTINCT`
+    pub fn distinct(mut self) -> Self {
+        self.select.distinct = Some(Distinct::Distinct);
+        self
+    }
+
+    /// Select `expr`
+    pub fn column(mut self, expr: Expr) -> Self {
+        self.select.projection.push(SelectItem::UnnamedExpr(expr));
+        self
+    }
+
+    /// Select `expr AS alias`
+    pub fn column_as(mut self, expr: Expr, alias: Ident) -> Self {
+        self.select
+            .projection
+            .push(SelectItem::ExprWithAlias { expr, alias });
+        self
+    }
+
+    /// Select `*`
+    pub fn wildcard(mut self) -> Self {
+        self.select.projection.push(SelectItem::Wildcard);
+        self
+    }
+
+    /// Select `*` without the given columns, which MySQL can't run until a
+    /// rewriter expands the wildcard, see `Select::wildcard_except`
+    pub fn wildcard_except(mut self, except: Vec<Ident>) -> Self {
+        self.select.projection.push(SelectItem::Wildcard);
+        self.select.wildcard_except.extend(except);
+        self
+    }
+
+    /// Select from the table, after the ones already added
+    pub fn from(self, name: ObjectName) -> Self {
+        self.from_relation(TableWithJoins {
+            relation: TableFactor::Table {
+                name,
+                alias: None,
+                force: None,
+                args: vec![],
+                with_hints: vec![],
+            },
+            joins: vec![],
+        })
+    }
+
+    /// Select from a table with joins, a derived table, etc.
+    pub fn from_relation(mut self, relation: TableWithJoins) -> Self {
+        self.select.from.push(relation);
+        self
+    }
+
+    /// Add a `WHERE` condition, which is ANDed with the ones already added
+    pub fn filter(mut self, condition: Expr) -> Self {
+        self.select.selection = Some(and(self.select.selection.take(), condition));
+        self
+    }
+
+    pub fn group_by(mut self, expr: Expr) -> Self {
+        self.select.group_by.push(expr);
+        self
+    }
+
+    /// Add a `HAVING` condition, which is ANDed with the ones already added
+    pub fn having(mut self, condition: Expr) -> Self {
+        self.select.having = Some(and(self.select.having.take(), condition));
+        self
+    }
+
+    /// Order by `expr`, ascending unless `asc` is `Some(false)`
+    pub fn order_by(mut self, expr: Expr, asc: Option<bool>) -> Self {
+        self.order_by.push(OrderByExpr {
+            expr,
+            asc,
+            nulls_first: None,
+        });
+        self
+    }
+
+    pub fn limit(mut self, limit: Expr) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: Expr) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// `FOR UPDATE`
+    pub fn for_update(mut self) -> Self {
+        self.for_update = true;
+        self
+    }
+
+    pub fn build(self) -> Query {
+        Query {
+            ctes: vec![],
+            body: SetExpr::Select(Box::new(self.select)),
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset.map(|value| Offset {
+                value,
+                rows: OffsetRows::None,
+            }),
+            update: self.for_update,
+            fetch: None,
+        }
+    }
+
+    /// The SQL of the query, or an error if it can't be printed as SQL that
+    /// MySQL runs as built, as `Display` prints the columns of
+    /// `wildcard_except` in a comment
+    pub fn try_to_sql(&self) -> Result<String, BuildError> {
+        if !self.select.wildcard_except.is_empty() {
+            return Err(BuildError::WildcardExcept(self.select.wildcard_except.clone()));
+        }
+        Ok(self.clone().build().to_string())
+    }
+}
+
+/// Why a builder can't print its statement as SQL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The columns left out of a wildcard, which MySQL has no syntax for
+    WildcardExcept(Vec<Ident>),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::WildcardExcept(columns) => write!(
+                f,
+                "a wildcard can't leave out {} in MySQL",
+                display_comma_separated(columns)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BuildError {}
+
+/// Builds an `INSERT` statement, from `VALUES` rows or a query:
+///
+/// ```
+/// # use sqlparser::ast::*;
+/// let insert = InsertBuilder::new(ObjectName(vec![Ident::new("t")]))
+///     .columns(vec![Ident::new("a")])
+///     .values(vec![Expr::Value(Value::Number("1".into()))])
+///     .build();
+/// assert_eq!("INSERT INTO t (a) VALUES (1)", insert.to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct InsertBuilder {
+    table_name: ObjectName,
+    priority: Option<Priority>,
+    ignore: bool,
+    columns: Vec<Ident>,
+    rows: Vec<Vec<Expr>>,
+    query: Option<Query>,
+    update: Vec<Assignment>,
+}
+
+impl InsertBuilder {
+    pub fn new(table_name: ObjectName) -> Self {
+        InsertBuilder {
+            table_name,
+            priority: None,
+            ignore: false,
+            columns: vec![],
+            rows: vec![],
+            query: None,
+            update: vec![],
+        }
+    }
+
+    /// `LOW_PRIORITY`, `DELAYED` or `HIGH_PRIORITY`
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// `INSERT IGNORE`
+    pub fn ignore(mut self) -> Self {
+        self.ignore = true;
+        self
+    }
+
+    pub fn columns(mut self, columns: Vec<Ident>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Add a row of `VALUES`
+    pub fn values(mut self, row: Vec<Expr>) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    /// Insert the rows of the query instead of `VALUES`
+    pub fn query(mut self, query: Query) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// Add `column = value` to `ON DUPLICATE KEY UPDATE`
+    pub fn on_duplicate_key_update(mut self, column: Ident, value: Expr) -> Self {
+        self.update.push(Assignment { id: vec![column], value });
+        self
+    }
+
+    pub fn build(self) -> Statement {
+        let rows = self.rows;
+        let source = self.query.unwrap_or_else(|| Query {
+            ctes: vec![],
+            body: SetExpr::Values(Values(rows)),
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            update: false,
+            fetch: None,
+        });
+        Statement::Insert {
+            hints: None,
+            comments: vec![],
+            priority: self.priority,
+            ignore: self.ignore,
+            into: true,
+            table_name: self.table_name,
+            columns: self.columns,
+            set: false,
+            source: Box::new(source),
+            update: if self.update.is_empty() {
+                None
+            } else {
+                Some(self.update)
+            },
+        }
+    }
+}
+
+/// Builds an `UPDATE` statement
+#[derive(Debug, Clone)]
+pub struct UpdateBuilder {
+    table_name: ObjectName,
+    assignments: Vec<Assignment>,
+    selection: Option<Expr>,
+    limit: Option<Expr>,
+}
+
+impl UpdateBuilder {
+    pub fn new(table_name: ObjectName) -> Self {
+        UpdateBuilder {
+            table_name,
+            assignments: vec![],
+            selection: None,
+            limit: None,
+        }
+    }
+
+    /// Add `column = value` to `SET`
+    pub fn set(mut self, column: Ident, value: Expr) -> Self {
+        self.assignments.push(Assignment { id: vec![column], value });
+        self
+    }
+
+    /// Add a `WHERE` condition, which is ANDed with the ones already added
+    pub fn filter(mut self, condition: Expr) -> Self {
+        self.selection = Some(and(self.selection.take(), condition));
+        self
+    }
+
+    pub fn limit(mut self, limit: Expr) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn build(self) -> Statement {
+        Statement::Update {
+            hints: None,
+            comments: vec![],
+            table_name: self.table_name,
+            assignments: self.assignments,
+            selection: self.selection,
+            limit: self.limit,
+        }
+    }
+}
+
+/// Builds a `DELETE` statement
+#[derive(Debug, Clone)]
+pub struct DeleteBuilder {
+    table_name: ObjectName,
+    selection: Option<Expr>,
+}
+
+impl DeleteBuilder {
+    pub fn new(table_name: ObjectName) -> Self {
+        DeleteBuilder {
+            table_name,
+            selection: None,
+        }
+    }
+
+    /// Add a `WHERE` condition, which is ANDed with the ones already added
+    pub fn filter(mut self, condition: Expr) -> Self {
+        self.selection = Some(and(self.selection.take(), condition));
+        self
+    }
+
+    pub fn build(self) -> Statement {
+        Statement::Delete {
+            hints: None,
+            comments: vec![],
+            table_name: self.table_name,
+            selection: self.selection,
+        }
+    }
+}
@@ -13,7 +13,9 @@
 use super::ObjectName;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 /// SQL data types
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -53,8 +55,11 @@ pub enum DataType {
     Date,
     /// Time
     Time,
-    /// Timestamp
-    Timestamp,
+    /// Timestamp with optional fractional seconds precision e.g. TIMESTAMP(3)
+    Timestamp(Option<u64>),
+    /// MySQL date and time with optional fractional seconds precision e.g.
+    /// DATETIME(6)
+    Datetime(Option<u64>),
     /// Interval
     Interval,
     /// Regclass used in postgresql serial
@@ -81,13 +86,10 @@ impl fmt::Display for DataType {
             DataType::Binary(size) => write!(f, "BINARY({})", size),
             DataType::Varbinary(size) => write!(f, "VARBINARY({})", size),
             DataType::Blob(size) => write!(f, "BLOB({})", size),
-            DataType::Decimal(precision, scale) => {
-                if let Some(scale) = scale {
-                    write!(f, "NUMERIC({},{})", precision.unwrap(), scale)
-                } else {
-                    format_type_with_optional_length(f, "NUMERIC", precision)
-                }
-            }
+            DataType::Decimal(precision, scale) => match (precision, scale) {
+                (Some(precision), Some(scale)) => write!(f, "NUMERIC({},{})", precision, scale),
+                _ => format_type_with_optional_length(f, "NUMERIC", precision),
+            },
             DataType::Float(size) => format_type_with_optional_length(f, "FLOAT", size),
             DataType::SmallInt => write!(f, "SMALLINT"),
             DataType::Int => write!(f, "INT"),
@@ -97,7 +99,12 @@ impl fmt::Display for DataType {
             DataType::Boolean => write!(f, "BOOLEAN"),
             DataType::Date => write!(f, "DATE"),
             DataType::Time => write!(f, "TIME"),
-            DataType::Timestamp => write!(f, "TIMESTAMP"),
+            DataType::Timestamp(precision) => {
+                format_type_with_optional_length(f, "TIMESTAMP", precision)
+            }
+            DataType::Datetime(precision) => {
+                format_type_with_optional_length(f, "DATETIME", precision)
+            }
             DataType::Interval => write!(f, "INTERVAL"),
             DataType::Regclass => write!(f, "REGCLASS"),
             DataType::Text => write!(f, "TEXT"),
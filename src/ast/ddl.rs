@@ -12,10 +12,14 @@
 
 //! AST types specific to CREATE/ALTER variants of [Statement]
 //! (commonly referred to as Data Definition Language, or DDL)
-use super::{display_comma_separated, DataType, Expr, Ident, ObjectName};
+use super::{
+    display_comma_separated, display_separated, DataType, Expr, Ident, ObjectName, OrderByExpr,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::ToString, vec::Vec};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -24,10 +28,62 @@ pub struct IndexInfo{
     pub index_type: Option<MysqlIndexStorageType>,
     pub index: IndexDef,
 }
+/// What kind of index an `IndexInfo` is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IndexKind {
+    Primary,
+    Unique,
+    Normal,
+    Fulltext,
+    Spatial,
+    Foreign,
+}
+
+impl IndexInfo {
+    pub fn kind(&self) -> IndexKind {
+        match (&self.index, &self.index_type) {
+            (IndexDef::PrimaryKey(_), _) => IndexKind::Primary,
+            (IndexDef::Unique(_), _) => IndexKind::Unique,
+            (IndexDef::ForeignKey(_), _) => IndexKind::Foreign,
+            (IndexDef::Normal(_), Some(MysqlIndexStorageType::FullText)) => IndexKind::Fulltext,
+            (IndexDef::Normal(_), Some(MysqlIndexStorageType::Spatial)) => IndexKind::Spatial,
+            (IndexDef::Normal(_), None) => IndexKind::Normal,
+        }
+    }
+
+    /// Whether no two rows can have the same key, true for primary keys too
+    pub fn is_unique(&self) -> bool {
+        matches!(self.kind(), IndexKind::Primary | IndexKind::Unique)
+    }
+
+    /// The name of the index, or else of its `CONSTRAINT`, which MySQL
+    /// names a unique or foreign key by when the index has no name. A
+    /// primary key has no name, MySQL always calls it `PRIMARY`.
+    pub fn name(&self) -> Option<&Ident> {
+        match &self.index {
+            IndexDef::PrimaryKey(_) => None,
+            IndexDef::Normal(index) => index.index_name.as_ref(),
+            IndexDef::Unique(index) | IndexDef::ForeignKey(index) => {
+                index.index_name.as_ref().or(self.constraint.as_ref())
+            }
+        }
+    }
+
+    /// The key parts, which are only missing for `DROP INDEX` and the like
+    pub fn columns(&self) -> &[IndexKeyPart] {
+        let (IndexDef::Normal(index)
+        | IndexDef::PrimaryKey(index)
+        | IndexDef::ForeignKey(index)
+        | IndexDef::Unique(index)) = &self.index;
+        index.key_parts.as_deref().unwrap_or(&[])
+    }
+}
+
 impl fmt::Display for IndexInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(c) = &self.constraint{
-            write!(f, "CONSTRAINT {}", c)?;
+            write!(f, "CONSTRAINT {} ", c)?;
         }
         if let Some(i) = &self.index_type{
             write!(f, "{}", i)?;
@@ -49,9 +105,9 @@ impl fmt::Display for IndexDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self{
             IndexDef::Normal(i) => write!(f, "{}", i),
-            IndexDef::Unique(i) => write!(f, "Unique {}", i),
-            IndexDef::PrimaryKey(i) => write!(f, "PRIMARY KEY{}", i),
-            IndexDef::ForeignKey(i) => write!(f, "FOREIGN KEY{}", i)
+            IndexDef::Unique(i) => write!(f, "UNIQUE{}{}", i.separator(), i),
+            IndexDef::PrimaryKey(i) => write!(f, "PRIMARY KEY{}{}", i.separator(), i),
+            IndexDef::ForeignKey(i) => write!(f, "FOREIGN KEY{}{}", i.separator(), i)
         }
     }
 }
@@ -97,53 +153,146 @@ pub enum IndexOptions{
     IndexType(Ident),
     WithParser(Ident),
     Comment(Expr),
+    Visible,
+    Invisible,
+    EngineAttribute(Expr),
+    SecondaryEngineAttribute(Expr),
+    /// `REFERENCES <table> (<columns>) [MATCH ...] [ON DELETE <action>] [ON UPDATE <action>]`
     References{
-        table: Ident,
-        column: Vec<Ident>
+        table: ObjectName,
+        column: Vec<Ident>,
+        match_type: Option<ReferentialMatch>,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
     }
 }
 impl fmt::Display for IndexOptions {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self{
-            IndexOptions::KeyBlockSize(expr) => write!(f, "KEY_BLOCK_SIZE {}", expr),
+            IndexOptions::KeyBlockSize(expr) => write!(f, "KEY_BLOCK_SIZE={}", expr),
             IndexOptions::IndexType(t) => write!(f, "USING {}", t),
             IndexOptions::WithParser(i) => write!(f, "WITH PARSER {}", i),
             IndexOptions::Comment(e) => write!(f, "COMMENT {}", e),
-            IndexOptions::References { table, column } => write!(f, "REFERENCES {}({})", table,display_comma_separated(column))
+            IndexOptions::Visible => write!(f, "VISIBLE"),
+            IndexOptions::Invisible => write!(f, "INVISIBLE"),
+            IndexOptions::EngineAttribute(e) => write!(f, "ENGINE_ATTRIBUTE={}", e),
+            IndexOptions::SecondaryEngineAttribute(e) => write!(f, "SECONDARY_ENGINE_ATTRIBUTE={}", e),
+            IndexOptions::References { table, column, match_type, on_delete, on_update } => {
+                write!(f, "REFERENCES {} ({})", table,display_comma_separated(column))?;
+                if let Some(match_type) = match_type {
+                    write!(f, " MATCH {}", match_type)?;
+                }
+                if let Some(action) = on_delete {
+                    write!(f, " ON DELETE {}", action)?;
+                }
+                if let Some(action) = on_update {
+                    write!(f, " ON UPDATE {}", action)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
+/// An index, printed as `SHOW CREATE TABLE` and mysqldump do, e.g.
+/// `KEY idx_a (a)`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MysqlIndex{
-    pub name: Option<Ident>,                            // key、index
+    /// The `KEY` or `INDEX` keyword, as written
+    pub name: Option<Ident>,
     pub index_name: Option<Ident>,
+    /// `USING <type>`, written before or after the key parts
     pub index_type: Option<Ident>,
-    pub key_parts: Option<Vec<Ident>>,
-    pub index_option: Option<IndexOptions>,
+    /// Whether `index_type` was written after the key parts, as
+    /// `SHOW CREATE TABLE` prints it, rather than before them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub index_type_after_key_parts: bool,
+    pub key_parts: Option<Vec<IndexKeyPart>>,
+    pub index_option: Vec<IndexOptions>,
+}
+
+impl MysqlIndex {
+    /// The space between the keywords before the index, such as `UNIQUE`,
+    /// and the index, if it isn't empty as for `DROP PRIMARY KEY`
+    fn separator(&self) -> &'static str {
+        if self.name.is_none()
+            && self.index_name.is_none()
+            && self.index_type.is_none()
+            && self.key_parts.is_none()
+        {
+            ""
+        } else {
+            " "
+        }
+    }
 }
+
 impl fmt::Display for MysqlIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut separator = "";
         if let Some(n) = &self.name{
             write!(f, "{}", n)?;
+            separator = " ";
         }
         if let Some(i) = &self.index_name{
-            write!(f, " {}", i)?;
+            write!(f, "{}{}", separator, i)?;
+            separator = " ";
         }
-        if let Some(i) = &self.index_type{
-            write!(f, " {}", i)?;
+        if let Some(i) = self.index_type.as_ref().filter(|_| !self.index_type_after_key_parts) {
+            write!(f, "{}USING {}", separator, i)?;
+            separator = " ";
         }
         if let Some(k) = &self.key_parts{
-            write!(f, "({})", display_comma_separated(k))?;
+            write!(f, "{}({})", separator, display_comma_separated(k))?;
         }
-        if let Some(i) = &self.index_option{
+        if let Some(i) = self.index_type.as_ref().filter(|_| self.index_type_after_key_parts) {
+            write!(f, " USING {}", i)?;
+        }
+        for i in &self.index_option{
             write!(f, " {}", i)?;
         }
         write!(f, "")
     }
 }
 
+/// One element of an index's key part list
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IndexKeyPart {
+    /// `<column> [(<length>)] [ASC | DESC]`
+    Column {
+        name: Ident,
+        length: Option<u64>,
+        asc: Option<bool>,
+    },
+    /// `(<expr>) [ASC | DESC]`, a MySQL 8 functional key part
+    Expr { expr: Expr, asc: Option<bool> },
+}
+
+impl fmt::Display for IndexKeyPart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let asc = match self {
+            IndexKeyPart::Column { name, length, asc } => {
+                write!(f, "{}", name)?;
+                if let Some(length) = length {
+                    write!(f, "({})", length)?;
+                }
+                asc
+            }
+            IndexKeyPart::Expr { expr, asc } => {
+                write!(f, "({})", expr)?;
+                asc
+            }
+        };
+        match asc {
+            Some(true) => write!(f, " ASC"),
+            Some(false) => write!(f, " DESC"),
+            None => Ok(()),
+        }
+    }
+}
+
 /// An `ALTER TABLE` (`Statement::AlterTable`) operation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -152,8 +301,12 @@ pub enum AlterTableOperation {
     AddConstraint(TableConstraint),
     /// `ADD [ COLUMN ] <column_def>`
     AddColumn { column_def: ColumnDef },
-    /// TODO: implement `DROP CONSTRAINT <name>`
-    DropConstraint { name: Ident },
+    /// `DROP CONSTRAINT <name>`
+    DropConstraint(Ident),
+    /// `DROP FOREIGN KEY <name>`
+    DropForeignKey(Ident),
+    /// `DROP CHECK <name>`
+    DropCheck(Ident),
     /// `DROP [ COLUMN ] [ IF EXISTS ] <column_name> [ CASCADE ]`
     DropColumn {
         column_name: Ident,
@@ -165,6 +318,12 @@ pub enum AlterTableOperation {
         old_column_name: Ident,
         new_column_name: Ident,
     },
+    /// `MODIFY [ COLUMN ] <column_def> [ FIRST | AFTER <column_name> ]`
+    ModifyColumn {
+        column_def: ColumnDef,
+        /// `ColumnOption::First` or `ColumnOption::After`
+        first_or_after: Option<ColumnOption>,
+    },
     /// CHANGE [ column ] old_column_name new_column_name column_def
     ChangeColumn {
         old_column_name: Ident,
@@ -178,8 +337,32 @@ pub enum AlterTableOperation {
     DropIndex {
         index_def: IndexDef,
     },
-    /// `RENAME TO <table_name>`
-    RenameTable { table_name: Ident },
+    /// `CONVERT TO CHARACTER SET <charset> [ COLLATE <collation> ]` when `convert`
+    /// is set, `[ DEFAULT ] CHARACTER SET <charset> [ COLLATE <collation> ]` otherwise
+    ConvertCharset {
+        charset: Ident,
+        collation: Option<Ident>,
+        convert: bool,
+    },
+    /// Table options, e.g. `ENGINE=InnoDB ROW_FORMAT=COMPRESSED`
+    SetTableOptions(Vec<TableOptionDef>),
+    /// `ENABLE KEYS`
+    EnableKeys,
+    /// `DISABLE KEYS`
+    DisableKeys,
+    /// `FORCE`
+    Force,
+    /// `ORDER BY <column> [ASC | DESC], ...`, where each column is an
+    /// `Expr::Identifier`
+    OrderBy(Vec<OrderByExpr>),
+    /// MySQL `ALGORITHM [=] { DEFAULT | INSTANT | INPLACE | COPY }`
+    Algorithm(Ident),
+    /// MySQL `LOCK [=] { DEFAULT | NONE | SHARED | EXCLUSIVE }`
+    Lock(Ident),
+    /// `RENAME { INDEX | KEY } <old_name> TO <new_name>`
+    RenameIndex { old_name: Ident, new_name: Ident },
+    /// `RENAME [ TO | AS ] <table_name>`
+    RenameTable { table_name: ObjectName },
 }
 
 impl fmt::Display for AlterTableOperation {
@@ -189,7 +372,9 @@ impl fmt::Display for AlterTableOperation {
             AlterTableOperation::AddColumn { column_def } => {
                 write!(f, "ADD COLUMN {}", column_def.to_string())
             }
-            AlterTableOperation::DropConstraint { name } => write!(f, "DROP CONSTRAINT {}", name),
+            AlterTableOperation::DropConstraint(name) => write!(f, "DROP CONSTRAINT {}", name),
+            AlterTableOperation::DropForeignKey(name) => write!(f, "DROP FOREIGN KEY {}", name),
+            AlterTableOperation::DropCheck(name) => write!(f, "DROP CHECK {}", name),
             AlterTableOperation::DropColumn {
                 column_name,
                 if_exists,
@@ -209,11 +394,51 @@ impl fmt::Display for AlterTableOperation {
                 "RENAME COLUMN {} TO {}",
                 old_column_name, new_column_name
             ),
+            AlterTableOperation::ModifyColumn {
+                column_def,
+                first_or_after,
+            } => {
+                write!(f, "MODIFY COLUMN {}", column_def)?;
+                if let Some(position) = first_or_after {
+                    write!(f, " {}", position)?;
+                }
+                Ok(())
+            }
             AlterTableOperation::ChangeColumn {
                 old_column_name,
                 new_column_def } =>  write!(
-                f, "{} {}", old_column_name, new_column_def
+                f, "CHANGE COLUMN {} {}", old_column_name, new_column_def
             ),
+            AlterTableOperation::ConvertCharset {
+                charset,
+                collation,
+                convert,
+            } => {
+                write!(
+                    f,
+                    "{} {}",
+                    if *convert { "CONVERT TO CHARACTER SET" } else { "DEFAULT CHARACTER SET" },
+                    charset
+                )?;
+                if let Some(collation) = collation {
+                    write!(f, " COLLATE {}", collation)?;
+                }
+                Ok(())
+            }
+            AlterTableOperation::SetTableOptions(options) => {
+                write!(f, "{}", display_separated(options, " "))
+            }
+            AlterTableOperation::EnableKeys => write!(f, "ENABLE KEYS"),
+            AlterTableOperation::DisableKeys => write!(f, "DISABLE KEYS"),
+            AlterTableOperation::Force => write!(f, "FORCE"),
+            AlterTableOperation::OrderBy(columns) => {
+                write!(f, "ORDER BY {}", display_comma_separated(columns))
+            }
+            AlterTableOperation::Algorithm(algorithm) => write!(f, "ALGORITHM={}", algorithm),
+            AlterTableOperation::Lock(lock) => write!(f, "LOCK={}", lock),
+            AlterTableOperation::RenameIndex { old_name, new_name } => {
+                write!(f, "RENAME INDEX {} TO {}", old_name, new_name)
+            }
             AlterTableOperation::RenameTable { table_name } => {
                 write!(f, "RENAME TO {}", table_name)
             }
@@ -275,7 +500,7 @@ impl fmt::Display for TableConstraint {
                 referred_columns,
             } => write!(
                 f,
-                "{}FOREIGN KEY ({}) REFERENCES {}({})",
+                "{}FOREIGN KEY ({}) REFERENCES {} ({})",
                 display_constraint_name(name),
                 display_comma_separated(columns),
                 foreign_table,
@@ -296,13 +521,29 @@ pub struct ColumnDef {
     pub data_type: DataType,
     pub collation: Option<ObjectName>,
     pub options: Vec<ColumnOptionDef>,
+    /// The column's position in `CREATE TABLE`, counted from 1 like MySQL's
+    /// `ORDINAL_POSITION`, or 0 for a column defined elsewhere, e.g. in
+    /// `ALTER TABLE ... ADD COLUMN`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ordinal: usize,
+}
+
+impl ColumnDef {
+    /// The `DEFAULT` value written for the column, if any. `DEFAULT NULL`
+    /// gives `Some(Expr::Value(Value::Null))`, unlike no `DEFAULT` at all.
+    pub fn explicit_default(&self) -> Option<&Expr> {
+        self.options.iter().find_map(|option| match &option.option {
+            ColumnOption::Default(expr) | ColumnOption::DefaultExpr(expr) => Some(expr),
+            _ => None,
+        })
+    }
 }
 
 impl fmt::Display for ColumnDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{} {}", self.name, self.data_type)?;
         if let Some(v) = &self.collation{
-            write!(f, "{}", v)?;
+            write!(f, " COLLATE {}", v)?;
         }
         for option in &self.options {
             write!(f, " {}", option)?;
@@ -321,16 +562,16 @@ pub struct TableOptionDef {
 impl fmt::Display for TableOptionDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(v) = &self.name{
-            write!(f, " {} ", v)?;
+            write!(f, "{} ", v)?;
         }
-        write!(f, " {}", self.option)
+        write!(f, "{}", self.option)
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TableOption {
-    /// default charset=?
+    /// [default] {charset | character set}=?
     Charset(Expr),
     /// table comment
     Comment(Expr),
@@ -339,7 +580,61 @@ pub enum TableOption {
     /// collate=?
     Collate(Expr),
     /// auto_increment
-    Auto_Increment(usize)
+    Auto_Increment(usize),
+    /// autoextend_size=?
+    AutoextendSize(Expr),
+    /// avg_row_length=?
+    AvgRowLength(Expr),
+    /// checksum={0 | 1}
+    Checksum(Expr),
+    /// compression={'ZLIB' | 'LZ4' | 'NONE'}
+    Compression(Expr),
+    /// connection='connect_string'
+    Connection(Expr),
+    /// data directory='path'
+    DataDirectory(Expr),
+    /// index directory='path'
+    IndexDirectory(Expr),
+    /// delay_key_write={0 | 1}
+    DelayKeyWrite(Expr),
+    /// encryption={'Y' | 'N'}
+    Encryption(Expr),
+    /// engine_attribute='string'
+    EngineAttribute(Expr),
+    /// insert_method={NO | FIRST | LAST}
+    InsertMethod(Expr),
+    /// key_block_size=?
+    KeyBlockSize(Expr),
+    /// max_rows=?
+    MaxRows(Expr),
+    /// min_rows=?
+    MinRows(Expr),
+    /// pack_keys={0 | 1 | DEFAULT}
+    PackKeys(Expr),
+    /// password='string'
+    Password(Expr),
+    /// row_format={DEFAULT | DYNAMIC | FIXED | COMPRESSED | REDUNDANT | COMPACT}
+    RowFormat(Expr),
+    /// secondary_engine_attribute='string'
+    SecondaryEngineAttribute(Expr),
+    /// stats_auto_recalc={DEFAULT | 0 | 1}
+    StatsAutoRecalc(Expr),
+    /// stats_persistent={DEFAULT | 0 | 1}
+    StatsPersistent(Expr),
+    /// stats_sample_pages=?
+    StatsSamplePages(Expr),
+    /// tablespace name [storage {disk | memory}]
+    Tablespace {
+        name: Ident,
+        storage: Option<ColumnStorage>,
+    },
+    /// union=(tbl_name[, tbl_name]...)
+    Union(Vec<ObjectName>),
+    /// any other `name=value` option, kept as written
+    Other {
+        name: Ident,
+        value: Expr,
+    },
 }
 
 impl fmt::Display for TableOption {
@@ -347,11 +642,206 @@ impl fmt::Display for TableOption {
         use TableOption::*;
         match self {
             Charset(expr) => write!(f, "CHARSET={}", expr),
-            Comment(expr) => write!(f, "COMMENT {}", expr),
+            Comment(expr) => write!(f, "COMMENT={}", expr),
             Engine(expr) => write!(f, "ENGINE={}", expr),
             Collate(expr) => write!(f, "COLLATE={}", expr),
             Auto_Increment(a) => write!(f, "AUTO_INCREMENT={}", a),
+            AutoextendSize(expr) => write!(f, "AUTOEXTEND_SIZE={}", expr),
+            AvgRowLength(expr) => write!(f, "AVG_ROW_LENGTH={}", expr),
+            Checksum(expr) => write!(f, "CHECKSUM={}", expr),
+            Compression(expr) => write!(f, "COMPRESSION={}", expr),
+            Connection(expr) => write!(f, "CONNECTION={}", expr),
+            DataDirectory(expr) => write!(f, "DATA DIRECTORY={}", expr),
+            IndexDirectory(expr) => write!(f, "INDEX DIRECTORY={}", expr),
+            DelayKeyWrite(expr) => write!(f, "DELAY_KEY_WRITE={}", expr),
+            Encryption(expr) => write!(f, "ENCRYPTION={}", expr),
+            EngineAttribute(expr) => write!(f, "ENGINE_ATTRIBUTE={}", expr),
+            InsertMethod(expr) => write!(f, "INSERT_METHOD={}", expr),
+            KeyBlockSize(expr) => write!(f, "KEY_BLOCK_SIZE={}", expr),
+            MaxRows(expr) => write!(f, "MAX_ROWS={}", expr),
+            MinRows(expr) => write!(f, "MIN_ROWS={}", expr),
+            PackKeys(expr) => write!(f, "PACK_KEYS={}", expr),
+            Password(expr) => write!(f, "PASSWORD={}", expr),
+            RowFormat(expr) => write!(f, "ROW_FORMAT={}", expr),
+            SecondaryEngineAttribute(expr) => write!(f, "SECONDARY_ENGINE_ATTRIBUTE={}", expr),
+            StatsAutoRecalc(expr) => write!(f, "STATS_AUTO_RECALC={}", expr),
+            StatsPersistent(expr) => write!(f, "STATS_PERSISTENT={}", expr),
+            StatsSamplePages(expr) => write!(f, "STATS_SAMPLE_PAGES={}", expr),
+            Tablespace { name, storage } => {
+                write!(f, "TABLESPACE {}", name)?;
+                if let Some(storage) = storage {
+                    write!(f, " STORAGE {}", storage)?;
+                }
+                Ok(())
+            }
+            Union(tables) => write!(f, "UNION=({})", display_comma_separated(tables)),
+            Other { name, value } => write!(f, "{}={}", name, value),
+        }
+    }
+}
+
+/// The `PARTITION BY` clause of a MySQL `CREATE TABLE`:
+/// `PARTITION BY <method> [PARTITIONS <n>]
+///  [SUBPARTITION BY <method> [SUBPARTITIONS <n>]] [(<partition>, ...)]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartitionDefinition {
+    pub partition_by: PartitionMethod,
+    pub partitions: Option<u64>,
+    pub subpartition_by: Option<PartitionMethod>,
+    pub subpartitions: Option<u64>,
+    pub definitions: Vec<Partition>,
+}
+
+impl fmt::Display for PartitionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PARTITION BY {}", self.partition_by)?;
+        if let Some(n) = self.partitions {
+            write!(f, " PARTITIONS {}", n)?;
+        }
+        if let Some(method) = &self.subpartition_by {
+            write!(f, " SUBPARTITION BY {}", method)?;
         }
+        if let Some(n) = self.subpartitions {
+            write!(f, " SUBPARTITIONS {}", n)?;
+        }
+        if !self.definitions.is_empty() {
+            write!(f, " ({})", display_comma_separated(&self.definitions))?;
+        }
+        Ok(())
+    }
+}
+
+/// How rows are assigned to partitions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PartitionMethod {
+    /// `[LINEAR] HASH (<expr>)`
+    Hash { linear: bool, expr: Expr },
+    /// `[LINEAR] KEY [ALGORITHM = {1 | 2}] (<columns>)`
+    Key {
+        linear: bool,
+        algorithm: Option<u64>,
+        columns: Vec<Ident>,
+    },
+    /// `RANGE { (<expr>) | COLUMNS (<columns>) }`
+    Range(PartitionKey),
+    /// `LIST { (<expr>) | COLUMNS (<columns>) }`
+    List(PartitionKey),
+}
+
+impl fmt::Display for PartitionMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartitionMethod::Hash { linear, expr } => write!(
+                f,
+                "{}HASH ({})",
+                if *linear { "LINEAR " } else { "" },
+                expr
+            ),
+            PartitionMethod::Key {
+                linear,
+                algorithm,
+                columns,
+            } => {
+                write!(f, "{}KEY", if *linear { "LINEAR " } else { "" })?;
+                if let Some(algorithm) = algorithm {
+                    write!(f, " ALGORITHM={}", algorithm)?;
+                }
+                write!(f, " ({})", display_comma_separated(columns))
+            }
+            PartitionMethod::Range(key) => write!(f, "RANGE {}", key),
+            PartitionMethod::List(key) => write!(f, "LIST {}", key),
+        }
+    }
+}
+
+/// The partitioning key of a `RANGE` or `LIST` partitioned table
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PartitionKey {
+    /// `(<expr>)`
+    Expr(Expr),
+    /// `COLUMNS (<columns>)`
+    Columns(Vec<Ident>),
+}
+
+impl fmt::Display for PartitionKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartitionKey::Expr(expr) => write!(f, "({})", expr),
+            PartitionKey::Columns(columns) => {
+                write!(f, "COLUMNS ({})", display_comma_separated(columns))
+            }
+        }
+    }
+}
+
+/// A single partition: `PARTITION <name> [VALUES ...] [<options>] [(<subpartition>, ...)]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Partition {
+    pub name: Ident,
+    pub values: Option<PartitionValues>,
+    pub options: Vec<TableOption>,
+    pub subpartitions: Vec<Subpartition>,
+}
+
+impl fmt::Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PARTITION {}", self.name)?;
+        if let Some(values) = &self.values {
+            write!(f, " VALUES {}", values)?;
+        }
+        if !self.options.is_empty() {
+            write!(f, " {}", display_separated(&self.options, " "))?;
+        }
+        if !self.subpartitions.is_empty() {
+            write!(f, " ({})", display_comma_separated(&self.subpartitions))?;
+        }
+        Ok(())
+    }
+}
+
+/// The bound of a partition
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PartitionValues {
+    /// `LESS THAN (<expr>, ...)`
+    LessThan(Vec<Expr>),
+    /// `LESS THAN MAXVALUE`
+    LessThanMaxValue,
+    /// `IN (<expr>, ...)`
+    In(Vec<Expr>),
+}
+
+impl fmt::Display for PartitionValues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartitionValues::LessThan(exprs) => {
+                write!(f, "LESS THAN ({})", display_comma_separated(exprs))
+            }
+            PartitionValues::LessThanMaxValue => write!(f, "LESS THAN MAXVALUE"),
+            PartitionValues::In(exprs) => write!(f, "IN ({})", display_comma_separated(exprs)),
+        }
+    }
+}
+
+/// `SUBPARTITION <name> [<options>]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Subpartition {
+    pub name: Ident,
+    pub options: Vec<TableOption>,
+}
+
+impl fmt::Display for Subpartition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SUBPARTITION {}", self.name)?;
+        if !self.options.is_empty() {
+            write!(f, " {}", display_separated(&self.options, " "))?;
+        }
+        Ok(())
     }
 }
 
@@ -397,6 +887,9 @@ pub enum ColumnOption {
     AutoIncrement,
     /// `DEFAULT <restricted-expr>`
     Default(Expr),
+    /// `DEFAULT (<expr>)`, the MySQL 8 expression default; the parentheses
+    /// are required for anything other than a literal
+    DefaultExpr(Expr),
     /// `{ PRIMARY KEY | UNIQUE }`
     Unique {
         is_primary: bool,
@@ -418,6 +911,16 @@ pub enum ColumnOption {
     Character(Expr),
     Collate(Expr),
     After(Expr),
+    /// `FIRST`
+    First,
+    /// `INVISIBLE`
+    Invisible,
+    /// `VISIBLE`
+    Visible,
+    /// `COLUMN_FORMAT { FIXED | DYNAMIC | DEFAULT }`
+    ColumnFormat(ColumnFormat),
+    /// `STORAGE { DISK | MEMORY }`
+    Storage(ColumnStorage),
 }
 
 impl fmt::Display for ColumnOption {
@@ -429,6 +932,7 @@ impl fmt::Display for ColumnOption {
             Unsigned => write!(f, "UNSIGNED"),
             AutoIncrement => write!(f, "AUTO_INCREMENT"),
             Default(expr) => write!(f, "DEFAULT {}", expr),
+            DefaultExpr(expr) => write!(f, "DEFAULT ({})", expr),
             Unique { is_primary } => {
                 write!(f, "{}", if *is_primary { "PRIMARY KEY" } else { "UNIQUE" })
             }
@@ -436,6 +940,11 @@ impl fmt::Display for ColumnOption {
             Collate(expr) => write!(f, "COLLATE {}", expr),
             Comment(expr) => write!(f, "COMMENT {}", expr),
             After(expr) => write!(f, "AFTER {}", expr),
+            First => write!(f, "FIRST"),
+            Invisible => write!(f, "INVISIBLE"),
+            Visible => write!(f, "VISIBLE"),
+            ColumnFormat(format) => write!(f, "COLUMN_FORMAT {}", format),
+            Storage(storage) => write!(f, "STORAGE {}", storage),
             ForeignKey {
                 foreign_table,
                 referred_columns,
@@ -459,6 +968,42 @@ impl fmt::Display for ColumnOption {
     }
 }
 
+/// `COLUMN_FORMAT` column attribute (MySQL NDB)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColumnFormat {
+    Fixed,
+    Dynamic,
+    Default,
+}
+
+impl fmt::Display for ColumnFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ColumnFormat::Fixed => "FIXED",
+            ColumnFormat::Dynamic => "DYNAMIC",
+            ColumnFormat::Default => "DEFAULT",
+        })
+    }
+}
+
+/// `STORAGE` column attribute (MySQL NDB)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColumnStorage {
+    Disk,
+    Memory,
+}
+
+impl fmt::Display for ColumnStorage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ColumnStorage::Disk => "DISK",
+            ColumnStorage::Memory => "MEMORY",
+        })
+    }
+}
+
 fn display_constraint_name<'a>(name: &'a Option<Ident>) -> impl fmt::Display + 'a {
     struct ConstraintName<'a>(&'a Option<Ident>);
     impl<'a> fmt::Display for ConstraintName<'a> {
@@ -497,3 +1042,39 @@ impl fmt::Display for ReferentialAction {
         })
     }
 }
+
+/// `MATCH { FULL | PARTIAL | SIMPLE }` of a foreign key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReferentialMatch {
+    Full,
+    Partial,
+    Simple,
+}
+
+impl fmt::Display for ReferentialMatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ReferentialMatch::Full => "FULL",
+            ReferentialMatch::Partial => "PARTIAL",
+            ReferentialMatch::Simple => "SIMPLE",
+        })
+    }
+}
+
+/// MySQL `IGNORE` or `REPLACE` before the query of `CREATE TABLE ... SELECT`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CreateTableOnDuplicate {
+    Ignore,
+    Replace,
+}
+
+impl fmt::Display for CreateTableOnDuplicate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CreateTableOnDuplicate::Ignore => "IGNORE",
+            CreateTableOnDuplicate::Replace => "REPLACE",
+        })
+    }
+}
@@ -0,0 +1,310 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The columns a query returns and where they come from, see
+//! `Query::output_columns`
+
+use super::*;
+
+#[cfg(not(feature = "std"))]
+use alloc::format;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A column of the result of a query
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OutputColumn {
+    /// A column computed by an expression
+    Expr {
+        /// The alias of the column, or the name MySQL gives it: the name of
+        /// a column reference, or else the text of the expression
+        name: String,
+        /// The columns the expression reads, each qualified by its table
+        /// when the query makes that clear
+        sources: Vec<Vec<Ident>>,
+    },
+    /// `*` or `<table>.*`, which can't be expanded without the schema
+    Wildcard {
+        /// The tables the wildcard stands for the columns of
+        from: Vec<ObjectName>,
+        /// The columns left out, see [Select::wildcard_except]
+        except: Vec<Ident>,
+    },
+}
+
+impl Query {
+    /// The columns the query returns, in order. A column reference is
+    /// resolved to the table it belongs to when it is qualified by the
+    /// table or its alias, or when there is only one table. Columns of
+    /// derived tables and CTEs are resolved to the columns they are computed
+    /// from. For `UNION` and the other set operations, the first query
+    /// names the columns and each column's sources are those of all the
+    /// queries. Columns read by subqueries aren't reported.
+    pub fn output_columns(&self) -> Vec<OutputColumn> {
+        query_columns(self, &[])
+    }
+}
+
+/// A CTE in scope, with its columns
+type CteColumns = (Ident, Vec<OutputColumn>);
+
+fn query_columns(query: &Query, outer_ctes: &[CteColumns]) -> Vec<OutputColumn> {
+    let mut ctes = outer_ctes.to_vec();
+    for cte in &query.ctes {
+        let columns = renamed(query_columns(&cte.query, &ctes), &cte.alias.columns);
+        ctes.push((cte.alias.name.clone(), columns));
+    }
+    set_expr_columns(&query.body, &ctes)
+}
+
+fn set_expr_columns(body: &SetExpr, ctes: &[CteColumns]) -> Vec<OutputColumn> {
+    match body {
+        SetExpr::Select(select) => select_columns(select, ctes),
+        SetExpr::Query(query) => query_columns(query, ctes),
+        SetExpr::SetOperation { left, right, .. } => {
+            let mut columns = set_expr_columns(left, ctes);
+            let right = set_expr_columns(right, ctes);
+            // after a wildcard, the columns of the two sides can't be paired
+            for (column, other) in columns.iter_mut().zip(right) {
+                match (column, other) {
+                    (
+                        OutputColumn::Expr { sources, .. },
+                        OutputColumn::Expr {
+                            sources: other_sources,
+                            ..
+                        },
+                    ) => add_sources(sources, other_sources),
+                    _ => break,
+                }
+            }
+            columns
+        }
+        SetExpr::Values(values) | SetExpr::Value(values) => {
+            let width = values.0.first().map_or(0, Vec::len);
+            (0..width)
+                .map(|i| OutputColumn::Expr {
+                    name: format!("column_{}", i),
+                    sources: vec![],
+                })
+                .collect()
+        }
+    }
+}
+
+/// What a name in the `FROM` clause refers to
+enum Source {
+    Table(ObjectName),
+    Derived(Vec<OutputColumn>),
+}
+
+/// The tables of a `FROM` clause, by the name that qualifies their columns
+struct Scope {
+    sources: Vec<(Ident, Source)>,
+}
+
+impl Scope {
+    fn new(select: &Select, ctes: &[CteColumns]) -> Self {
+        let mut scope = Scope { sources: vec![] };
+        for table in &select.from {
+            scope.add_table_with_joins(table, ctes);
+        }
+        scope
+    }
+
+    fn add_table_with_joins(&mut self, table: &TableWithJoins, ctes: &[CteColumns]) {
+        self.add_table_factor(&table.relation, ctes);
+        for join in &table.joins {
+            self.add_table_factor(&join.relation, ctes);
+        }
+    }
+
+    fn add_table_factor(&mut self, table_factor: &TableFactor, ctes: &[CteColumns]) {
+        match table_factor {
+            TableFactor::Table { name, alias, .. } => {
+                let cte = match name.0.as_slice() {
+                    [ident] => ctes
+                        .iter()
+                        .rev()
+                        .find(|(cte, _)| cte.eq_ignore_case(&ident.value)),
+                    _ => None,
+                };
+                let key = match (alias, name.0.last()) {
+                    (Some(alias), _) => alias.name.clone(),
+                    (None, Some(last)) => last.clone(),
+                    (None, None) => return,
+                };
+                let source = match cte {
+                    Some((_, columns)) => Source::Derived(columns.clone()),
+                    None => Source::Table(name.clone()),
+                };
+                let source = match (alias, source) {
+                    (Some(alias), Source::Derived(columns)) => {
+                        Source::Derived(renamed(columns, &alias.columns))
+                    }
+                    (_, source) => source,
+                };
+                self.sources.push((key, source));
+            }
+            TableFactor::Derived {
+                subquery,
+                alias: Some(alias),
+                ..
+            } => {
+                let columns = renamed(query_columns(subquery, ctes), &alias.columns);
+                self.sources
+                    .push((alias.name.clone(), Source::Derived(columns)));
+            }
+            TableFactor::NestedJoin(table) => self.add_table_with_joins(table, ctes),
+            TableFactor::Derived { alias: None, .. } | TableFactor::Dual => {}
+        }
+    }
+
+    fn find(&self, qualifier: &Ident) -> Option<&(Ident, Source)> {
+        self.sources
+            .iter()
+            .find(|(name, _)| name.eq_ignore_case(&qualifier.value))
+    }
+
+    /// The columns a column reference stands for
+    fn resolve(&self, column: &[Ident]) -> Vec<Vec<Ident>> {
+        let resolved = match column {
+            [name] => match self.sources.as_slice() {
+                [(_, source)] => resolve_in(source, name),
+                // with several tables, only a derived table's column is known
+                // to be one of its columns
+                _ => self.sources.iter().find_map(|(_, source)| match source {
+                    Source::Derived(columns) => derived_column(columns, name),
+                    Source::Table(_) => None,
+                }),
+            },
+            [qualifier, name] => self
+                .find(qualifier)
+                .and_then(|(_, source)| resolve_in(source, name)),
+            _ => None,
+        };
+        resolved.unwrap_or_else(|| vec![column.to_vec()])
+    }
+
+    fn wildcard(&self, qualifier: Option<&[Ident]>) -> Vec<ObjectName> {
+        let name = |(key, source): &(Ident, Source)| match source {
+            Source::Table(name) => name.clone(),
+            Source::Derived(_) => ObjectName(vec![key.clone()]),
+        };
+        match qualifier {
+            None => self.sources.iter().map(name).collect(),
+            Some([qualifier]) => match self.find(qualifier) {
+                Some(source) => vec![name(source)],
+                None => vec![ObjectName(vec![qualifier.clone()])],
+            },
+            Some(qualifier) => vec![ObjectName(qualifier.to_vec())],
+        }
+    }
+}
+
+fn resolve_in(source: &Source, name: &Ident) -> Option<Vec<Vec<Ident>>> {
+    match source {
+        Source::Table(table) => {
+            let mut column = table.0.clone();
+            column.push(name.clone());
+            Some(vec![column])
+        }
+        Source::Derived(columns) => derived_column(columns, name),
+    }
+}
+
+fn derived_column(columns: &[OutputColumn], name: &Ident) -> Option<Vec<Vec<Ident>>> {
+    columns.iter().find_map(|column| match column {
+        OutputColumn::Expr {
+            name: column_name,
+            sources,
+        } if name.eq_ignore_case(column_name) => Some(sources.clone()),
+        _ => None,
+    })
+}
+
+fn select_columns(select: &Select, ctes: &[CteColumns]) -> Vec<OutputColumn> {
+    let scope = Scope::new(select, ctes);
+    select
+        .projection
+        .iter()
+        .map(|item| match item {
+            SelectItem::UnnamedExpr(expr) => OutputColumn::Expr {
+                name: match expr {
+                    Expr::Identifier(ident) => ident.value.clone(),
+                    Expr::CompoundIdentifier(idents) => {
+                        idents.last().map_or_else(String::new, |i| i.value.clone())
+                    }
+                    _ => expr.to_string(),
+                },
+                sources: expr_sources(expr, &scope),
+            },
+            SelectItem::ExprWithAlias { expr, alias } => OutputColumn::Expr {
+                name: alias.value.clone(),
+                sources: expr_sources(expr, &scope),
+            },
+            SelectItem::QualifiedWildcard(name) => OutputColumn::Wildcard {
+                from: scope.wildcard(Some(&name.0)),
+                except: select.wildcard_except.clone(),
+            },
+            SelectItem::Wildcard => OutputColumn::Wildcard {
+                from: scope.wildcard(None),
+                except: select.wildcard_except.clone(),
+            },
+        })
+        .collect()
+}
+
+fn expr_sources(expr: &Expr, scope: &Scope) -> Vec<Vec<Ident>> {
+    let mut collector = ColumnReferences { columns: vec![] };
+    // the visitor needs mutable access, which this doesn't make use of
+    collector.visit_expr(&mut expr.clone());
+    let mut sources = vec![];
+    for column in collector.columns {
+        add_sources(&mut sources, scope.resolve(&column));
+    }
+    sources
+}
+
+fn add_sources(sources: &mut Vec<Vec<Ident>>, more: Vec<Vec<Ident>>) {
+    for source in more {
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+}
+
+/// The columns with the names of a `<alias> (<columns>)` list, if any
+fn renamed(mut columns: Vec<OutputColumn>, names: &[Ident]) -> Vec<OutputColumn> {
+    for (column, new_name) in columns.iter_mut().zip(names) {
+        if let OutputColumn::Expr { name, .. } = column {
+            *name = new_name.value.clone();
+        }
+    }
+    columns
+}
+
+struct ColumnReferences {
+    columns: Vec<Vec<Ident>>,
+}
+
+impl VisitorMut for ColumnReferences {
+    fn visit_query(&mut self, _query: &mut Query) {}
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Identifier(ident) => self.columns.push(vec![ident.clone()]),
+            Expr::CompoundIdentifier(idents) => self.columns.push(idents.clone()),
+            _ => walk_expr_mut(self, expr),
+        }
+    }
+}
@@ -12,31 +12,70 @@
 
 //! SQL Abstract Syntax Tree (AST) types
 
+mod accessors;
+mod builder;
 mod data_type;
 mod ddl;
+mod lineage;
+mod normalize;
 mod operator;
+mod parameterize;
+mod predicate;
 mod query;
+mod quote;
+mod simplify;
+mod span;
+mod statement_kind;
+mod table_reference;
+mod validate;
 mod value;
+mod visit_mut;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
+pub use self::accessors::*;
+pub use self::builder::{BuildError, DeleteBuilder, InsertBuilder, SelectBuilder, UpdateBuilder};
 pub use self::data_type::DataType;
 pub use self::ddl::{
-    AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, ReferentialAction,
-    TableConstraint, TableOptionDef, TableOption, MysqlIndex, IndexOptions, MysqlIndexStorageType,MysqlIndexType,
-    IndexDef,IndexInfo
+    AlterTableOperation, ColumnDef, ColumnFormat, ColumnOption, ColumnOptionDef, ColumnStorage,
+    ReferentialAction, ReferentialMatch, CreateTableOnDuplicate, TableConstraint, TableOptionDef, TableOption, MysqlIndex, IndexOptions, MysqlIndexStorageType,MysqlIndexType,
+    IndexDef,IndexInfo, IndexKeyPart, IndexKind, Partition, PartitionDefinition, PartitionKey, PartitionMethod, PartitionValues,
+    Subpartition
 };
+pub use self::lineage::OutputColumn;
 pub use self::operator::{BinaryOperator, UnaryOperator};
+pub use self::parameterize::ParameterizeOptions;
+pub use self::predicate::{Predicate, PredicateOp};
 pub use self::query::{
-    Cte, Fetch, Join, JoinConstraint, JoinOperator, Offset, OffsetRows, OrderByExpr, Query, Select,
-    SelectItem, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, Top, Values, LockInfo,
+    Cte, Distinct, Fetch, Join, JoinConstraint, JoinOperator, Offset, OffsetRows, OrderByExpr, Query, Select,
+    SelectItem, SelectModifier, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, Top, Values, LockInfo,
     LOCKType,
 };
-pub use self::value::{DateTimeField, Value};
+pub(crate) use self::quote::visit_names;
+pub use self::quote::{QuoteOptions, QuotePolicy};
+pub use self::simplify::simplify;
+pub use self::span::{Located, LocatedStatement, NodeSpans, ParsedSource, SourceStatement, Span};
+pub use self::statement_kind::StatementKind;
+pub use self::table_reference::{TableAccess, TableReference};
+pub use self::validate::{ValidateOptions, ValidationError, ValidationRule};
+pub use self::value::{
+    escape_double_quote_string, escape_single_quote_string, DateTimeField, Value,
+};
+pub use self::visit_mut::{
+    walk_expr_mut, walk_query_mut, walk_select_mut, walk_set_expr_mut, walk_statement_mut,
+    walk_table_factor_mut, walk_table_with_joins_mut, VisitorMut,
+};
 
-struct DisplaySeparated<'a, T>
+pub(crate) struct DisplaySeparated<'a, T>
 where
     T: fmt::Display,
 {
@@ -66,7 +105,7 @@ where
     DisplaySeparated { slice, sep }
 }
 
-fn display_comma_separated<T>(slice: &[T]) -> DisplaySeparated<'_, T>
+pub(crate) fn display_comma_separated<T>(slice: &[T]) -> DisplaySeparated<'_, T>
 where
     T: fmt::Display,
 {
@@ -96,18 +135,47 @@ impl Ident {
         }
     }
 
-    /// Create a new quoted identifier with the given quote and value. This function
-    /// panics if the given quote is not a valid quote character.
+    /// Create a new quoted identifier with the given quote and value. A quote
+    /// other than `[` is also used to close the identifier.
     pub fn with_quote<S>(quote: char, value: S) -> Self
     where
         S: Into<String>,
     {
-        assert!(quote == '\'' || quote == '"' || quote == '`' || quote == '[');
         Ident {
             value: value.into(),
             quote_style: Some(quote),
         }
     }
+
+    /// Whether the identifier is `name` in any case, quoted or not, as
+    /// MySQL compares column names and keywords
+    pub fn eq_ignore_case(&self, name: &str) -> bool {
+        self.value
+            .chars()
+            .flat_map(char::to_lowercase)
+            .eq(name.chars().flat_map(char::to_lowercase))
+    }
+
+    /// The identifier without its quotes, and in lower case unless
+    /// `case_sensitive`, so that identifiers naming the same object
+    /// normalize the same
+    pub fn normalized(&self, case_sensitive: bool) -> Ident {
+        Ident::new(if case_sensitive {
+            self.value.clone()
+        } else {
+            self.value.to_lowercase()
+        })
+    }
+
+    /// Compare the identifier with `name`, ignoring quotes and, unless
+    /// `case_sensitive`, case
+    fn matches(&self, name: &str, case_sensitive: bool) -> bool {
+        if case_sensitive {
+            self.value == name
+        } else {
+            self.eq_ignore_case(name)
+        }
+    }
 }
 
 impl From<&str> for Ident {
@@ -122,19 +190,100 @@ impl From<&str> for Ident {
 impl fmt::Display for Ident {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.quote_style {
-            Some(q) if q == '"' || q == '\'' || q == '`' => write!(f, "{}{}{}", q, self.value, q),
-            Some(q) if q == '[' => write!(f, "[{}]", self.value),
+            Some(q) if q == '[' => write!(f, "[{}]", escape_quoted_identifier(&self.value, ']')),
+            Some(q) => write!(f, "{}{}{}", q, escape_quoted_identifier(&self.value, q), q),
             None => f.write_str(&self.value),
-            _ => panic!("unexpected quote style"),
         }
     }
 }
 
+pub struct EscapeQuotedIdentifier<'a>(&'a str, char);
+
+impl<'a> fmt::Display for EscapeQuotedIdentifier<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            if c == self.1 {
+                write!(f, "{}{}", c, c)?;
+            } else {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Double every `quote_end` in a delimited identifier
+pub fn escape_quoted_identifier(s: &str, quote_end: char) -> EscapeQuotedIdentifier<'_> {
+    EscapeQuotedIdentifier(s, quote_end)
+}
+
+pub struct EscapeComment<'a>(&'a str);
+
+impl<'a> fmt::Display for EscapeComment<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = self.0.split("*/");
+        if let Some(first) = parts.next() {
+            f.write_str(first)?;
+        }
+        for part in parts {
+            write!(f, "* /{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+/// The text of a `/* ... */` comment, with any `*/` in it, which can't be
+/// escaped, broken up so that it doesn't end the comment
+pub fn escape_comment(s: &str) -> EscapeComment<'_> {
+    EscapeComment(s)
+}
+
 /// A name of a table, view, custom type, etc., possibly multi-part, i.e. db.schema.obj
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ObjectName(pub Vec<Ident>);
 
+impl ObjectName {
+    /// The last part of the name, e.g. `t` in `db.t`, or `None` for the
+    /// empty name of e.g. `Statement::Drop::on_info` without `ON`
+    pub fn name(&self) -> Option<&Ident> {
+        self.0.last()
+    }
+
+    /// The part before the last one, e.g. `db` in `db.t`, if any
+    pub fn schema(&self) -> Option<&Ident> {
+        self.0.len().checked_sub(2).map(|index| &self.0[index])
+    }
+
+    /// Whether this is the name of the object `name` in `schema`, ignoring
+    /// quotes and, unless `case_sensitive`, case. Without a `schema`, only
+    /// the last part of the name is compared, while with one the name must
+    /// be qualified by that schema. MySQL table and schema names are case
+    /// sensitive where the file system is, e.g. on Linux.
+    pub fn matches(&self, schema: Option<&str>, name: &str, case_sensitive: bool) -> bool {
+        let schema_matches = match schema {
+            Some(schema) => self
+                .schema()
+                .is_some_and(|ident| ident.matches(schema, case_sensitive)),
+            None => true,
+        };
+        schema_matches
+            && self
+                .name()
+                .is_some_and(|ident| ident.matches(name, case_sensitive))
+    }
+
+    /// The name with every part normalized, see `Ident::normalized`
+    pub fn normalized(&self, case_sensitive: bool) -> ObjectName {
+        ObjectName(
+            self.0
+                .iter()
+                .map(|ident| ident.normalized(case_sensitive))
+                .collect(),
+        )
+    }
+}
+
 impl fmt::Display for ObjectName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", display_separated(&self.0, "."))
@@ -146,7 +295,10 @@ impl fmt::Display for ObjectName {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExplainStmt{
     Stmt(Box<Statement>),
-    Connection(Value)
+    /// `FOR CONNECTION <id>`, with the id a number or a placeholder
+    Connection(Value),
+    /// `EXPLAIN <table>`, a synonym of `DESCRIBE <table>`
+    TableDescribe(ObjectName),
 }
 impl fmt::Display for ExplainStmt {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -154,6 +306,7 @@ impl fmt::Display for ExplainStmt {
         match self {
             Stmt(ident) => write!(f, "{}", ident),
             Connection(literal) => write!(f, "FOR CONNECTION {}", literal),
+            TableDescribe(table_name) => write!(f, "{}", table_name),
         }
     }
 }
@@ -169,13 +322,31 @@ impl fmt::Display for ExplainFormat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use ExplainFormat::*;
         match self {
-            TRADITIONAL => write!(f, "=TRADITIONAL"),
-            JSON => write!(f, "=JSON"),
-            TREE => write!(f, "=TREE"),
+            TRADITIONAL => write!(f, "TRADITIONAL"),
+            JSON => write!(f, "JSON"),
+            TREE => write!(f, "TREE"),
         }
     }
 }
 
+/// The `EXTENDED` and `PARTITIONS` modifiers of `EXPLAIN`, which MySQL 5.7
+/// deprecated and 8.0 removed, as their output is always shown now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExplainModifier {
+    Extended,
+    Partitions,
+}
+
+impl fmt::Display for ExplainModifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ExplainModifier::Extended => "EXTENDED",
+            ExplainModifier::Partitions => "PARTITIONS",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExplainType{
@@ -253,6 +424,15 @@ pub enum Expr {
         field: DateTimeField,
         expr: Box<Expr>,
     },
+    /// MySQL `INTERVAL <expr> <unit>`, such as `INTERVAL ? DAY`, whose value
+    /// is an expression rather than the string of `Value::Interval`
+    Interval {
+        value: Box<Expr>,
+        unit: DateTimeField,
+    },
+    /// A reference to the select item at a position, counted from 1, such
+    /// as the `2` in `ORDER BY 2` or `GROUP BY 2`
+    Position(u64),
     /// `expr COLLATE collation`
     Collate {
         expr: Box<Expr>,
@@ -339,6 +519,8 @@ impl fmt::Display for Expr {
             Expr::UnaryOp { op, expr } => write!(f, "{} {}", op, expr),
             Expr::Cast { expr, data_type } => write!(f, "CAST({} AS {})", expr, data_type),
             Expr::Extract { field, expr } => write!(f, "EXTRACT({} FROM {})", field, expr),
+            Expr::Interval { value, unit } => write!(f, "INTERVAL {} {}", value, unit),
+            Expr::Position(position) => write!(f, "{}", position),
             Expr::Collate { expr, collation } => write!(f, "{} COLLATE {}", expr, collation),
             Expr::Nested(ast) => write!(f, "({})", ast),
             Expr::BitwiseNested(ast) => write!(f, "~{}", ast),
@@ -495,30 +677,62 @@ pub enum Statement {
     /// EXPLAIN
     Explain {
         analyze: Option<bool>,
+        /// The legacy `EXTENDED` or `PARTITIONS`
+        #[cfg_attr(feature = "serde", serde(default))]
+        modifier: Option<ExplainModifier>,
         format_type: Option<ExplainFormat>,
         body: ExplainStmt
     },
     /// INSERT
     Insert {
+        /// MySQL optimizer hints `/*+ ... */`
+        hints: Option<String>,
+        /// Comments `/* ... */` following the keyword and hints, as written
+        #[cfg_attr(feature = "serde", serde(default))]
+        comments: Vec<String>,
         ///PRIORITY
         priority: Option<Priority>,
         /// IGNORE
         ignore: bool,
+        /// Whether `INTO` was written, as MySQL lets it be left out
+        #[cfg_attr(feature = "serde", serde(default))]
+        into: bool,
         /// TABLE
         table_name: ObjectName,
         /// COLUMNS
         columns: Vec<Ident>,
+        /// Whether the row was written as `SET <column> = <value>, ...`,
+        /// which is kept as `columns` and a `source` of one `VALUES` row
+        #[cfg_attr(feature = "serde", serde(default))]
+        set: bool,
         /// A SQL query that specifies what to insert
         source: Box<Query>,
         /// ON DUPLICATE KEY UPDATE
         update: Option<Vec<Assignment>>,
     },
-    /// REPLACE
+    /// REPLACE, written like `INSERT` without `IGNORE` and
+    /// `ON DUPLICATE KEY UPDATE`
     Replace {
+        /// MySQL optimizer hints `/*+ ... */`
+        #[cfg_attr(feature = "serde", serde(default))]
+        hints: Option<String>,
+        /// Comments `/* ... */` following the keyword and hints, as written
+        #[cfg_attr(feature = "serde", serde(default))]
+        comments: Vec<String>,
+        /// `LOW_PRIORITY` or `DELAYED`
+        #[cfg_attr(feature = "serde", serde(default))]
+        priority: Option<Priority>,
+        /// Whether `INTO` was written, as MySQL lets it be left out
+        #[cfg_attr(feature = "serde", serde(default))]
+        into: bool,
         /// TABLE
         table_name: ObjectName,
         /// COLUMNS
         columns: Vec<Ident>,
+        /// Whether the row was written as `SET <column> = <value>, ...`, as
+        /// with `Statement::Insert`
+        #[cfg_attr(feature = "serde", serde(default))]
+        set: bool,
         /// A SQL query that specifies what to insert
         source: Box<Query>,
     },
@@ -533,6 +747,11 @@ pub enum Statement {
     },
     /// UPDATE
     Update {
+        /// MySQL optimizer hints `/*+ ... */`
+        hints: Option<String>,
+        /// Comments `/* ... */` following the keyword and hints, as written
+        #[cfg_attr(feature = "serde", serde(default))]
+        comments: Vec<String>,
         /// TABLE
         table_name: ObjectName,
         /// Column assignments
@@ -544,6 +763,11 @@ pub enum Statement {
     },
     /// DELETE
     Delete {
+        /// MySQL optimizer hints `/*+ ... */`
+        hints: Option<String>,
+        /// Comments `/* ... */` following the keyword and hints, as written
+        #[cfg_attr(feature = "serde", serde(default))]
+        comments: Vec<String>,
         /// FROM
         table_name: ObjectName,
         /// WHERE
@@ -568,6 +792,10 @@ pub enum Statement {
         constraints: Vec<TableConstraint>,
         with_options: Vec<SqlOption>,
         table_options: Vec<TableOptionDef>,
+        /// MySQL `PARTITION BY ...`
+        partition: Option<PartitionDefinition>,
+        /// MySQL `IGNORE` or `REPLACE` before the `SELECT`
+        on_duplicate: Option<CreateTableOnDuplicate>,
         if_not_exists: bool,
         external: bool,
         file_format: Option<FileFormat>,
@@ -587,8 +815,18 @@ pub enum Statement {
         /// index name
         name: ObjectName,
         table_name: ObjectName,
-        columns: Vec<Ident>,
+        columns: Vec<IndexKeyPart>,
         unique: bool,
+        /// MySQL `FULLTEXT` or `SPATIAL`
+        kind: Option<MysqlIndexStorageType>,
+        /// `USING { BTREE | HASH }` before `ON`
+        using: Option<MysqlIndexType>,
+        /// index options after the key parts
+        options: Vec<IndexOptions>,
+        /// `ALGORITHM [=] { DEFAULT | INPLACE | COPY }`
+        algorithm: Option<Ident>,
+        /// `LOCK [=] { DEFAULT | NONE | SHARED | EXCLUSIVE }`
+        lock: Option<Ident>,
         if_not_exists: bool,
     },
     /// ALTER TABLE
@@ -607,9 +845,15 @@ pub enum Statement {
         names: Vec<ObjectName>,
         /// an optional `drop index` for mysql
         on_info: ObjectName,
-        /// Whether `CASCADE` was specified. This will be `false` when
-        /// `RESTRICT` or no drop behavior at all was specified.
+        /// MySQL `DROP INDEX ... ALGORITHM [=] { DEFAULT | INPLACE | COPY }`
+        algorithm: Option<Ident>,
+        /// MySQL `DROP INDEX ... LOCK [=] { DEFAULT | NONE | SHARED | EXCLUSIVE }`
+        lock: Option<Ident>,
+        /// Whether `CASCADE` was specified
         cascade: bool,
+        /// Whether `RESTRICT` was specified, which can't go with `CASCADE`
+        #[cfg_attr(feature = "serde", serde(default))]
+        restrict: bool,
     },
     /// SET <variable>
     ///
@@ -641,8 +885,7 @@ pub enum Statement {
     ShowVariable {
         variable: Ident ,
         global: bool,
-        /// WHERE
-        selection: Option<Expr>,
+        filter: Option<ShowStatementFilter>,
     },
     /// SHOW COLUMNS
     ///
@@ -650,7 +893,28 @@ pub enum Statement {
     ShowColumns {
         extended: bool,
         full: bool,
+        /// `FIELDS` rather than `COLUMNS`
+        #[cfg_attr(feature = "serde", serde(default))]
+        fields: bool,
+        /// `IN` rather than `FROM` before the table
+        #[cfg_attr(feature = "serde", serde(default))]
+        table_in: bool,
         table_name: ObjectName,
+        /// The `FROM <database>` after the table, if any
+        #[cfg_attr(feature = "serde", serde(default))]
+        database: Option<Ident>,
+        /// `IN` rather than `FROM` before the database
+        #[cfg_attr(feature = "serde", serde(default))]
+        database_in: bool,
+        filter: Option<ShowStatementFilter>,
+    },
+    /// `SHOW { OPEN TABLES | TRIGGERS | EVENTS } [ FROM <database> ]` or
+    /// `SHOW { PROCEDURE | FUNCTION } STATUS`, with an optional filter
+    ///
+    /// Note: this is a MySQL-specific statement.
+    ShowObjects {
+        kind: ShowObjectKind,
+        database: Option<Ident>,
         filter: Option<ShowStatementFilter>,
     },
     /// SHOW CREATE TABLE
@@ -666,8 +930,12 @@ pub enum Statement {
     Commit { chain: bool },
     /// `ROLLBACK [ TRANSACTION | WORK ] [ AND [ NO ] CHAIN ]`
     Rollback { chain: bool },
-    /// CREATE SCHEMA
-    CreateSchema { schema_name: ObjectName },
+    /// `CREATE { SCHEMA | DATABASE } [ IF NOT EXISTS ] <name>`
+    CreateSchema {
+        schema_name: ObjectName,
+        #[cfg_attr(feature = "serde", serde(default))]
+        if_not_exists: bool,
+    },
 
     /// ASSERT <condition> [AS <message>]
     Assert {
@@ -683,17 +951,28 @@ pub enum Statement {
 
     /// call statement
     Call {
-        name: Ident,
+        /// The procedure, possibly qualified with its database
+        name: ObjectName,
+        /// The arguments, or `None` when the parentheses are left out
         parameter: Option<Vec<Expr>>
     },
     /// USE DATABASE
     ChangeDatabase {
-        database: String
+        database: Ident
     },
 
     Desc {
         table_name: ObjectName
     },
+    /// A statement written entirely inside a MySQL versioned comment,
+    /// e.g. `/*!40101 SET NAMES utf8 */`
+    VersionedComment {
+        version: Option<u64>,
+        statement: Box<Statement>,
+    },
+    /// The `DELIMITER <delimiter>` command of the `mysql` command-line
+    /// client, see `Parser::parse_sql_with_delimiters`
+    ClientDelimiter { delimiter: String },
 }
 
 impl fmt::Display for Statement {
@@ -703,52 +982,59 @@ impl fmt::Display for Statement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Statement::Query(s) => write!(f, "{}", s),
-            Statement::Explain{ analyze, format_type, body } => {
+            Statement::Explain{ analyze, modifier, format_type, body } => {
                 write!(f, "EXPLAIN")?;
-                if let Some(_a) = analyze{
-                    write!(f, "ANALYZE")?;
+                if let Some(true) = analyze {
+                    write!(f, " ANALYZE")?;
+                }
+                if let Some(modifier) = modifier {
+                    write!(f, " {}", modifier)?;
                 }
                 if let Some(a) = format_type{
-                    write!(f, "FORMAT = {}", a)?;
+                    write!(f, " FORMAT={}", a)?;
                 }
-                write!(f, "{}", body)
+                write!(f, " {}", body)
             }
             Statement::Insert {
-                priority, ignore, table_name,
+                hints,
+                comments,
+                priority, ignore, into, table_name,
                 columns,
+                set,
                 source, update,
             } => {
                 write!(f, "INSERT ")?;
-                if let Some(pp) = priority{
-                    match pp{
-                        Priority::DELAYED => write!(f, "DELAYED ")?,
-                        Priority::HIGH_PRIORITY => write!(f, "HIGH_PRIORITY ")?,
-                        Priority::LOW_PRIORITY => write!(f, "LOW_PRIORITY ")?
-                    }
-                }
+                write_insert_prefix(f, hints, comments, priority)?;
                 if *ignore{
                     write!(f, "IGNORE ")?;
                 }
-                write!(f, "INTO {} ", table_name)?;
-                if !columns.is_empty() {
-                    write!(f, "({}) ", display_comma_separated(columns))?;
+                if *into {
+                    write!(f, "INTO ")?;
                 }
-                write!(f, "{}", source)?;
+                write!(f, "{} ", table_name)?;
+                write_insert_source(f, columns, *set, source)?;
                 if let Some(update) = update {
                     write!(f, " ON DUPLICATE KEY UPDATE {}", display_comma_separated(update))?;
                 }
                 Ok(())
             }
             Statement::Replace {
+                hints,
+                comments,
+                priority,
+                into,
                 table_name,
                 columns,
+                set,
                 source,
             } => {
-                write!(f, "INSERT INTO {} ", table_name)?;
-                if !columns.is_empty() {
-                    write!(f, "({}) ", display_comma_separated(columns))?;
+                write!(f, "REPLACE ")?;
+                write_insert_prefix(f, hints, comments, priority)?;
+                if *into {
+                    write!(f, "INTO ")?;
                 }
-                write!(f, "{}", source)
+                write!(f, "{} ", table_name)?;
+                write_insert_source(f, columns, *set, source)
             }
             Statement::Copy {
                 table_name,
@@ -776,12 +1062,21 @@ impl fmt::Display for Statement {
                 write!(f, "\n\\.")
             }
             Statement::Update {
+                hints,
+                comments,
                 table_name,
                 assignments,
                 selection,
                 limit
             } => {
-                write!(f, "UPDATE {}", table_name)?;
+                write!(f, "UPDATE ")?;
+                if let Some(hints) = hints {
+                    write!(f, "/*+{}*/ ", escape_comment(hints))?;
+                }
+                for comment in comments {
+                    write!(f, "/*{}*/ ", escape_comment(comment))?;
+                }
+                write!(f, "{}", table_name)?;
                 if !assignments.is_empty() {
                     write!(f, " SET ")?;
                     write!(f, "{}", display_comma_separated(assignments))?;
@@ -795,10 +1090,19 @@ impl fmt::Display for Statement {
                 Ok(())
             }
             Statement::Delete {
+                hints,
+                comments,
                 table_name,
                 selection,
             } => {
-                write!(f, "DELETE FROM {}", table_name)?;
+                write!(f, "DELETE ")?;
+                if let Some(hints) = hints {
+                    write!(f, "/*+{}*/ ", escape_comment(hints))?;
+                }
+                for comment in comments {
+                    write!(f, "/*{}*/ ", escape_comment(comment))?;
+                }
+                write!(f, "FROM {}", table_name)?;
                 if let Some(selection) = selection {
                     write!(f, " WHERE {}", selection)?;
                 }
@@ -833,7 +1137,7 @@ impl fmt::Display for Statement {
                 columns,
                 index, constraints,
                 with_options,
-                table_options, if_not_exists,
+                table_options, partition, on_duplicate, if_not_exists,
                 external,
                 file_format,
                 location,
@@ -854,17 +1158,16 @@ impl fmt::Display for Statement {
                     if_not_exists = if *if_not_exists { "IF NOT EXISTS " } else { "" },
                     name = name,
                 )?;
-                if !columns.is_empty() || !constraints.is_empty() {
-                    write!(f, " ({}", display_comma_separated(columns))?;
-                    if !columns.is_empty() && !constraints.is_empty() {
-                        write!(f, ", ")?;
-                    }
-
-                    if !index.is_empty(){
-                        write!(f, ", {}", display_comma_separated(index))?;
-                    }
-
-                    write!(f, "{})", display_comma_separated(constraints))?;
+                // columns, then indexes, then constraints, as the parser
+                // reads them in any order but keeps them apart
+                let elements: Vec<&dyn fmt::Display> = columns
+                    .iter()
+                    .map(|column| column as &dyn fmt::Display)
+                    .chain(index.iter().map(|index| index as &dyn fmt::Display))
+                    .chain(constraints.iter().map(|constraint| constraint as &dyn fmt::Display))
+                    .collect();
+                if !elements.is_empty() {
+                    write!(f, " ({})", display_comma_separated(&elements))?;
                 } else if query.is_none() {
                     // PostgreSQL allows `CREATE TABLE t ();`, but requires empty parens
                     write!(f, " ()")?;
@@ -875,21 +1178,28 @@ impl fmt::Display for Statement {
                 }
 
                 if *external {
-                    write!(
-                        f,
-                        " STORED AS {} LOCATION '{}'",
-                        file_format.as_ref().unwrap(),
-                        location.as_ref().unwrap()
-                    )?;
+                    if let Some(file_format) = file_format {
+                        write!(f, " STORED AS {}", file_format)?;
+                    }
+                    if let Some(location) = location {
+                        write!(f, " LOCATION '{}'", value::escape_single_quote_string(location))?;
+                    }
                 }
 
                 for tbl_option in table_options{
-                    write!(f, "{}", tbl_option)?;
+                    write!(f, " {}", tbl_option)?;
+                }
+
+                if let Some(partition) = partition {
+                    write!(f, " {}", partition)?;
                 }
 
                 if !with_options.is_empty() {
                     write!(f, " WITH ({})", display_comma_separated(with_options))?;
                 }
+                if let Some(on_duplicate) = on_duplicate {
+                    write!(f, " {}", on_duplicate)?;
+                }
                 if let Some(query) = query {
                     write!(f, " AS {}", query)?;
                 }
@@ -918,41 +1228,76 @@ impl fmt::Display for Statement {
                 table_name,
                 columns,
                 unique,
+                kind,
+                using,
+                options,
+                algorithm,
+                lock,
                 if_not_exists,
             } => {
+                write!(f, "CREATE {}", if *unique { "UNIQUE " } else { "" })?;
+                if let Some(kind) = kind {
+                    write!(f, "{}", kind)?;
+                }
                 write!(
                     f,
-                    "CREATE{}INDEX{}{} ON {}({}",
-                    if *unique { " UNIQUE " } else { " " },
-                    if *if_not_exists {
-                        " IF NOT EXISTS "
-                    } else {
-                        " "
-                    },
-                    name,
-                    table_name,
-                    display_separated(columns, ",")
+                    "INDEX {}{}",
+                    if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                    name
                 )?;
-                write!(f, ");")
+                if let Some(using) = using {
+                    write!(f, " {}", using)?;
+                }
+                write!(f, " ON {} ({})", table_name, display_comma_separated(columns))?;
+                for option in options {
+                    write!(f, " {}", option)?;
+                }
+                if let Some(algorithm) = algorithm {
+                    write!(f, " ALGORITHM={}", algorithm)?;
+                }
+                if let Some(lock) = lock {
+                    write!(f, " LOCK={}", lock)?;
+                }
+                Ok(())
             }
             Statement::AlterTable { name, operation } => {
-                write!(f, "ALTER TABLE {} {}", name, display_separated(operation, ","))
+                write!(f, "ALTER TABLE {} {}", name, display_comma_separated(operation))
 
             }
             Statement::Drop {
                 object_type,
                 if_exists,
                 names,
-                on_info, cascade,
-            } => write!(
-                f,
-                "DROP {}{} {}{} {}",
-                object_type,
-                if *if_exists { " IF EXISTS" } else { "" },
-                display_comma_separated(names),
-                if *cascade { " CASCADE" } else { "" },
-                if object_type == &ObjectType::Index { format!( "ON {}", on_info)} else { "".to_string() },
-            ),
+                on_info,
+                algorithm,
+                lock,
+                cascade,
+                restrict,
+            } => {
+                write!(
+                    f,
+                    "DROP {}{} {}",
+                    object_type,
+                    if *if_exists { " IF EXISTS" } else { "" },
+                    display_comma_separated(names),
+                )?;
+                if !on_info.0.is_empty() {
+                    write!(f, " ON {}", on_info)?;
+                }
+                if let Some(algorithm) = algorithm {
+                    write!(f, " ALGORITHM={}", algorithm)?;
+                }
+                if let Some(lock) = lock {
+                    write!(f, " LOCK={}", lock)?;
+                }
+                if *cascade {
+                    write!(f, " CASCADE")?;
+                }
+                if *restrict {
+                    write!(f, " RESTRICT")?;
+                }
+                Ok(())
+            }
             Statement::SetVariable {
                 local,
                 variable,
@@ -962,31 +1307,38 @@ impl fmt::Display for Statement {
                 if *local {
                     f.write_str("LOCAL ")?;
                 }
-                write!(f, "{} = {}", variable, value)
+                // MySQL only accepts `SET NAMES <charset>`, without `=`
+                if variable.quote_style.is_none() && variable.eq_ignore_case("NAMES") {
+                    write!(f, "{} {}", variable, value)
+                } else {
+                    write!(f, "{} = {}", variable, value)
+                }
             },
             Statement::ReLoad { variable, selection } => {
+                write!(f, "RELOAD {}", variable)?;
                 if let Some(selection) = selection {
-                    write!(f, "RELOAD USER")?;
                     write!(f, " WHERE {}", selection)?;
-
-                }else {
-                    write!(f, "RELOAD CONFIG")?;
                 }
                 Ok(())
             },
-            Statement::ShowVariable { variable, global, selection } => {
-                write!(f, "SHOW {} {}", if *global {"GLOBAL"} else {""}, variable )?;
-                if let Some(selection) = selection {
-                    write!(f, " WHERE {}", selection)?;
+            Statement::ShowVariable { variable, global, filter } => {
+                write!(f, "SHOW {}{}", if *global { "GLOBAL " } else { "" }, variable)?;
+                if let Some(filter) = filter {
+                    write!(f, " {}", filter)?;
                 }
                 Ok(())
             }
             Statement::ShowColumns {
                 extended,
                 full,
+                fields,
+                table_in,
                 table_name,
+                database,
+                database_in,
                 filter,
             } => {
+                let from_or_in = |is_in: bool| if is_in { "IN" } else { "FROM" };
                 f.write_str("SHOW ")?;
                 if *extended {
                     f.write_str("EXTENDED ")?;
@@ -994,7 +1346,16 @@ impl fmt::Display for Statement {
                 if *full {
                     f.write_str("FULL ")?;
                 }
-                write!(f, "COLUMNS FROM {}", table_name)?;
+                write!(
+                    f,
+                    "{} {} {}",
+                    if *fields { "FIELDS" } else { "COLUMNS" },
+                    from_or_in(*table_in),
+                    table_name
+                )?;
+                if let Some(database) = database {
+                    write!(f, " {} {}", from_or_in(*database_in), database)?;
+                }
                 if let Some(filter) = filter {
                     write!(f, " {}", filter)?;
                 }
@@ -1020,7 +1381,15 @@ impl fmt::Display for Statement {
             Statement::Rollback { chain } => {
                 write!(f, "ROLLBACK{}", if *chain { " AND CHAIN" } else { "" },)
             }
-            Statement::CreateSchema { schema_name } => write!(f, "CREATE SCHEMA {}", schema_name),
+            Statement::CreateSchema {
+                schema_name,
+                if_not_exists,
+            } => write!(
+                f,
+                "CREATE SCHEMA {if_not_exists}{name}",
+                if_not_exists = if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                name = schema_name
+            ),
             Statement::Assert { condition, message } => {
                 write!(f, "ASSERT {}", condition)?;
 
@@ -1029,16 +1398,16 @@ impl fmt::Display for Statement {
                 }
                 Ok(())
             }
-            Statement::UNLock { chain } => {
-                write!(f, "UNLOCK{}", if *chain { " AND CHAIN" } else { "" },)
+            Statement::UNLock { .. } => {
+                write!(f, "UNLOCK TABLES")
             }
             Statement::Lock { lock_tables } => {
-                write!(f, "LOCK{:?}", lock_tables)
+                write!(f, "LOCK TABLES {}", display_comma_separated(lock_tables))
             }
             Statement::Call { name, parameter } => {
-                write!(f, "Call {}", name)?;
+                write!(f, "CALL {}", name)?;
                 if let Some(p) = parameter{
-                    write!(f, "parameter{:?}",p)?;
+                    write!(f, "({})", display_comma_separated(p))?;
                 }
                 Ok(())
             }
@@ -1048,31 +1417,110 @@ impl fmt::Display for Statement {
             Statement::AdminSetVariable { variable, value, selection } => {
                 write!(f, "SET {} = {}", variable, value)?;
                 if let Some(p) = selection{
-                    write!(f, "WHERE {}", p)?;
+                    write!(f, " WHERE {}", p)?;
                 }
                 Ok(())
             }
             Statement::Desc { table_name } => {
                 write!(f, "DESC {}", table_name)
             }
+            Statement::ShowObjects {
+                kind,
+                database,
+                filter,
+            } => {
+                write!(f, "SHOW {}", kind)?;
+                if let Some(database) = database {
+                    write!(f, " FROM {}", database)?;
+                }
+                if let Some(filter) = filter {
+                    write!(f, " {}", filter)?;
+                }
+                Ok(())
+            }
             Statement::ShowCreate { table_name } => {
                 write!(f, "SHOW CREATE TABLE {}", table_name)
             }
+            Statement::VersionedComment { version, statement } => {
+                f.write_str("/*!")?;
+                if let Some(version) = version {
+                    write!(f, "{}", version)?;
+                }
+                write!(f, " {} */", statement)
+            }
+            Statement::ClientDelimiter { delimiter } => write!(f, "DELIMITER {}", delimiter),
         }
     }
 }
 
+/// Writes the hints, comments and priority following `INSERT` or `REPLACE`
+fn write_insert_prefix(
+    f: &mut fmt::Formatter,
+    hints: &Option<String>,
+    comments: &[String],
+    priority: &Option<Priority>,
+) -> fmt::Result {
+    if let Some(hints) = hints {
+        write!(f, "/*+{}*/ ", escape_comment(hints))?;
+    }
+    for comment in comments {
+        write!(f, "/*{}*/ ", escape_comment(comment))?;
+    }
+    match priority {
+        Some(Priority::DELAYED) => write!(f, "DELAYED "),
+        Some(Priority::HIGH_PRIORITY) => write!(f, "HIGH_PRIORITY "),
+        Some(Priority::LOW_PRIORITY) => write!(f, "LOW_PRIORITY "),
+        None => Ok(()),
+    }
+}
+
+/// Writes the columns and source of `INSERT` or `REPLACE`, as `SET`
+/// assignments with `set` as long as the source is one row of a value for
+/// each column
+fn write_insert_source(
+    f: &mut fmt::Formatter,
+    columns: &[Ident],
+    set: bool,
+    source: &Query,
+) -> fmt::Result {
+    let values_only = source.ctes.is_empty()
+        && source.order_by.is_empty()
+        && source.limit.is_none()
+        && source.offset.is_none()
+        && !source.update
+        && source.fetch.is_none();
+    if set && values_only {
+        if let SetExpr::Values(Values(rows)) = &source.body {
+            if let [row] = rows.as_slice() {
+                if !columns.is_empty() && row.len() == columns.len() {
+                    write!(f, "SET ")?;
+                    for (i, (column, value)) in columns.iter().zip(row).enumerate() {
+                        let separator = if i == 0 { "" } else { ", " };
+                        write!(f, "{}{} = {}", separator, column, value)?;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+    }
+    if !columns.is_empty() {
+        write!(f, "({}) ", display_comma_separated(columns))?;
+    }
+    write!(f, "{}", source)
+}
+
 /// SQL assignment `foo = expr` as used in SQLUpdate
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Assignment {
-    pub id: Ident,
+    /// The assigned column, qualified as in `t.foo` for a multi-table update
+    pub id: Vec<Ident>,
     pub value: Expr,
 }
 
 impl fmt::Display for Assignment {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} = {}", self.id, self.value)
+        write!(f, "{} = {}", display_separated(&self.id, "."), self.value)
     }
 }
 
@@ -1085,10 +1533,17 @@ pub struct Function {
     pub over: Option<WindowSpec>,
     // aggregate functions may specify eg `COUNT(DISTINCT x)`
     pub distinct: bool,
+    /// Whether the function was called without parentheses, as the
+    /// niladic functions such as `CURRENT_TIMESTAMP` can be
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub special: bool,
 }
 
 impl fmt::Display for Function {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.special {
+            return write!(f, "{}", self.name);
+        }
         write!(
             f,
             "{}({}{})",
@@ -1292,6 +1747,39 @@ impl fmt::Display for TransactionIsolationLevel {
     }
 }
 
+/// What `Statement::ShowObjects` lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShowObjectKind {
+    OpenTables,
+    Triggers,
+    Events,
+    ProcedureStatus,
+    FunctionStatus,
+}
+
+impl ShowObjectKind {
+    /// Whether the objects can be listed `FROM <database>`
+    pub fn has_database(self) -> bool {
+        match self {
+            ShowObjectKind::OpenTables | ShowObjectKind::Triggers | ShowObjectKind::Events => true,
+            ShowObjectKind::ProcedureStatus | ShowObjectKind::FunctionStatus => false,
+        }
+    }
+}
+
+impl fmt::Display for ShowObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ShowObjectKind::OpenTables => "OPEN TABLES",
+            ShowObjectKind::Triggers => "TRIGGERS",
+            ShowObjectKind::Events => "EVENTS",
+            ShowObjectKind::ProcedureStatus => "PROCEDURE STATUS",
+            ShowObjectKind::FunctionStatus => "FUNCTION STATUS",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ShowStatementFilter {
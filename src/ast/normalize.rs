@@ -0,0 +1,273 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Normalized statements and their fingerprints, for aggregating query digests
+
+use super::quote::{is_keyword_expr, needs_quotes, visit_names};
+use super::*;
+use crate::format::to_lowercase_keywords;
+
+impl Statement {
+    /// The statement with every literal replaced by `?`, comments and
+    /// optimizer hints removed and keywords and function names lower cased,
+    /// so that statements differing only in their values normalize the same.
+    /// The names of tables, columns and the like keep their case. With `collapse_lists`,
+    /// `IN` lists become `in (?+)` and the rows of `VALUES` become
+    /// `values (?+)`, as `pt-fingerprint` does.
+    pub fn normalize(&self, collapse_lists: bool) -> String {
+        let mut statement = self.clone();
+        while let Statement::VersionedComment { statement: inner, .. } = statement {
+            statement = *inner;
+        }
+        Normalizer { collapse_lists }.visit_statement(&mut statement);
+        to_lowercase_keywords(statement)
+    }
+
+    /// A stable hash of the statement's normalized form with lists
+    /// collapsed, equal for statements that differ only in their literals
+    pub fn fingerprint_hash(&self) -> u64 {
+        // 64-bit FNV-1a, which unlike `DefaultHasher` won't change between
+        // Rust releases
+        self.normalize(true)
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+            })
+    }
+
+    /// The statement without the differences in how it was written that
+    /// don't change its meaning, so that equivalent statements are equal and
+    /// hash the same. Keyword case and an `AS` before aliases aren't kept
+    /// when parsing already, and on top of that:
+    ///
+    /// - the comments of `SELECT`, `INSERT`, `REPLACE`, `UPDATE` and `DELETE`
+    ///   are removed, though optimizer hints are kept
+    /// - parentheses are removed around a whole expression, such as a select
+    ///   item, a `WHERE` condition or a function argument, and around
+    ///   literals, column references, function calls and other parentheses
+    ///   anywhere, but kept around an operand that is an operation itself
+    /// - parentheses are removed around a `SELECT` without `ORDER BY`,
+    ///   `LIMIT` or other clauses of its own
+    /// - the names of tables, columns and indexes and aliases, those
+    ///   `QuoteOptions` quotes, are lower cased unless `case_sensitive`, and
+    ///   then unquoted unless they need quotes. Function names, which MySQL
+    ///   compares in any case, are lower cased as well.
+    ///
+    /// Unlike `normalize`, literals are kept.
+    pub fn normalized(&self, case_sensitive: bool) -> Statement {
+        let mut statement = self.clone();
+        let mut canonicalizer = Canonicalizer {
+            case_sensitive,
+            in_operand: false,
+        };
+        canonicalizer.visit_statement(&mut statement);
+        visit_names(&mut statement, &|ident| {
+            // `@var` is a variable rather than a name
+            if ident.value.starts_with('@') {
+                return;
+            }
+            if !case_sensitive {
+                ident.value = ident.value.to_lowercase();
+            }
+            if !needs_quotes(&ident.value) {
+                ident.quote_style = None;
+            }
+        });
+        statement
+    }
+}
+
+struct Normalizer {
+    collapse_lists: bool,
+}
+
+fn placeholder(text: &str) -> Expr {
+    Expr::Value(Value::Placeholder(text.to_string()))
+}
+
+impl VisitorMut for Normalizer {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Insert {
+                hints, comments, ..
+            }
+            | Statement::Replace {
+                hints, comments, ..
+            }
+            | Statement::Update {
+                hints, comments, ..
+            }
+            | Statement::Delete {
+                hints, comments, ..
+            } => {
+                *hints = None;
+                comments.clear();
+            }
+            Statement::SetVariable { value, .. } | Statement::AdminSetVariable { value, .. } => {
+                if let SetVariableValue::Literal(value) = value {
+                    *value = Value::Placeholder("?".to_string());
+                }
+            }
+            _ => {}
+        }
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        walk_query_mut(self, query);
+        if self.collapse_lists {
+            if let SetExpr::Values(values) | SetExpr::Value(values) = &mut query.body {
+                values.0 = vec![vec![placeholder("?+")]];
+            }
+        }
+    }
+
+    fn visit_select(&mut self, select: &mut Select) {
+        select.hints = None;
+        select.comments.clear();
+        walk_select_mut(self, select);
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Value(_) | Expr::TypedString { .. } => *expr = placeholder("?"),
+            // a negative number is a single literal
+            Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: inner,
+            }
+            | Expr::UnaryOp {
+                op: UnaryOperator::Plus,
+                expr: inner,
+            } if matches!(**inner, Expr::Value(_)) => *expr = placeholder("?"),
+            Expr::InList { expr, list, .. } if self.collapse_lists => {
+                self.visit_expr(expr);
+                *list = vec![placeholder("?+")];
+            }
+            // MySQL reads these in any case, like the keywords
+            Expr::Identifier(ident) if is_keyword_expr(ident) => {
+                ident.value = ident.value.to_lowercase()
+            }
+            Expr::Function(function) => {
+                for ident in &mut function.name.0 {
+                    ident.value = ident.value.to_lowercase();
+                }
+                walk_expr_mut(self, expr)
+            }
+            _ => walk_expr_mut(self, expr),
+        }
+    }
+}
+
+struct Canonicalizer {
+    case_sensitive: bool,
+    /// Whether the expression being visited is an operand of an operator,
+    /// where parentheses around an operation may change the precedence
+    in_operand: bool,
+}
+
+/// Whether the expression is written the same, however it's parenthesized
+fn is_self_contained(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::Value(_)
+            | Expr::TypedString { .. }
+            | Expr::Function(_)
+            | Expr::Cast { .. }
+            | Expr::Extract { .. }
+            | Expr::Case { .. }
+            | Expr::Exists(_)
+            | Expr::Subquery(_)
+            | Expr::Nested(_)
+    )
+}
+
+fn remove_query_parentheses(body: &mut SetExpr) {
+    match body {
+        SetExpr::Query(query)
+            if matches!(query.body, SetExpr::Select(_))
+                && query.ctes.is_empty()
+                && query.order_by.is_empty()
+                && query.limit.is_none()
+                && query.offset.is_none()
+                && query.fetch.is_none()
+                && !query.update =>
+        {
+            let select = core::mem::replace(&mut query.body, SetExpr::Values(Values(vec![])));
+            *body = select;
+        }
+        SetExpr::SetOperation { left, right, .. } => {
+            remove_query_parentheses(left);
+            remove_query_parentheses(right);
+        }
+        _ => {}
+    }
+}
+
+impl VisitorMut for Canonicalizer {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Insert { comments, .. }
+            | Statement::Replace { comments, .. }
+            | Statement::Update { comments, .. }
+            | Statement::Delete { comments, .. } => comments.clear(),
+            _ => {}
+        }
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        let in_operand = core::mem::replace(&mut self.in_operand, false);
+        walk_query_mut(self, query);
+        self.in_operand = in_operand;
+        remove_query_parentheses(&mut query.body);
+    }
+
+    fn visit_select(&mut self, select: &mut Select) {
+        select.comments.clear();
+        walk_select_mut(self, select);
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        while let Expr::Nested(inner) = expr {
+            if self.in_operand && !is_self_contained(inner) {
+                break;
+            }
+            let inner = core::mem::replace(&mut **inner, Expr::Wildcard);
+            *expr = inner;
+        }
+        if let Expr::Function(function) = expr {
+            if !self.case_sensitive {
+                for ident in &mut function.name.0 {
+                    ident.value = ident.value.to_lowercase();
+                }
+            }
+        }
+        // the arguments of a function, `CAST`, `CASE` etc. are whole
+        // expressions, delimited by their keywords and parentheses
+        let in_operand = core::mem::replace(
+            &mut self.in_operand,
+            !matches!(
+                expr,
+                Expr::Function(_)
+                    | Expr::Cast { .. }
+                    | Expr::Extract { .. }
+                    | Expr::Case { .. }
+                    | Expr::ListAgg(_)
+            ),
+        );
+        walk_expr_mut(self, expr);
+        self.in_operand = in_operand;
+    }
+}
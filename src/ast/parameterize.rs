@@ -0,0 +1,135 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Turning the literals of a statement into parameters of a prepared
+//! statement, and back
+
+use super::*;
+
+/// Which literals `Statement::parameterize_with` leaves in place
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ParameterizeOptions {
+    /// Keep the literals of `LIMIT`, `OFFSET` and `FETCH`, which MySQL
+    /// before 8.0 can't bind
+    pub keep_limit: bool,
+    /// Keep the literals of `CREATE`, `ALTER` and `DROP` statements
+    pub keep_ddl: bool,
+}
+
+impl Statement {
+    /// Replace every number, string, hex and boolean literal with a `?`
+    /// placeholder, returning the literals in the order of the placeholders.
+    /// `NULL` and `INTERVAL '...'` literals are kept. `bind` puts the
+    /// literals back, provided the statement had no placeholders before.
+    pub fn parameterize(&mut self) -> Vec<Value> {
+        self.parameterize_with(ParameterizeOptions::default())
+    }
+
+    /// Like `parameterize`, leaving some of the literals in place
+    pub fn parameterize_with(&mut self, options: ParameterizeOptions) -> Vec<Value> {
+        if options.keep_ddl && self.is_ddl() {
+            return vec![];
+        }
+        let mut parameterizer = Parameterizer {
+            options,
+            values: vec![],
+        };
+        parameterizer.visit_statement(self);
+        parameterizer.values
+    }
+
+    /// Replace the `?` placeholders with `values`, in order, returning how
+    /// many placeholders there are. Placeholders past the end of `values`
+    /// are left as they are.
+    pub fn bind(&mut self, values: &[Value]) -> usize {
+        let mut binder = Binder { values, count: 0 };
+        binder.visit_statement(self);
+        binder.count
+    }
+}
+
+struct Parameterizer {
+    options: ParameterizeOptions,
+    values: Vec<Value>,
+}
+
+impl VisitorMut for Parameterizer {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Update { limit, .. } if self.options.keep_limit => {
+                let kept = limit.take();
+                walk_statement_mut(self, statement);
+                if let Statement::Update { limit, .. } = statement {
+                    *limit = kept;
+                }
+            }
+            _ => walk_statement_mut(self, statement),
+        }
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        if self.options.keep_limit {
+            let limit = query.limit.take();
+            let offset = query.offset.take();
+            let fetch = query.fetch.take();
+            walk_query_mut(self, query);
+            query.limit = limit;
+            query.offset = offset;
+            query.fetch = fetch;
+        } else {
+            walk_query_mut(self, query);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Value(value) if is_parameter(value) => {
+                let value = core::mem::replace(value, Value::Placeholder("?".to_string()));
+                self.values.push(value);
+            }
+            _ => walk_expr_mut(self, expr),
+        }
+    }
+}
+
+fn is_parameter(value: &Value) -> bool {
+    match value {
+        Value::Number(_)
+        | Value::SingleQuotedString { .. }
+        | Value::DoubleQuotedString(_)
+        | Value::NationalStringLiteral(_)
+        | Value::HexStringLiteral(_)
+        | Value::Boolean(_) => true,
+        Value::VariableName(_) | Value::Placeholder(_) | Value::Interval { .. } | Value::Null => {
+            false
+        }
+    }
+}
+
+struct Binder<'a> {
+    values: &'a [Value],
+    count: usize,
+}
+
+impl VisitorMut for Binder<'_> {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Value(value) if *value == Value::Placeholder("?".to_string()) => {
+                if let Some(bound) = self.values.get(self.count) {
+                    *value = bound.clone();
+                }
+                self.count += 1;
+            }
+            _ => walk_expr_mut(self, expr),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The conditions on columns in a `WHERE` clause, see `Select::predicates`
+
+use super::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A condition comparing a column to literals or placeholders
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Predicate {
+    /// The column, qualified by its table if it is in the SQL
+    pub column: Vec<Ident>,
+    pub op: PredicateOp,
+    /// What the column is compared to: the value of a comparison or `LIKE`,
+    /// the bounds of `BETWEEN`, the list of `IN` and nothing for `IS NULL`
+    pub values: Vec<Value>,
+    /// Whether the condition must hold for every row of the result, so an
+    /// index can be used for it: it is not negated, and is one of the
+    /// conditions `AND`ed together at the top of the `WHERE` clause
+    pub sargable: bool,
+}
+
+/// The operator of a `Predicate`, written as if the column was on its left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PredicateOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    In,
+    NotIn,
+    Between,
+    NotBetween,
+    Like,
+    NotLike,
+    IsNull,
+    IsNotNull,
+}
+
+impl PredicateOp {
+    fn is_negated(self) -> bool {
+        matches!(
+            self,
+            PredicateOp::NotEq | PredicateOp::NotIn | PredicateOp::NotBetween | PredicateOp::NotLike
+        )
+    }
+}
+
+impl Select {
+    /// The conditions on columns in the `WHERE` clause, in the order they
+    /// appear. Only comparisons, `IN` lists, `BETWEEN`, `LIKE` and `IS [NOT]
+    /// NULL` of a column with literals or placeholders are reported, looking
+    /// through parentheses, `AND`, `OR` and `NOT` but not into subqueries.
+    /// A column can appear in more than one predicate.
+    pub fn predicates(&self) -> Vec<Predicate> {
+        let mut predicates = vec![];
+        if let Some(selection) = &self.selection {
+            collect_predicates(selection, true, &mut predicates);
+        }
+        predicates
+    }
+}
+
+fn collect_predicates(expr: &Expr, sargable: bool, predicates: &mut Vec<Predicate>) {
+    let mut push = |column: &Expr, op: PredicateOp, values: Vec<Value>| {
+        if let Some(column) = as_column(column) {
+            predicates.push(Predicate {
+                column,
+                op,
+                values,
+                sargable: sargable && !op.is_negated(),
+            });
+        }
+    };
+    match expr {
+        Expr::Nested(expr) => collect_predicates(expr, sargable, predicates),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            collect_predicates(left, sargable, predicates);
+            collect_predicates(right, sargable, predicates);
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => {
+            collect_predicates(left, false, predicates);
+            collect_predicates(right, false, predicates);
+        }
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => collect_predicates(expr, false, predicates),
+        Expr::BinaryOp { left, op, right } => {
+            let op = match op {
+                BinaryOperator::Eq => PredicateOp::Eq,
+                BinaryOperator::NotEq => PredicateOp::NotEq,
+                BinaryOperator::Lt => PredicateOp::Lt,
+                BinaryOperator::LtEq => PredicateOp::LtEq,
+                BinaryOperator::Gt => PredicateOp::Gt,
+                BinaryOperator::GtEq => PredicateOp::GtEq,
+                BinaryOperator::Like => PredicateOp::Like,
+                BinaryOperator::NotLike => PredicateOp::NotLike,
+                _ => return,
+            };
+            if let Some(value) = as_value(right) {
+                push(left, op, vec![value]);
+            } else if let (Some(value), Some(op)) = (as_value(left), reversed(op)) {
+                // `5 < a` is `a > 5`
+                push(right, op, vec![value]);
+            }
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            let values: Option<Vec<Value>> = list.iter().map(as_value).collect();
+            if let Some(values) = values {
+                let op = if *negated {
+                    PredicateOp::NotIn
+                } else {
+                    PredicateOp::In
+                };
+                push(expr, op, values);
+            }
+        }
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => {
+            if let (Some(low), Some(high)) = (as_value(low), as_value(high)) {
+                let op = if *negated {
+                    PredicateOp::NotBetween
+                } else {
+                    PredicateOp::Between
+                };
+                push(expr, op, vec![low, high]);
+            }
+        }
+        Expr::IsNull(expr) => push(expr, PredicateOp::IsNull, vec![]),
+        Expr::IsNotNull(expr) => push(expr, PredicateOp::IsNotNull, vec![]),
+        _ => {}
+    }
+}
+
+/// The operator with its operands swapped, unless that can't be written
+fn reversed(op: PredicateOp) -> Option<PredicateOp> {
+    match op {
+        PredicateOp::Eq | PredicateOp::NotEq => Some(op),
+        PredicateOp::Lt => Some(PredicateOp::Gt),
+        PredicateOp::LtEq => Some(PredicateOp::GtEq),
+        PredicateOp::Gt => Some(PredicateOp::Lt),
+        PredicateOp::GtEq => Some(PredicateOp::LtEq),
+        _ => None,
+    }
+}
+
+fn as_column(expr: &Expr) -> Option<Vec<Ident>> {
+    match expr {
+        Expr::Identifier(ident) => Some(vec![ident.clone()]),
+        Expr::CompoundIdentifier(idents) => Some(idents.clone()),
+        Expr::Nested(expr) => as_column(expr),
+        _ => None,
+    }
+}
+
+fn as_value(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Value(value) => Some(value.clone()),
+        Expr::Nested(expr) => as_value(expr),
+        _ => None,
+    }
+}
@@ -74,6 +74,21 @@ pub struct LockInfo{
     pub lock: LOCKType
 }
 
+impl fmt::Display for LOCKType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            LOCKType::Read => "READ",
+            LOCKType::Write => "WRITE",
+        })
+    }
+}
+
+impl fmt::Display for LockInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.table_name, self.lock)
+    }
+}
+
 
 /// A node in a tree, representing a "query body" expression, roughly:
 /// `SELECT ... [ {UNION|EXCEPT|INTERSECT} SELECT ...]`
@@ -141,14 +156,27 @@ impl fmt::Display for SetOperator {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Select {
-    /// comment /*...*/
-    pub comment: Option<Ident>,
-    ///
-    pub distinct: bool,
+    /// MySQL optimizer hints `/*+ ... */`, the raw text between `/*+` and `*/`
+    pub hints: Option<String>,
+    /// Comments `/* ... */` following `SELECT` and the hints, as written
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comments: Vec<String>,
+    /// `ALL`, `DISTINCT` or `DISTINCTROW`, as written
+    pub distinct: Option<Distinct>,
+    /// MySQL modifiers such as `SQL_NO_CACHE` following `distinct`, as
+    /// written
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modifiers: Vec<SelectModifier>,
     /// MSSQL syntax: `TOP (<N>) [ PERCENT ] [ WITH TIES ]`
     pub top: Option<Top>,
     /// projection expressions
     pub projection: Vec<SelectItem>,
+    /// Columns left out of the wildcards of `projection`, for a rewriter to
+    /// expand later. The parser never sets them and MySQL has no syntax for
+    /// this, so they print in a comment that MySQL ignores; see
+    /// `SelectBuilder::try_to_sql` to refuse them instead.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wildcard_except: Vec<Ident>,
     /// FROM
     pub from: Vec<TableWithJoins>,
     /// WHERE
@@ -159,17 +187,86 @@ pub struct Select {
     pub having: Option<Expr>,
 }
 
+impl Select {
+    /// Whether duplicate rows are removed, by `DISTINCT` or `DISTINCTROW`
+    pub fn is_distinct(&self) -> bool {
+        matches!(self.distinct, Some(Distinct::Distinct) | Some(Distinct::DistinctRow))
+    }
+}
+
+/// The keyword after `SELECT` that says whether duplicate rows are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Distinct {
+    All,
+    Distinct,
+    /// MySQL's synonym of `DISTINCT`
+    DistinctRow,
+}
+
+impl fmt::Display for Distinct {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Distinct::All => "ALL",
+            Distinct::Distinct => "DISTINCT",
+            Distinct::DistinctRow => "DISTINCTROW",
+        })
+    }
+}
+
+/// A MySQL modifier of `SELECT`, which doesn't change the rows returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SelectModifier {
+    HighPriority,
+    StraightJoin,
+    SqlSmallResult,
+    SqlBigResult,
+    SqlBufferResult,
+    /// Only in MySQL before 8.0, which dropped the query cache
+    SqlCache,
+    SqlNoCache,
+    SqlCalcFoundRows,
+}
+
+impl fmt::Display for SelectModifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SelectModifier::HighPriority => "HIGH_PRIORITY",
+            SelectModifier::StraightJoin => "STRAIGHT_JOIN",
+            SelectModifier::SqlSmallResult => "SQL_SMALL_RESULT",
+            SelectModifier::SqlBigResult => "SQL_BIG_RESULT",
+            SelectModifier::SqlBufferResult => "SQL_BUFFER_RESULT",
+            SelectModifier::SqlCache => "SQL_CACHE",
+            SelectModifier::SqlNoCache => "SQL_NO_CACHE",
+            SelectModifier::SqlCalcFoundRows => "SQL_CALC_FOUND_ROWS",
+        })
+    }
+}
+
 impl fmt::Display for Select {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SELECT")?;
-        if let Some(c) = &self.comment{
-            write!(f, " /*{}*/", c)?;
+        if let Some(hints) = &self.hints {
+            write!(f, " /*+{}*/", escape_comment(hints))?;
+        }
+        for comment in &self.comments {
+            write!(f, " /*{}*/", escape_comment(comment))?;
+        }
+        if let Some(distinct) = &self.distinct {
+            write!(f, " {}", distinct)?;
+        }
+        for modifier in &self.modifiers {
+            write!(f, " {}", modifier)?;
         }
-        write!(f, "{}", if self.distinct { " DISTINCT" } else { "" })?;
         if let Some(ref top) = self.top {
             write!(f, " {}", top)?;
         }
         write!(f, " {}", display_comma_separated(&self.projection))?;
+        if !self.wildcard_except.is_empty() {
+            let except = display_comma_separated(&self.wildcard_except).to_string();
+            write!(f, " /* EXCEPT {} */", escape_comment(&except))?;
+        }
         if !self.from.is_empty() {
             write!(f, " FROM {}", display_comma_separated(&self.from))?;
         }
@@ -271,6 +368,9 @@ pub enum TableFactor {
     /// nesting of bare tables (`table_with_joins.joins.is_empty()`), so the
     /// name `NestedJoin` is a bit of misnomer.
     NestedJoin(Box<TableWithJoins>),
+    /// MySQL's `DUAL`, the dummy table of `SELECT 1 FROM DUAL`, which
+    /// stands for no table at all
+    Dual,
 }
 
 impl fmt::Display for TableFactor {
@@ -313,6 +413,7 @@ impl fmt::Display for TableFactor {
                 Ok(())
             }
             TableFactor::NestedJoin(table_reference) => write!(f, "({})", table_reference),
+            TableFactor::Dual => write!(f, "DUAL"),
         }
     }
 }
@@ -450,6 +551,33 @@ impl fmt::Display for OrderByExpr {
     }
 }
 
+impl OrderByExpr {
+    /// Replace `NULLS FIRST` or `NULLS LAST` with the `<expr> IS NULL` idiom
+    /// MySQL understands. MySQL sorts NULLs first in ascending order and
+    /// last in descending order, so `a NULLS LAST` becomes `a IS NULL, a`
+    /// while `a DESC NULLS LAST` needs no extra item.
+    pub fn desugar_nulls_ordering(&self) -> Vec<OrderByExpr> {
+        let nulls_first = match self.nulls_first {
+            Some(nulls_first) => nulls_first,
+            None => return vec![self.clone()],
+        };
+        let item = OrderByExpr {
+            nulls_first: None,
+            ..self.clone()
+        };
+        let ascending = self.asc.unwrap_or(true);
+        if nulls_first == ascending {
+            return vec![item];
+        }
+        let is_null = OrderByExpr {
+            expr: Expr::IsNull(Box::new(self.expr.clone())),
+            asc: if nulls_first { Some(false) } else { None },
+            nulls_first: None,
+        };
+        vec![is_null, item]
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Offset {
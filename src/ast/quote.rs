@@ -0,0 +1,331 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quoting of identifiers on output, see `Statement::to_sql_with`
+
+use super::*;
+use crate::dialect::keywords;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which identifiers to quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QuotePolicy {
+    /// Quote every identifier
+    Always,
+    /// Quote the identifiers that are reserved words or have characters
+    /// other than letters, digits, `_` and `$`, and leave the rest as they
+    /// were parsed
+    WhenNeeded,
+}
+
+/// How to quote identifiers, see `Statement::to_sql_with`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteOptions {
+    pub policy: QuotePolicy,
+    /// The quote character, e.g. '`' for MySQL or '"' for ANSI SQL.
+    /// Identifiers that were quoted with another one switch to it.
+    pub quote: char,
+}
+
+impl Default for QuoteOptions {
+    fn default() -> Self {
+        QuoteOptions {
+            policy: QuotePolicy::WhenNeeded,
+            quote: '`',
+        }
+    }
+}
+
+/// Reserved words that the parser reads as an `Expr::Identifier` but are
+/// keywords there, which quoting would turn into column names
+const KEYWORD_EXPRS: &[&str] = &[
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_USER",
+    "DEFAULT",
+    "LOCALTIME",
+    "LOCALTIMESTAMP",
+    "UTC_DATE",
+    "UTC_TIME",
+    "UTC_TIMESTAMP",
+];
+
+/// Whether the identifier is one of `KEYWORD_EXPRS`, written without quotes
+pub(crate) fn is_keyword_expr(ident: &Ident) -> bool {
+    ident.quote_style.is_none()
+        && KEYWORD_EXPRS
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(&ident.value))
+}
+
+impl QuoteOptions {
+    /// Quote the names of the tables, columns and indexes, and the aliases,
+    /// in the statement. Function names and variables are left alone.
+    pub fn apply(&self, statement: &mut Statement) {
+        visit_names(statement, &|ident| self.apply_to_ident(ident))
+    }
+
+    /// Quote the identifier, unless the policy doesn't require it
+    pub fn apply_to_ident(&self, ident: &mut Ident) {
+        // `@var` is a variable rather than a name
+        if ident.value.starts_with('@') {
+            return;
+        }
+        let quoted = ident.quote_style.is_some()
+            || self.policy == QuotePolicy::Always
+            || needs_quotes(&ident.value);
+        if quoted {
+            ident.quote_style = Some(self.quote);
+        }
+    }
+}
+
+/// Whether the identifier can only be written quoted
+pub(super) fn needs_quotes(value: &str) -> bool {
+    value.is_empty()
+        || value.starts_with(|c: char| c.is_ascii_digit())
+        || !value
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        || keywords::is_reserved(value)
+}
+
+impl Statement {
+    /// The SQL of the statement, as `Display` prints it but with its
+    /// identifiers quoted according to the options
+    pub fn to_sql_with(&self, options: &QuoteOptions) -> String {
+        let mut statement = self.clone();
+        options.apply(&mut statement);
+        statement.to_string()
+    }
+}
+
+/// Call `f` on the names of the tables, columns and indexes, and the
+/// aliases, in the statement, which are the identifiers `QuoteOptions` quotes
+pub(crate) fn visit_names(statement: &mut Statement, f: &dyn Fn(&mut Ident)) {
+    Quoter(f).visit_statement(statement)
+}
+
+struct Quoter<'a>(&'a dyn Fn(&mut Ident));
+
+impl<'a> Quoter<'a> {
+    fn quote(&self, ident: &mut Ident) {
+        (self.0)(ident)
+    }
+
+    fn quote_all<'i>(&self, idents: impl IntoIterator<Item = &'i mut Ident>) {
+        idents.into_iter().for_each(|ident| self.quote(ident))
+    }
+
+    fn quote_alias(&self, alias: &mut Option<TableAlias>) {
+        if let Some(alias) = alias {
+            self.quote(&mut alias.name);
+            self.quote_all(&mut alias.columns);
+        }
+    }
+
+    fn quote_column_def(&self, column: &mut ColumnDef) {
+        self.quote(&mut column.name);
+        for option in &mut column.options {
+            if let ColumnOption::ForeignKey {
+                referred_columns, ..
+            } = &mut option.option
+            {
+                self.quote_all(referred_columns);
+            }
+        }
+    }
+
+    fn quote_constraint(&self, constraint: &mut TableConstraint) {
+        match constraint {
+            TableConstraint::Unique { columns, .. } => self.quote_all(columns),
+            TableConstraint::ForeignKey {
+                columns,
+                referred_columns,
+                ..
+            } => self.quote_all(columns.iter_mut().chain(referred_columns)),
+            TableConstraint::Check { .. } => {}
+        }
+    }
+
+    fn quote_index_def(&self, index: &mut IndexDef) {
+        let (IndexDef::Normal(index)
+        | IndexDef::PrimaryKey(index)
+        | IndexDef::ForeignKey(index)
+        | IndexDef::Unique(index)) = index;
+        // `name` is the `KEY` or `INDEX` keyword
+        if let Some(name) = &mut index.index_name {
+            self.quote(name);
+        }
+        self.quote_key_parts(index.key_parts.iter_mut().flatten());
+    }
+
+    fn quote_key_parts<'k>(&self, key_parts: impl Iterator<Item = &'k mut IndexKeyPart>) {
+        for key_part in key_parts {
+            if let IndexKeyPart::Column { name, .. } = key_part {
+                self.quote(name);
+            }
+        }
+    }
+
+    fn quote_alter_table_operation(&self, operation: &mut AlterTableOperation) {
+        match operation {
+            AlterTableOperation::AddConstraint(constraint) => self.quote_constraint(constraint),
+            AlterTableOperation::AddColumn { column_def }
+            | AlterTableOperation::ModifyColumn { column_def, .. } => {
+                self.quote_column_def(column_def)
+            }
+            AlterTableOperation::DropColumn { column_name, .. } => self.quote(column_name),
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            }
+            | AlterTableOperation::RenameIndex {
+                old_name: old_column_name,
+                new_name: new_column_name,
+            } => {
+                self.quote(old_column_name);
+                self.quote(new_column_name);
+            }
+            AlterTableOperation::ChangeColumn {
+                old_column_name,
+                new_column_def,
+            } => {
+                self.quote(old_column_name);
+                self.quote_column_def(new_column_def);
+            }
+            AlterTableOperation::AddIndex { index_def } => self.quote_index_def(&mut index_def.index),
+            AlterTableOperation::DropIndex { index_def } => self.quote_index_def(index_def),
+            AlterTableOperation::OrderBy(columns) => {
+                for column in columns {
+                    if let Expr::Identifier(ident) = &mut column.expr {
+                        self.quote(ident);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> VisitorMut for Quoter<'a> {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::Insert {
+                columns, update, ..
+            } => {
+                self.quote_all(columns);
+                for assignment in update.iter_mut().flatten() {
+                    self.quote_all(&mut assignment.id);
+                }
+            }
+            Statement::Replace { columns, .. }
+            | Statement::Copy { columns, .. }
+            | Statement::CreateView { columns, .. } => self.quote_all(columns),
+            Statement::Update { assignments, .. } => {
+                for assignment in assignments {
+                    self.quote_all(&mut assignment.id);
+                }
+            }
+            Statement::CreateTable {
+                columns,
+                index,
+                constraints,
+                ..
+            } => {
+                for column in columns {
+                    self.quote_column_def(column);
+                }
+                for info in index {
+                    self.quote_index_def(&mut info.index);
+                }
+                for constraint in constraints {
+                    self.quote_constraint(constraint);
+                }
+            }
+            Statement::CreateIndex { name, columns, .. } => {
+                self.quote_all(&mut name.0);
+                self.quote_key_parts(columns.iter_mut());
+            }
+            Statement::AlterTable { operation, .. } => {
+                for operation in operation {
+                    self.quote_alter_table_operation(operation);
+                }
+            }
+            Statement::Drop {
+                object_type: ObjectType::Index,
+                names,
+                ..
+            }
+            | Statement::Drop {
+                object_type: ObjectType::Schema,
+                names,
+                ..
+            } => self.quote_all(names.iter_mut().flat_map(|name| &mut name.0)),
+            Statement::CreateSchema { schema_name, .. } => self.quote_all(&mut schema_name.0),
+            Statement::ChangeDatabase { database } => self.quote(database),
+            _ => {}
+        }
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        for cte in &mut query.ctes {
+            self.quote(&mut cte.alias.name);
+            self.quote_all(&mut cte.alias.columns);
+        }
+        walk_query_mut(self, query);
+    }
+
+    fn visit_select(&mut self, select: &mut Select) {
+        for item in &mut select.projection {
+            match item {
+                SelectItem::ExprWithAlias { alias, .. } => self.quote(alias),
+                SelectItem::QualifiedWildcard(prefix) => self.quote_all(&mut prefix.0),
+                SelectItem::UnnamedExpr(_) | SelectItem::Wildcard => {}
+            }
+        }
+        self.quote_all(&mut select.wildcard_except);
+        walk_select_mut(self, select);
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &mut TableFactor) {
+        match table_factor {
+            TableFactor::Table { alias, .. } | TableFactor::Derived { alias, .. } => {
+                self.quote_alias(alias)
+            }
+            TableFactor::NestedJoin(_) | TableFactor::Dual => {}
+        }
+        walk_table_factor_mut(self, table_factor);
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Identifier(ident) if is_keyword_expr(ident) => {}
+            Expr::Identifier(ident) => self.quote(ident),
+            Expr::CompoundIdentifier(idents) | Expr::QualifiedWildcard(idents) => {
+                self.quote_all(idents)
+            }
+            _ => {}
+        }
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_table_name(&mut self, name: &mut ObjectName) {
+        self.quote_all(&mut name.0);
+    }
+}
@@ -0,0 +1,149 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Constant folding of expressions, e.g. to route on a `WHERE` condition
+
+use super::*;
+
+/// The expression with its constant parts folded, for use as a condition:
+///
+/// - `TRUE AND x` and `FALSE OR x` become `x`, `FALSE AND x` becomes `FALSE`
+///   and `TRUE OR x` becomes `TRUE`, either way round
+/// - `NOT TRUE` becomes `FALSE`, and `NOT NOT x` becomes `x`
+/// - `+`, `-`, `*`, `%` and comparisons of integers are evaluated, as is
+///   unary `-`, so `1 = 1` becomes `TRUE`
+/// - parentheses around a literal, an identifier or other parentheses are
+///   removed
+///
+/// Anything involving columns or functions is kept as it is, apart from
+/// its constant subexpressions. `NULL` is never folded, as `NULL AND x` or
+/// `NULL = NULL` depend on `x` or on how the result is used. As `AND`,
+/// `OR` and `NOT` only keep the truth of `x` rather than its value, e.g.
+/// `NOT NOT 5` is 1 in MySQL, the result is meant for boolean contexts.
+///
+/// ```
+/// # use sqlparser::ast::simplify;
+/// # use sqlparser::dialect::MySqlDialect;
+/// # use sqlparser::parser::Parser;
+/// let expr = Parser::parse_expr_str(&MySqlDialect {}, "1 = 1 AND id = 2 + 3").unwrap();
+/// assert_eq!("id = 5", simplify(expr).to_string());
+/// ```
+pub fn simplify(expr: Expr) -> Expr {
+    match expr {
+        Expr::Nested(inner) => match simplify(*inner) {
+            inner @ Expr::Value(_)
+            | inner @ Expr::Identifier(_)
+            | inner @ Expr::CompoundIdentifier(_)
+            | inner @ Expr::Nested(_) => inner,
+            inner => Expr::Nested(Box::new(inner)),
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => match simplify(*expr) {
+            Expr::Value(Value::Boolean(b)) => boolean(!b),
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => *expr,
+            expr => Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr: Box::new(expr),
+            },
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => {
+            let expr = simplify(*expr);
+            match as_integer(&expr).and_then(i64::checked_neg) {
+                Some(n) => integer(n),
+                None => Expr::UnaryOp {
+                    op: UnaryOperator::Minus,
+                    expr: Box::new(expr),
+                },
+            }
+        }
+        Expr::BinaryOp { left, op, right } => {
+            simplify_binary_op(simplify(*left), op, simplify(*right))
+        }
+        expr => expr,
+    }
+}
+
+fn simplify_binary_op(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    match (&op, as_boolean(&left), as_boolean(&right)) {
+        (BinaryOperator::And, Some(true), _) => return right,
+        (BinaryOperator::And, _, Some(true)) => return left,
+        (BinaryOperator::And, Some(false), _) | (BinaryOperator::And, _, Some(false)) => {
+            return boolean(false)
+        }
+        (BinaryOperator::Or, Some(false), _) => return right,
+        (BinaryOperator::Or, _, Some(false)) => return left,
+        (BinaryOperator::Or, Some(true), _) | (BinaryOperator::Or, _, Some(true)) => {
+            return boolean(true)
+        }
+        _ => {}
+    }
+    if let (Some(l), Some(r)) = (as_integer(&left), as_integer(&right)) {
+        let folded = match op {
+            BinaryOperator::Plus => l.checked_add(r).map(integer),
+            BinaryOperator::Minus => l.checked_sub(r).map(integer),
+            BinaryOperator::Multiply => l.checked_mul(r).map(integer),
+            // `x % 0` is NULL in MySQL
+            BinaryOperator::Modulus => l.checked_rem(r).map(integer),
+            BinaryOperator::Eq => Some(boolean(l == r)),
+            BinaryOperator::NotEq => Some(boolean(l != r)),
+            BinaryOperator::Lt => Some(boolean(l < r)),
+            BinaryOperator::LtEq => Some(boolean(l <= r)),
+            BinaryOperator::Gt => Some(boolean(l > r)),
+            BinaryOperator::GtEq => Some(boolean(l >= r)),
+            _ => None,
+        };
+        if let Some(folded) = folded {
+            return folded;
+        }
+    }
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+fn as_boolean(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Value(Value::Boolean(b)) => Some(*b),
+        _ => None,
+    }
+}
+
+fn as_integer(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Value(Value::Number(n)) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+fn boolean(b: bool) -> Expr {
+    Expr::Value(Value::Boolean(b))
+}
+
+#[cfg(not(feature = "bigdecimal"))]
+fn integer(n: i64) -> Expr {
+    Expr::Value(Value::Number(n.to_string()))
+}
+
+#[cfg(feature = "bigdecimal")]
+fn integer(n: i64) -> Expr {
+    Expr::Value(Value::Number(n.into()))
+}
@@ -0,0 +1,183 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Source locations of AST nodes, see `Parser::parse_sql_located`
+
+use core::ops::Range;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{Expr, Ident, ObjectName, SelectItem, Statement};
+use crate::tokenizer::{Token, TokenWithLocation, Whitespace};
+
+/// A range of byte offsets `start..end` in the parsed SQL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An AST node along with the part of the SQL it was parsed from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Located<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+/// The located nodes of a statement, in the order the parser finished them
+/// (so inner expressions come before the expressions containing them)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeSpans {
+    pub idents: Vec<Located<Ident>>,
+    pub object_names: Vec<Located<ObjectName>>,
+    pub exprs: Vec<Located<Expr>>,
+    pub select_items: Vec<Located<SelectItem>>,
+}
+
+impl NodeSpans {
+    /// How many nodes of each kind are recorded, to go back to with
+    /// `truncate` when a speculative parse fails
+    pub(crate) fn lens(&self) -> [usize; 4] {
+        [
+            self.idents.len(),
+            self.object_names.len(),
+            self.exprs.len(),
+            self.select_items.len(),
+        ]
+    }
+
+    /// Forget the nodes recorded since `lens` was taken
+    pub(crate) fn truncate(&mut self, lens: [usize; 4]) {
+        let [idents, object_names, exprs, select_items] = lens;
+        self.idents.truncate(idents);
+        self.object_names.truncate(object_names);
+        self.exprs.truncate(exprs);
+        self.select_items.truncate(select_items);
+    }
+}
+
+/// A statement along with its span and the spans of its nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedStatement {
+    pub statement: Statement,
+    pub span: Span,
+    pub nodes: NodeSpans,
+}
+
+impl LocatedStatement {
+    /// The range of the whole statement, without the trailing semicolon.
+    /// A statement that is all there is in a versioned comment `/*!NNNNN
+    /// ... */` spans the comment, as it is printed along with it.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// SQL parsed along with all of its tokens, including the whitespace and
+/// comments, see `Parser::parse_with_tokens`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSource {
+    /// The SQL that was parsed
+    pub source: String,
+    /// Every token of the SQL, the whitespace and comments included
+    pub tokens: Vec<TokenWithLocation>,
+    pub statements: Vec<SourceStatement>,
+}
+
+/// A statement of a `ParsedSource`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceStatement {
+    /// The statement, which can be changed before calling
+    /// `ParsedSource::to_source`
+    pub statement: Statement,
+    /// The range of `ParsedSource::tokens` that the statement was parsed
+    /// from, including the markers of a versioned comment around it, but
+    /// not the trailing `;`
+    pub tokens: Range<usize>,
+    /// The range of `ParsedSource::tokens` with the whitespace and comments
+    /// between the previous statement, or `;`, and this one
+    pub leading_trivia: Range<usize>,
+    /// The part of the SQL covered by `tokens`
+    pub span: Span,
+    /// The statement as it was parsed
+    parsed: Statement,
+}
+
+impl SourceStatement {
+    pub(crate) fn new(
+        statement: Statement,
+        tokens: Range<usize>,
+        leading_trivia: Range<usize>,
+        span: Span,
+    ) -> Self {
+        SourceStatement {
+            parsed: statement.clone(),
+            statement,
+            tokens,
+            leading_trivia,
+            span,
+        }
+    }
+
+    /// Whether `statement` differs from the statement that was parsed
+    pub fn is_modified(&self) -> bool {
+        self.statement != self.parsed
+    }
+}
+
+impl ParsedSource {
+    /// The SQL again, with the statements that were changed printed by
+    /// `Display` in place of their original text. Everything else,
+    /// comments and whitespace included, is copied from `source`, so
+    /// without changes this is exactly `source`.
+    pub fn to_source(&self) -> String {
+        let mut sql = String::with_capacity(self.source.len());
+        let mut copied = 0;
+        for statement in &self.statements {
+            sql.push_str(&self.source[copied..statement.span.start]);
+            if statement.is_modified() {
+                sql.push_str(&statement.statement.to_string());
+            } else {
+                sql.push_str(&self.source[statement.span.start..statement.span.end]);
+            }
+            copied = statement.span.end;
+        }
+        sql.push_str(&self.source[copied..]);
+        sql
+    }
+
+    /// The whitespace and comments before the statement, as written
+    pub fn leading_trivia(&self, statement: &SourceStatement) -> &str {
+        match self.tokens.get(statement.leading_trivia.start) {
+            Some(first) => &self.source[first.location.offset..statement.span.start],
+            None => "",
+        }
+    }
+
+    /// The text of the `--`, `#` and `/* */` comments before the statement,
+    /// without the comment markers and surrounding whitespace
+    pub fn leading_comments(&self, statement: &SourceStatement) -> Vec<String> {
+        self.tokens[statement.leading_trivia.clone()]
+            .iter()
+            .filter_map(|t| match &t.token {
+                Token::Whitespace(Whitespace::SingleLineComment { comment, .. })
+                | Token::Whitespace(Whitespace::MultiLineComment(comment)) => {
+                    Some(comment.trim().to_string())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,216 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Classification of statements, e.g. to route them to a primary or replica
+
+use super::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The broad class of a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StatementKind {
+    /// `SELECT`, `WITH ... SELECT`, `VALUES` and `ASSERT`
+    Query,
+    /// `INSERT`, `REPLACE`, `UPDATE`, `DELETE`, `COPY` and `CALL`
+    Dml,
+    /// `CREATE`, `ALTER` and `DROP`
+    Ddl,
+    /// `START TRANSACTION`, `SET TRANSACTION`, `COMMIT`, `ROLLBACK`,
+    /// `LOCK TABLES` and `UNLOCK TABLES`
+    TransactionControl,
+    /// `SHOW`, `DESC` and `EXPLAIN`
+    Show,
+    /// `SET <variable>` and `USE`
+    Session,
+    /// The proxy's own `SET ... WHERE` and `RELOAD`
+    Admin,
+}
+
+impl Statement {
+    /// The broad class of the statement, see `StatementKind`
+    pub fn statement_kind(&self) -> StatementKind {
+        match self {
+            Statement::Query(_) | Statement::Assert { .. } => StatementKind::Query,
+            Statement::Insert { .. }
+            | Statement::Replace { .. }
+            | Statement::Copy { .. }
+            | Statement::Update { .. }
+            | Statement::Delete { .. }
+            | Statement::Call { .. } => StatementKind::Dml,
+            Statement::CreateView { .. }
+            | Statement::CreateTable { .. }
+            | Statement::CreateVirtualTable { .. }
+            | Statement::CreateIndex { .. }
+            | Statement::AlterTable { .. }
+            | Statement::Drop { .. }
+            | Statement::CreateSchema { .. } => StatementKind::Ddl,
+            Statement::StartTransaction { .. }
+            | Statement::SetTransaction { .. }
+            | Statement::Commit { .. }
+            | Statement::Rollback { .. }
+            | Statement::Lock { .. }
+            | Statement::UNLock { .. } => StatementKind::TransactionControl,
+            Statement::Explain { .. }
+            | Statement::ShowVariable { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowObjects { .. }
+            | Statement::ShowCreate { .. }
+            | Statement::Desc { .. } => StatementKind::Show,
+            Statement::SetVariable { .. }
+            | Statement::ChangeDatabase { .. }
+            | Statement::ClientDelimiter { .. } => StatementKind::Session,
+            Statement::AdminSetVariable { .. } | Statement::ReLoad { .. } => StatementKind::Admin,
+            Statement::VersionedComment { statement, .. } => statement.statement_kind(),
+        }
+    }
+
+    /// Whether the statement changes data, i.e. its kind is `StatementKind::Dml`
+    pub fn is_dml(&self) -> bool {
+        self.statement_kind() == StatementKind::Dml
+    }
+
+    /// Whether the statement changes the schema, i.e. its kind is
+    /// `StatementKind::Ddl`
+    pub fn is_ddl(&self) -> bool {
+        self.statement_kind() == StatementKind::Ddl
+    }
+
+    /// Whether the statement starts, ends or configures a transaction, or
+    /// locks or unlocks tables
+    pub fn is_transaction_control(&self) -> bool {
+        self.statement_kind() == StatementKind::TransactionControl
+    }
+
+    /// Whether the statement can run without changing any data or server
+    /// state other than that of its own session, so e.g. on a replica.
+    ///
+    /// Queries are read-only unless they, or a subquery or derived table in
+    /// them, lock rows with `FOR UPDATE`, and `EXPLAIN ANALYZE` is only
+    /// read-only when the explained statement is.
+    /// `SET` is read-only unless it sets a global variable, and `START
+    /// TRANSACTION` only when the transaction is `READ ONLY`. `COMMIT`,
+    /// `ROLLBACK` and `LOCK TABLES` aren't read-only.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Statement::Query(_) => !locks_rows(self),
+            Statement::Assert { .. } => true,
+            Statement::Explain {
+                analyze: Some(true),
+                body: ExplainStmt::Stmt(statement),
+                ..
+            } => statement.is_read_only(),
+            Statement::Explain { .. }
+            | Statement::ShowVariable { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowObjects { .. }
+            | Statement::ShowCreate { .. }
+            | Statement::Desc { .. }
+            | Statement::ChangeDatabase { .. }
+            | Statement::ClientDelimiter { .. } => true,
+            Statement::SetVariable { variable, .. } => !is_global_variable(variable),
+            Statement::StartTransaction { modes } => modes.contains(
+                &TransactionMode::AccessMode(TransactionAccessMode::ReadOnly),
+            ),
+            Statement::Insert { .. }
+            | Statement::Replace { .. }
+            | Statement::Copy { .. }
+            | Statement::Update { .. }
+            | Statement::Delete { .. }
+            | Statement::Call { .. }
+            | Statement::CreateView { .. }
+            | Statement::CreateTable { .. }
+            | Statement::CreateVirtualTable { .. }
+            | Statement::CreateIndex { .. }
+            | Statement::AlterTable { .. }
+            | Statement::Drop { .. }
+            | Statement::CreateSchema { .. }
+            | Statement::SetTransaction { .. }
+            | Statement::Commit { .. }
+            | Statement::Rollback { .. }
+            | Statement::Lock { .. }
+            | Statement::UNLock { .. }
+            | Statement::AdminSetVariable { .. }
+            | Statement::ReLoad { .. } => false,
+            Statement::VersionedComment { statement, .. } => statement.is_read_only(),
+        }
+    }
+
+    /// The kind of object that a DDL or DML statement changes, or `None` for
+    /// other statements
+    pub fn object_type(&self) -> Option<ObjectType> {
+        match self {
+            Statement::Insert { .. }
+            | Statement::Replace { .. }
+            | Statement::Copy { .. }
+            | Statement::Update { .. }
+            | Statement::Delete { .. }
+            | Statement::CreateTable { .. }
+            | Statement::CreateVirtualTable { .. }
+            | Statement::AlterTable { .. } => Some(ObjectType::Table),
+            Statement::CreateView { .. } => Some(ObjectType::View),
+            Statement::CreateIndex { .. } => Some(ObjectType::Index),
+            Statement::CreateSchema { .. } => Some(ObjectType::Schema),
+            Statement::Drop { object_type, .. } => Some(object_type.clone()),
+            Statement::VersionedComment { statement, .. } => statement.object_type(),
+            Statement::Query(_)
+            | Statement::Explain { .. }
+            | Statement::Call { .. }
+            | Statement::SetVariable { .. }
+            | Statement::AdminSetVariable { .. }
+            | Statement::ReLoad { .. }
+            | Statement::ShowVariable { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowObjects { .. }
+            | Statement::ShowCreate { .. }
+            | Statement::StartTransaction { .. }
+            | Statement::SetTransaction { .. }
+            | Statement::Commit { .. }
+            | Statement::Rollback { .. }
+            | Statement::Assert { .. }
+            | Statement::Lock { .. }
+            | Statement::UNLock { .. }
+            | Statement::ChangeDatabase { .. }
+            | Statement::ClientDelimiter { .. }
+            | Statement::Desc { .. } => None,
+        }
+    }
+}
+
+/// Whether any query in the statement has `FOR UPDATE`, subqueries and
+/// derived tables included
+fn locks_rows(statement: &Statement) -> bool {
+    struct LocksRows(bool);
+
+    impl VisitorMut for LocksRows {
+        fn visit_query(&mut self, query: &mut Query) {
+            self.0 |= query.update;
+            walk_query_mut(self, query);
+        }
+    }
+
+    let mut visitor = LocksRows(false);
+    // the visitor needs mutable access, which this doesn't make use of
+    visitor.visit_statement(&mut statement.clone());
+    visitor.0
+}
+
+/// Whether `SET` of the variable changes it for the whole server, as with
+/// `@@global.x` or `@@persist.x`
+fn is_global_variable(variable: &Ident) -> bool {
+    let name = variable.value.to_ascii_lowercase();
+    ["@@global.", "@@persist.", "@@persist_only."]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
@@ -0,0 +1,163 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The tables a statement refers to, see `Statement::table_references`
+
+use super::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A table (or view) name that appears in a statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TableReference {
+    pub name: ObjectName,
+    pub access: TableAccess,
+    /// The name refers to a CTE of an enclosing `WITH`, not to a real table
+    pub cte: bool,
+}
+
+/// How a statement uses a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TableAccess {
+    /// Read from, e.g. in a `FROM` clause or a subquery
+    Read,
+    /// Written to, created, altered or dropped
+    Write,
+}
+
+impl Statement {
+    /// The tables referred to by the statement, in the order they appear and
+    /// including repeated ones. Tables in subqueries, CTEs and the sources
+    /// of `INSERT`, `CREATE TABLE ... AS` and `CREATE VIEW` are read, the
+    /// target of the statement itself is written.
+    pub fn table_references(&self) -> Vec<TableReference> {
+        let mut collector = TableReferences {
+            references: vec![],
+            access: TableAccess::Read,
+            query_depth: 0,
+            ctes: vec![],
+        };
+        // the visitor needs mutable access, which this doesn't make use of
+        collector.visit_statement(&mut self.clone());
+        collector.references
+    }
+
+    /// Qualify every table name that has no database with `default_db`,
+    /// e.g. so the statement can run from a session using another database.
+    /// Names of CTEs in scope are left alone, as are columns, indexes and
+    /// other objects.
+    pub fn qualify_tables(&mut self, default_db: &str) {
+        QualifyTables {
+            default_db: Ident::new(default_db),
+            ctes: vec![],
+        }
+        .visit_statement(self);
+    }
+}
+
+fn is_cte(ctes: &[Ident], name: &ObjectName) -> bool {
+    match name.0.as_slice() {
+        [ident] => ctes.iter().any(|cte| cte.eq_ignore_case(&ident.value)),
+        _ => false,
+    }
+}
+
+struct TableReferences {
+    references: Vec<TableReference>,
+    /// The access to the tables named by the statement itself
+    access: TableAccess,
+    /// How many queries the visitor is inside of
+    query_depth: usize,
+    /// The names of the CTEs in scope
+    ctes: Vec<Ident>,
+}
+
+impl TableReferences {
+    fn is_cte(&self, name: &ObjectName) -> bool {
+        is_cte(&self.ctes, name)
+    }
+}
+
+impl VisitorMut for TableReferences {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        let access = self.access;
+        self.access = match statement {
+            Statement::Query(_)
+            | Statement::Explain { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowObjects { .. }
+            | Statement::ShowCreate { .. }
+            | Statement::Desc { .. } => TableAccess::Read,
+            _ => TableAccess::Write,
+        };
+        walk_statement_mut(self, statement);
+        self.access = access;
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        self.query_depth += 1;
+        let scope = self.ctes.len();
+        // a CTE can refer to the ones before it, but not to itself or the
+        // ones after it
+        for cte in &mut query.ctes {
+            self.visit_query(&mut cte.query);
+            self.ctes.push(cte.alias.name.clone());
+        }
+        let ctes = core::mem::take(&mut query.ctes);
+        walk_query_mut(self, query);
+        query.ctes = ctes;
+        self.ctes.truncate(scope);
+        self.query_depth -= 1;
+    }
+
+    fn visit_table_name(&mut self, name: &mut ObjectName) {
+        let access = if self.query_depth > 0 {
+            TableAccess::Read
+        } else {
+            self.access
+        };
+        self.references.push(TableReference {
+            name: name.clone(),
+            access,
+            cte: self.is_cte(name),
+        });
+    }
+}
+
+struct QualifyTables {
+    default_db: Ident,
+    /// The names of the CTEs in scope
+    ctes: Vec<Ident>,
+}
+
+impl VisitorMut for QualifyTables {
+    fn visit_query(&mut self, query: &mut Query) {
+        let scope = self.ctes.len();
+        for cte in &mut query.ctes {
+            self.visit_query(&mut cte.query);
+            self.ctes.push(cte.alias.name.clone());
+        }
+        let ctes = core::mem::take(&mut query.ctes);
+        walk_query_mut(self, query);
+        query.ctes = ctes;
+        self.ctes.truncate(scope);
+    }
+
+    fn visit_table_name(&mut self, name: &mut ObjectName) {
+        if name.0.len() == 1 && !is_cte(&self.ctes, name) {
+            name.0.insert(0, self.default_db.clone());
+        }
+    }
+}
@@ -0,0 +1,284 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rules MySQL enforces that the parser doesn't, see `Statement::validate`
+
+use super::*;
+
+#[cfg(not(feature = "std"))]
+use alloc::format;
+
+/// A rule checked by `Statement::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationRule {
+    /// A derived table, `(SELECT ...)` in `FROM`, must have an alias
+    DerivedTableAlias,
+    /// An aggregate function can't be an argument of another one, outside
+    /// of a window function
+    NestedAggregate,
+    /// A subquery of `IN` can't have a `LIMIT`
+    LimitInSubquery,
+    /// The subqueries of an `UPDATE` or `DELETE` can't read the table it
+    /// writes to, unless through a derived table
+    TargetTableInSubquery,
+    /// A query can't limit its rows with more than one of `TOP`, `LIMIT`
+    /// and `FETCH`, which only a dialect other than MySQL parses
+    ConflictingRowLimits,
+}
+
+/// A statement breaking a `ValidationRule`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    pub rule: ValidationRule,
+    /// The message MySQL gives, or a description of the error where MySQL
+    /// only reports a syntax error
+    pub message: String,
+    /// Where the offending expression is, when validating a
+    /// `LocatedStatement`
+    pub span: Option<Span>,
+}
+
+/// Which rules `Statement::validate` checks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ValidateOptions {
+    /// The rules not to check
+    pub skip: Vec<ValidationRule>,
+}
+
+impl Statement {
+    /// Check the statement for the errors MySQL would reject it with that
+    /// the parser lets through, in the order they appear. `SELECT ... INTO`
+    /// and `ANY`, `ALL` and `SOME` subqueries aren't parsed, so their rules
+    /// aren't checked.
+    pub fn validate(&self, options: &ValidateOptions) -> Vec<ValidationError> {
+        validate(self, options, None)
+    }
+}
+
+impl LocatedStatement {
+    /// Like `Statement::validate`, with the span of the expression at fault
+    /// for the rules about expressions
+    pub fn validate(&self, options: &ValidateOptions) -> Vec<ValidationError> {
+        validate(&self.statement, options, Some(&self.nodes))
+    }
+}
+
+impl Query {
+    /// The clauses limiting the rows of the query, if it has more than one
+    /// of `TOP`, `LIMIT` and `FETCH`
+    pub fn conflicting_row_limits(&self) -> Option<Vec<&'static str>> {
+        let top = match &self.body {
+            SetExpr::Select(select) => select.top.is_some(),
+            _ => false,
+        };
+        let clauses: Vec<&'static str> = [
+            ("TOP", top),
+            ("LIMIT", self.limit.is_some()),
+            ("FETCH", self.fetch.is_some()),
+        ]
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(clause, _)| *clause)
+        .collect();
+        if clauses.len() > 1 {
+            Some(clauses)
+        } else {
+            None
+        }
+    }
+}
+
+fn validate(
+    statement: &Statement,
+    options: &ValidateOptions,
+    nodes: Option<&NodeSpans>,
+) -> Vec<ValidationError> {
+    let mut validator = Validator {
+        options,
+        nodes,
+        errors: vec![],
+        in_aggregate: false,
+        target: None,
+        in_subquery: false,
+    };
+    // the visitor needs mutable access, which this doesn't make use of
+    validator.visit_statement(&mut statement.clone());
+    validator.errors
+}
+
+/// MySQL's aggregate functions, which are window functions with `OVER`
+const AGGREGATE_FUNCTIONS: &[&str] = &[
+    "AVG",
+    "BIT_AND",
+    "BIT_OR",
+    "BIT_XOR",
+    "COUNT",
+    "GROUP_CONCAT",
+    "JSON_ARRAYAGG",
+    "JSON_OBJECTAGG",
+    "MAX",
+    "MIN",
+    "STD",
+    "STDDEV",
+    "STDDEV_POP",
+    "STDDEV_SAMP",
+    "SUM",
+    "VARIANCE",
+    "VAR_POP",
+    "VAR_SAMP",
+];
+
+fn is_aggregate(function: &Function) -> bool {
+    function.over.is_none()
+        && match function.name.0.as_slice() {
+            [name] => AGGREGATE_FUNCTIONS
+                .iter()
+                .any(|aggregate| name.eq_ignore_case(aggregate)),
+            _ => false,
+        }
+}
+
+struct Validator<'a> {
+    options: &'a ValidateOptions,
+    nodes: Option<&'a NodeSpans>,
+    errors: Vec<ValidationError>,
+    /// Whether an argument of an aggregate function is being visited
+    in_aggregate: bool,
+    /// The table the `UPDATE` or `DELETE` being visited writes to
+    target: Option<ObjectName>,
+    /// Whether a subquery in an expression is being visited, and not a
+    /// derived table within it
+    in_subquery: bool,
+}
+
+impl Validator<'_> {
+    fn error(&mut self, rule: ValidationRule, message: String, expr: Option<&Expr>) {
+        if self.options.skip.contains(&rule) {
+            return;
+        }
+        let span = match (self.nodes, expr) {
+            (Some(nodes), Some(expr)) => nodes
+                .exprs
+                .iter()
+                .find(|located| located.node == *expr)
+                .map(|located| located.span),
+            _ => None,
+        };
+        self.errors.push(ValidationError {
+            rule,
+            message,
+            span,
+        });
+    }
+
+    fn is_target(&self, name: &ObjectName) -> bool {
+        match (&self.target, name.name()) {
+            (Some(target), Some(last)) => {
+                // a name without a database may be in the target's one
+                let schema = |name: &ObjectName| name.schema().map(|ident| ident.value.clone());
+                target.matches(schema(name).as_deref(), &last.value, false)
+                    || target.name().is_some_and(|target_last| {
+                        name.matches(schema(target).as_deref(), &target_last.value, false)
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl VisitorMut for Validator<'_> {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        let target = match statement {
+            Statement::Update { table_name, .. } | Statement::Delete { table_name, .. } => {
+                Some(table_name.clone())
+            }
+            _ => None,
+        };
+        let target = core::mem::replace(&mut self.target, target);
+        walk_statement_mut(self, statement);
+        self.target = target;
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        // an aggregate of a subquery is a new one
+        if let Some(clauses) = query.conflicting_row_limits() {
+            let message = format!("A query can't combine {}", clauses.join(" and "));
+            self.error(ValidationRule::ConflictingRowLimits, message, None);
+        }
+        let in_aggregate = core::mem::replace(&mut self.in_aggregate, false);
+        walk_query_mut(self, query);
+        self.in_aggregate = in_aggregate;
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &mut TableFactor) {
+        match table_factor {
+            TableFactor::Table { name, .. } if self.in_subquery && self.is_target(name) => {
+                let message = format!(
+                    "You can't specify target table '{}' for update in FROM clause",
+                    name.name().map_or("", |ident| &ident.value)
+                );
+                self.error(ValidationRule::TargetTableInSubquery, message, None);
+            }
+            TableFactor::Derived { alias: None, .. } => self.error(
+                ValidationRule::DerivedTableAlias,
+                "Every derived table must have its own alias".to_string(),
+                None,
+            ),
+            _ => {}
+        }
+        // a derived table is materialized first, so it can read the target
+        let in_subquery = self.in_subquery
+            && !matches!(table_factor, TableFactor::Derived { .. });
+        let in_subquery = core::mem::replace(&mut self.in_subquery, in_subquery);
+        walk_table_factor_mut(self, table_factor);
+        self.in_subquery = in_subquery;
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        let in_aggregate = self.in_aggregate;
+        match expr {
+            Expr::Function(function) if is_aggregate(function) => {
+                if in_aggregate {
+                    let expr = expr.clone();
+                    self.error(
+                        ValidationRule::NestedAggregate,
+                        "Invalid use of group function".to_string(),
+                        Some(&expr),
+                    );
+                }
+                self.in_aggregate = true;
+            }
+            Expr::InSubquery { subquery, .. }
+                if subquery.limit.is_some() || subquery.fetch.is_some() =>
+            {
+                let expr = expr.clone();
+                self.error(
+                    ValidationRule::LimitInSubquery,
+                    "This version of MySQL doesn't yet support \
+                     'LIMIT & IN/ALL/ANY/SOME subquery'"
+                        .to_string(),
+                    Some(&expr),
+                );
+            }
+            _ => {}
+        }
+        let in_subquery = self.in_subquery
+            || matches!(
+                expr,
+                Expr::Subquery(_) | Expr::InSubquery { .. } | Expr::Exists(_)
+            );
+        let in_subquery = core::mem::replace(&mut self.in_subquery, in_subquery);
+        walk_expr_mut(self, expr);
+        self.in_subquery = in_subquery;
+        self.in_aggregate = in_aggregate;
+    }
+}
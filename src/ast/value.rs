@@ -14,7 +14,9 @@
 use bigdecimal::BigDecimal;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 
 /// Primitive SQL values such as number and string
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -25,14 +27,21 @@ pub enum Value {
     Number(String),
     #[cfg(feature = "bigdecimal")]
     Number(BigDecimal),
-    /// 'string value'
-    SingleQuotedString(String),
+    /// 'string value', with `backslash_escapes` set when it was read with
+    /// backslashes as escapes, as MySQL does without `NO_BACKSLASH_ESCAPES`,
+    /// so that backslashes and NUL are escaped again when printed
+    SingleQuotedString {
+        value: String,
+        backslash_escapes: bool,
+    },
+    /// "string value", as MySQL reads double quotes without `ANSI_QUOTES`
+    DoubleQuotedString(String),
     /// N'string value'
     NationalStringLiteral(String),
     /// Environment variable name
     VariableName(String),
-    /// one char for ?
-    Char(char),
+    /// A prepared statement placeholder: `?`
+    Placeholder(String),
     /// X'hex value'
     HexStringLiteral(String),
     /// Boolean value true or false
@@ -64,22 +73,31 @@ impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Value::Number(v) => write!(f, "{}", v),
-            Value::SingleQuotedString(v) => write!(f, "'{}'", escape_single_quote_string(v)),
-            Value::NationalStringLiteral(v) => write!(f, "N'{}'", v),
-            Value::HexStringLiteral(v) => write!(f, "X'{}'", v),
+            Value::SingleQuotedString {
+                value,
+                backslash_escapes: true,
+            } => write!(f, "'{}'", escape_single_quote_string(value)),
+            Value::SingleQuotedString {
+                value,
+                backslash_escapes: false,
+            } => write!(f, "'{}'", value.replace('\'', "''")),
+            Value::DoubleQuotedString(v) => write!(f, "\"{}\"", escape_double_quote_string(v)),
+            Value::NationalStringLiteral(v) => {
+                write!(f, "N'{}'", escape_single_quote_string(v))
+            }
+            Value::HexStringLiteral(v) => write!(f, "X'{}'", escape_single_quote_string(v)),
             Value::Boolean(v) => write!(f, "{}", v),
             Value::VariableName(v) => write!(f, "{}", v),
-            Value::Char(c) => write!(f, "{}", c),
+            Value::Placeholder(v) => write!(f, "{}", v),
             Value::Interval {
                 value,
                 leading_field: Some(DateTimeField::Second),
                 leading_precision: Some(leading_precision),
-                last_field,
-                fractional_seconds_precision: Some(fractional_seconds_precision),
-            } => {
                 // When the leading field is SECOND, the parser guarantees that
                 // the last field is None.
-                assert!(last_field.is_none());
+                last_field: None,
+                fractional_seconds_precision: Some(fractional_seconds_precision),
+            } => {
                 write!(
                     f,
                     "INTERVAL '{}' SECOND ({}, {})",
@@ -119,22 +137,51 @@ impl fmt::Display for Value {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DateTimeField {
     Year,
+    Quarter,
     Month,
+    Week,
     Day,
     Hour,
     Minute,
     Second,
+    Microsecond,
+    /// MySQL's compound units, whose values are strings such as `'1-6'`
+    YearMonth,
+    DayHour,
+    DayMinute,
+    DaySecond,
+    DayMicrosecond,
+    HourMinute,
+    HourSecond,
+    HourMicrosecond,
+    MinuteSecond,
+    MinuteMicrosecond,
+    SecondMicrosecond,
 }
 
 impl fmt::Display for DateTimeField {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(match self {
             DateTimeField::Year => "YEAR",
+            DateTimeField::Quarter => "QUARTER",
             DateTimeField::Month => "MONTH",
+            DateTimeField::Week => "WEEK",
             DateTimeField::Day => "DAY",
             DateTimeField::Hour => "HOUR",
             DateTimeField::Minute => "MINUTE",
             DateTimeField::Second => "SECOND",
+            DateTimeField::Microsecond => "MICROSECOND",
+            DateTimeField::YearMonth => "YEAR_MONTH",
+            DateTimeField::DayHour => "DAY_HOUR",
+            DateTimeField::DayMinute => "DAY_MINUTE",
+            DateTimeField::DaySecond => "DAY_SECOND",
+            DateTimeField::DayMicrosecond => "DAY_MICROSECOND",
+            DateTimeField::HourMinute => "HOUR_MINUTE",
+            DateTimeField::HourSecond => "HOUR_SECOND",
+            DateTimeField::HourMicrosecond => "HOUR_MICROSECOND",
+            DateTimeField::MinuteSecond => "MINUTE_SECOND",
+            DateTimeField::MinuteMicrosecond => "MINUTE_MICROSECOND",
+            DateTimeField::SecondMicrosecond => "SECOND_MICROSECOND",
         })
     }
 }
@@ -143,17 +190,37 @@ pub struct EscapeSingleQuoteString<'a>(&'a str);
 
 impl<'a> fmt::Display for EscapeSingleQuoteString<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for c in self.0.chars() {
-            if c == '\'' {
-                write!(f, "\'\'")?;
-            } else {
-                write!(f, "{}", c)?;
-            }
+        write_escaped_string(f, self.0, '\'')
+    }
+}
+
+pub struct EscapeDoubleQuoteString<'a>(&'a str);
+
+impl<'a> fmt::Display for EscapeDoubleQuoteString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_escaped_string(f, self.0, '"')
+    }
+}
+
+/// Write the content of a string literal delimited by `quote`, doubling the
+/// quote and escaping backslashes and NUL, as MySQL reads them; see
+/// `Value::SingleQuotedString` for strings read without backslash escapes
+fn write_escaped_string(f: &mut fmt::Formatter, s: &str, quote: char) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '\\' => write!(f, "\\\\")?,
+            '\0' => write!(f, "\\0")?,
+            c if c == quote => write!(f, "{}{}", c, c)?,
+            _ => write!(f, "{}", c)?,
         }
-        Ok(())
     }
+    Ok(())
 }
 
 pub fn escape_single_quote_string(s: &str) -> EscapeSingleQuoteString<'_> {
     EscapeSingleQuoteString(s)
 }
+
+pub fn escape_double_quote_string(s: &str) -> EscapeDoubleQuoteString<'_> {
+    EscapeDoubleQuoteString(s)
+}
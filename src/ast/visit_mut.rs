@@ -0,0 +1,469 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mutable traversal of the AST, for rewriting statements in place
+
+use super::*;
+
+/// Visits the nodes of an AST with mutable access to them.
+///
+/// Each `visit_*` method defaults to the matching `walk_*_mut` function,
+/// which visits the children of the node. An implementation that overrides
+/// a method can call the `walk_*_mut` function to keep going deeper.
+pub trait VisitorMut {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement)
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        walk_query_mut(self, query)
+    }
+
+    fn visit_select(&mut self, select: &mut Select) {
+        walk_select_mut(self, select)
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &mut TableFactor) {
+        walk_table_factor_mut(self, table_factor)
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr)
+    }
+
+    /// A name that refers to a table or view, wherever it appears
+    fn visit_table_name(&mut self, name: &mut ObjectName) {
+        self.visit_object_name(name)
+    }
+
+    /// Any other object name, such as a function, index or collation
+    fn visit_object_name(&mut self, _name: &mut ObjectName) {}
+}
+
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::Query(query) => visitor.visit_query(query),
+        Statement::Explain { body, .. } => match body {
+            ExplainStmt::Stmt(statement) => visitor.visit_statement(statement),
+            ExplainStmt::TableDescribe(table_name) => visitor.visit_table_name(table_name),
+            ExplainStmt::Connection(_) => {}
+        },
+        Statement::Insert {
+            table_name,
+            source,
+            update,
+            ..
+        } => {
+            visitor.visit_table_name(table_name);
+            visitor.visit_query(source);
+            for assignment in update.iter_mut().flatten() {
+                visitor.visit_expr(&mut assignment.value);
+            }
+        }
+        Statement::Replace {
+            table_name, source, ..
+        } => {
+            visitor.visit_table_name(table_name);
+            visitor.visit_query(source);
+        }
+        Statement::Copy { table_name, .. } => visitor.visit_table_name(table_name),
+        Statement::Update {
+            table_name,
+            assignments,
+            selection,
+            limit,
+            ..
+        } => {
+            visitor.visit_table_name(table_name);
+            for assignment in assignments {
+                visitor.visit_expr(&mut assignment.value);
+            }
+            walk_opt_expr(visitor, selection);
+            walk_opt_expr(visitor, limit);
+        }
+        Statement::Delete {
+            table_name,
+            selection,
+            ..
+        } => {
+            visitor.visit_table_name(table_name);
+            walk_opt_expr(visitor, selection);
+        }
+        Statement::CreateView { name, query, .. } => {
+            visitor.visit_table_name(name);
+            visitor.visit_query(query);
+        }
+        Statement::CreateTable {
+            name,
+            columns,
+            index,
+            constraints,
+            query,
+            ..
+        } => {
+            visitor.visit_table_name(name);
+            for column in columns {
+                walk_column_def_mut(visitor, column);
+            }
+            for index in index {
+                walk_index_info_mut(visitor, index);
+            }
+            for constraint in constraints {
+                walk_table_constraint_mut(visitor, constraint);
+            }
+            if let Some(query) = query {
+                visitor.visit_query(query);
+            }
+        }
+        Statement::CreateVirtualTable { name, .. } => visitor.visit_table_name(name),
+        Statement::CreateIndex {
+            name, table_name, ..
+        } => {
+            visitor.visit_object_name(name);
+            visitor.visit_table_name(table_name);
+        }
+        Statement::AlterTable { name, operation } => {
+            visitor.visit_table_name(name);
+            for operation in operation {
+                walk_alter_table_operation_mut(visitor, operation);
+            }
+        }
+        Statement::Drop {
+            object_type,
+            names,
+            on_info,
+            ..
+        } => {
+            for name in names {
+                match object_type {
+                    ObjectType::Table | ObjectType::View => visitor.visit_table_name(name),
+                    ObjectType::Index | ObjectType::Schema => visitor.visit_object_name(name),
+                }
+            }
+            if !on_info.0.is_empty() {
+                visitor.visit_table_name(on_info);
+            }
+        }
+        Statement::AdminSetVariable { selection, .. }
+        | Statement::ReLoad { selection, .. } => walk_opt_expr(visitor, selection),
+        Statement::ShowVariable { filter, .. } | Statement::ShowObjects { filter, .. } => {
+            if let Some(ShowStatementFilter::Where(expr)) = filter {
+                visitor.visit_expr(expr);
+            }
+        }
+        Statement::ShowColumns {
+            table_name, filter, ..
+        } => {
+            visitor.visit_table_name(table_name);
+            if let Some(ShowStatementFilter::Where(expr)) = filter {
+                visitor.visit_expr(expr);
+            }
+        }
+        Statement::ShowCreate { table_name } | Statement::Desc { table_name } => {
+            visitor.visit_table_name(table_name)
+        }
+        Statement::CreateSchema { schema_name, .. } => visitor.visit_object_name(schema_name),
+        Statement::Assert { condition, message } => {
+            visitor.visit_expr(condition);
+            walk_opt_expr(visitor, message);
+        }
+        Statement::Lock { lock_tables } => {
+            for lock in lock_tables {
+                visitor.visit_table_name(&mut lock.table_name);
+            }
+        }
+        Statement::Call { name, parameter } => {
+            visitor.visit_object_name(name);
+            for expr in parameter.iter_mut().flatten() {
+                visitor.visit_expr(expr);
+            }
+        }
+        Statement::VersionedComment { statement, .. } => visitor.visit_statement(statement),
+        Statement::SetVariable { .. }
+        | Statement::StartTransaction { .. }
+        | Statement::SetTransaction { .. }
+        | Statement::Commit { .. }
+        | Statement::Rollback { .. }
+        | Statement::UNLock { .. }
+        | Statement::ChangeDatabase { .. }
+        | Statement::ClientDelimiter { .. } => {}
+    }
+}
+
+pub fn walk_query_mut<V: VisitorMut + ?Sized>(visitor: &mut V, query: &mut Query) {
+    for cte in &mut query.ctes {
+        visitor.visit_query(&mut cte.query);
+    }
+    walk_set_expr_mut(visitor, &mut query.body);
+    for order_by in &mut query.order_by {
+        visitor.visit_expr(&mut order_by.expr);
+    }
+    walk_opt_expr(visitor, &mut query.limit);
+    if let Some(offset) = &mut query.offset {
+        visitor.visit_expr(&mut offset.value);
+    }
+    if let Some(fetch) = &mut query.fetch {
+        walk_opt_expr(visitor, &mut fetch.quantity);
+    }
+}
+
+pub fn walk_set_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, set_expr: &mut SetExpr) {
+    match set_expr {
+        SetExpr::Select(select) => visitor.visit_select(select),
+        SetExpr::Query(query) => visitor.visit_query(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            walk_set_expr_mut(visitor, left);
+            walk_set_expr_mut(visitor, right);
+        }
+        SetExpr::Values(values) | SetExpr::Value(values) => {
+            for expr in values.0.iter_mut().flatten() {
+                visitor.visit_expr(expr);
+            }
+        }
+    }
+}
+
+pub fn walk_select_mut<V: VisitorMut + ?Sized>(visitor: &mut V, select: &mut Select) {
+    if let Some(top) = &mut select.top {
+        walk_opt_expr(visitor, &mut top.quantity);
+    }
+    for item in &mut select.projection {
+        match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                visitor.visit_expr(expr)
+            }
+            SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => {}
+        }
+    }
+    for table in &mut select.from {
+        walk_table_with_joins_mut(visitor, table);
+    }
+    walk_opt_expr(visitor, &mut select.selection);
+    for expr in &mut select.group_by {
+        visitor.visit_expr(expr);
+    }
+    walk_opt_expr(visitor, &mut select.having);
+}
+
+pub fn walk_table_with_joins_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    table: &mut TableWithJoins,
+) {
+    visitor.visit_table_factor(&mut table.relation);
+    for join in &mut table.joins {
+        visitor.visit_table_factor(&mut join.relation);
+        match &mut join.join_operator {
+            JoinOperator::Inner(JoinConstraint::On(expr))
+            | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+            | JoinOperator::RightOuter(JoinConstraint::On(expr))
+            | JoinOperator::FullOuter(JoinConstraint::On(expr)) => visitor.visit_expr(expr),
+            _ => {}
+        }
+    }
+}
+
+pub fn walk_table_factor_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    table_factor: &mut TableFactor,
+) {
+    match table_factor {
+        TableFactor::Table {
+            name,
+            args,
+            with_hints,
+            ..
+        } => {
+            visitor.visit_table_name(name);
+            for expr in args.iter_mut().chain(with_hints) {
+                visitor.visit_expr(expr);
+            }
+        }
+        TableFactor::Derived { subquery, .. } => visitor.visit_query(subquery),
+        TableFactor::NestedJoin(table) => walk_table_with_joins_mut(visitor, table),
+        TableFactor::Dual => {}
+    }
+}
+
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    match expr {
+        Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Extract { expr, .. }
+        | Expr::Interval { value: expr, .. }
+        | Expr::Nested(expr)
+        | Expr::BitwiseNested(expr) => visitor.visit_expr(expr),
+        Expr::InList { expr, list, .. } => {
+            visitor.visit_expr(expr);
+            for expr in list {
+                visitor.visit_expr(expr);
+            }
+        }
+        Expr::InSubquery { expr, subquery, .. } => {
+            visitor.visit_expr(expr);
+            visitor.visit_query(subquery);
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            visitor.visit_expr(expr);
+            visitor.visit_expr(low);
+            visitor.visit_expr(high);
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Collate { expr, collation } => {
+            visitor.visit_expr(expr);
+            visitor.visit_object_name(collation);
+        }
+        Expr::Function(function) => {
+            visitor.visit_object_name(&mut function.name);
+            for expr in &mut function.args {
+                visitor.visit_expr(expr);
+            }
+            if let Some(over) = &mut function.over {
+                for expr in &mut over.partition_by {
+                    visitor.visit_expr(expr);
+                }
+                for order_by in &mut over.order_by {
+                    visitor.visit_expr(&mut order_by.expr);
+                }
+            }
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            if let Some(operand) = operand {
+                visitor.visit_expr(operand);
+            }
+            for expr in conditions.iter_mut().chain(results) {
+                visitor.visit_expr(expr);
+            }
+            if let Some(else_result) = else_result {
+                visitor.visit_expr(else_result);
+            }
+        }
+        Expr::Exists(query) | Expr::Subquery(query) => visitor.visit_query(query),
+        Expr::ListAgg(listagg) => {
+            visitor.visit_expr(&mut listagg.expr);
+            if let Some(separator) = &mut listagg.separator {
+                visitor.visit_expr(separator);
+            }
+            if let Some(ListAggOnOverflow::Truncate {
+                filler: Some(filler),
+                ..
+            }) = &mut listagg.on_overflow
+            {
+                visitor.visit_expr(filler);
+            }
+            for order_by in &mut listagg.within_group {
+                visitor.visit_expr(&mut order_by.expr);
+            }
+        }
+        Expr::Identifier(_)
+        | Expr::Wildcard
+        | Expr::QualifiedWildcard(_)
+        | Expr::CompoundIdentifier(_)
+        | Expr::Value(_)
+        | Expr::Position(_)
+        | Expr::TypedString { .. } => {}
+    }
+}
+
+fn walk_opt_expr<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut Option<Expr>) {
+    if let Some(expr) = expr {
+        visitor.visit_expr(expr);
+    }
+}
+
+fn walk_column_def_mut<V: VisitorMut + ?Sized>(visitor: &mut V, column: &mut ColumnDef) {
+    for option in &mut column.options {
+        match &mut option.option {
+            ColumnOption::Default(expr)
+            | ColumnOption::DefaultExpr(expr)
+            | ColumnOption::Check(expr) => visitor.visit_expr(expr),
+            ColumnOption::ForeignKey { foreign_table, .. } => {
+                visitor.visit_table_name(foreign_table)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn walk_table_constraint_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    constraint: &mut TableConstraint,
+) {
+    match constraint {
+        TableConstraint::ForeignKey { foreign_table, .. } => visitor.visit_table_name(foreign_table),
+        TableConstraint::Check { expr, .. } => visitor.visit_expr(expr),
+        TableConstraint::Unique { .. } => {}
+    }
+}
+
+fn walk_index_info_mut<V: VisitorMut + ?Sized>(visitor: &mut V, index: &mut IndexInfo) {
+    let index = match &mut index.index {
+        IndexDef::Normal(index)
+        | IndexDef::PrimaryKey(index)
+        | IndexDef::ForeignKey(index)
+        | IndexDef::Unique(index) => index,
+    };
+    for option in &mut index.index_option {
+        if let IndexOptions::References { table, .. } = option {
+            visitor.visit_table_name(table);
+        }
+    }
+}
+
+fn walk_alter_table_operation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    operation: &mut AlterTableOperation,
+) {
+    match operation {
+        AlterTableOperation::AddConstraint(constraint) => {
+            walk_table_constraint_mut(visitor, constraint)
+        }
+        AlterTableOperation::AddColumn { column_def }
+        | AlterTableOperation::ModifyColumn { column_def, .. }
+        | AlterTableOperation::ChangeColumn {
+            new_column_def: column_def,
+            ..
+        } => walk_column_def_mut(visitor, column_def),
+        AlterTableOperation::AddIndex { index_def } => walk_index_info_mut(visitor, index_def),
+        AlterTableOperation::RenameTable { table_name } => visitor.visit_table_name(table_name),
+        _ => {}
+    }
+}
+
+impl Statement {
+    /// Call `f` on every table name in the statement, including the ones in
+    /// subqueries, CTEs and the sources of `INSERT` and `CREATE VIEW`.
+    ///
+    /// Columns qualified by a table name, like `t.a`, are left alone.
+    pub fn rewrite_table_names<F: FnMut(&mut ObjectName)>(&mut self, f: F) {
+        struct TableNames<F>(F);
+
+        impl<F: FnMut(&mut ObjectName)> VisitorMut for TableNames<F> {
+            fn visit_table_name(&mut self, name: &mut ObjectName) {
+                (self.0)(name)
+            }
+        }
+
+        TableNames(f).visit_statement(self)
+    }
+}
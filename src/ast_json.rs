@@ -0,0 +1,90 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The AST as versioned JSON, for passing statements between processes.
+//!
+//! A statement is written as `{"version": N, "statement": {...}}` with the
+//! statement in serde's default representation. Changes that only add
+//! enum variants, or struct fields with `#[serde(default)]`, keep the
+//! version, so JSON written by an older release of the same version still
+//! reads. Anything else bumps `SCHEMA_VERSION`.
+
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::Statement;
+
+/// The version of the JSON shape of the AST
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Document<'a> {
+    version: u32,
+    statement: &'a Statement,
+}
+
+#[derive(Deserialize)]
+struct OwnedDocument {
+    version: u32,
+    statement: serde_json::Value,
+}
+
+/// Why `from_json` failed
+#[derive(Debug)]
+pub enum JsonError {
+    /// The JSON isn't a statement document
+    Json(serde_json::Error),
+    /// The document is of another schema version than `SCHEMA_VERSION`
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Json(e) => write!(f, "invalid AST JSON: {}", e),
+            JsonError::UnsupportedVersion(version) => write!(
+                f,
+                "AST JSON version {} is not supported, expected {}",
+                version, SCHEMA_VERSION
+            ),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(e: serde_json::Error) -> Self {
+        JsonError::Json(e)
+    }
+}
+
+/// The statement as a JSON document of the current `SCHEMA_VERSION`
+pub fn to_json(statement: &Statement) -> String {
+    serde_json::to_string(&Document {
+        version: SCHEMA_VERSION,
+        statement,
+    })
+    .expect("the AST serializes to JSON")
+}
+
+/// The statement of a JSON document written by `to_json`
+pub fn from_json(json: &str) -> Result<Statement, JsonError> {
+    // check the version before the statement, whose shape depends on it
+    let document: OwnedDocument = serde_json::from_str(json)?;
+    if document.version != SCHEMA_VERSION {
+        return Err(JsonError::UnsupportedVersion(document.version));
+    }
+    Ok(serde_json::from_value(document.statement)?)
+}
@@ -10,7 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::dialect::{Dialect, DBType};
+use crate::dialect::Dialect;
 
 #[derive(Debug)]
 pub struct AnsiDialect {}
@@ -26,8 +26,4 @@ impl Dialect for AnsiDialect {
             || (ch >= '0' && ch <= '9')
             || ch == '_'
     }
-
-    fn check_db_type(&self) -> DBType {
-        unimplemented!()
-    }
 }
@@ -33,4 +33,8 @@ impl Dialect for GenericDialect {
     fn check_db_type(&self) -> DBType {
         DBType::Generic
     }
+
+    fn supports_limit_comma(&self) -> bool {
+        true
+    }
 }
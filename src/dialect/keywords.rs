@@ -42,6 +42,7 @@ macro_rules! define_keywords {
         $ident:ident $(= $string_keyword:expr)?
     ),*) => {
         #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[allow(non_camel_case_types)]
         pub enum Keyword {
             NoKeyword,
@@ -67,6 +68,7 @@ define_keywords!(
     ACTION,
     ADD,
     AFTER,
+    ALGORITHM,
     ALL,
     ALLOCATE,
     ALTER,
@@ -86,8 +88,10 @@ define_keywords!(
     AT,
     ATOMIC,
     AUTHORIZATION,
+    AUTOEXTEND_SIZE,
     AUTO_INCREMENT,
     AVG,
+    AVG_ROW_LENGTH,
     AVRO,
     BEGIN,
     BEGIN_FRAME,
@@ -98,6 +102,7 @@ define_keywords!(
     BLOB,
     BOOLEAN,
     BOTH,
+    BTREE,
     BY,
     BYTEA,
     CALL,
@@ -117,6 +122,7 @@ define_keywords!(
     CHARSET,
     CHAR_LENGTH,
     CHECK,
+    CHECKSUM,
     CLOB,
     CLOSE,
     COALESCE,
@@ -124,9 +130,11 @@ define_keywords!(
     COLLECT,
     COLUMN,
     COLUMNS,
+    COLUMN_FORMAT,
     COMMENT,
     COMMIT,
     COMMITTED,
+    COMPRESSION,
     CONDITION,
     CONFIG,
     CONNECT,
@@ -159,38 +167,54 @@ define_keywords!(
     CURRENT_USER,
     CURSOR,
     CYCLE,
+    DATA,
     DATABASE,
     DATE,
+    DATETIME,
     DAY,
+    DAY_HOUR,
+    DAY_MICROSECOND,
+    DAY_MINUTE,
+    DAY_SECOND,
     DEALLOCATE,
     DEC,
     DECIMAL,
     DECLARE,
     DEFAULT,
     DELAYED,
+    DELAY_KEY_WRITE,
     DELETE,
     DENSE_RANK,
     DEREF,
     DESC,
     DESCRIBE,
     DETERMINISTIC,
+    DIRECTORY,
+    DISABLE,
     DISCONNECT,
+    DISK,
     DISTINCT,
+    DISTINCTROW,
     DOUBLE,
     DROP,
+    DUAL,
     DUPLICATE,
     DYNAMIC,
     EACH,
     ELEMENT,
     ELSE,
+    ENABLE,
+    ENCRYPTION,
     END,
     END_EXEC = "END-EXEC",
     END_FRAME,
     END_PARTITION,
     ENGINE,
+    ENGINE_ATTRIBUTE,
     EQUALS,
     ERROR,
     ESCAPE,
+    EVENTS,
     EVERY,
     EXCEPT,
     EXEC,
@@ -207,6 +231,7 @@ define_keywords!(
     FILTER,
     FIRST,
     FIRST_VALUE,
+    FIXED,
     FLOAT,
     FLOOR,
     FOLLOWING,
@@ -227,11 +252,15 @@ define_keywords!(
     GROUP,
     GROUPING,
     GROUPS,
+    HASH,
     HAVING,
     HEADER,
     HIGH_PRIORITY,
     HOLD,
     HOUR,
+    HOUR_MICROSECOND,
+    HOUR_MINUTE,
+    HOUR_SECOND,
     IDENTITY,
     IF,
     IGNORE,
@@ -242,18 +271,21 @@ define_keywords!(
     INOUT,
     INSENSITIVE,
     INSERT,
+    INSERT_METHOD,
     INT,
     INTEGER,
     INTERSECT,
     INTERSECTION,
     INTERVAL,
     INTO,
+    INVISIBLE,
     IS,
     ISOLATION,
     JOIN,
     JSON,
     JSONFILE,
     KEY,
+    KEYS,
     KEY_BLOCK_SIZE,
     LAG,
     LANGUAGE,
@@ -264,27 +296,37 @@ define_keywords!(
     LEAD,
     LEADING,
     LEFT,
+    LESS,
     LEVEL,
     LIKE,
     LIKE_REGEX,
     LIMIT,
+    LINEAR,
+    LIST,
     LISTAGG,
     LN,
     LOCAL,
     LOCALTIME,
     LOCALTIMESTAMP,
     LOCATION,
-    LOW_PRIORITY,
-    LOWER,
     LOCK,
+    LOWER,
+    LOW_PRIORITY,
     MATCH,
     MATERIALIZED,
     MAX,
+    MAXVALUE,
+    MAX_ROWS,
     MEMBER,
+    MEMORY,
     MERGE,
     METHOD,
+    MICROSECOND,
     MIN,
     MINUTE,
+    MINUTE_MICROSECOND,
+    MINUTE_SECOND,
+    MIN_ROWS,
     MOD,
     MODIFIES,
     MODIFY,
@@ -325,11 +367,14 @@ define_keywords!(
     OVERFLOW,
     OVERLAPS,
     OVERLAY,
+    PACK_KEYS,
     PARAMETER,
     PARQUET,
     PARSER,
+    PARTIAL,
     PARTITION,
     PARTITIONS,
+    PASSWORD,
     PERCENT,
     PERCENTILE_CONT,
     PERCENTILE_DISC,
@@ -345,6 +390,7 @@ define_keywords!(
     PREPARE,
     PRIMARY,
     PROCEDURE,
+    QUARTER,
     RANGE,
     RANK,
     RCFILE,
@@ -381,6 +427,7 @@ define_keywords!(
     ROW,
     ROWID,
     ROWS,
+    ROW_FORMAT,
     ROW_NUMBER,
     SAVEPOINT,
     SCHEMA,
@@ -388,6 +435,8 @@ define_keywords!(
     SCROLL,
     SEARCH,
     SECOND,
+    SECONDARY_ENGINE_ATTRIBUTE,
+    SECOND_MICROSECOND,
     SELECT,
     SENSITIVE,
     SEQUENCEFILE,
@@ -397,6 +446,7 @@ define_keywords!(
     SET,
     SHOW,
     SIMILAR,
+    SIMPLE,
     SMALLINT,
     SOME,
     SPATIAL,
@@ -406,14 +456,28 @@ define_keywords!(
     SQLEXCEPTION,
     SQLSTATE,
     SQLWARNING,
+    SQL_BIG_RESULT,
+    SQL_BUFFER_RESULT,
+    SQL_CACHE,
+    SQL_CALC_FOUND_ROWS,
+    SQL_NO_CACHE,
+    SQL_SMALL_RESULT,
     SQRT,
     START,
     STATIC,
+    STATS_AUTO_RECALC,
+    STATS_PERSISTENT,
+    STATS_SAMPLE_PAGES,
+    STATUS,
     STDDEV_POP,
     STDDEV_SAMP,
     STDIN,
+    STORAGE,
     STORED,
+    STRAIGHT_JOIN,
     SUBMULTISET,
+    SUBPARTITION,
+    SUBPARTITIONS,
     SUBSTRING,
     SUBSTRING_REGEX,
     SUCCEEDS,
@@ -425,8 +489,10 @@ define_keywords!(
     TABLE,
     TABLES,
     TABLESAMPLE,
+    TABLESPACE,
     TEXT,
     TEXTFILE,
+    THAN,
     THEN,
     TIES,
     TIME,
@@ -444,6 +510,7 @@ define_keywords!(
     TREAT,
     TREE,
     TRIGGER,
+    TRIGGERS,
     TRIM,
     TRIM_ARRAY,
     TRUE,
@@ -462,6 +529,9 @@ define_keywords!(
     USE,
     USER,
     USING,
+    UTC_DATE,
+    UTC_TIME,
+    UTC_TIMESTAMP,
     UUID,
     VALUE,
     VALUES,
@@ -474,6 +544,8 @@ define_keywords!(
     VERSIONING,
     VIEW,
     VIRTUAL,
+    VISIBLE,
+    WEEK,
     WHEN,
     WHENEVER,
     WHERE,
@@ -485,6 +557,7 @@ define_keywords!(
     WORK,
     WRITE,
     YEAR,
+    YEAR_MONTH,
     ZONE
 );
 
@@ -546,6 +619,79 @@ pub const RESERVED_FOR_COLUMN_ALIAS: &[Keyword] = &[
     Keyword::FROM,
 ];
 
+/// The words MySQL reserves, which can only be used as identifiers when
+/// quoted. Some of them are not keywords of the parser, so this is a list of
+/// strings rather than of `Keyword`s.
+pub const RESERVED_WORDS: &[&str] = &[
+    "ACCESSIBLE", "ADD", "ALL", "ALTER", "ANALYZE", "AND", "AS", "ASC", "ASENSITIVE", "BEFORE",
+    "BETWEEN", "BIGINT", "BINARY", "BLOB", "BOTH", "BY", "CALL", "CASCADE", "CASE", "CHANGE",
+    "CHAR", "CHARACTER", "CHECK", "COLLATE", "COLUMN", "CONDITION", "CONSTRAINT", "CONTINUE",
+    "CONVERT", "CREATE", "CROSS", "CUBE", "CUME_DIST", "CURRENT_DATE", "CURRENT_TIME",
+    "CURRENT_TIMESTAMP", "CURRENT_USER", "CURSOR", "DATABASE", "DATABASES", "DAY_HOUR",
+    "DAY_MICROSECOND", "DAY_MINUTE", "DAY_SECOND", "DEC", "DECIMAL", "DECLARE", "DEFAULT",
+    "DELAYED", "DELETE", "DENSE_RANK", "DESC", "DESCRIBE", "DETERMINISTIC", "DISTINCT",
+    "DISTINCTROW", "DIV", "DOUBLE", "DROP", "DUAL", "EACH", "ELSE", "ELSEIF", "EMPTY", "ENCLOSED",
+    "ESCAPED", "EXCEPT", "EXISTS", "EXIT", "EXPLAIN", "FALSE", "FETCH", "FIRST_VALUE", "FLOAT",
+    "FLOAT4", "FLOAT8", "FOR", "FORCE", "FOREIGN", "FROM", "FULLTEXT", "FUNCTION", "GENERATED",
+    "GET", "GRANT", "GROUP", "GROUPING", "GROUPS", "HAVING", "HIGH_PRIORITY", "HOUR_MICROSECOND",
+    "HOUR_MINUTE", "HOUR_SECOND", "IF", "IGNORE", "IN", "INDEX", "INFILE", "INNER", "INOUT",
+    "INSENSITIVE", "INSERT", "INT", "INT1", "INT2", "INT3", "INT4", "INT8", "INTEGER", "INTERSECT",
+    "INTERVAL", "INTO", "IO_AFTER_GTIDS", "IO_BEFORE_GTIDS", "IS", "ITERATE", "JOIN", "JSON_TABLE",
+    "KEY", "KEYS", "KILL", "LAG", "LAST_VALUE", "LATERAL", "LEAD", "LEADING", "LEAVE", "LEFT",
+    "LIKE", "LIMIT", "LINEAR", "LINES", "LOAD", "LOCALTIME", "LOCALTIMESTAMP", "LOCK", "LONG",
+    "LONGBLOB", "LONGTEXT", "LOOP", "LOW_PRIORITY", "MASTER_BIND", "MASTER_SSL_VERIFY_SERVER_CERT",
+    "MATCH", "MAXVALUE", "MEDIUMBLOB", "MEDIUMINT", "MEDIUMTEXT", "MIDDLEINT", "MINUTE_MICROSECOND",
+    "MINUTE_SECOND", "MOD", "MODIFIES", "NATURAL", "NOT", "NO_WRITE_TO_BINLOG", "NTH_VALUE",
+    "NTILE", "NULL", "NUMERIC", "OF", "ON", "OPTIMIZE", "OPTIMIZER_COSTS", "OPTION", "OPTIONALLY",
+    "OR", "ORDER", "OUT", "OUTER", "OUTFILE", "OVER", "PARTITION", "PERCENT_RANK", "PRECISION",
+    "PRIMARY", "PROCEDURE", "PURGE", "RANGE", "RANK", "READ", "READS", "READ_WRITE", "REAL",
+    "RECURSIVE", "REFERENCES", "REGEXP", "RELEASE", "RENAME", "REPEAT", "REPLACE", "REQUIRE",
+    "RESIGNAL", "RESTRICT", "RETURN", "REVOKE", "RIGHT", "RLIKE", "ROW", "ROWS", "ROW_NUMBER",
+    "SCHEMA", "SCHEMAS", "SECOND_MICROSECOND", "SELECT", "SENSITIVE", "SEPARATOR", "SET", "SHOW",
+    "SIGNAL", "SMALLINT", "SPATIAL", "SPECIFIC", "SQL", "SQLEXCEPTION", "SQLSTATE", "SQLWARNING",
+    "SQL_BIG_RESULT", "SQL_CALC_FOUND_ROWS", "SQL_SMALL_RESULT", "SSL", "STARTING", "STORED",
+    "STRAIGHT_JOIN", "SYSTEM", "TABLE", "TERMINATED", "THEN", "TINYBLOB", "TINYINT", "TINYTEXT",
+    "TO", "TRAILING", "TRIGGER", "TRUE", "UNDO", "UNION", "UNIQUE", "UNLOCK", "UNSIGNED", "UPDATE",
+    "USAGE", "USE", "USING", "UTC_DATE", "UTC_TIME", "UTC_TIMESTAMP", "VALUES", "VARBINARY",
+    "VARCHAR", "VARCHARACTER", "VARYING", "VIRTUAL", "WHEN", "WHERE", "WHILE", "WINDOW", "WITH",
+    "WRITE", "XOR", "YEAR_MONTH", "ZEROFILL",
+];
+
+/// Whether MySQL reserves the word, in any case
+pub fn is_reserved(word: &str) -> bool {
+    RESERVED_WORDS
+        .binary_search_by(|reserved| cmp_uppercase(reserved, word))
+        .is_ok()
+}
+
+/// Compare an upper case word with `word` upper cased, without allocating.
+/// The keywords are all ASCII, so that's enough for a case-insensitive match.
+fn cmp_uppercase(upper: &str, word: &str) -> core::cmp::Ordering {
+    upper
+        .bytes()
+        .cmp(word.bytes().map(|b| b.to_ascii_uppercase()))
+}
+
+impl Keyword {
+    /// The keyword spelled `word` in any case, e.g. `Keyword::SELECT` for
+    /// `"select"`, or `None` if it isn't one
+    pub fn from_str_ci(word: &str) -> Option<Keyword> {
+        ALL_KEYWORDS
+            .binary_search_by(|keyword| cmp_uppercase(keyword, word))
+            .ok()
+            .map(|index| ALL_KEYWORDS_INDEX[index])
+    }
+
+    /// Whether MySQL reserves the keyword, so that it can only be used as an
+    /// identifier when quoted
+    pub fn is_reserved(self) -> bool {
+        match ALL_KEYWORDS_INDEX.binary_search(&self) {
+            Ok(index) => is_reserved(ALL_KEYWORDS[index]),
+            Err(_) => false,
+        }
+    }
+}
+
 mod tests {
     use crate::dialect::keywords::ALL_KEYWORDS;
     #[test]
@@ -554,4 +700,23 @@ mod tests {
         let b = ALL_KEYWORDS.binary_search(&&*a);
         println!("{:?}",&b);
     }
+
+    #[test]
+    fn reserved_words_sorted() {
+        use crate::dialect::keywords::{is_reserved, Keyword, RESERVED_WORDS};
+        assert!(RESERVED_WORDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(is_reserved("order") && is_reserved("SQL_BIG_RESULT"));
+        assert!(!is_reserved("status"));
+        assert!(Keyword::SELECT.is_reserved() && !Keyword::ENGINE.is_reserved());
+        assert!(!Keyword::NoKeyword.is_reserved());
+    }
+
+    #[test]
+    fn keyword_from_str_ci() {
+        use crate::dialect::keywords::Keyword;
+        assert_eq!(Some(Keyword::SELECT), Keyword::from_str_ci("select"));
+        assert_eq!(Some(Keyword::LOW_PRIORITY), Keyword::from_str_ci("Low_Priority"));
+        assert_eq!(None, Keyword::from_str_ci("selects"));
+        assert_eq!(None, Keyword::from_str_ci(""));
+    }
 }
\ No newline at end of file
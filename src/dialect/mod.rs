@@ -17,7 +17,12 @@ mod mssql;
 mod mysql;
 mod postgresql;
 
-use std::fmt::Debug;
+use core::fmt::Debug;
+
+use crate::tokenizer::Word;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 pub use self::ansi::AnsiDialect;
 pub use self::generic::GenericDialect;
@@ -25,7 +30,9 @@ pub use self::mssql::MsSqlDialect;
 pub use self::mysql::MySqlDialect;
 pub use self::postgresql::PostgreSqlDialect;
 
-#[derive(Clone)]
+/// The database a dialect is for, see `Dialect::check_db_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DBType{
     Generic,
     MsSql,
@@ -47,5 +54,88 @@ pub trait Dialect: Debug {
     /// Determine if a character is a valid unquoted identifier character
     fn is_identifier_part(&self, ch: char) -> bool;
 
-    fn check_db_type(&self) -> DBType;
+    /// The database the dialect is for. The parser decides between
+    /// syntaxes with the `supports_*` methods instead, so a custom dialect
+    /// can mix them.
+    fn check_db_type(&self) -> DBType {
+        DBType::Generic
+    }
+    /// Whether `/*!NNNNN ... */` comments contain SQL to execute, as in MySQL
+    fn supports_versioned_comments(&self) -> bool {
+        false
+    }
+    /// Whether `"..."` is a string literal rather than a delimited identifier,
+    /// as in MySQL unless the `ANSI_QUOTES` SQL mode is enabled
+    fn supports_double_quoted_strings(&self) -> bool {
+        false
+    }
+    /// Whether a backslash in a string literal escapes the next character
+    fn supports_backslash_escapes(&self) -> bool {
+        false
+    }
+    /// Whether `#` starts a comment that runs to the end of the line
+    fn supports_hash_comments(&self) -> bool {
+        false
+    }
+    /// Whether `--` only starts a comment when followed by whitespace or a
+    /// control character
+    fn requires_whitespace_after_double_dash(&self) -> bool {
+        false
+    }
+    /// Whether `CREATE TABLE` and `ALTER TABLE ... ADD` read MySQL index
+    /// definitions such as `KEY k (a)` rather than table constraints
+    fn supports_index_definitions(&self) -> bool {
+        false
+    }
+    /// Whether `CREATE TABLE` can be followed by `[IGNORE | REPLACE] SELECT`
+    /// without `AS`, as in MySQL
+    fn supports_create_table_select_without_as(&self) -> bool {
+        false
+    }
+    /// Whether an expression can be followed by `COLLATE <collation>`
+    fn supports_collate_expr(&self) -> bool {
+        true
+    }
+    /// Whether `LIMIT` accepts a second value after a comma, as in MySQL
+    fn supports_limit_comma(&self) -> bool {
+        false
+    }
+    /// Whether `INTERVAL` can be followed by any expression and a unit, as
+    /// in MySQL's `INTERVAL 1 DAY`, rather than only by a string literal
+    fn supports_interval_expr(&self) -> bool {
+        false
+    }
+    /// Whether an `ORDER BY` item can end with `NULLS FIRST` or `NULLS LAST`,
+    /// which MySQL lacks
+    fn supports_nulls_ordering(&self) -> bool {
+        true
+    }
+    /// Whether `SELECT` can be followed by MySQL's modifiers such as
+    /// `SQL_NO_CACHE` or `STRAIGHT_JOIN`, see `SelectModifier`
+    fn supports_select_modifiers(&self) -> bool {
+        false
+    }
+    /// Whether `SELECT TOP <n>` limits the rows, as in MSSQL
+    fn supports_top(&self) -> bool {
+        true
+    }
+    /// Whether a query can end with `FETCH { FIRST | NEXT } ...`
+    fn supports_fetch(&self) -> bool {
+        true
+    }
+    /// Whether the word can't be a column alias unless it follows `AS`, so
+    /// that `SELECT <expr> <alias>` can be parsed without looking ahead
+    fn is_reserved_for_column_alias(&self, word: &Word) -> bool {
+        keywords::RESERVED_FOR_COLUMN_ALIAS.contains(&word.keyword)
+    }
+    /// Whether the word can't name a column in a column definition or in
+    /// `ALTER TABLE ... ADD` and `DROP`, unless it's quoted
+    fn is_reserved_for_column_name(&self, _word: &Word) -> bool {
+        false
+    }
+    /// Whether the word can't be a table alias unless it follows `AS`, so
+    /// that `FROM <table> <alias>` can be parsed without looking ahead
+    fn is_reserved_for_table_alias(&self, word: &Word) -> bool {
+        keywords::RESERVED_FOR_TABLE_ALIAS.contains(&word.keyword)
+    }
 }
@@ -10,7 +10,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::dialect::keywords::{self, Keyword};
 use crate::dialect::{Dialect, DBType};
+use crate::tokenizer::Word;
 
 #[derive(Debug)]
 pub struct MySqlDialect {}
@@ -34,4 +36,74 @@ impl Dialect for MySqlDialect {
     fn check_db_type(&self) -> DBType {
         DBType::MySql
     }
+
+    fn supports_versioned_comments(&self) -> bool {
+        true
+    }
+
+    fn supports_double_quoted_strings(&self) -> bool {
+        true
+    }
+
+    fn supports_backslash_escapes(&self) -> bool {
+        true
+    }
+
+    fn supports_hash_comments(&self) -> bool {
+        true
+    }
+
+    fn requires_whitespace_after_double_dash(&self) -> bool {
+        true
+    }
+
+    fn supports_index_definitions(&self) -> bool {
+        true
+    }
+
+    fn supports_create_table_select_without_as(&self) -> bool {
+        true
+    }
+
+    fn supports_limit_comma(&self) -> bool {
+        true
+    }
+
+    fn supports_interval_expr(&self) -> bool {
+        true
+    }
+
+    fn supports_nulls_ordering(&self) -> bool {
+        false
+    }
+
+    fn supports_select_modifiers(&self) -> bool {
+        true
+    }
+
+    fn supports_top(&self) -> bool {
+        false
+    }
+
+    fn supports_fetch(&self) -> bool {
+        false
+    }
+
+    /// The words MySQL reserves, so that e.g. `SELECT x offset` has an alias
+    /// but `SELECT x rank` is an error
+    fn is_reserved_for_column_alias(&self, word: &Word) -> bool {
+        word.quote_style.is_none() && keywords::is_reserved(&word.value)
+    }
+
+    /// The words MySQL reserves, so that `ALTER TABLE t ADD KEY ...` adds an
+    /// index but `ALTER TABLE t ADD comment INT` a column
+    fn is_reserved_for_column_name(&self, word: &Word) -> bool {
+        word.quote_style.is_none() && keywords::is_reserved(&word.value)
+    }
+
+    /// The words MySQL reserves, and `FULL` for the `FULL JOIN` the parser
+    /// accepts
+    fn is_reserved_for_table_alias(&self, word: &Word) -> bool {
+        word.keyword == Keyword::FULL || self.is_reserved_for_column_alias(word)
+    }
 }
@@ -0,0 +1,949 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pretty-printing of statements over several lines.
+//!
+//! The `Display` implementations of the AST print a statement on a single
+//! line. `format_statement` lays out queries, `INSERT`, `UPDATE`, `DELETE`,
+//! `CREATE TABLE` and `CREATE VIEW` with each clause and join on its own
+//! line and subqueries indented, and prints the parts within a clause, and
+//! all other statements, as `Display` does.
+
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::ast::*;
+use crate::dialect::keywords::ALL_KEYWORDS;
+
+/// How `format_statement` lays out a statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// The number of spaces per level of indentation
+    pub indent: usize,
+    /// Whether keywords are upper case, as `Display` prints them, rather than
+    /// lower case. The words the statement holds as written keep their case,
+    /// so that the statement parses back the same: the names of tables,
+    /// columns and indexes, aliases, function names such as `COUNT`, and
+    /// the values of options such as `ROW_FORMAT=DYNAMIC`.
+    pub uppercase_keywords: bool,
+    /// Whether to quote the names of tables and columns, and aliases, with
+    /// backticks
+    pub quote_identifiers: bool,
+    /// Lists and `AND`ed conditions longer than this are split into one
+    /// line per item
+    pub max_line_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent: 2,
+            uppercase_keywords: true,
+            quote_identifiers: false,
+            max_line_width: 80,
+        }
+    }
+}
+
+/// Pretty-print the statement over several lines
+pub fn format_statement(statement: &Statement, options: &FormatOptions) -> String {
+    let mut statement = statement.clone();
+    if options.quote_identifiers {
+        QuoteOptions {
+            policy: QuotePolicy::Always,
+            quote: '`',
+        }
+        .apply(&mut statement);
+    }
+    if !options.uppercase_keywords {
+        mark_words(&mut statement);
+    }
+    let mut formatter = Formatter {
+        options,
+        out: String::new(),
+        depth: 0,
+    };
+    formatter.statement(&statement);
+    formatter.out
+}
+
+/// The statement as `Display` prints it, but with lower case keywords
+pub(crate) fn to_lowercase_keywords(mut statement: Statement) -> String {
+    mark_words(&mut statement);
+    lowercase_keywords(&statement.to_string())
+}
+
+/// The quote around the words `lowercase_keywords` skips and drops
+const NAME_MARK: char = '\u{E000}';
+
+fn mark(ident: &mut Ident) {
+    if ident.quote_style.is_none() {
+        ident.quote_style = Some(NAME_MARK);
+    }
+}
+
+fn mark_all<'i>(idents: impl IntoIterator<Item = &'i mut Ident>) {
+    idents.into_iter().for_each(mark)
+}
+
+/// Mark the unquoted words the statement holds as written, rather than as
+/// keywords `Display` prints: the names of tables, columns and indexes,
+/// aliases, function names, and the values of options such as
+/// `ROW_FORMAT=DYNAMIC` or `LOCK=NONE`. Lower casing the rest then leaves a
+/// statement that parses back to the same one.
+fn mark_words(statement: &mut Statement) {
+    visit_names(statement, &mark);
+    Words.visit_statement(statement);
+}
+
+/// The words `visit_names` leaves out
+struct Words;
+
+impl Words {
+    fn data_type(&mut self, data_type: &mut DataType) {
+        match data_type {
+            DataType::Custom(name) => mark_all(&mut name.0),
+            DataType::Array(data_type) => self.data_type(data_type),
+            _ => {}
+        }
+    }
+
+    fn column_def(&mut self, column: &mut ColumnDef) {
+        self.data_type(&mut column.data_type);
+        if let Some(collation) = &mut column.collation {
+            mark_all(&mut collation.0);
+        }
+        for option in &mut column.options {
+            if let Some(name) = &mut option.name {
+                mark(name);
+            }
+            self.column_option(&mut option.option);
+        }
+    }
+
+    fn column_option(&mut self, option: &mut ColumnOption) {
+        match option {
+            ColumnOption::Character(expr)
+            | ColumnOption::Collate(expr)
+            | ColumnOption::After(expr) => self.visit_expr(expr),
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                ..
+            } => mark_all(foreign_table.0.iter_mut().chain(referred_columns)),
+            _ => {}
+        }
+    }
+
+    fn constraint(&mut self, constraint: &mut TableConstraint) {
+        let (TableConstraint::Unique { name, .. }
+        | TableConstraint::ForeignKey { name, .. }
+        | TableConstraint::Check { name, .. }) = constraint;
+        if let Some(name) = name {
+            mark(name);
+        }
+    }
+
+    fn index_info(&mut self, info: &mut IndexInfo) {
+        if let Some(constraint) = &mut info.constraint {
+            mark(constraint);
+        }
+        self.index_def(&mut info.index);
+    }
+
+    fn index_def(&mut self, index: &mut IndexDef) {
+        let (IndexDef::Normal(index)
+        | IndexDef::PrimaryKey(index)
+        | IndexDef::ForeignKey(index)
+        | IndexDef::Unique(index)) = index;
+        // `name` is the `KEY` or `INDEX` keyword, as written
+        mark_all(
+            index
+                .name
+                .iter_mut()
+                .chain(&mut index.index_name)
+                .chain(&mut index.index_type),
+        );
+        self.key_parts(index.key_parts.iter_mut().flatten());
+        self.index_options(&mut index.index_option);
+    }
+
+    fn key_parts<'k>(&mut self, key_parts: impl Iterator<Item = &'k mut IndexKeyPart>) {
+        for key_part in key_parts {
+            match key_part {
+                IndexKeyPart::Column { name, .. } => mark(name),
+                IndexKeyPart::Expr { expr, .. } => self.visit_expr(expr),
+            }
+        }
+    }
+
+    fn index_options(&mut self, options: &mut [IndexOptions]) {
+        for option in options {
+            match option {
+                IndexOptions::KeyBlockSize(expr)
+                | IndexOptions::Comment(expr)
+                | IndexOptions::EngineAttribute(expr)
+                | IndexOptions::SecondaryEngineAttribute(expr) => self.visit_expr(expr),
+                IndexOptions::IndexType(ident) | IndexOptions::WithParser(ident) => mark(ident),
+                IndexOptions::References { table, column, .. } => {
+                    mark_all(table.0.iter_mut().chain(column))
+                }
+                IndexOptions::Visible | IndexOptions::Invisible => {}
+            }
+        }
+    }
+
+    fn table_options<'o>(&mut self, options: impl IntoIterator<Item = &'o mut TableOption>) {
+        for option in options {
+            match option {
+                TableOption::Auto_Increment(_) => {}
+                TableOption::Tablespace { name, .. } => mark(name),
+                TableOption::Union(tables) => mark_all(tables.iter_mut().flat_map(|t| &mut t.0)),
+                TableOption::Other { name, value } => {
+                    mark(name);
+                    self.visit_expr(value);
+                }
+                TableOption::Charset(expr)
+                | TableOption::Comment(expr)
+                | TableOption::Engine(expr)
+                | TableOption::Collate(expr)
+                | TableOption::AutoextendSize(expr)
+                | TableOption::AvgRowLength(expr)
+                | TableOption::Checksum(expr)
+                | TableOption::Compression(expr)
+                | TableOption::Connection(expr)
+                | TableOption::DataDirectory(expr)
+                | TableOption::IndexDirectory(expr)
+                | TableOption::DelayKeyWrite(expr)
+                | TableOption::Encryption(expr)
+                | TableOption::EngineAttribute(expr)
+                | TableOption::InsertMethod(expr)
+                | TableOption::KeyBlockSize(expr)
+                | TableOption::MaxRows(expr)
+                | TableOption::MinRows(expr)
+                | TableOption::PackKeys(expr)
+                | TableOption::Password(expr)
+                | TableOption::RowFormat(expr)
+                | TableOption::SecondaryEngineAttribute(expr)
+                | TableOption::StatsAutoRecalc(expr)
+                | TableOption::StatsPersistent(expr)
+                | TableOption::StatsSamplePages(expr) => self.visit_expr(expr),
+            }
+        }
+    }
+
+    fn table_option_defs(&mut self, options: &mut [TableOptionDef]) {
+        for option in options.iter_mut() {
+            if let Some(name) = &mut option.name {
+                mark(name);
+            }
+        }
+        self.table_options(options.iter_mut().map(|option| &mut option.option));
+    }
+
+    fn partition_method(&mut self, method: &mut PartitionMethod) {
+        match method {
+            PartitionMethod::Hash { expr, .. }
+            | PartitionMethod::Range(PartitionKey::Expr(expr))
+            | PartitionMethod::List(PartitionKey::Expr(expr)) => self.visit_expr(expr),
+            PartitionMethod::Key { columns, .. }
+            | PartitionMethod::Range(PartitionKey::Columns(columns))
+            | PartitionMethod::List(PartitionKey::Columns(columns)) => mark_all(columns),
+        }
+    }
+
+    fn partition(&mut self, partition: &mut PartitionDefinition) {
+        self.partition_method(&mut partition.partition_by);
+        if let Some(method) = &mut partition.subpartition_by {
+            self.partition_method(method);
+        }
+        for definition in &mut partition.definitions {
+            mark(&mut definition.name);
+            match &mut definition.values {
+                Some(PartitionValues::LessThan(exprs)) | Some(PartitionValues::In(exprs)) => {
+                    exprs.iter_mut().for_each(|expr| self.visit_expr(expr))
+                }
+                Some(PartitionValues::LessThanMaxValue) | None => {}
+            }
+            self.table_options(&mut definition.options);
+            for subpartition in &mut definition.subpartitions {
+                mark(&mut subpartition.name);
+                self.table_options(&mut subpartition.options);
+            }
+        }
+    }
+
+    fn alter_table_operation(&mut self, operation: &mut AlterTableOperation) {
+        match operation {
+            AlterTableOperation::AddConstraint(constraint) => self.constraint(constraint),
+            AlterTableOperation::AddColumn { column_def }
+            | AlterTableOperation::ChangeColumn {
+                new_column_def: column_def,
+                ..
+            } => self.column_def(column_def),
+            AlterTableOperation::ModifyColumn {
+                column_def,
+                first_or_after,
+            } => {
+                self.column_def(column_def);
+                if let Some(option) = first_or_after {
+                    self.column_option(option);
+                }
+            }
+            AlterTableOperation::DropConstraint(ident)
+            | AlterTableOperation::DropForeignKey(ident)
+            | AlterTableOperation::DropCheck(ident)
+            | AlterTableOperation::Algorithm(ident)
+            | AlterTableOperation::Lock(ident) => mark(ident),
+            AlterTableOperation::AddIndex { index_def } => self.index_info(index_def),
+            AlterTableOperation::DropIndex { index_def } => self.index_def(index_def),
+            AlterTableOperation::ConvertCharset {
+                charset, collation, ..
+            } => mark_all(Some(charset).into_iter().chain(collation)),
+            AlterTableOperation::SetTableOptions(options) => self.table_option_defs(options),
+            _ => {}
+        }
+    }
+}
+
+impl VisitorMut for Words {
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::CreateView { with_options, .. } => {
+                mark_all(with_options.iter_mut().map(|option| &mut option.name))
+            }
+            Statement::CreateTable {
+                columns,
+                index,
+                constraints,
+                with_options,
+                table_options,
+                partition,
+                ..
+            } => {
+                columns.iter_mut().for_each(|column| self.column_def(column));
+                index.iter_mut().for_each(|info| self.index_info(info));
+                constraints
+                    .iter_mut()
+                    .for_each(|constraint| self.constraint(constraint));
+                mark_all(with_options.iter_mut().map(|option| &mut option.name));
+                self.table_option_defs(table_options);
+                if let Some(partition) = partition {
+                    self.partition(partition);
+                }
+            }
+            Statement::CreateVirtualTable {
+                module_name,
+                module_args,
+                ..
+            } => mark_all(Some(module_name).into_iter().chain(module_args)),
+            Statement::CreateIndex {
+                columns,
+                options,
+                algorithm,
+                lock,
+                ..
+            } => {
+                self.key_parts(columns.iter_mut());
+                self.index_options(options);
+                mark_all(algorithm.iter_mut().chain(lock));
+            }
+            Statement::AlterTable { operation, .. } => operation
+                .iter_mut()
+                .for_each(|operation| self.alter_table_operation(operation)),
+            Statement::Drop {
+                names,
+                on_info,
+                algorithm,
+                lock,
+                ..
+            } => {
+                mark_all(names.iter_mut().flat_map(|name| &mut name.0));
+                mark_all(on_info.0.iter_mut().chain(algorithm).chain(lock));
+            }
+            Statement::SetVariable {
+                variable, value, ..
+            }
+            | Statement::AdminSetVariable {
+                variable, value, ..
+            } => {
+                mark(variable);
+                if let SetVariableValue::Ident(ident) = value {
+                    mark(ident);
+                }
+            }
+            Statement::ReLoad { variable, .. } | Statement::ShowVariable { variable, .. } => {
+                mark(variable)
+            }
+            Statement::ShowColumns { database, .. } | Statement::ShowObjects { database, .. } => {
+                mark_all(database)
+            }
+            _ => {}
+        }
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &mut TableFactor) {
+        if let TableFactor::Table {
+            force: Some(force), ..
+        } = table_factor
+        {
+            mark(force);
+        }
+        walk_table_factor_mut(self, table_factor);
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Identifier(ident) => mark(ident),
+            Expr::CompoundIdentifier(idents) | Expr::QualifiedWildcard(idents) => {
+                mark_all(idents)
+            }
+            Expr::Cast { data_type, .. } | Expr::TypedString { data_type, .. } => {
+                self.data_type(data_type)
+            }
+            _ => {}
+        }
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_object_name(&mut self, name: &mut ObjectName) {
+        mark_all(&mut name.0);
+    }
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+    out: String,
+    /// The current level of indentation
+    depth: usize,
+}
+
+impl<'a> Formatter<'a> {
+    /// Start a new line at the current indentation
+    fn line(&mut self, text: &str) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out.push_str(&" ".repeat(self.depth * self.options.indent));
+        self.push(text);
+    }
+
+    fn push(&mut self, text: &str) {
+        if self.options.uppercase_keywords {
+            self.out.push_str(text);
+        } else {
+            self.out.push_str(&lowercase_keywords(text));
+        }
+    }
+
+    /// Whether the text fits on a new line at the current indentation
+    fn fits(&self, text: &str) -> bool {
+        let width = text.chars().filter(|&c| c != NAME_MARK).count();
+        self.depth * self.options.indent + width <= self.options.max_line_width
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Insert { source: query, .. }
+            | Statement::Replace { source: query, .. }
+            | Statement::CreateView { query, .. } => {
+                // the rest is printed as `Display` does, with the query on
+                // its own lines
+                let text = statement.to_string();
+                match split_around(&text, &query.to_string()) {
+                    Some((before, after)) => {
+                        self.line(before.trim_end());
+                        self.query(query);
+                        if !after.is_empty() {
+                            self.line(after.trim_start());
+                        }
+                    }
+                    None => self.line(&text),
+                }
+            }
+            Statement::CreateTable {
+                columns,
+                index,
+                constraints,
+                query,
+                ..
+            } => self.create_table(statement, columns, index, constraints, query),
+            Statement::Update {
+                hints,
+                comments,
+                table_name,
+                assignments,
+                selection,
+                limit,
+            } => {
+                self.line("UPDATE ");
+                if let Some(hints) = hints {
+                    self.push(&format!("/*+{}*/ ", escape_comment(hints)));
+                }
+                for comment in comments {
+                    self.push(&format!("/*{}*/ ", escape_comment(comment)));
+                }
+                self.push(&table_name.to_string());
+                if !assignments.is_empty() {
+                    self.list("SET", assignments, |f, assignment| {
+                        f.push(&format!("{} = ", ObjectName(assignment.id.clone())));
+                        f.expr(&assignment.value);
+                    });
+                }
+                if let Some(selection) = selection {
+                    self.condition("WHERE", selection);
+                }
+                if let Some(limit) = limit {
+                    self.line(&format!("LIMIT {}", limit));
+                }
+            }
+            Statement::Delete {
+                hints,
+                comments,
+                table_name,
+                selection,
+            } => {
+                self.line("DELETE ");
+                if let Some(hints) = hints {
+                    self.push(&format!("/*+{}*/ ", escape_comment(hints)));
+                }
+                for comment in comments {
+                    self.push(&format!("/*{}*/ ", escape_comment(comment)));
+                }
+                self.push(&format!("FROM {}", table_name));
+                if let Some(selection) = selection {
+                    self.condition("WHERE", selection);
+                }
+            }
+            _ => self.line(&statement.to_string()),
+        }
+    }
+
+    fn create_table(
+        &mut self,
+        statement: &Statement,
+        columns: &[ColumnDef],
+        index: &[IndexInfo],
+        constraints: &[TableConstraint],
+        query: &Option<Box<Query>>,
+    ) {
+        let text = statement.to_string();
+        let elements: Vec<String> = columns
+            .iter()
+            .map(ToString::to_string)
+            .chain(index.iter().map(ToString::to_string))
+            .chain(constraints.iter().map(ToString::to_string))
+            .collect();
+        let (mut header, mut rest) = if elements.is_empty() {
+            (text.as_str(), "")
+        } else {
+            let list = format!(" ({})", elements.join(", "));
+            match split_around(&text, &list) {
+                Some(split) => split,
+                None => return self.line(&text),
+            }
+        };
+        // `AS <query>` comes last
+        let query_text = query.as_ref().map(|query| query.to_string());
+        if let Some(query_text) = &query_text {
+            if elements.is_empty() {
+                header = header.strip_suffix(query_text.as_str()).unwrap_or(header);
+            } else {
+                rest = rest.strip_suffix(query_text.as_str()).unwrap_or(rest);
+            }
+        }
+
+        self.line(header.trim_end());
+        if !elements.is_empty() {
+            self.push(" (");
+            self.depth += 1;
+            for (i, element) in elements.iter().enumerate() {
+                self.line(element);
+                if i + 1 < elements.len() {
+                    self.push(",");
+                }
+            }
+            self.depth -= 1;
+            self.line(")");
+            self.push(rest.trim_end());
+        }
+        if let Some(query) = query {
+            self.query(query);
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        for (i, cte) in query.ctes.iter().enumerate() {
+            if i == 0 {
+                self.line("WITH ");
+            } else {
+                self.push(", ");
+            }
+            self.push(&format!("{} AS (", cte.alias));
+            self.nested_query(&cte.query);
+        }
+        self.set_expr(&query.body);
+        if !query.order_by.is_empty() {
+            self.list("ORDER BY", &query.order_by, |f, order_by| {
+                f.push(&order_by.to_string())
+            });
+        }
+        if let Some(limit) = &query.limit {
+            self.line(&format!("LIMIT {}", limit));
+        }
+        if let Some(offset) = &query.offset {
+            self.line(&offset.to_string());
+        }
+        if query.update {
+            self.line("FOR UPDATE");
+        }
+        if let Some(fetch) = &query.fetch {
+            self.line(&fetch.to_string());
+        }
+    }
+
+    /// The query of a `(` already printed, indented, and the closing `)`
+    fn nested_query(&mut self, query: &Query) {
+        self.depth += 1;
+        self.query(query);
+        self.depth -= 1;
+        self.line(")");
+    }
+
+    fn set_expr(&mut self, set_expr: &SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Query(query) => {
+                self.line("(");
+                self.nested_query(query);
+            }
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                self.set_expr(left);
+                self.line(&format!("{}{}", op, if *all { " ALL" } else { "" }));
+                self.set_expr(right);
+            }
+            SetExpr::Values(values) | SetExpr::Value(values) => {
+                let rows: Vec<String> = values
+                    .0
+                    .iter()
+                    .map(|row| format!("({})", display_comma_separated(row)))
+                    .collect();
+                self.list("VALUES", &rows, |f, row| f.push(row));
+            }
+        }
+    }
+
+    fn select(&mut self, select: &Select) {
+        let mut keyword = "SELECT".to_string();
+        if let Some(hints) = &select.hints {
+            keyword += &format!(" /*+{}*/", escape_comment(hints));
+        }
+        for comment in &select.comments {
+            keyword += &format!(" /*{}*/", escape_comment(comment));
+        }
+        if let Some(distinct) = &select.distinct {
+            keyword += &format!(" {}", distinct);
+        }
+        for modifier in &select.modifiers {
+            keyword += &format!(" {}", modifier);
+        }
+        if let Some(top) = &select.top {
+            keyword += &format!(" {}", top);
+        }
+        self.list(&keyword, &select.projection, |f, item| match item {
+            SelectItem::UnnamedExpr(expr) => f.expr(expr),
+            SelectItem::ExprWithAlias { expr, alias } => {
+                f.expr(expr);
+                f.push(&format!(" AS {}", alias));
+            }
+            _ => f.push(&item.to_string()),
+        });
+        if !select.wildcard_except.is_empty() {
+            let except = display_comma_separated(&select.wildcard_except).to_string();
+            self.push(&format!(" /* EXCEPT {} */", escape_comment(&except)));
+        }
+        for (i, table) in select.from.iter().enumerate() {
+            if i == 0 {
+                self.line("FROM ");
+            } else {
+                self.push(",");
+                self.line("  ");
+            }
+            self.table_with_joins(table);
+        }
+        if let Some(selection) = &select.selection {
+            self.condition("WHERE", selection);
+        }
+        if !select.group_by.is_empty() {
+            self.list("GROUP BY", &select.group_by, Formatter::expr);
+        }
+        if let Some(having) = &select.having {
+            self.condition("HAVING", having);
+        }
+    }
+
+    /// The keyword followed by the items on the same line if they fit, or
+    /// else on one indented line each
+    fn list<T: fmt::Display>(&mut self, keyword: &str, items: &[T], item: fn(&mut Self, &T)) {
+        let split = !self.fits(&format!("{} {}", keyword, display_comma_separated(items)));
+        self.line(keyword);
+        if split {
+            self.depth += 1;
+        }
+        for (i, value) in items.iter().enumerate() {
+            if split {
+                self.line("");
+            } else {
+                self.push(if i == 0 { " " } else { ", " });
+            }
+            item(self, value);
+            if split && i + 1 < items.len() {
+                self.push(",");
+            }
+        }
+        if split {
+            self.depth -= 1;
+        }
+    }
+
+    /// The keyword followed by the condition, with each `AND`ed term on its
+    /// own line if the condition doesn't fit on one
+    fn condition(&mut self, keyword: &str, expr: &Expr) {
+        self.line(&format!("{} ", keyword));
+        if self.fits(&format!("{} {}", keyword, expr)) {
+            return self.expr(expr);
+        }
+        let mut terms = vec![];
+        and_terms(expr, &mut terms);
+        self.expr(terms[0]);
+        self.depth += 1;
+        for term in &terms[1..] {
+            self.line("AND ");
+            self.expr(term);
+        }
+        self.depth -= 1;
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Subquery(query) => {
+                self.push("(");
+                self.nested_query(query);
+            }
+            Expr::Exists(query) => {
+                self.push("EXISTS (");
+                self.nested_query(query);
+            }
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                self.expr(expr);
+                self.push(if *negated { " NOT IN (" } else { " IN (" });
+                self.nested_query(subquery);
+            }
+            Expr::BinaryOp { left, op, right } => {
+                self.expr(left);
+                self.push(&format!(" {} ", op));
+                self.expr(right);
+            }
+            Expr::UnaryOp { op, expr } => {
+                self.push(&format!("{} ", op));
+                self.expr(expr);
+            }
+            Expr::Nested(expr) => {
+                self.push("(");
+                self.expr(expr);
+                self.push(")");
+            }
+            _ => self.push(&expr.to_string()),
+        }
+    }
+
+    fn table_with_joins(&mut self, table: &TableWithJoins) {
+        self.table_factor(&table.relation);
+        self.depth += 1;
+        for join in &table.joins {
+            self.line("");
+            self.join(join);
+        }
+        self.depth -= 1;
+    }
+
+    fn table_factor(&mut self, table_factor: &TableFactor) {
+        match table_factor {
+            TableFactor::Derived {
+                lateral,
+                subquery,
+                alias,
+            } => {
+                if *lateral {
+                    self.push("LATERAL ");
+                }
+                self.push("(");
+                self.nested_query(subquery);
+                if let Some(alias) = alias {
+                    self.push(&format!(" AS {}", alias));
+                }
+            }
+            TableFactor::NestedJoin(table) => {
+                self.push("(");
+                self.table_with_joins(table);
+                self.push(")");
+            }
+            TableFactor::Table { .. } | TableFactor::Dual => self.push(&table_factor.to_string()),
+        }
+    }
+
+    fn join(&mut self, join: &Join) {
+        let (keyword, constraint) = match &join.join_operator {
+            JoinOperator::Inner(constraint) => ("JOIN", Some(constraint)),
+            JoinOperator::LeftOuter(constraint) => ("LEFT JOIN", Some(constraint)),
+            JoinOperator::RightOuter(constraint) => ("RIGHT JOIN", Some(constraint)),
+            JoinOperator::FullOuter(constraint) => ("FULL JOIN", Some(constraint)),
+            JoinOperator::CrossJoin => ("CROSS JOIN", None),
+            JoinOperator::CrossApply => ("CROSS APPLY", None),
+            JoinOperator::OuterApply => ("OUTER APPLY", None),
+        };
+        if let Some(JoinConstraint::Natural) = constraint {
+            self.push("NATURAL ");
+        }
+        self.push(&format!("{} ", keyword));
+        self.table_factor(&join.relation);
+        match constraint {
+            Some(JoinConstraint::On(expr)) => {
+                self.push(" ON ");
+                self.expr(expr);
+            }
+            Some(JoinConstraint::Using(attrs)) => {
+                self.push(&format!(" USING({})", display_comma_separated(attrs)))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The text before and after the first occurrence of `part`
+fn split_around<'s>(text: &'s str, part: &str) -> Option<(&'s str, &'s str)> {
+    let start = text.find(part)?;
+    Some((&text[..start], &text[start + part.len()..]))
+}
+
+fn and_terms<'e>(expr: &'e Expr, terms: &mut Vec<&'e Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            and_terms(left, terms);
+            and_terms(right, terms);
+        }
+        _ => terms.push(expr),
+    }
+}
+
+/// Lower case the upper case keywords outside of strings, quoted identifiers,
+/// comments, variables and the words between `NAME_MARK`s, and drop the
+/// marks. The statement of a versioned comment `/*!NNNNN ... */` is SQL like
+/// the rest.
+fn lowercase_keywords(sql: &str) -> String {
+    let chars: Vec<char> = sql.chars().collect();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '@';
+    let mut out = String::with_capacity(sql.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        match chars[i] {
+            NAME_MARK => {
+                i += 1;
+                while i < chars.len() && chars[i] != NAME_MARK {
+                    out.push(chars[i]);
+                    i += 1;
+                }
+                i += 1;
+            }
+            quote @ '\'' | quote @ '"' | quote @ '`' => {
+                i += 1;
+                while i < chars.len() {
+                    // a backslash or a doubled quote escapes the quote
+                    if (chars[i] == '\\' && quote != '`')
+                        || (chars[i] == quote && chars.get(i + 1) == Some(&quote))
+                    {
+                        i += 2;
+                    } else if chars[i] == quote {
+                        i += 1;
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+                out.extend(&chars[start..i.min(chars.len())]);
+            }
+            '/' if chars.get(i + 1) == Some(&'*') && chars.get(i + 2) == Some(&'!') => {
+                i += 3;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                out.extend(&chars[start..i]);
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i - 1] == '*' && chars[i] == '/') {
+                    i += 1;
+                }
+                i += 1;
+                out.extend(&chars[start..i.min(chars.len())]);
+            }
+            '@' => {
+                // `@var` or `@@session.var`, which keeps its case
+                while i < chars.len() && (is_word_char(chars[i]) || chars[i] == '.') {
+                    i += 1;
+                }
+                out.extend(&chars[start..i]);
+            }
+            c if is_word_char(c) => {
+                while i < chars.len() && is_word_char(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if word.chars().all(|c| !c.is_lowercase())
+                    && ALL_KEYWORDS.binary_search(&word.as_str()).is_ok()
+                {
+                    out.push_str(&word.to_lowercase());
+                } else {
+                    out.push_str(&word);
+                }
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
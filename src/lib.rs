@@ -33,13 +33,23 @@
 //! println!("AST: {:?}", ast);
 //! ```
 #![warn(clippy::all)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 
 pub mod ast;
+#[cfg(feature = "json")]
+pub mod ast_json;
 pub mod dialect;
+pub mod format;
+// serde's derives for `ParserError` name its deprecated variant
+#[cfg_attr(feature = "serde", allow(deprecated))]
 pub mod parser;
 pub mod tokenizer;
 
 #[doc(hidden)]
 // This is required to make utilities accessible by both the crate-internal
 // unit-tests and by the integration tests <https://stackoverflow.com/a/44541071/1026>
+#[cfg(feature = "std")]
 pub mod test_utils;
@@ -12,26 +12,105 @@
 
 //! SQL Parser
 
+#[cfg(feature = "std")]
 use log::debug;
 
 use super::ast::*;
-use super::dialect::keywords;
 use super::dialect::keywords::Keyword;
-use super::dialect::Dialect;
+use super::dialect::{DBType, Dialect, MySqlDialect};
 use super::tokenizer::*;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+#[allow(deprecated)]
 pub enum ParserError {
     TokenizerError(String),
+    /// Never returned since the parser reports `SyntaxError`s, which have
+    /// the kind and location of the error along with the message
+    #[deprecated(note = "the parser reports `ParserError::SyntaxError` instead")]
     ParserError(String),
+    /// An error in the SQL found by the parser, see `SyntaxError::kind`
+    SyntaxError(SyntaxError),
 }
 
-// Use `Parser::expected` instead, if possible
+impl ParserError {
+    /// What kind of syntax error this is, or `None` for an error of the
+    /// tokenizer
+    pub fn kind(&self) -> Option<&SyntaxErrorKind> {
+        match self {
+            ParserError::SyntaxError(e) => Some(&e.kind),
+            _ => None,
+        }
+    }
+}
+
+/// An error in the SQL, along with where it was found when the parser knows
+/// the token locations
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SyntaxError {
+    pub kind: SyntaxErrorKind,
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+/// What went wrong in a `SyntaxError`, for callers that handle errors
+/// without matching on the message
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum SyntaxErrorKind {
+    /// A token other than the ones expected, see `Parser::expected`. The
+    /// token is boxed to keep errors small.
+    UnexpectedToken { expected: String, found: Box<Token> },
+    /// The SQL ended where `expected` should have followed
+    UnexpectedEof { expected: String },
+    /// Syntax the dialect doesn't support, such as `TOP` under MySQL
+    Unsupported { feature: String },
+    /// A number that doesn't fit where it's written
+    NumberParse { text: String },
+    /// More statements than `ParserOptions::max_statements`
+    TooManyStatements,
+    /// Expressions or queries nested deeper than `ParserOptions::max_depth`
+    RecursionLimit,
+    /// `IN ()`, located at the `)`
+    EmptyInList,
+    /// A comma right before the `)` of an `IN` list, located at the comma,
+    /// unless `ParserOptions::allow_trailing_comma_in_in_list` is set
+    TrailingCommaInInList,
+    /// Any other error, described by the message
+    Other,
+}
+
+// Without `std` there's no logging
+#[cfg(not(feature = "std"))]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+
+// Use `Parser::expected` or `Parser::error_at` instead, if possible. The
+// error is located at the token the parser just consumed.
 macro_rules! parser_err {
-    ($MSG:expr) => {
-        Err(ParserError::ParserError($MSG.to_string()))
+    ($parser:expr, $KIND:expr, $MSG:expr) => {
+        $parser.error_after($KIND, $MSG.to_string())
     };
 }
 
@@ -57,7 +136,6 @@ pub enum IsLateral {
 }
 use crate::ast::Statement::CreateVirtualTable;
 use IsLateral::*;
-use crate::dialect::DBType;
 use crate::ast::Expr::Exists;
 
 
@@ -71,6 +149,7 @@ impl From<TokenizerError> for ParserError {
 }
 
 impl fmt::Display for ParserError {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
@@ -78,57 +157,752 @@ impl fmt::Display for ParserError {
             match self {
                 ParserError::TokenizerError(s) => s,
                 ParserError::ParserError(s) => s,
+                ParserError::SyntaxError(e) => &e.message,
             }
         )
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for ParserError {}
 
+/// The default `ParserOptions::max_depth`
+pub const DEFAULT_MAX_DEPTH: usize = 50;
+
+/// How many operators chained one after the other, as in `a OR b OR c`,
+/// count as one level of `ParserOptions::max_depth`. Each of them nests the
+/// AST one level deeper, but takes far less stack than a parenthesized
+/// expression or a subquery, as the parser doesn't recurse into it.
+const CHAINED_OPERATORS_PER_LEVEL: usize = 10;
+
+/// Limits on the SQL to parse, e.g. when it comes from untrusted clients,
+/// and what malformed SQL to accept anyway, see
+/// `Parser::parse_sql_with_options`. The defaults are strict, and unlimited
+/// but for `max_depth`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// The longest SQL accepted, in bytes
+    pub max_input_bytes: Option<usize>,
+    /// The most tokens accepted, whitespace and comments included
+    pub max_tokens: Option<usize>,
+    /// The most statements accepted
+    pub max_statements: Option<usize>,
+    /// Accept a trailing comma in an `IN` list, as in `IN (1, 2,)`, which
+    /// some ORMs generate
+    pub allow_trailing_comma_in_in_list: bool,
+    /// Skip MySQL versioned comments `/*!NNNNN ... */` newer than this
+    /// server version (e.g. `80023`)
+    pub mysql_version: Option<u64>,
+    /// MySQL `NO_BACKSLASH_ESCAPES` SQL mode: a backslash in a string
+    /// literal is an ordinary character
+    pub no_backslash_escapes: bool,
+    /// MySQL `ANSI_QUOTES` SQL mode: `"..."` is a delimited identifier
+    /// rather than a string literal
+    pub ansi_quotes: bool,
+    /// How deeply expressions, subqueries and nested joins may nest before
+    /// the parser fails with `SyntaxErrorKind::RecursionLimit`, as parsing
+    /// them, and dropping or printing their AST, recurses as deep. Operators
+    /// chained one after the other, as in `a OR b OR c`, count a tenth of a
+    /// level each, so the default of `DEFAULT_MAX_DEPTH` allows a few
+    /// hundred of them in a row.
+    pub max_depth: usize,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            max_input_bytes: None,
+            max_tokens: None,
+            max_statements: None,
+            allow_trailing_comma_in_in_list: false,
+            mysql_version: None,
+            no_backslash_escapes: false,
+            ansi_quotes: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// Set the limits, the MySQL version and SQL mode on `tokenizer`
+    fn configure(&self, tokenizer: &mut Tokenizer) {
+        tokenizer.max_input_bytes = self.max_input_bytes;
+        tokenizer.max_tokens = self.max_tokens;
+        if self.mysql_version.is_some() {
+            tokenizer.mysql_version = self.mysql_version;
+        }
+        if self.no_backslash_escapes {
+            tokenizer.no_backslash_escapes = true;
+        }
+        if self.ansi_quotes {
+            tokenizer.ansi_quotes = true;
+        }
+    }
+}
+
+/// A statement along with the database of the dialect it was parsed with,
+/// see `Parser::parse_sql_with_db_type`.
+///
+/// Some statements have a different shape depending on the dialect: under
+/// MySQL, `CREATE TABLE` and `ALTER TABLE ... ADD` put the keys in
+/// `IndexInfo`s, where other dialects give `TableConstraint`s, and `SHOW`,
+/// `LIMIT` and the like follow the syntax of MySQL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedStatement {
+    pub statement: Statement,
+    pub dialect: DBType,
+}
+
+/// What `peek_token_ref` returns past the end of the tokens
+static EOF_TOKEN: Token = Token::EOF;
+
+/// Iterator over the statements of a query, see `Parser::iter_statements`
+pub struct StatementIter<'a> {
+    dialect: &'a dyn Dialect,
+    tokenizer: StatementTokenizer<'a>,
+    /// The parser over the tokens up to the next `;`, which may hold more than
+    /// one statement when the `;` is inside a versioned comment
+    parser: Option<Parser<'a>>,
+}
+
+impl<'a> Iterator for StatementIter<'a> {
+    type Item = Result<LocatedStatement, ParserError>;
 
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let parser = match &mut self.parser {
+                Some(parser) => parser,
+                None => {
+                    let tokens = match self.tokenizer.next_statement()? {
+                        Ok(tokens) => tokens,
+                        Err(e) => return Some(Err(e.into())),
+                    };
+                    let mut parser = Parser::new_with_locations(tokens, self.dialect);
+                    parser.end = Some(self.tokenizer.location());
+                    parser.take_literals = true;
+                    self.parser.get_or_insert(parser)
+                }
+            };
+            if !parser.next_statement_start() {
+                self.parser = None;
+                continue;
+            }
+            let result = parser
+                .parse_statement_to_end(false)
+                .map(|statement| statement.located);
+            if result.is_err() {
+                // skip the rest of the statement
+                self.parser = None;
+            }
+            return Some(result);
+        }
+    }
+}
+
+
+/// What `Parser::parse_script` keeps track of about where in the SQL the
+/// tokens and nodes are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tracking {
+    /// Nothing, when the locations could only be needed by an error, and
+    /// the SQL is parsed again should it fail. A long `INSERT` has millions
+    /// of tokens, which then needn't have their locations stored.
+    Nothing,
+    /// The location of each token, for the spans of the statements and the
+    /// locations of errors
+    Tokens,
+    /// The locations of the tokens and the nodes, see
+    /// `LocatedStatement::nodes`
+    Nodes,
+}
+
+/// A statement parsed by `Parser::parse_script`, along with the tokens it
+/// was parsed from
+struct ScriptStatement {
+    /// The statement and its span, which covers `tokens`
+    located: LocatedStatement,
+    /// The tokens of the statement, see `SourceStatement::tokens`
+    tokens: Range<usize>,
+    /// The whitespace and comments before it, see
+    /// `SourceStatement::leading_trivia`
+    leading_trivia: Range<usize>,
+}
 
 /// SQL Parser
-pub struct Parser {
+pub struct Parser<'a> {
     tokens: Vec<Token>,
+    /// The location of each token in `self.tokens`, if known
+    locations: Vec<Location>,
+    /// Where the SQL ends, which is the location of `Token::EOF`, if known
+    end: Option<Location>,
+    /// The located nodes of the current statement, when requested
+    node_spans: Option<NodeSpans>,
     /// The index of the first unprocessed token in `self.tokens`
     index: usize,
-
-    dialect_type: DBType
+    /// The dialect, which decides between the syntaxes that differ
+    dialect: &'a dyn Dialect,
+    /// See `ParserOptions::allow_trailing_comma_in_in_list`
+    allow_trailing_comma_in_in_list: bool,
+    /// Whether the tokens were read with backslashes as escapes in string
+    /// literals, see `ParserOptions::no_backslash_escapes`
+    backslash_escapes: bool,
+    /// How deep the AST parsed so far nests, in chained operators, see
+    /// `CHAINED_OPERATORS_PER_LEVEL`
+    depth: usize,
+    /// See `ParserOptions::max_depth`
+    max_depth: usize,
+    /// Whether the literals of `VALUES` rows and `IN` lists may be taken out
+    /// of the tokens rather than cloned, as nothing reads the tokens after
+    /// parsing them
+    take_literals: bool,
+    /// How many attempts to parse that rewind on failure are under way, see
+    /// `maybe_parse`, during which the tokens may be read again
+    rewindable: usize,
 }
 
-impl Parser {
+impl<'a> Parser<'a> {
     /// Parse the specified tokens
-    pub fn new(tokens: Vec<Token>, db_type : DBType) -> Self {
-        Parser { tokens, index: 0 , dialect_type: db_type}
+    pub fn new(tokens: Vec<Token>, dialect: &'a dyn Dialect) -> Self {
+        Parser {
+            tokens,
+            locations: vec![],
+            end: None,
+            node_spans: None,
+            index: 0,
+            dialect,
+            allow_trailing_comma_in_in_list: false,
+            backslash_escapes: dialect.supports_backslash_escapes(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            take_literals: false,
+            rewindable: 0,
+        }
+    }
+
+    /// Parse the specified tokens, reporting their locations in errors
+    pub fn new_with_locations(tokens: Vec<TokenWithLocation>, dialect: &'a dyn Dialect) -> Self {
+        let (tokens, locations) = tokens
+            .into_iter()
+            .map(|t| (t.token, t.location))
+            .unzip();
+        Parser {
+            tokens,
+            locations,
+            end: None,
+            node_spans: None,
+            index: 0,
+            dialect,
+            allow_trailing_comma_in_in_list: false,
+            backslash_escapes: dialect.supports_backslash_escapes(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            take_literals: false,
+            rewindable: 0,
+        }
+    }
+
+    /// Parse the tokens of `tokenizer`, which go straight into the parser
+    /// rather than through a `Vec<TokenWithLocation>`, as a long `INSERT`
+    /// has millions of them. The tokens are only for the parser, so the
+    /// literals of `VALUES` rows and `IN` lists are taken out of them.
+    fn from_tokenizer(tokenizer: &mut Tokenizer, dialect: &'a dyn Dialect) -> Result<Self, ParserError> {
+        Parser::from_tokenizer_tracking(tokenizer, dialect, Tracking::Tokens)
+    }
+
+    /// Like `from_tokenizer`, storing the locations of the tokens unless
+    /// `tracking` is `Tracking::Nothing`
+    fn from_tokenizer_tracking(
+        tokenizer: &mut Tokenizer,
+        dialect: &'a dyn Dialect,
+        tracking: Tracking,
+    ) -> Result<Self, ParserError> {
+        let mut parser = Parser::new(vec![], dialect);
+        parser.take_literals = true;
+        parser.backslash_escapes = tokenizer.backslash_escapes();
+        let end = if tracking == Tracking::Nothing {
+            tokenizer.tokenize_into(|token, _| parser.tokens.push(token))?
+        } else {
+            tokenizer.tokenize_into(|token, location| {
+                parser.tokens.push(token);
+                parser.locations.push(location);
+            })?
+        };
+        parser.end = Some(end);
+        Ok(parser)
+    }
+
+    /// The database of the dialect the parser follows
+    pub fn dialect_type(&self) -> DBType {
+        self.dialect.check_db_type()
     }
 
     /// Parse a SQL statement and produce an Abstract Syntax Tree (AST)
     pub fn parse_sql(dialect: &dyn Dialect, sql: &str) -> Result<Vec<Statement>, ParserError> {
-        let mut tokenizer = Tokenizer::new(dialect, &sql);
-        let tokens = tokenizer.tokenize()?;
-        // println!("Parsing sql tokens '{:?}'...", &tokens);
-        let mut parser = Parser::new(tokens, dialect.check_db_type());
-        let mut stmts = Vec::new();
-        let mut expecting_statement_delimiter = false;
+        Self::parse_sql_with_options(dialect, sql, &ParserOptions::default())
+    }
+
+    /// Like `parse_sql`, with each statement tagged with the database of
+    /// `dialect`, for callers handling the SQL of more than one
+    pub fn parse_sql_with_db_type(
+        dialect: &dyn Dialect,
+        sql: &str,
+    ) -> Result<Vec<ParsedStatement>, ParserError> {
+        let db_type = dialect.check_db_type();
+        Ok(Self::parse_sql(dialect, sql)?
+            .into_iter()
+            .map(|statement| ParsedStatement {
+                statement,
+                dialect: db_type,
+            })
+            .collect())
+    }
+
+    /// Like `parse_sql`, failing on SQL over the limits of `options` before
+    /// spending time and memory on all of it
+    pub fn parse_sql_with_options(
+        dialect: &dyn Dialect,
+        sql: &str,
+        options: &ParserOptions,
+    ) -> Result<Vec<Statement>, ParserError> {
+        // only an error needs the locations of the tokens, so they are worked
+        // out by parsing again should it fail
+        let parse = |tracking| {
+            let mut tokenizer = Tokenizer::new(dialect, sql);
+            Self::parse_tokenized(dialect, sql, &mut tokenizer, tracking, options, 0)
+        };
+        let statements = match parse(Tracking::Nothing) {
+            Err(ParserError::SyntaxError(_)) => parse(Tracking::Tokens)?,
+            result => result?,
+        };
+        Ok(statements.into_iter().map(|s| s.statement).collect())
+    }
+
+    /// Like `parse_sql`, but also return where in `sql` each statement and
+    /// its identifiers, object names, expressions and select items are
+    pub fn parse_sql_located(
+        dialect: &dyn Dialect,
+        sql: &str,
+    ) -> Result<Vec<LocatedStatement>, ParserError> {
+        Self::parse_sql_located_with_options(dialect, sql, &ParserOptions::default())
+    }
+
+    /// Like `parse_sql_located`, with the limits of `options`
+    pub fn parse_sql_located_with_options(
+        dialect: &dyn Dialect,
+        sql: &str,
+        options: &ParserOptions,
+    ) -> Result<Vec<LocatedStatement>, ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, sql);
+        Self::parse_tokenized(dialect, sql, &mut tokenizer, Tracking::Nodes, options, 0)
+    }
+
+    /// Like `parse_sql`, for a script of the `mysql` command-line client,
+    /// whose `DELIMITER <delimiter>` lines change what ends the statements
+    /// after them, e.g. so that a routine body can contain `;`. The SQL up
+    /// to each delimiter is parsed on its own, and the `DELIMITER` lines are
+    /// returned as `Statement::ClientDelimiter`.
+    pub fn parse_sql_with_delimiters(
+        dialect: &dyn Dialect,
+        sql: &str,
+    ) -> Result<Vec<Statement>, ParserError> {
+        Self::parse_sql_with_delimiters_and_options(dialect, sql, &ParserOptions::default())
+    }
+
+    /// Like `parse_sql_with_delimiters`, with the limits of `options`. The
+    /// size and statement limits apply to the whole script, the token limit
+    /// to the SQL between each pair of delimiters.
+    pub fn parse_sql_with_delimiters_and_options(
+        dialect: &dyn Dialect,
+        sql: &str,
+        options: &ParserOptions,
+    ) -> Result<Vec<Statement>, ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, sql);
+        options.configure(&mut tokenizer);
+        if options.max_input_bytes.is_some_and(|max| sql.len() > max) {
+            // fails on the first token, pointing past the limit
+            Parser::from_tokenizer(&mut tokenizer, dialect)?;
+        }
+        let mut statements = vec![];
+        let mut parsed_before = 0;
+        for chunk in split_client_delimiters(sql, tokenizer.backslash_escapes()) {
+            match chunk {
+                ClientChunk::Delimiter(delimiter) => statements.push(Statement::ClientDelimiter {
+                    delimiter: delimiter.to_string(),
+                }),
+                ClientChunk::Sql(chunk, start) => {
+                    let mut tokenizer = Tokenizer::new(dialect, chunk).with_start(start);
+                    let parsed = Self::parse_tokenized(
+                        dialect,
+                        chunk,
+                        &mut tokenizer,
+                        Tracking::Tokens,
+                        options,
+                        parsed_before,
+                    )?;
+                    parsed_before += parsed.len();
+                    statements.extend(parsed.into_iter().map(|s| s.statement));
+                }
+            }
+        }
+        Ok(statements)
+    }
+
+    /// Parse the statements of `sql`, failing on more than
+    /// `options.max_statements` along with the `parsed_before` statements
+    /// before it
+    fn parse_tokenized(
+        dialect: &dyn Dialect,
+        sql: &str,
+        tokenizer: &mut Tokenizer,
+        tracking: Tracking,
+        options: &ParserOptions,
+        parsed_before: usize,
+    ) -> Result<Vec<LocatedStatement>, ParserError> {
+        let (_, statements) =
+            Self::parse_script(dialect, sql, tokenizer, tracking, false, options, parsed_before)?;
+        Ok(statements.into_iter().map(|s| s.located).collect())
+    }
+
+    /// Parse the statements of `sql` with the tokens of `tokenizer`, along
+    /// with the parser holding the tokens they were parsed from, which are
+    /// left as they were with `keep_tokens`. This is the loop over the
+    /// statements behind all the `parse_sql*` functions.
+    fn parse_script<'d>(
+        dialect: &'d dyn Dialect,
+        sql: &str,
+        tokenizer: &mut Tokenizer,
+        tracking: Tracking,
+        keep_tokens: bool,
+        options: &ParserOptions,
+        parsed_before: usize,
+    ) -> Result<(Parser<'d>, Vec<ScriptStatement>), ParserError> {
+        options.configure(tokenizer);
+        let mut parser = Parser::from_tokenizer_tracking(tokenizer, dialect, tracking)?;
+        parser.take_literals = !keep_tokens;
+        parser.allow_trailing_comma_in_in_list = options.allow_trailing_comma_in_in_list;
+        parser.max_depth = options.max_depth;
+        let mut statements = Vec::new();
         debug!("Parsing sql '{}'...", sql);
-        loop {
-            // ignore empty statements (between successive statement delimiters)
-            while parser.consume_token(&Token::SemiColon) {
-                expecting_statement_delimiter = false;
+        while parser.next_statement_start() {
+            let parsed = parsed_before + statements.len();
+            if let Some(max) = options.max_statements.filter(|&max| parsed >= max) {
+                return parser.error_at(
+                    SyntaxErrorKind::TooManyStatements,
+                    format!("More than {} statements", max),
+                    &parser.peek_token(),
+                );
             }
+            statements.push(parser.parse_statement_to_end(tracking == Tracking::Nodes)?);
+        }
+        Ok((parser, statements))
+    }
 
-            if parser.peek_token() == Token::EOF {
-                break;
+    /// Skip the empty statements between successive `;`, returning whether
+    /// another statement follows
+    fn next_statement_start(&mut self) -> bool {
+        while self.consume_token(&Token::SemiColon) {}
+        self.peek_token_ref() != &Token::EOF
+    }
+
+    /// Parse a statement of a script, which must be followed by `;` or the
+    /// end of the input, unless it ends with the data of `COPY ... FROM
+    /// stdin;`
+    fn parse_statement_to_end(&mut self, located: bool) -> Result<ScriptStatement, ParserError> {
+        let statement = self.parse_located_statement(located)?;
+        match self.peek_token_ref() {
+            Token::SemiColon | Token::EOF => Ok(statement),
+            _ if self.ends_with_copy_data() => Ok(statement),
+            _ => self.expected("end of statement", self.peek_token()),
+        }
+    }
+
+    /// Parse an iterator over the statements of `sql`, which tokenizes and
+    /// parses one statement at a time rather than the whole of `sql` upfront.
+    /// A statement that fails to parse is skipped up to the next `;`.
+    pub fn iter_statements(dialect: &'a dyn Dialect, sql: &'a str) -> StatementIter<'a> {
+        StatementIter {
+            dialect,
+            tokenizer: StatementTokenizer::new(dialect, sql),
+            parser: None,
+        }
+    }
+
+    /// Parse `sql` as a single expression, such as a `WHERE` condition
+    pub fn parse_expr_str(dialect: &dyn Dialect, sql: &str) -> Result<Expr, ParserError> {
+        let mut parser = Parser::new_for_fragment(dialect, sql)?;
+        let expr = parser.parse_expr()?;
+        parser.expect_end_of_input()?;
+        Ok(expr)
+    }
+
+    /// Parse `sql` as a single query, without a trailing `;`
+    pub fn parse_query_str(dialect: &dyn Dialect, sql: &str) -> Result<Query, ParserError> {
+        let mut parser = Parser::new_for_fragment(dialect, sql)?;
+        let query = parser.parse_query()?;
+        parser.expect_end_of_input()?;
+        Ok(query)
+    }
+
+    fn new_for_fragment(dialect: &'a dyn Dialect, sql: &str) -> Result<Self, ParserError> {
+        Parser::from_tokenizer(&mut Tokenizer::new(dialect, sql), dialect)
+    }
+
+    /// Whether the statement just parsed ends with the data of `COPY ...
+    /// FROM stdin;`, which needs no `;` after it
+    fn ends_with_copy_data(&self) -> bool {
+        self.tokens[..self.index.min(self.tokens.len())]
+            .iter()
+            .rev()
+            .find(|token| !matches!(token, Token::Whitespace(_)))
+            .is_some_and(|token| matches!(token, Token::CopyData(_)))
+    }
+
+    /// Bail out unless all the tokens have been consumed
+    fn expect_end_of_input(&self) -> Result<(), ParserError> {
+        match self.peek_token_ref() {
+            Token::EOF => Ok(()),
+            _ => self.expected("end of input", self.peek_token()),
+        }
+    }
+
+    /// Like `parse_sql`, but keep every token, whitespace and comments
+    /// included, along with the tokens each statement was parsed from, so
+    /// that the SQL can be reproduced exactly, see `ParsedSource::to_source`
+    pub fn parse_with_tokens(dialect: &dyn Dialect, sql: &str) -> Result<ParsedSource, ParserError> {
+        Self::parse_with_tokens_and_options(dialect, sql, &ParserOptions::default())
+    }
+
+    /// Like `parse_with_tokens`, with the limits of `options`
+    pub fn parse_with_tokens_and_options(
+        dialect: &dyn Dialect,
+        sql: &str,
+        options: &ParserOptions,
+    ) -> Result<ParsedSource, ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, sql);
+        let (parser, statements) =
+            Parser::parse_script(dialect, sql, &mut tokenizer, Tracking::Tokens, true, options, 0)?;
+        let tokens = parser
+            .tokens
+            .into_iter()
+            .zip(parser.locations)
+            .map(|(token, location)| TokenWithLocation { token, location })
+            .collect();
+        let statements = statements
+            .into_iter()
+            .map(|s| SourceStatement::new(s.located.statement, s.tokens, s.leading_trivia, s.located.span))
+            .collect();
+        Ok(ParsedSource {
+            source: sql.to_string(),
+            tokens,
+            statements,
+        })
+    }
+
+    /// Like `parse_sql`, but also return the range of bytes of `sql` that
+    /// each statement was parsed from: its span, see
+    /// `LocatedStatement::span`, along with the `;` after it, if any
+    pub fn parse_sql_with_locations(
+        dialect: &dyn Dialect,
+        sql: &str,
+    ) -> Result<Vec<(Statement, Range<usize>)>, ParserError> {
+        Self::parse_sql_with_locations_and_options(dialect, sql, &ParserOptions::default())
+    }
+
+    /// Like `parse_sql_with_locations`, with the limits of `options`
+    pub fn parse_sql_with_locations_and_options(
+        dialect: &dyn Dialect,
+        sql: &str,
+        options: &ParserOptions,
+    ) -> Result<Vec<(Statement, Range<usize>)>, ParserError> {
+        let mut tokenizer = Tokenizer::new(dialect, sql);
+        let (parser, statements) = Parser::parse_script(
+            dialect,
+            sql,
+            &mut tokenizer,
+            Tracking::Tokens,
+            false,
+            options,
+            0,
+        )?;
+        Ok(statements
+            .into_iter()
+            .map(|s| {
+                let span = s.located.span;
+                let semicolon = parser.tokens[s.tokens.end..]
+                    .iter()
+                    .position(|token| !matches!(token, Token::Whitespace(_)))
+                    .map(|i| s.tokens.end + i)
+                    .filter(|&i| parser.tokens[i] == Token::SemiColon);
+                let end = match semicolon {
+                    Some(i) => parser.locations[i].offset + 1,
+                    None => span.end,
+                };
+                (s.located.statement, span.start..end)
+            })
+            .collect())
+    }
+
+    /// Parse all the statements of `sql` like `parse_sql`, but carry on after
+    /// a statement that fails to parse, from the next `;`. Each error comes
+    /// with the index of its statement among all the statements of `sql`.
+    pub fn parse_sql_with_recovery(
+        dialect: &dyn Dialect,
+        sql: &str,
+    ) -> (Vec<Statement>, Vec<(usize, ParserError)>) {
+        let mut statements = vec![];
+        let mut errors = vec![];
+        for (index, result) in Parser::iter_statements(dialect, sql).enumerate() {
+            match result {
+                Ok(located) => statements.push(located.statement),
+                Err(e) => errors.push((index, e)),
             }
-            if expecting_statement_delimiter {
-                return parser.expected("end of statement", parser.peek_token());
+        }
+        (statements, errors)
+    }
+
+    /// Parse a statement, wrapped in `Statement::VersionedComment` if it is
+    /// the whole content of one, along with its span
+    fn parse_located_statement(&mut self, located: bool) -> Result<ScriptStatement, ParserError> {
+        let versioned = self.versioned_comment_start();
+        let trivia_start = self.index.min(self.tokens.len());
+        if located {
+            self.node_spans = Some(NodeSpans::default());
+        }
+        let statement = self.parse_statement()?;
+        let nodes = self.node_spans.take().unwrap_or_default();
+        let is_token = |token: &Token| !matches!(token, Token::Whitespace(_));
+        let mut start = (trivia_start..self.tokens.len())
+            .find(|&i| is_token(&self.tokens[i]))
+            .unwrap_or(trivia_start);
+        let mut end = self.tokens[..self.index.min(self.tokens.len())]
+            .iter()
+            .rposition(is_token)
+            .map_or(start, |last| last + 1);
+        let statement = match versioned {
+            // the statement spans the whole comment, markers included
+            Some((comment_start, version)) if self.versioned_comment_end(comment_start) => {
+                start = comment_start;
+                end = (end..self.tokens.len())
+                    .find(|&i| self.tokens[i] == Token::Whitespace(Whitespace::VersionedCommentEnd))
+                    .map_or(end, |i| i + 1);
+                Statement::VersionedComment {
+                    version,
+                    statement: Box::new(statement),
+                }
             }
+            _ => statement,
+        };
+        let offset = |index: usize| {
+            self.locations
+                .get(index)
+                .or(self.end.as_ref())
+                .map_or(0, |l| l.offset)
+        };
+        Ok(ScriptStatement {
+            located: LocatedStatement {
+                statement,
+                span: Span {
+                    start: offset(start),
+                    end: offset(end),
+                },
+                nodes,
+            },
+            tokens: start..end,
+            leading_trivia: trivia_start..start,
+        })
+    }
+
+    /// The span of the tokens consumed since `start`, ignoring whitespace
+    fn span_from(&self, start: usize) -> Option<Span> {
+        let end = self.index.min(self.tokens.len());
+        let is_token = |i: &usize| !matches!(self.tokens[*i], Token::Whitespace(_));
+        let first = (start..end).find(is_token)?;
+        let last = (first..end).rev().find(is_token)?;
+        Some(Span {
+            start: self.locations.get(first)?.offset,
+            end: self
+                .locations
+                .get(last + 1)
+                .or(self.end.as_ref())
+                .map_or(0, |l| l.offset),
+        })
+    }
 
-            let statement = parser.parse_statement()?;
-            stmts.push(statement);
-            expecting_statement_delimiter = true;
+    /// Remember where `node`, parsed from the tokens since `start`, came from
+    /// when parsing with `parse_sql_located`
+    fn record_span<T: Clone>(
+        &mut self,
+        start: usize,
+        node: &T,
+        nodes: fn(&mut NodeSpans) -> &mut Vec<Located<T>>,
+    ) {
+        if self.node_spans.is_none() {
+            return;
         }
-        Ok(stmts)
+        if let Some(span) = self.span_from(start) {
+            let located = Located {
+                node: node.clone(),
+                span,
+            };
+            nodes(self.node_spans.as_mut().unwrap()).push(located);
+        }
+    }
+
+    /// Where to roll the recorded node spans back to, see `rollback_spans`
+    fn spans_checkpoint(&self) -> Option<[usize; 4]> {
+        self.node_spans.as_ref().map(NodeSpans::lens)
+    }
+
+    /// Forget the node spans recorded since `checkpoint`, by an attempt to
+    /// parse that failed
+    fn rollback_spans(&mut self, checkpoint: Option<[usize; 4]>) {
+        if let (Some(nodes), Some(lens)) = (self.node_spans.as_mut(), checkpoint) {
+            nodes.truncate(lens);
+        }
+    }
+
+    /// If the next statement starts with a MySQL versioned comment, return
+    /// the index of its opening token and its version
+    fn versioned_comment_start(&self) -> Option<(usize, Option<u64>)> {
+        for (index, token) in self.tokens.iter().enumerate().skip(self.index) {
+            match token {
+                Token::Whitespace(Whitespace::VersionedCommentStart(version)) => {
+                    return Some((index, *version))
+                }
+                Token::Whitespace(_) => continue,
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Whether the statement just parsed ends by closing the versioned comment
+    /// opened at `start`, without any other versioned comment in between
+    fn versioned_comment_end(&self, start: usize) -> bool {
+        let is_marker = |token: &Token| {
+            matches!(
+                token,
+                Token::Whitespace(Whitespace::VersionedCommentStart(_))
+                    | Token::Whitespace(Whitespace::VersionedCommentEnd)
+            )
+        };
+        // `self.index` may already be past trailing whitespace at EOF, so
+        // look from the last token of the statement instead
+        let end = self.tokens[..self.index.min(self.tokens.len())]
+            .iter()
+            .rposition(|token| !matches!(token, Token::Whitespace(_)))
+            .map_or(start, |last| last + 1);
+        if end <= start || self.tokens[start + 1..end].iter().any(is_marker) {
+            return false;
+        }
+        for token in &self.tokens[end..] {
+            match token {
+                Token::Whitespace(Whitespace::VersionedCommentEnd) => return true,
+                Token::Whitespace(_) => continue,
+                _ => return false,
+            }
+        }
+        false
     }
 
     /// Parse a single top-level statement (such as SELECT, INSERT, CREATE, etc.),
@@ -147,7 +921,7 @@ impl Parser {
                 Keyword::DROP => Ok(self.parse_drop()?),
                 Keyword::DELETE => Ok(self.parse_delete()?),
                 Keyword::INSERT => Ok(self.parse_insert()?),
-                Keyword::REPLACE => Ok(self.parse_insert()?),
+                Keyword::REPLACE => Ok(self.parse_replace()?),
                 Keyword::RELOAD => Ok(self.parse_reload()?),
                 Keyword::UPDATE => Ok(self.parse_update()?),
                 Keyword::ALTER => Ok(self.parse_alter()?),
@@ -179,33 +953,51 @@ impl Parser {
 
     pub fn parse_explain(&mut self) -> Result<Statement, ParserError>{
         let analyze = self.parse_explain_analyze()?;
+        let modifier = match self.parse_one_of_keywords(&[Keyword::EXTENDED, Keyword::PARTITIONS]) {
+            Some(Keyword::EXTENDED) => Some(ExplainModifier::Extended),
+            Some(_) => Some(ExplainModifier::Partitions),
+            None => None,
+        };
         let format_type = self.parse_explain_format()?;
         let body = match self.next_token(){
             Token::Word(w) => match w.keyword {
-                Keyword::SELECT | Keyword::WITH | Keyword::VALUE => {
+                Keyword::SELECT | Keyword::WITH | Keyword::VALUE | Keyword::VALUES => {
                     self.prev_token();
                     Ok(ExplainStmt::Stmt(Box::new(Statement::Query(Box::new(self.parse_query()?)))))
                 }
                 Keyword::UPDATE => Ok(ExplainStmt::Stmt(Box::new(self.parse_update()?))),
                 Keyword::DELETE => Ok(ExplainStmt::Stmt(Box::new(self.parse_delete()?))),
+                Keyword::INSERT => Ok(ExplainStmt::Stmt(Box::new(self.parse_insert()?))),
+                Keyword::REPLACE => Ok(ExplainStmt::Stmt(Box::new(self.parse_replace()?))),
                 Keyword::FOR => Ok(self.parse_explain_for_connection()?),
+                // `EXPLAIN t` describes the table, unless ANALYZE or FORMAT
+                // asked for a plan
+                _ if analyze.is_none() && modifier.is_none() && format_type.is_none() => {
+                    self.prev_token();
+                    Ok(ExplainStmt::TableDescribe(self.parse_object_name()?))
+                }
                 _ => self.expected("Explain explainable_stmt ", Token::Word(w))
             }
+            Token::LParen => {
+                self.prev_token();
+                Ok(ExplainStmt::Stmt(Box::new(Statement::Query(Box::new(self.parse_query()?)))))
+            }
             unexpected => self.expected("Explain explainable_stmt ", unexpected),
         }?;
-        Ok(Statement::Explain { analyze, format_type, body })
+        Ok(Statement::Explain { analyze, modifier, format_type, body })
     }
 
+    /// Parse `CONNECTION <id>` after `EXPLAIN FOR`, where the id is an
+    /// integer or a placeholder
     pub fn parse_explain_for_connection(&mut self) -> Result<ExplainStmt, ParserError>{
-        if self.parse_keyword(Keyword::CONNECTION){
-            let token = self.peek_token();
-            let value = match (self.parse_value(), token) {
-                (Ok(value), _) => ExplainStmt::Connection(value),
-                (Err(_), unexpected) => self.expected("connection value", unexpected)?,
-            };
-            Ok(value)
-        }else {
-            self.expected("EXPLAIN FOR  ", self.peek_token())
+        self.expect_keyword(Keyword::CONNECTION)?;
+        let token = self.peek_token();
+        match self.parse_value() {
+            Ok(value @ Value::Placeholder(_)) => Ok(ExplainStmt::Connection(value)),
+            Ok(Value::Number(n)) if n.to_string().bytes().all(|b| b.is_ascii_digit()) => {
+                Ok(ExplainStmt::Connection(Value::Number(n)))
+            }
+            _ => self.expected("a connection id", token),
         }
     }
 
@@ -247,59 +1039,34 @@ impl Parser {
     }
 
     pub fn parse_use(&mut self) -> Result<Statement, ParserError> {
-        let database_name = self.parse_identifier()?;
-        if self.consume_token(&Token::EOF){
-            return Ok(Statement::ChangeDatabase {database: database_name.to_string()});
-        }
-        return self.expected(
-            "Use Wrong syntax",
-            self.peek_token(),
-        );
+        Ok(Statement::ChangeDatabase {
+            database: self.parse_identifier()?,
+        })
     }
 
     pub fn parse_call(&mut self) -> Result<Statement, ParserError>{
-        let fun_name = self.parse_identifier()?;
-        return if self.consume_token(&Token::EOF) {
-            Ok(Statement::Call { name: fun_name, parameter: None })
+        let name = self.parse_object_name()?;
+        let parameter = if self.peek_token() == Token::LParen {
+            Some(self.parse_call_parameter()?)
         } else {
-            Ok(Statement::Call { name: fun_name, parameter: Some(self.parse_call_parameter()?) })
-        }
-
+            None
+        };
+        Ok(Statement::Call { name, parameter })
     }
 
+    /// Parse the parenthesized, possibly empty, arguments of `CALL`
     pub fn parse_call_parameter(&mut self) -> Result<Vec<Expr>, ParserError> {
-        let mut ident_list = vec![];
-        let mut r_paren =  false;
-        if self.consume_token(&Token::LParen){
-            loop {
-                if self.consume_token(&Token::RParen){
-                    r_paren = true;
-                    continue;
-                }
-
-                if r_paren && self.consume_token(&Token::EOF){
-                    break;
-                }else if r_paren {
-                    return self.expected(
-                        "Call Wrong syntax",
-                        self.peek_token(),
-                    );
-                }
-
-                ident_list = self.parse_comma_separated(Parser::parse_expr)?;
-
-            }
-        }else {
-            return self.expected(
-                "Call Wrong syntax",
-                self.peek_token(),
-            );
+        self.expect_token(&Token::LParen)?;
+        if self.consume_token(&Token::RParen) {
+            return Ok(vec![]);
         }
-        return Ok(ident_list)
+        let parameter = self.parse_comma_separated(Parser::parse_expr)?;
+        self.expect_token(&Token::RParen)?;
+        Ok(parameter)
     }
 
     pub fn parse_unlock(&mut self) -> Result<Statement, ParserError>{
-        if self.parse_keyword(Keyword::TABLES) && self.consume_token(&Token::EOF){
+        if self.parse_keyword(Keyword::TABLES) && self.peek_statement_end() {
             return Ok(Statement::UNLock { chain: true })
         }
         return self.expected(
@@ -357,11 +1124,24 @@ impl Parser {
 
     /// Parse a new expression
     pub fn parse_expr(&mut self) -> Result<Expr, ParserError> {
-        self.parse_subexpr(0)
+        let start = self.index;
+        let expr = self.parse_subexpr(0)?;
+        self.record_span(start, &expr, |nodes| &mut nodes.exprs);
+        Ok(expr)
     }
 
     /// Parse tokens until the precedence changes
     pub fn parse_subexpr(&mut self, precedence: u8) -> Result<Expr, ParserError> {
+        let depth = self.depth;
+        let expr = self.parse_nested_subexpr(precedence);
+        self.depth = depth;
+        expr
+    }
+
+    /// Parse tokens until the precedence changes, one level deeper than the
+    /// caller
+    fn parse_nested_subexpr(&mut self, precedence: u8) -> Result<Expr, ParserError> {
+        self.deepen(CHAINED_OPERATORS_PER_LEVEL)?;
         debug!("parsing expr");
         let mut expr = self.parse_prefix()?;
         debug!("prefix: {:?}", expr);
@@ -372,10 +1152,27 @@ impl Parser {
                 break;
             }
 
+            // the expression so far becomes an operand, one level deeper
+            self.deepen(1)?;
             expr = self.parse_infix(expr, next_precedence)?;
         }
         Ok(expr)
     }
+
+    /// Account for `by` more chained operators of depth, see
+    /// `CHAINED_OPERATORS_PER_LEVEL`, failing past `ParserOptions::max_depth`
+    /// rather than overflowing the stack. The caller restores `self.depth`.
+    fn deepen(&mut self, by: usize) -> Result<(), ParserError> {
+        self.depth += by;
+        if self.depth > self.max_depth.saturating_mul(CHAINED_OPERATORS_PER_LEVEL) {
+            return self.error_at(
+                SyntaxErrorKind::RecursionLimit,
+                format!("Nested deeper than {} levels", self.max_depth),
+                &self.peek_token(),
+            );
+        }
+        Ok(())
+    }
     pub fn parse_assert(&mut self) -> Result<Statement, ParserError> {
         let condition = self.parse_expr()?;
         let message = if self.parse_keyword(Keyword::AS) {
@@ -387,6 +1184,38 @@ impl Parser {
         Ok(Statement::Assert { condition, message })
     }
 
+    /// Whether the next tokens can start a typed string such as `DATE
+    /// '2020-05-20'` or an `INTERVAL`: a word followed by a string literal,
+    /// possibly after the rest of a type such as `TIMESTAMP WITH TIME ZONE`
+    /// or `DECIMAL(10, 2)`. Only the first few tokens are looked at, so a
+    /// column such as `date` or a function call isn't tried as a type.
+    fn may_start_typed_string(&self) -> bool {
+        // enough for the longest types, e.g. `TIMESTAMP(6) WITH TIME ZONE`
+        const MAX_TYPE_TOKENS: usize = 8;
+        let mut tokens = self.tokens[self.index.min(self.tokens.len())..]
+            .iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)));
+        match tokens.next() {
+            Some(Token::Word(w)) if w.keyword == Keyword::INTERVAL => return true,
+            Some(Token::Word(_)) => {}
+            _ => return false,
+        }
+        let mut in_parens = false;
+        for token in tokens.take(MAX_TYPE_TOKENS) {
+            match token {
+                Token::SingleQuotedString(_) | Token::DoubleQuotedString(_) => return !in_parens,
+                // the rest of a multi-word type is keywords
+                Token::Word(w) if !in_parens && w.keyword != Keyword::NoKeyword => {}
+                // only numbers, such as a length or precision, are in its parens
+                Token::LParen if !in_parens => in_parens = true,
+                Token::Number(_) | Token::Comma if in_parens => {}
+                Token::RParen if in_parens => in_parens = false,
+                _ => return false,
+            }
+        }
+        false
+    }
+
     /// Parse an expression prefix
     pub fn parse_prefix(&mut self) -> Result<Expr, ParserError> {
         // PostgreSQL allows any string literal to be preceded by a type name, indicating that the
@@ -405,29 +1234,33 @@ impl Parser {
         // Note also that naively `SELECT date` looks like a syntax error because the `date` type
         // name is not followed by a string literal, but in fact in PostgreSQL it is a valid
         // expression that should parse as the column name "date".
-        return_ok_if_some!(self.maybe_parse(|parser| {
-            match parser.parse_data_type()? {
-                DataType::Interval => parser.parse_literal_interval(),
-                // PosgreSQL allows almost any identifier to be used as custom data type name,
-                // and we support that in `parse_data_type()`. But unlike Postgres we don't
-                // have a list of globally reserved keywords (since they vary across dialects),
-                // so given `NOT 'a' LIKE 'b'`, we'd accept `NOT` as a possible custom data type
-                // name, resulting in `NOT 'a'` being recognized as a `TypedString` instead of
-                // an unary negation `NOT ('a' LIKE 'b')`. To solve this, we don't accept the
-                // `type 'string'` syntax for the custom data types at all.
-                DataType::Custom(..) => parser_err!("dummy"),
-                data_type => Ok(Expr::TypedString {
-                    data_type,
-                    value: parser.parse_literal_string()?,
-                }),
-            }
-        }));
+        //
+        // Only a word can start a type name, so don't bother trying otherwise,
+        // nor for a column such as `date` that no string literal follows.
+        if self.may_start_typed_string() {
+            return_ok_if_some!(self.maybe_parse(|parser| {
+                match parser.parse_data_type()? {
+                    DataType::Interval => parser.parse_literal_interval(),
+                    // PosgreSQL allows almost any identifier to be used as custom data type name,
+                    // and we support that in `parse_data_type()`. But unlike Postgres we don't
+                    // have a list of globally reserved keywords (since they vary across dialects),
+                    // so given `NOT 'a' LIKE 'b'`, we'd accept `NOT` as a possible custom data type
+                    // name, resulting in `NOT 'a'` being recognized as a `TypedString` instead of
+                    // an unary negation `NOT ('a' LIKE 'b')`. To solve this, we don't accept the
+                    // `type 'string'` syntax for the custom data types at all.
+                    DataType::Custom(..) => parser_err!(parser, SyntaxErrorKind::Other, "dummy"),
+                    data_type => Ok(Expr::TypedString {
+                        data_type,
+                        value: parser.parse_literal_string()?,
+                    }),
+                }
+            }));
+        }
 
         let expr = match self.next_token() {
             Token::Word(w) => match w.keyword {
                 Keyword::TRUE | Keyword::FALSE | Keyword::NULL => {
-                    self.prev_token();
-                    Ok(Expr::Value(self.parse_value()?))
+                    Ok(Expr::Value(self.value_from_token(Token::Word(w))?))
                 }
                 Keyword::CASE => self.parse_case_expr(),
                 Keyword::CAST => self.parse_cast_expr(),
@@ -439,6 +1272,26 @@ impl Parser {
                     op: UnaryOperator::Not,
                     expr: Box::new(self.parse_subexpr(Self::UNARY_NOT_PREC)?),
                 }),
+                // the niladic functions, which can be called without parentheses
+                Keyword::CURRENT_DATE
+                | Keyword::CURRENT_TIME
+                | Keyword::CURRENT_TIMESTAMP
+                | Keyword::CURRENT_USER
+                | Keyword::LOCALTIME
+                | Keyword::LOCALTIMESTAMP
+                | Keyword::UTC_DATE
+                | Keyword::UTC_TIME
+                | Keyword::UTC_TIMESTAMP
+                    if self.peek_token_ref() != &Token::LParen =>
+                {
+                    Ok(Expr::Function(Function {
+                        name: ObjectName(vec![w.to_ident()]),
+                        args: vec![],
+                        over: None,
+                        distinct: false,
+                        special: true,
+                    }))
+                }
                 // Here `w` is a word, check if it's a part of a multi-part
                 // identifier, a function call, or a simple identifier:
                 _ => match self.peek_token() {
@@ -482,15 +1335,15 @@ impl Parser {
                     expr: Box::new(self.parse_subexpr(Self::PLUS_MINUS_PREC)?),
                 })
             }
-            Token::Number(_)
-            | Token::SingleQuotedString(_)
-            | Token::NationalStringLiteral(_)
-            | Token::HexStringLiteral(_)
-            | Token::VariableString(_)
-            | Token::Char(_) => {
-                self.prev_token();
-                Ok(Expr::Value(self.parse_value()?))
-            }
+            // the bulk of long `IN` lists and `VALUES` rows, so the token is
+            // turned into the value rather than taken again
+            tok @ Token::Number(_)
+            | tok @ Token::SingleQuotedString(_)
+            | tok @ Token::DoubleQuotedString(_)
+            | tok @ Token::NationalStringLiteral(_)
+            | tok @ Token::HexStringLiteral(_)
+            | tok @ Token::VariableString(_)
+            | tok @ Token::Placeholder(_) => Ok(Expr::Value(self.value_from_token(tok)?)),
             Token::LParen => {
                 let expr =
                     if self.parse_keyword(Keyword::SELECT) || self.parse_keyword(Keyword::WITH) {
@@ -508,18 +1361,13 @@ impl Parser {
             unexpected => self.expected("an expression", unexpected),
         }?;
 
-        match &self.dialect_type{
-            DBType::MySql => Ok(expr),
-            _ => {
-                return if self.parse_keyword(Keyword::COLLATE) {
-                    Ok(Expr::Collate {
-                        expr: Box::new(expr),
-                        collation: self.parse_object_name()?,
-                    })
-                } else {
-                    Ok(expr)
-                }
-            }
+        if self.dialect.supports_collate_expr() && self.parse_keyword(Keyword::COLLATE) {
+            Ok(Expr::Collate {
+                expr: Box::new(expr),
+                collation: self.parse_object_name()?,
+            })
+        } else {
+            Ok(expr)
         }
 
     }
@@ -564,6 +1412,7 @@ impl Parser {
             args,
             over,
             distinct,
+            special: false,
         }))
     }
 
@@ -703,6 +1552,7 @@ impl Parser {
                         None
                     }
                     Token::SingleQuotedString(_)
+                    | Token::DoubleQuotedString(_)
                     | Token::NationalStringLiteral(_)
                     | Token::HexStringLiteral(_) => Some(Box::new(self.parse_expr()?)),
                     unexpected => {
@@ -746,14 +1596,9 @@ impl Parser {
     // be split in two.
     pub fn parse_date_time_field(&mut self) -> Result<DateTimeField, ParserError> {
         match self.next_token() {
-            Token::Word(w) => match w.keyword {
-                Keyword::YEAR => Ok(DateTimeField::Year),
-                Keyword::MONTH => Ok(DateTimeField::Month),
-                Keyword::DAY => Ok(DateTimeField::Day),
-                Keyword::HOUR => Ok(DateTimeField::Hour),
-                Keyword::MINUTE => Ok(DateTimeField::Minute),
-                Keyword::SECOND => Ok(DateTimeField::Second),
-                _ => self.expected("date/time field", Token::Word(w))?,
+            Token::Word(w) => match date_time_field(w.keyword) {
+                Some(field) => Ok(field),
+                None => self.expected("date/time field", Token::Word(w)),
             },
             unexpected => self.expected("date/time field", unexpected),
         }
@@ -769,6 +1614,7 @@ impl Parser {
     ///   4. `INTERVAL '1:1:1.1' HOUR (5) TO SECOND (5)`
     ///   5. `INTERVAL '1.1' SECOND (2, 2)`
     ///   6. `INTERVAL '1:1' HOUR (5) TO MINUTE (5)`
+    ///   7. `INTERVAL ? DAY`, MySQL's `INTERVAL <expr> <unit>`
     ///
     /// Note that we do not currently attempt to parse the quoted value.
     pub fn parse_literal_interval(&mut self) -> Result<Expr, ParserError> {
@@ -777,6 +1623,18 @@ impl Parser {
         // don't currently try to parse it. (The sign can instead be included
         // inside the value string.)
 
+        // MySQL also takes any expression followed by a unit, which is how
+        // a number or a placeholder is written
+        match self.peek_token() {
+            Token::SingleQuotedString(_) | Token::DoubleQuotedString(_) => {}
+            _ if self.dialect.supports_interval_expr() => {
+                let value = Box::new(self.parse_expr()?);
+                let unit = self.parse_date_time_field()?;
+                return Ok(Expr::Interval { value, unit });
+            }
+            _ => {}
+        }
+
         // The first token in an interval is a string literal which specifies
         // the duration of the interval.
         let value = self.parse_literal_string()?;
@@ -787,18 +1645,7 @@ impl Parser {
         // Note that PostgreSQL allows omitting the qualifier, so we provide
         // this more general implemenation.
         let leading_field = match self.peek_token() {
-            Token::Word(kw)
-                if [
-                    Keyword::YEAR,
-                    Keyword::MONTH,
-                    Keyword::DAY,
-                    Keyword::HOUR,
-                    Keyword::MINUTE,
-                    Keyword::SECOND,
-                ]
-                .iter()
-                .any(|d| kw.keyword == *d) =>
-            {
+            Token::Word(kw) if date_time_field(kw.keyword).is_some() => {
                 Some(self.parse_date_time_field()?)
             }
             _ => None,
@@ -905,13 +1752,13 @@ impl Parser {
                     }
                 }
                 // Can only happen if `get_next_precedence` got out of sync with this function
-                _ => panic!("No infix parser for token {:?}", tok),
+                _ => parser_err!(self, SyntaxErrorKind::Other, format!("No infix parser for token {}", tok)),
             }
         } else if Token::DoubleColon == tok {
             self.parse_pg_cast(expr)
         } else {
             // Can only happen if `get_next_precedence` got out of sync with this function
-            panic!("No infix parser for token {:?}", tok)
+            parser_err!(self, SyntaxErrorKind::Other, format!("No infix parser for token {}", tok))
         }
     }
 
@@ -925,10 +1772,18 @@ impl Parser {
                 subquery: Box::new(self.parse_query()?),
                 negated,
             }
+        } else if let Some(subquery) = self.maybe_parse_parenthesized_in_subquery() {
+            // `IN ((SELECT ...) UNION (SELECT ...))`, which `IN ((1), (2))`
+            // can only be told apart from by trying to parse it
+            Expr::InSubquery {
+                expr: Box::new(expr),
+                subquery: Box::new(subquery),
+                negated,
+            }
         } else {
             Expr::InList {
                 expr: Box::new(expr),
-                list: self.parse_comma_separated(Parser::parse_expr)?,
+                list: self.parse_in_list()?,
                 negated,
             }
         };
@@ -936,6 +1791,52 @@ impl Parser {
         Ok(in_op)
     }
 
+    /// Parse the values of an `IN` list, up to the closing parenthesis
+    fn parse_in_list(&mut self) -> Result<Vec<Expr>, ParserError> {
+        if self.peek_token_ref() == &Token::RParen {
+            return self.error_at(
+                SyntaxErrorKind::EmptyInList,
+                "IN list may not be empty".to_string(),
+                &Token::RParen,
+            );
+        }
+        if let Some(list) = self.parse_literal_list()? {
+            return Ok(list);
+        }
+        let mut list = Vec::with_capacity(self.comma_separated_len());
+        loop {
+            list.push(self.parse_expr()?);
+            if !self.consume_token(&Token::Comma) {
+                return Ok(list);
+            }
+            if self.peek_token_ref() == &Token::RParen {
+                if self.allow_trailing_comma_in_in_list {
+                    return Ok(list);
+                }
+                return self.error_at(
+                    SyntaxErrorKind::TrailingCommaInInList,
+                    "Trailing comma in IN list".to_string(),
+                    &Token::Comma,
+                );
+            }
+        }
+    }
+
+    /// Try to parse a query starting with a `(` that fills the whole `IN`
+    /// list, rewinding if it isn't one
+    fn maybe_parse_parenthesized_in_subquery(&mut self) -> Option<Query> {
+        if self.peek_token() != Token::LParen {
+            return None;
+        }
+        self.maybe_parse(|parser| {
+            let query = parser.parse_query()?;
+            match parser.peek_token() {
+                Token::RParen => Ok(query),
+                unexpected => parser.expected(")", unexpected),
+            }
+        })
+    }
+
     /// Parses `BETWEEN <low> AND <high>`, assuming the `BETWEEN` keyword was already consumed
     pub fn parse_between(&mut self, expr: Expr, negated: bool) -> Result<Expr, ParserError> {
         // Stop parsing subexpressions for <low> and <high> on tokens with
@@ -965,12 +1866,12 @@ impl Parser {
 
     /// Get the precedence of the next token
     pub fn get_next_precedence(&self) -> Result<u8, ParserError> {
-        let token = self.peek_token();
+        let token = self.peek_token_ref();
         debug!("get_next_precedence() {:?}", token);
         match token {
             Token::Word(w) if w.keyword == Keyword::OR => Ok(5),
             Token::Word(w) if w.keyword == Keyword::AND => Ok(10),
-            Token::Word(w) if w.keyword == Keyword::NOT => match self.peek_nth_token(1) {
+            Token::Word(w) if w.keyword == Keyword::NOT => match self.peek_nth_token_ref(1) {
                 // The precedence of NOT varies depending on keyword that
                 // follows it. If it is followed by IN, BETWEEN, or LIKE,
                 // it takes on the precedence of those tokens. Otherwise it
@@ -1004,7 +1905,23 @@ impl Parser {
     }
 
     /// Return nth non-whitespace token that has not yet been processed
-    pub fn peek_nth_token(&self, mut n: usize) -> Token {
+    pub fn peek_nth_token(&self, n: usize) -> Token {
+        self.peek_nth_token_ref(n).clone()
+    }
+
+    /// Whether the statement being parsed ends at the next token, a `;` or
+    /// the end of the SQL. The `;` is left for the caller to consume.
+    fn peek_statement_end(&self) -> bool {
+        matches!(self.peek_token_ref(), Token::SemiColon | Token::EOF)
+    }
+
+    /// Like `peek_token`, but without cloning the token
+    pub fn peek_token_ref(&self) -> &Token {
+        self.peek_nth_token_ref(0)
+    }
+
+    /// Like `peek_nth_token`, but without cloning the token
+    pub fn peek_nth_token_ref(&self, mut n: usize) -> &Token {
         let mut index = self.index;
         loop {
             index += 1;
@@ -1012,7 +1929,7 @@ impl Parser {
                 Some(Token::Whitespace(_)) => continue,
                 non_whitespace => {
                     if n == 0 {
-                        return non_whitespace.cloned().unwrap_or(Token::EOF);
+                        return non_whitespace.unwrap_or(&EOF_TOKEN);
                     }
                     n -= 1;
                 }
@@ -1024,11 +1941,21 @@ impl Parser {
     /// (or None if reached end-of-file) and mark it as processed. OK to call
     /// repeatedly after reaching EOF.
     pub fn next_token(&mut self) -> Token {
+        self.advance_token();
+        self.tokens
+            .get(self.index - 1)
+            .cloned()
+            .unwrap_or(Token::EOF)
+    }
+
+    /// Mark the first non-whitespace token that has not yet been processed
+    /// as processed, like `next_token` but without returning it
+    fn advance_token(&mut self) {
         loop {
             self.index += 1;
             match self.tokens.get(self.index - 1) {
                 Some(Token::Whitespace(_)) => continue,
-                token => return token.cloned().unwrap_or(Token::EOF),
+                _ => return,
             }
         }
     }
@@ -1040,10 +1967,12 @@ impl Parser {
         loop {
             self.index += 1;
             match self.tokens.get(self.index - 1) {
-                Some(Token::Whitespace(Whitespace::SingleLineComment(_))) => continue,
+                Some(Token::Whitespace(Whitespace::SingleLineComment { .. })) => continue,
                 Some(Token::Whitespace(Whitespace::Space)) => continue,
                 Some(Token::Whitespace(Whitespace::Newline)) => continue,
                 Some(Token::Whitespace(Whitespace::Tab)) => continue,
+                Some(Token::Whitespace(Whitespace::VersionedCommentStart(_))) => continue,
+                Some(Token::Whitespace(Whitespace::VersionedCommentEnd)) => continue,
                 token => return token.cloned().unwrap_or(Token::EOF),
             }
         }
@@ -1071,15 +2000,66 @@ impl Parser {
 
     /// Report unexpected token
     fn expected<T>(&self, expected: &str, found: Token) -> Result<T, ParserError> {
-        parser_err!(format!("Expected {}, found: {}", expected, found))
+        let message = format!("Expected {}, found: {}", expected, found);
+        let expected = expected.to_string();
+        let kind = match found {
+            Token::EOF => SyntaxErrorKind::UnexpectedEof { expected },
+            ref found => SyntaxErrorKind::UnexpectedToken {
+                expected,
+                found: Box::new(found.clone()),
+            },
+        };
+        self.error_at(kind, message, &found)
+    }
+
+    /// Report a syntax error at `found`, which is either the next token or
+    /// the one just consumed
+    fn error_at<T>(&self, kind: SyntaxErrorKind, message: String, found: &Token) -> Result<T, ParserError> {
+        Err(ParserError::SyntaxError(SyntaxError {
+            kind,
+            message,
+            location: self.location_of(found),
+        }))
+    }
+
+    /// The location of `found`, which is either the next token or the one
+    /// just consumed
+    fn location_of(&self, found: &Token) -> Option<Location> {
+        let next = (self.index..self.tokens.len())
+            .find(|&i| !matches!(self.tokens[i], Token::Whitespace(_)));
+        let index = next
+            .filter(|&i| &self.tokens[i] == found)
+            .or_else(|| {
+                self.index
+                    .checked_sub(1)
+                    .filter(|&i| self.tokens.get(i) == Some(found))
+            })
+            .or(next);
+        match index {
+            Some(index) => self.locations.get(index).copied(),
+            // EOF is at the end of the SQL
+            None => self.end,
+        }
+    }
+
+    /// Report a syntax error at the token just consumed
+    fn error_after<T>(&self, kind: SyntaxErrorKind, message: String) -> Result<T, ParserError> {
+        let previous = self.tokens[..self.index.min(self.tokens.len())]
+            .iter()
+            .rposition(|token| !matches!(token, Token::Whitespace(_)));
+        Err(ParserError::SyntaxError(SyntaxError {
+            kind,
+            message,
+            location: previous.and_then(|index| self.locations.get(index).copied()),
+        }))
     }
 
     /// Look for an expected keyword and consume it if it exists
     #[must_use]
     pub fn parse_keyword(&mut self, expected: Keyword) -> bool {
-        match self.peek_token() {
+        match self.peek_token_ref() {
             Token::Word(w) if expected == w.keyword => {
-                self.next_token();
+                self.advance_token();
                 true
             }
             _ => false,
@@ -1104,18 +2084,14 @@ impl Parser {
     /// Look for one of the given keywords and return the one that matches.
     #[must_use]
     pub fn parse_one_of_keywords(&mut self, keywords: &[Keyword]) -> Option<Keyword> {
-        match self.peek_token() {
-            Token::Word(w) => {
-                keywords
-                    .iter()
-                    .find(|keyword| **keyword == w.keyword)
-                    .map(|keyword| {
-                        self.next_token();
-                        *keyword
-                    })
-            }
+        let keyword = match self.peek_token_ref() {
+            Token::Word(w) => keywords.iter().find(|keyword| **keyword == w.keyword).copied(),
             _ => None,
+        };
+        if keyword.is_some() {
+            self.advance_token();
         }
+        keyword
     }
 
     /// Bail out if the current token is not one of the expected keywords, or consume it if it is
@@ -1153,8 +2129,8 @@ impl Parser {
     #[must_use]
     pub fn consume_token(&mut self, expected: &Token) -> bool {
         // println!("consume_token: {:?}, {:?}", self.peek_token(), &expected);
-        if self.peek_token() == *expected {
-            self.next_token();
+        if self.peek_token_ref() == expected {
+            self.advance_token();
             true
         } else {
             false
@@ -1173,7 +2149,7 @@ impl Parser {
     /// Parse a comma-separated list of 1+ items accepted by `F`
     pub fn parse_comma_separated<T, F>(&mut self, mut f: F) -> Result<Vec<T>, ParserError>
     where
-        F: FnMut(&mut Parser) -> Result<T, ParserError>,
+        F: FnMut(&mut Parser<'a>) -> Result<T, ParserError>,
     {
         let mut values = vec![];
         loop {
@@ -1185,18 +2161,132 @@ impl Parser {
         Ok(values)
     }
 
+    /// Like `parse_comma_separated`, for lists that may be long, e.g. the
+    /// rows of `VALUES`: the items are counted ahead to allocate them at once
+    fn parse_long_comma_separated<T, F>(&mut self, mut f: F) -> Result<Vec<T>, ParserError>
+    where
+        F: FnMut(&mut Parser<'a>) -> Result<T, ParserError>,
+    {
+        let mut values = Vec::with_capacity(self.comma_separated_len());
+        loop {
+            values.push(f(self)?);
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Parse a comma separated list of literals only, up to the `)` that
+    /// closes it, such as a row of a dump or a long `IN` list. They are
+    /// turned into values straight from the tokens rather than through
+    /// `parse_expr`, and `None` leaves any other list to it.
+    fn parse_literal_list(&mut self) -> Result<Option<Vec<Expr>>, ParserError> {
+        // each item is one level deeper than the row, as with `parse_expr`
+        let max_depth = self.max_depth.saturating_mul(CHAINED_OPERATORS_PER_LEVEL);
+        if self.depth + CHAINED_OPERATORS_PER_LEVEL > max_depth {
+            return Ok(None);
+        }
+        let mut tokens = self.tokens[self.index.min(self.tokens.len())..]
+            .iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)));
+        let mut len = 0;
+        loop {
+            match tokens.next() {
+                Some(
+                    Token::Number(_)
+                    | Token::SingleQuotedString(_)
+                    | Token::DoubleQuotedString(_)
+                    | Token::NationalStringLiteral(_)
+                    | Token::HexStringLiteral(_),
+                ) => {}
+                Some(Token::Word(w))
+                    if matches!(w.keyword, Keyword::NULL | Keyword::TRUE | Keyword::FALSE) => {}
+                _ => return Ok(None),
+            }
+            len += 1;
+            match tokens.next() {
+                Some(Token::Comma) => {}
+                Some(Token::RParen) => break,
+                _ => return Ok(None),
+            }
+        }
+        let mut exprs = Vec::with_capacity(len);
+        for i in 0..len {
+            if i > 0 {
+                self.advance_token(); // the comma
+            }
+            let start = self.index;
+            self.advance_token();
+            let token = self.take_literal(self.index - 1);
+            let expr = Expr::Value(self.value_from_token(token)?);
+            self.record_span(start, &expr, |nodes| &mut nodes.exprs);
+            exprs.push(expr);
+        }
+        Ok(Some(exprs))
+    }
+
+    /// The literal at `index`, which was just consumed, taken out of the
+    /// tokens when they won't be read again, see `take_literals`, rather
+    /// than cloned. What is left is the same kind of token, with no text.
+    fn take_literal(&mut self, index: usize) -> Token {
+        let token = &mut self.tokens[index];
+        if !self.take_literals || self.rewindable > 0 {
+            return token.clone();
+        }
+        match token {
+            Token::Number(s) => Token::Number(core::mem::take(s)),
+            Token::SingleQuotedString(s) => Token::SingleQuotedString(core::mem::take(s)),
+            Token::DoubleQuotedString(s) => Token::DoubleQuotedString(core::mem::take(s)),
+            Token::NationalStringLiteral(s) => Token::NationalStringLiteral(core::mem::take(s)),
+            Token::HexStringLiteral(s) => Token::HexStringLiteral(core::mem::take(s)),
+            // a keyword such as `NULL` is told by its keyword alone
+            Token::Word(w) => Token::Word(Word {
+                value: String::new(),
+                quote_style: None,
+                keyword: w.keyword,
+            }),
+            token => token.clone(),
+        }
+    }
+
+    /// The number of items of the comma separated list at the current
+    /// token, counting the commas up to the `)` that closes it or the end of
+    /// the statement. Commas inside parentheses are skipped, and the count
+    /// may be too high when the list is followed by another one.
+    fn comma_separated_len(&self) -> usize {
+        let mut depth = 0;
+        let mut len = 1;
+        for token in self.tokens.iter().skip(self.index) {
+            match token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth == 0 => break,
+                Token::RParen => depth -= 1,
+                Token::Comma if depth == 0 => len += 1,
+                Token::SemiColon => break,
+                _ => {}
+            }
+        }
+        len
+    }
+
     /// Run a parser method `f`, reverting back to the current position
     /// if unsuccessful.
     #[must_use]
     fn maybe_parse<T, F>(&mut self, mut f: F) -> Option<T>
     where
-        F: FnMut(&mut Parser) -> Result<T, ParserError>,
+        F: FnMut(&mut Parser<'a>) -> Result<T, ParserError>,
     {
         let index = self.index;
-        if let Ok(t) = f(self) {
+        let checkpoint = self.spans_checkpoint();
+        self.rewindable += 1;
+        let result = f(self);
+        self.rewindable -= 1;
+        if let Ok(t) = result {
             Some(t)
         } else {
             self.index = index;
+            self.rollback_spans(checkpoint);
             None
         }
     }
@@ -1207,12 +2297,65 @@ impl Parser {
         let all = self.parse_keyword(Keyword::ALL);
         let distinct = self.parse_keyword(Keyword::DISTINCT);
         if all && distinct {
-            return parser_err!("Cannot specify both ALL and DISTINCT".to_string());
+            return parser_err!(self, SyntaxErrorKind::Other, "Cannot specify both ALL and DISTINCT");
         } else {
             Ok(distinct)
         }
     }
 
+    /// Parse the optional `ALL`, `DISTINCT` or `DISTINCTROW` after `SELECT`
+    pub fn parse_select_distinct(&mut self) -> Result<Option<Distinct>, ParserError> {
+        let all = self.parse_keyword(Keyword::ALL);
+        let distinct = match self.parse_one_of_keywords(&[Keyword::DISTINCT, Keyword::DISTINCTROW]) {
+            Some(Keyword::DISTINCT) => Some(Distinct::Distinct),
+            Some(_) => Some(Distinct::DistinctRow),
+            None if all => Some(Distinct::All),
+            None => None,
+        };
+        if all && distinct != Some(Distinct::All) {
+            return parser_err!(self, SyntaxErrorKind::Other, "Cannot specify both ALL and DISTINCT");
+        }
+        Ok(distinct)
+    }
+
+    /// Parse MySQL's modifiers of `SELECT` following `DISTINCT`, such as the
+    /// `SQL_NO_CACHE` of mysqldump's `SELECT /*!40001 SQL_NO_CACHE */ * FROM t`.
+    /// A modifier keyword followed by what ends a select item, such as `,` or
+    /// `FROM`, is a column instead.
+    fn parse_select_modifiers(&mut self) -> Vec<SelectModifier> {
+        let mut modifiers = vec![];
+        if !self.dialect.supports_select_modifiers() {
+            return modifiers;
+        }
+        loop {
+            let modifier = match self.peek_token_ref() {
+                Token::Word(w) if w.quote_style.is_none() => match w.keyword {
+                    Keyword::HIGH_PRIORITY => SelectModifier::HighPriority,
+                    Keyword::STRAIGHT_JOIN => SelectModifier::StraightJoin,
+                    Keyword::SQL_SMALL_RESULT => SelectModifier::SqlSmallResult,
+                    Keyword::SQL_BIG_RESULT => SelectModifier::SqlBigResult,
+                    Keyword::SQL_BUFFER_RESULT => SelectModifier::SqlBufferResult,
+                    Keyword::SQL_CACHE => SelectModifier::SqlCache,
+                    Keyword::SQL_NO_CACHE => SelectModifier::SqlNoCache,
+                    Keyword::SQL_CALC_FOUND_ROWS => SelectModifier::SqlCalcFoundRows,
+                    _ => return modifiers,
+                },
+                _ => return modifiers,
+            };
+            match self.peek_nth_token_ref(1) {
+                Token::Comma | Token::Period | Token::RParen | Token::SemiColon | Token::EOF => {
+                    return modifiers
+                }
+                Token::Word(w) if matches!(w.keyword, Keyword::FROM | Keyword::AS) => {
+                    return modifiers
+                }
+                _ => {}
+            }
+            self.advance_token();
+            modifiers.push(modifier);
+        }
+    }
+
     /// Parse a SQL CREATE statement
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parse_keyword(Keyword::TABLE) {
@@ -1221,6 +2364,10 @@ impl Parser {
             self.parse_create_index(false)
         } else if self.parse_keywords(&[Keyword::UNIQUE, Keyword::INDEX]) {
             self.parse_create_index(true)
+        } else if self.parse_keywords(&[Keyword::FULLTEXT, Keyword::INDEX]) {
+            self.parse_create_index_of_kind(false, Some(MysqlIndexStorageType::FullText))
+        } else if self.parse_keywords(&[Keyword::SPATIAL, Keyword::INDEX]) {
+            self.parse_create_index_of_kind(false, Some(MysqlIndexStorageType::Spatial))
         } else if self.parse_keyword(Keyword::MATERIALIZED) || self.parse_keyword(Keyword::VIEW) {
             self.prev_token();
             self.parse_create_view()
@@ -1258,8 +2405,12 @@ impl Parser {
     }
 
     pub fn parse_create_schema(&mut self) -> Result<Statement, ParserError> {
+        let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let schema_name = self.parse_object_name()?;
-        Ok(Statement::CreateSchema { schema_name })
+        Ok(Statement::CreateSchema {
+            schema_name,
+            if_not_exists,
+        })
     }
 
     pub fn parse_create_external_table(&mut self) -> Result<Statement, ParserError> {
@@ -1279,6 +2430,8 @@ impl Parser {
             constraints,
             with_options: vec![],
             table_options: vec![],
+            partition: None,
+            on_duplicate: None,
             if_not_exists: false,
             external: true,
             file_format: Some(file_format),
@@ -1343,40 +2496,106 @@ impl Parser {
         let if_exists = self.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
         let names = self.parse_comma_separated(Parser::parse_object_name)?;
         let mut on_info = ObjectName{ 0: vec![] };
+        let mut algorithm = None;
+        let mut lock = None;
         if let ObjectType::Index = object_type{
             if self.parse_keyword(Keyword::ON){
                 on_info = self.parse_object_name()?;
             }
+            while self.parse_index_algorithm_or_lock(&mut algorithm, &mut lock)? {}
         }
         let cascade = self.parse_keyword(Keyword::CASCADE);
         let restrict = self.parse_keyword(Keyword::RESTRICT);
         if cascade && restrict {
-            return parser_err!("Cannot specify both CASCADE and RESTRICT in DROP");
+            return parser_err!(self, SyntaxErrorKind::Other, "Cannot specify both CASCADE and RESTRICT in DROP");
         }
         Ok(Statement::Drop {
             object_type,
             if_exists,
             names,
             on_info,
+            algorithm,
+            lock,
             cascade,
+            restrict,
         })
     }
 
     pub fn parse_create_index(&mut self, unique: bool) -> Result<Statement, ParserError> {
+        self.parse_create_index_of_kind(unique, None)
+    }
+
+    fn parse_create_index_of_kind(
+        &mut self,
+        unique: bool,
+        kind: Option<MysqlIndexStorageType>,
+    ) -> Result<Statement, ParserError> {
         let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let index_name = self.parse_object_name()?;
+        let using = if self.parse_keyword(Keyword::USING) {
+            Some(self.parse_index_type()?)
+        } else {
+            None
+        };
         self.expect_keyword(Keyword::ON)?;
         let table_name = self.parse_object_name()?;
-        let columns = self.parse_parenthesized_column_list(Mandatory)?;
+        let columns = self.parse_index_key_parts()?;
+        let mut options = vec![];
+        let mut algorithm = None;
+        let mut lock = None;
+        loop {
+            if self.parse_index_algorithm_or_lock(&mut algorithm, &mut lock)? {
+                continue;
+            }
+            match self.peek_token() {
+                Token::EOF | Token::SemiColon => break,
+                _ => options.push(self.parse_alter_index_def_option()?),
+            }
+        }
         Ok(Statement::CreateIndex {
             name: index_name,
             table_name,
             columns,
             unique,
+            kind,
+            using,
+            options,
+            algorithm,
+            lock,
             if_not_exists,
         })
     }
 
+    /// Parse a MySQL `ALGORITHM [=] x` or `LOCK [=] x` clause of `CREATE INDEX`
+    /// or `DROP INDEX`, returning whether one was found
+    fn parse_index_algorithm_or_lock(
+        &mut self,
+        algorithm: &mut Option<Ident>,
+        lock: &mut Option<Ident>,
+    ) -> Result<bool, ParserError> {
+        let target = if self.parse_keyword(Keyword::ALGORITHM) {
+            algorithm
+        } else if self.parse_keyword(Keyword::LOCK) {
+            lock
+        } else {
+            return Ok(false);
+        };
+        let _ = self.consume_token(&Token::Eq);
+        *target = Some(self.parse_identifier()?);
+        Ok(true)
+    }
+
+    /// Parse `BTREE` or `HASH` after `USING`
+    pub fn parse_index_type(&mut self) -> Result<MysqlIndexType, ParserError> {
+        if self.parse_keyword(Keyword::BTREE) {
+            Ok(MysqlIndexType::Btree)
+        } else if self.parse_keyword(Keyword::HASH) {
+            Ok(MysqlIndexType::Hash)
+        } else {
+            self.expected("BTREE or HASH", self.peek_token())
+        }
+    }
+
     pub fn parse_create_table(&mut self) -> Result<Statement, ParserError> {
         let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let table_name = self.parse_object_name()?;
@@ -1389,8 +2608,26 @@ impl Parser {
         // PostgreSQL supports `WITH ( options )`, before `AS`
         let with_options = self.parse_with_options()?;
         let table_options = self.parse_table_options()?;
-        // Parse optional `AS ( query )`
-        let query = if self.parse_keyword(Keyword::AS) {
+        let partition = if self.parse_keywords(&[Keyword::PARTITION, Keyword::BY]) {
+            Some(self.parse_partition_definition()?)
+        } else {
+            None
+        };
+        // MySQL allows `IGNORE` or `REPLACE` before the query
+        let implicit_as = self.dialect.supports_create_table_select_without_as();
+        let on_duplicate = if implicit_as && self.parse_keyword(Keyword::IGNORE) {
+            Some(CreateTableOnDuplicate::Ignore)
+        } else if implicit_as && self.parse_keyword(Keyword::REPLACE) {
+            Some(CreateTableOnDuplicate::Replace)
+        } else {
+            None
+        };
+        // Parse optional `AS ( query )`, MySQL also allows omitting `AS`
+        let query = if self.parse_keyword(Keyword::AS)
+            || on_duplicate.is_some()
+            || (implicit_as
+                && matches!(self.peek_token(), Token::Word(w) if w.keyword == Keyword::SELECT))
+        {
             Some(Box::new(self.parse_query()?))
         } else {
             None
@@ -1403,6 +2640,8 @@ impl Parser {
             constraints,
             with_options,
             table_options,
+            partition,
+            on_duplicate,
             if_not_exists,
             external: false,
             file_format: None,
@@ -1412,7 +2651,31 @@ impl Parser {
         })
     }
 
+    /// Whether the next token can be a column name: a word that
+    /// `Dialect::is_reserved_for_column_name` allows, such as `comment` or
+    /// `status` in MySQL
+    fn next_is_column_name(&self) -> bool {
+        match self.peek_token_ref() {
+            Token::Word(w) => !self.dialect.is_reserved_for_column_name(w),
+            _ => false,
+        }
+    }
+
+    /// Whether `ALTER TABLE ... ADD` or `DROP` is followed by a column name
+    /// without `COLUMN`, which only dialects with MySQL index definitions
+    /// allow; elsewhere an index follows
+    fn next_is_column_without_keyword(&self) -> bool {
+        self.dialect.supports_index_definitions() && self.next_is_column_name()
+    }
+
+    /// Parse a column definition. A `COLLATE` right after the data type goes
+    /// into `collation`; `CHARACTER SET` and any later `COLLATE` are options,
+    /// so the two can be written in either order. `FIRST` or `AFTER <column>`
+    /// ends the definition.
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
+        if !self.next_is_column_name() {
+            return self.expected("column name", self.peek_token());
+        }
         let name = self.parse_identifier()?;
         let data_type = self.parse_data_type()?;
 
@@ -1429,7 +2692,14 @@ impl Parser {
         loop {
             match self.peek_token() {
                 Token::EOF | Token::Comma | Token::RParen | Token::SemiColon => break,
-                _ => options.push(self.parse_column_option_def()?),
+                _ => {
+                    let option = self.parse_column_option_def()?;
+                    let position = matches!(option.option, ColumnOption::First | ColumnOption::After(_));
+                    options.push(option);
+                    if position {
+                        break;
+                    }
+                }
             }
         }
         Ok(ColumnDef {
@@ -1437,6 +2707,7 @@ impl Parser {
             data_type,
             collation,
             options,
+            ordinal: 0,
         })
     }
 
@@ -1449,13 +2720,14 @@ impl Parser {
         }
 
         loop {
-            match self.dialect_type{
-                DBType::MySql => {
+            match self.dialect.supports_index_definitions() {
+                true => {
                     if let Token::Word(_) = self.peek_token() {
                         if let Some(index_def) = self.parse_create_table_for_index()?{
                             index.push(index_def);
                         }else {
-                            let column_def = self.parse_column_def()?;
+                            let mut column_def = self.parse_column_def()?;
+                            column_def.ordinal = columns.len() + 1;
                             columns.push(column_def);
                         }
                     }
@@ -1471,7 +2743,8 @@ impl Parser {
                     if let Some(constraint) = self.parse_optional_table_constraint()? {
                         constraints.push(constraint);
                     } else if let Token::Word(_) = self.peek_token() {
-                        let column_def = self.parse_column_def()?;
+                        let mut column_def = self.parse_column_def()?;
+                        column_def.ordinal = columns.len() + 1;
                         columns.push(column_def);
                     } else {
                         return self.expected("column name or constraint definition", self.peek_token());
@@ -1492,17 +2765,11 @@ impl Parser {
 
     fn parse_create_table_for_index(&mut self) -> Result<Option<IndexInfo>, ParserError>{
         let keyword_list = [Keyword::KEY, Keyword::INDEX, Keyword::PRIMARY,
-            Keyword::UNIQUE, Keyword::FOREIGN, Keyword::FULLTEXT, Keyword::CONSTRAINT];
+            Keyword::UNIQUE, Keyword::FOREIGN, Keyword::FULLTEXT, Keyword::SPATIAL,
+            Keyword::CONSTRAINT];
         return if let Some(_k) = self.parse_one_of_keywords(&keyword_list){
             self.prev_token();
-            let constraint = self.parse_alter_index_constraint()?;
-            let index_type = self.parse_alter_index_storge_type()?;
-            let index = self.parse_alter_index_def()?;
-            Ok(Some(IndexInfo{
-                constraint,
-                index_type,
-                index
-            }))
+            Ok(Some(self.parse_index_info()?))
         }else {
             Ok(None)
         }
@@ -1511,41 +2778,140 @@ impl Parser {
     pub fn parse_table_options(&mut self) -> Result<Vec<TableOptionDef>, ParserError>{
         let mut table_options = vec![];
         loop{
-            if self.consume_token(&Token::EOF) || self.consume_token(&Token::SemiColon){
+            if self.peek_statement_end() {
                 break
             }
+            if let Token::Word(w) = self.peek_token() {
+                match w.keyword {
+                    Keyword::PARTITION | Keyword::IGNORE | Keyword::REPLACE | Keyword::AS
+                    | Keyword::SELECT => break,
+                    _ => {}
+                }
+            }
             table_options.push(self.parse_table_option_def()?);
-            
+            // MySQL allows the options to be separated by commas
+            let _ = self.consume_token(&Token::Comma);
         }
         return Ok(table_options)
     }
     
     pub fn parse_table_option_def(&mut self) -> Result<TableOptionDef, ParserError>{
         let mut name = None;
+        if self.parse_keyword(Keyword::DEFAULT) {
+            self.prev_token();
+            name = Some(self.parse_identifier()?);
+            match self.peek_token() {
+                Token::Word(w)
+                    if matches!(w.keyword, Keyword::CHARSET | Keyword::CHARACTER | Keyword::COLLATE) => {}
+                unexpected => {
+                    return self.expected("CHARACTER SET, CHARSET or COLLATE after DEFAULT", unexpected)
+                }
+            }
+        }
+        // `CHARACTER SET` and `CHARSET` are the same option, written as `CHARSET`
         let option = if self.parse_keyword(Keyword::COMMENT){
             self.consume_table_option_token()?;
-            TableOption::Comment(self.parse_expr()?)
+            TableOption::Comment(self.parse_table_option_value()?)
         }else if self.parse_keyword(Keyword::COLLATE){
             self.consume_table_option_token()?;
-            TableOption::Collate(self.parse_expr()?)
-        }else if self.parse_keyword(Keyword::DEFAULT) {
-            self.prev_token();
-            name = Some(self.parse_identifier()?);
-            if self.parse_keyword(Keyword::CHARSET){
-                self.consume_table_option_token()?;
-                TableOption::Charset(self.parse_expr()?)
-            }else {
-                return self.expected("talbe option for default charset", self.peek_token());
-            }
+            TableOption::Collate(self.parse_table_option_value()?)
+        }else if self.parse_keyword(Keyword::CHARSET)
+            || self.parse_keywords(&[Keyword::CHARACTER, Keyword::SET])
+        {
+            self.consume_table_option_token()?;
+            TableOption::Charset(self.parse_table_option_value()?)
         }else if self.parse_keyword(Keyword::AUTO_INCREMENT) {
             self.consume_table_option_token()?;
             match self.next_token(){
-                Token::Number(a) => TableOption::Auto_Increment(a.parse().unwrap()),
+                Token::Number(a) => match a.parse() {
+                    Ok(n) => TableOption::Auto_Increment(n),
+                    Err(e) => {
+                        let message = format!("Could not parse '{}' as AUTO_INCREMENT: {}", a, e);
+                        return parser_err!(self, SyntaxErrorKind::NumberParse { text: a }, message);
+                    }
+                },
                 _ =>  return self.expected("table option for auto_increment", self.peek_token())
             }
         }else if self.parse_keyword(Keyword::ENGINE) {
             self.consume_table_option_token()?;
-            TableOption::Engine(self.parse_expr()?)
+            TableOption::Engine(self.parse_table_option_value()?)
+        }else if self.parse_keywords(&[Keyword::DATA, Keyword::DIRECTORY]) {
+            self.consume_table_option_token()?;
+            TableOption::DataDirectory(self.parse_table_option_value()?)
+        }else if self.parse_keywords(&[Keyword::INDEX, Keyword::DIRECTORY]) {
+            self.consume_table_option_token()?;
+            TableOption::IndexDirectory(self.parse_table_option_value()?)
+        }else if self.parse_keyword(Keyword::TABLESPACE) {
+            self.consume_table_option_token()?;
+            let name = self.parse_identifier()?;
+            let storage = if self.parse_keyword(Keyword::STORAGE) {
+                if self.parse_keyword(Keyword::DISK) {
+                    Some(ColumnStorage::Disk)
+                } else if self.parse_keyword(Keyword::MEMORY) {
+                    Some(ColumnStorage::Memory)
+                } else {
+                    return self.expected("DISK or MEMORY after STORAGE", self.peek_token());
+                }
+            } else {
+                None
+            };
+            TableOption::Tablespace { name, storage }
+        }else if self.parse_keyword(Keyword::UNION) {
+            self.consume_table_option_token()?;
+            self.expect_token(&Token::LParen)?;
+            let tables = self.parse_comma_separated(Parser::parse_object_name)?;
+            self.expect_token(&Token::RParen)?;
+            TableOption::Union(tables)
+        }else if let Some(keyword) = self.parse_one_of_keywords(&[
+            Keyword::AUTOEXTEND_SIZE,
+            Keyword::AVG_ROW_LENGTH,
+            Keyword::CHECKSUM,
+            Keyword::COMPRESSION,
+            Keyword::CONNECTION,
+            Keyword::DELAY_KEY_WRITE,
+            Keyword::ENCRYPTION,
+            Keyword::ENGINE_ATTRIBUTE,
+            Keyword::INSERT_METHOD,
+            Keyword::KEY_BLOCK_SIZE,
+            Keyword::MAX_ROWS,
+            Keyword::MIN_ROWS,
+            Keyword::PACK_KEYS,
+            Keyword::PASSWORD,
+            Keyword::ROW_FORMAT,
+            Keyword::SECONDARY_ENGINE_ATTRIBUTE,
+            Keyword::STATS_AUTO_RECALC,
+            Keyword::STATS_PERSISTENT,
+            Keyword::STATS_SAMPLE_PAGES,
+        ]) {
+            self.consume_table_option_token()?;
+            let value = self.parse_table_option_value()?;
+            match keyword {
+                Keyword::AUTOEXTEND_SIZE => TableOption::AutoextendSize(value),
+                Keyword::AVG_ROW_LENGTH => TableOption::AvgRowLength(value),
+                Keyword::CHECKSUM => TableOption::Checksum(value),
+                Keyword::COMPRESSION => TableOption::Compression(value),
+                Keyword::CONNECTION => TableOption::Connection(value),
+                Keyword::DELAY_KEY_WRITE => TableOption::DelayKeyWrite(value),
+                Keyword::ENCRYPTION => TableOption::Encryption(value),
+                Keyword::ENGINE_ATTRIBUTE => TableOption::EngineAttribute(value),
+                Keyword::INSERT_METHOD => TableOption::InsertMethod(value),
+                Keyword::KEY_BLOCK_SIZE => TableOption::KeyBlockSize(value),
+                Keyword::MAX_ROWS => TableOption::MaxRows(value),
+                Keyword::MIN_ROWS => TableOption::MinRows(value),
+                Keyword::PACK_KEYS => TableOption::PackKeys(value),
+                Keyword::PASSWORD => TableOption::Password(value),
+                Keyword::ROW_FORMAT => TableOption::RowFormat(value),
+                Keyword::SECONDARY_ENGINE_ATTRIBUTE => TableOption::SecondaryEngineAttribute(value),
+                Keyword::STATS_AUTO_RECALC => TableOption::StatsAutoRecalc(value),
+                Keyword::STATS_PERSISTENT => TableOption::StatsPersistent(value),
+                Keyword::STATS_SAMPLE_PAGES => TableOption::StatsSamplePages(value),
+                _ => unreachable!(),
+            }
+        }else if let (Token::Word(_), Token::Eq) = (self.peek_token(), self.peek_nth_token(1)) {
+            // an option we don't know about yet, keep it as a name/value pair
+            let name = self.parse_identifier()?;
+            self.expect_token(&Token::Eq)?;
+            TableOption::Other { name, value: self.parse_table_option_value()? }
         }
         else {
             return self.expected("table option", self.peek_token());
@@ -1554,12 +2920,165 @@ impl Parser {
 
     }
 
-    pub fn consume_table_option_token(&mut self) -> Result<(), ParserError>{
-        return if self.consume_token(&Token::Eq) {
-            Ok(())
+    /// Parse what follows `PARTITION BY` in a MySQL `CREATE TABLE`
+    pub fn parse_partition_definition(&mut self) -> Result<PartitionDefinition, ParserError> {
+        let partition_by = self.parse_partition_method(false)?;
+        let partitions = if self.parse_keyword(Keyword::PARTITIONS) {
+            Some(self.parse_literal_uint()?)
+        } else {
+            None
+        };
+        let subpartition_by = if self.parse_keywords(&[Keyword::SUBPARTITION, Keyword::BY]) {
+            Some(self.parse_partition_method(true)?)
+        } else {
+            None
+        };
+        let subpartitions = if self.parse_keyword(Keyword::SUBPARTITIONS) {
+            Some(self.parse_literal_uint()?)
         } else {
-            self.expected("table option", self.peek_token())
+            None
+        };
+        let definitions = if self.consume_token(&Token::LParen) {
+            let definitions = self.parse_comma_separated(Parser::parse_partition)?;
+            self.expect_token(&Token::RParen)?;
+            definitions
+        } else {
+            vec![]
+        };
+        Ok(PartitionDefinition {
+            partition_by,
+            partitions,
+            subpartition_by,
+            subpartitions,
+            definitions,
+        })
+    }
+
+    /// Parse a partitioning method, subpartitions only allow `HASH` and `KEY`
+    fn parse_partition_method(&mut self, subpartition: bool) -> Result<PartitionMethod, ParserError> {
+        let linear = self.parse_keyword(Keyword::LINEAR);
+        if self.parse_keyword(Keyword::HASH) {
+            self.expect_token(&Token::LParen)?;
+            let expr = self.parse_expr()?;
+            self.expect_token(&Token::RParen)?;
+            Ok(PartitionMethod::Hash { linear, expr })
+        } else if self.parse_keyword(Keyword::KEY) {
+            let algorithm = if self.parse_keyword(Keyword::ALGORITHM) {
+                self.expect_token(&Token::Eq)?;
+                Some(self.parse_literal_uint()?)
+            } else {
+                None
+            };
+            // an empty column list means the primary key
+            self.expect_token(&Token::LParen)?;
+            let columns = if self.consume_token(&Token::RParen) {
+                vec![]
+            } else {
+                let columns = self.parse_comma_separated(Parser::parse_identifier)?;
+                self.expect_token(&Token::RParen)?;
+                columns
+            };
+            Ok(PartitionMethod::Key { linear, algorithm, columns })
+        } else if !linear && !subpartition && self.parse_keyword(Keyword::RANGE) {
+            Ok(PartitionMethod::Range(self.parse_partition_key()?))
+        } else if !linear && !subpartition && self.parse_keyword(Keyword::LIST) {
+            Ok(PartitionMethod::List(self.parse_partition_key()?))
+        } else if linear || subpartition {
+            self.expected("HASH or KEY", self.peek_token())
+        } else {
+            self.expected("RANGE, LIST, HASH or KEY", self.peek_token())
+        }
+    }
+
+    fn parse_partition_key(&mut self) -> Result<PartitionKey, ParserError> {
+        if self.parse_keyword(Keyword::COLUMNS) {
+            Ok(PartitionKey::Columns(self.parse_parenthesized_column_list(Mandatory)?))
+        } else {
+            self.expect_token(&Token::LParen)?;
+            let expr = self.parse_expr()?;
+            self.expect_token(&Token::RParen)?;
+            Ok(PartitionKey::Expr(expr))
+        }
+    }
+
+    fn parse_partition(&mut self) -> Result<Partition, ParserError> {
+        self.expect_keyword(Keyword::PARTITION)?;
+        let name = self.parse_identifier()?;
+        let values = if self.parse_keyword(Keyword::VALUES) {
+            if self.parse_keywords(&[Keyword::LESS, Keyword::THAN]) {
+                if self.parse_keyword(Keyword::MAXVALUE) {
+                    Some(PartitionValues::LessThanMaxValue)
+                } else {
+                    self.expect_token(&Token::LParen)?;
+                    let exprs = self.parse_comma_separated(Parser::parse_expr)?;
+                    self.expect_token(&Token::RParen)?;
+                    Some(PartitionValues::LessThan(exprs))
+                }
+            } else if self.parse_keyword(Keyword::IN) {
+                self.expect_token(&Token::LParen)?;
+                let exprs = self.parse_comma_separated(Parser::parse_expr)?;
+                self.expect_token(&Token::RParen)?;
+                Some(PartitionValues::In(exprs))
+            } else {
+                return self.expected("LESS THAN or IN after VALUES", self.peek_token());
+            }
+        } else {
+            None
+        };
+        let options = self.parse_partition_options()?;
+        let subpartitions = if self.consume_token(&Token::LParen) {
+            let subpartitions = self.parse_comma_separated(|parser| {
+                parser.expect_keyword(Keyword::SUBPARTITION)?;
+                let name = parser.parse_identifier()?;
+                let options = parser.parse_partition_options()?;
+                Ok(Subpartition { name, options })
+            })?;
+            self.expect_token(&Token::RParen)?;
+            subpartitions
+        } else {
+            vec![]
+        };
+        Ok(Partition {
+            name,
+            values,
+            options,
+            subpartitions,
+        })
+    }
+
+    /// Parse the table options (`ENGINE`, `COMMENT`, `DATA DIRECTORY`, ...) of a
+    /// partition or subpartition
+    fn parse_partition_options(&mut self) -> Result<Vec<TableOption>, ParserError> {
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                Token::EOF | Token::Comma | Token::LParen | Token::RParen | Token::SemiColon => break,
+                _ => {
+                    // `STORAGE ENGINE` is a synonym for `ENGINE`
+                    if let Token::Word(w) = self.peek_nth_token(1) {
+                        if w.keyword == Keyword::ENGINE {
+                            let _ = self.parse_keyword(Keyword::STORAGE);
+                        }
+                    }
+                    options.push(self.parse_table_option_def()?.option);
+                }
+            }
         }
+        Ok(options)
+    }
+
+    /// Parse the value of a table option: a number, a quoted string or an identifier
+    pub fn parse_table_option_value(&mut self) -> Result<Expr, ParserError> {
+        match self.peek_token() {
+            Token::Word(_) => Ok(Expr::Identifier(self.parse_identifier()?)),
+            _ => Ok(Expr::Value(self.parse_value()?)),
+        }
+    }
+
+    /// Consume the optional `=` between a table option name and its value
+    pub fn consume_table_option_token(&mut self) -> Result<(), ParserError>{
+        let _ = self.consume_token(&Token::Eq);
+        Ok(())
     }
 
     pub fn parse_column_option_def(&mut self) -> Result<ColumnOptionDef, ParserError> {
@@ -1581,16 +3100,51 @@ impl Parser {
             ColumnOption::Comment(self.parse_expr()?)
         } else if self.parse_keyword(Keyword::AFTER) {
             ColumnOption::After(self.parse_expr()?)
+        } else if self.parse_keyword(Keyword::FIRST) {
+            ColumnOption::First
+        } else if self.parse_keyword(Keyword::INVISIBLE) {
+            ColumnOption::Invisible
+        } else if self.parse_keyword(Keyword::VISIBLE) {
+            ColumnOption::Visible
+        } else if self.parse_keyword(Keyword::COLUMN_FORMAT) {
+            let format = if self.parse_keyword(Keyword::FIXED) {
+                ColumnFormat::Fixed
+            } else if self.parse_keyword(Keyword::DYNAMIC) {
+                ColumnFormat::Dynamic
+            } else if self.parse_keyword(Keyword::DEFAULT) {
+                ColumnFormat::Default
+            } else {
+                return self.expected("FIXED, DYNAMIC or DEFAULT after COLUMN_FORMAT", self.peek_token());
+            };
+            ColumnOption::ColumnFormat(format)
+        } else if self.parse_keyword(Keyword::STORAGE) {
+            let storage = if self.parse_keyword(Keyword::DISK) {
+                ColumnStorage::Disk
+            } else if self.parse_keyword(Keyword::MEMORY) {
+                ColumnStorage::Memory
+            } else {
+                return self.expected("DISK or MEMORY after STORAGE", self.peek_token());
+            };
+            ColumnOption::Storage(storage)
+        }else if self.parse_keyword(Keyword::CHARSET) {
+            // written as `CHARACTER SET`
+            ColumnOption::Character(self.parse_charset_name()?)
         }else if self.parse_keyword(Keyword::CHARACTER) {
             if self.parse_keyword(Keyword::SET){
-                ColumnOption::Character(self.parse_expr()?)
+                ColumnOption::Character(self.parse_charset_name()?)
             }else {
                 return self.expected("column character set ", self.peek_token());
             }
         } else if self.parse_keyword(Keyword::COLLATE) {
-            ColumnOption::Collate(self.parse_expr()?)
+            ColumnOption::Collate(self.parse_charset_name()?)
         } else if self.parse_keyword(Keyword::DEFAULT) {
-            ColumnOption::Default(self.parse_expr()?)
+            if self.consume_token(&Token::LParen) {
+                let expr = self.parse_expr()?;
+                self.expect_token(&Token::RParen)?;
+                ColumnOption::DefaultExpr(expr)
+            } else {
+                ColumnOption::Default(self.parse_expr()?)
+            }
         } else if self.parse_keywords(&[Keyword::PRIMARY, Keyword::KEY]) {
             ColumnOption::Unique { is_primary: true }
         } else if self.parse_keyword(Keyword::UNIQUE) {
@@ -1651,13 +3205,11 @@ impl Parser {
     }
     
     pub fn parse_alter_drop_index(&mut self) -> Result<IndexDef, ParserError>{
-        if self.parse_keyword(Keyword::INDEX) || self.parse_keyword(Keyword::KEY) {
-            Ok(IndexDef::Normal(self.parse_alter_index_def_normal(false, false, true)?))
+        if self.peek_index_keyword() {
+            Ok(IndexDef::Normal(self.parse_alter_index_def_normal(false, true)?))
         }else if self.parse_keyword(Keyword::PRIMARY) {
             self.expect_keyword(Keyword::KEY)?;
             Ok(IndexDef::PrimaryKey(self.parse_alter_index_def_primary(true)?))
-        }else if self.parse_keyword(Keyword::FOREIGN) {
-            Ok(IndexDef::ForeignKey(self.parse_alter_index_def_normal(false, true, true)?))
         }else {
             self.expected(
                 "alter table index def ",
@@ -1667,22 +3219,47 @@ impl Parser {
     }
 
     pub fn parse_alter_add_index(&mut self) -> Result<AlterTableOperation, ParserError>{
+        Ok(AlterTableOperation::AddIndex { index_def: self.parse_index_info()? })
+    }
+
+    /// Parse an index of `CREATE TABLE` or `ALTER TABLE ... ADD`, where
+    /// `KEY` or `INDEX` may be left out after `FULLTEXT` and `SPATIAL`
+    fn parse_index_info(&mut self) -> Result<IndexInfo, ParserError> {
         let constraint = self.parse_alter_index_constraint()?;
         let index_type = self.parse_alter_index_storge_type()?;
-        let index = self.parse_alter_index_def()?;
-        Ok(AlterTableOperation::AddIndex { index_def: IndexInfo{constraint, index_type, index} })
+        let index = if index_type.is_some() && !self.peek_index_keyword() {
+            IndexDef::Normal(self.parse_alter_index_def_normal(false, false)?)
+        } else {
+            self.parse_alter_index_def()?
+        };
+        Ok(IndexInfo {
+            constraint,
+            index_type,
+            index,
+        })
+    }
+
+    /// Whether the next token is the `KEY` or `INDEX` keyword, rather than
+    /// an index name quoted as `key`
+    fn peek_index_keyword(&self) -> bool {
+        match self.peek_token_ref() {
+            Token::Word(w) => {
+                matches!(w.keyword, Keyword::KEY | Keyword::INDEX) && w.quote_style.is_none()
+            }
+            _ => false,
+        }
     }
 
     pub fn parse_alter_index_def(&mut self) -> Result<IndexDef, ParserError>{
-        if self.parse_keyword(Keyword::INDEX) || self.parse_keyword(Keyword::KEY) {
-            Ok(IndexDef::Normal(self.parse_alter_index_def_normal(false, false, false)?))
+        if self.peek_index_keyword() {
+            Ok(IndexDef::Normal(self.parse_alter_index_def_normal(false, false)?))
         }else if self.parse_keyword(Keyword::PRIMARY) {
             self.expect_keyword(Keyword::KEY)?;
             Ok(IndexDef::PrimaryKey(self.parse_alter_index_def_primary(false)?))
         }else if self.parse_keyword(Keyword::UNIQUE) {
-            Ok(IndexDef::Unique(self.parse_alter_index_def_normal(true, false, false)?))
+            Ok(IndexDef::Unique(self.parse_alter_index_def_normal(false, false)?))
         }else if self.parse_keyword(Keyword::FOREIGN) {
-            Ok(IndexDef::ForeignKey(self.parse_alter_index_def_normal(false, true, false)?))
+            Ok(IndexDef::ForeignKey(self.parse_alter_index_def_normal(true, false)?))
         }else {
             self.expected(
                 "alter table index def ",
@@ -1695,83 +3272,177 @@ impl Parser {
     pub fn parse_alter_index_def_primary(&mut self, drop: bool) -> Result<MysqlIndex, ParserError> {
         if drop{
             Ok(
-                MysqlIndex{name:None, index_name:None, index_type: None, key_parts:None, index_option:None}
+                MysqlIndex{name:None, index_name:None, index_type: None, index_type_after_key_parts: false, key_parts:None, index_option:vec![]}
             )
         }else {
             let index_type = if self.parse_keyword(Keyword::USING){
                 Some(self.parse_identifier()?)
             }else { None };
-            let key_parts = Some(self.parse_parenthesized_column_list(Mandatory)?);
-            let index_option = self.parse_alter_index_def_options()?;
+            let key_parts = Some(self.parse_index_key_parts()?);
+            let (index_type, index_type_after_key_parts, index_option) =
+                self.parse_index_options_after_key_parts(index_type)?;
             let (name, index_name) = (None, None);
             Ok(
-                MysqlIndex{name, index_name, index_type, key_parts, index_option}
+                MysqlIndex{name, index_name, index_type, index_type_after_key_parts, key_parts, index_option}
             )
         }
     }
 
-    pub fn parse_alter_index_def_normal(&mut self, unique: bool, foreign: bool, drop: bool) -> Result<MysqlIndex, ParserError> {
+    pub fn parse_alter_index_def_normal(&mut self, foreign: bool, drop: bool) -> Result<MysqlIndex, ParserError> {
+        // `name` is the `KEY` or `INDEX` keyword as written, which may be
+        // left out after `UNIQUE`, `FULLTEXT` and `SPATIAL`
         let name = if foreign{
             self.expect_keyword(Keyword::KEY)?;
             None
-        }else {
-            if !unique{
-                self.prev_token();
-            }
-            Some(self.parse_identifier()?)
-        };
-
-
-        let index_name = if !self.consume_token(&Token::LParen){
+        }else if self.peek_index_keyword() {
             Some(self.parse_identifier()?)
         }else {
-            self.prev_token();
             None
         };
-        let (index_type, key_parts, index_option) = if drop{
-            (None, None, None)
+
+        let index_name = match self.peek_token_ref() {
+            Token::LParen => None,
+            Token::Word(w) if w.keyword == Keyword::USING && !drop => None,
+            _ => Some(self.parse_identifier()?),
+        };
+        let (index_type, index_type_after_key_parts, key_parts, index_option) = if drop{
+            (None, false, None, vec![])
         }else {
-            let index_type = if unique {
-                if !self.consume_token(&Token::LParen){
-                    Some(self.parse_identifier()?)
-                }else {
-                    self.prev_token();
-                    None
-                }
+            let index_type = if self.parse_keyword(Keyword::USING) {
+                Some(self.parse_identifier()?)
             } else {
                 None
             };
-            let key_parts = Some(self.parse_parenthesized_column_list(Mandatory)?);
-            let index_option = self.parse_alter_index_def_options()?;
-            (index_type, key_parts, index_option)
+            let key_parts = Some(self.parse_index_key_parts()?);
+            let (index_type, after, index_option) =
+                self.parse_index_options_after_key_parts(index_type)?;
+            (index_type, after, key_parts, index_option)
         };
         Ok(
-            MysqlIndex{name, index_name, index_type, key_parts, index_option}
+            MysqlIndex{name, index_name, index_type, index_type_after_key_parts, key_parts, index_option}
         )
     }
 
-    pub fn parse_alter_index_def_options(&mut self) -> Result<Option<IndexOptions>, ParserError> {
-        if self.consume_token(&Token::Comma) || self.consume_token(&Token::RParen) {
-            self.prev_token();
-            return Ok(None);
+    /// Parse the options after the key parts of an index. Without a
+    /// `USING <type>` before the key parts, the first one among the options
+    /// becomes the index type, printed right after the key parts, and whether
+    /// it came after them is returned with it.
+    fn parse_index_options_after_key_parts(
+        &mut self,
+        index_type: Option<Ident>,
+    ) -> Result<(Option<Ident>, bool, Vec<IndexOptions>), ParserError> {
+        let mut index_option = self.parse_alter_index_def_options()?;
+        if index_type.is_some() {
+            return Ok((index_type, false, index_option));
+        }
+        let position = index_option
+            .iter()
+            .position(|option| matches!(option, IndexOptions::IndexType(_)));
+        match position.map(|position| index_option.remove(position)) {
+            Some(IndexOptions::IndexType(index_type)) => Ok((Some(index_type), true, index_option)),
+            _ => Ok((None, false, index_option)),
+        }
+    }
+
+    /// Parse a parenthesized list of index key parts, e.g. `(name(20) DESC, (LOWER(email)))`
+    pub fn parse_index_key_parts(&mut self) -> Result<Vec<IndexKeyPart>, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let key_parts = self.parse_comma_separated(|parser| {
+            if parser.consume_token(&Token::LParen) {
+                let expr = parser.parse_expr()?;
+                parser.expect_token(&Token::RParen)?;
+                let asc = parser.parse_index_key_part_order();
+                Ok(IndexKeyPart::Expr { expr, asc })
+            } else {
+                let name = parser.parse_identifier()?;
+                let length = if parser.consume_token(&Token::LParen) {
+                    let length = parser.parse_literal_uint()?;
+                    parser.expect_token(&Token::RParen)?;
+                    Some(length)
+                } else {
+                    None
+                };
+                let asc = parser.parse_index_key_part_order();
+                Ok(IndexKeyPart::Column { name, length, asc })
+            }
+        })?;
+        self.expect_token(&Token::RParen)?;
+        Ok(key_parts)
+    }
+
+    fn parse_index_key_part_order(&mut self) -> Option<bool> {
+        if self.parse_keyword(Keyword::ASC) {
+            Some(true)
+        } else if self.parse_keyword(Keyword::DESC) {
+            Some(false)
+        } else {
+            None
         }
-        if self.consume_token(&Token::EOF) || self.consume_token(&Token::SemiColon){
-            return Ok(None)
+    }
+
+    pub fn parse_alter_index_def_options(&mut self) -> Result<Vec<IndexOptions>, ParserError> {
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                Token::EOF | Token::SemiColon | Token::Comma | Token::RParen => break,
+                _ => options.push(self.parse_alter_index_def_option()?),
+            }
         }
+        Ok(options)
+    }
+
+    pub fn parse_alter_index_def_option(&mut self) -> Result<IndexOptions, ParserError> {
         if self.parse_keyword(Keyword::KEY_BLOCK_SIZE){
-            Ok(Some(IndexOptions::KeyBlockSize(self.parse_expr()?)))
+            let _ = self.consume_token(&Token::Eq);
+            Ok(IndexOptions::KeyBlockSize(self.parse_expr()?))
         } else if self.parse_keyword(Keyword::WITH) {
             self.expect_keyword(Keyword::PARSER)?;
-            Ok(Some(IndexOptions::WithParser(self.parse_identifier()?)))
+            Ok(IndexOptions::WithParser(self.parse_identifier()?))
         } else if self.parse_keyword(Keyword::USING) {
-            Ok(Some(IndexOptions::IndexType(self.parse_identifier()?)))
+            Ok(IndexOptions::IndexType(self.parse_identifier()?))
         } else if self.parse_keyword(Keyword::COMMENT) {
-            Ok(Some(IndexOptions::Comment(self.parse_expr()?)))
+            Ok(IndexOptions::Comment(self.parse_expr()?))
+        } else if self.parse_keyword(Keyword::VISIBLE) {
+            Ok(IndexOptions::Visible)
+        } else if self.parse_keyword(Keyword::INVISIBLE) {
+            Ok(IndexOptions::Invisible)
+        } else if self.parse_keyword(Keyword::ENGINE_ATTRIBUTE) {
+            let _ = self.consume_token(&Token::Eq);
+            Ok(IndexOptions::EngineAttribute(self.parse_expr()?))
+        } else if self.parse_keyword(Keyword::SECONDARY_ENGINE_ATTRIBUTE) {
+            let _ = self.consume_token(&Token::Eq);
+            Ok(IndexOptions::SecondaryEngineAttribute(self.parse_expr()?))
         }
         else if self.parse_keyword(Keyword::REFERENCES) {
-            let table = self.parse_identifier()?;
+            let table = self.parse_object_name()?;
             let column = self.parse_parenthesized_column_list(Mandatory)?;
-            Ok(Some(IndexOptions::References {table, column}))
+            let match_type = if self.parse_keyword(Keyword::MATCH) {
+                if self.parse_keyword(Keyword::FULL) {
+                    Some(ReferentialMatch::Full)
+                } else if self.parse_keyword(Keyword::PARTIAL) {
+                    Some(ReferentialMatch::Partial)
+                } else if self.parse_keyword(Keyword::SIMPLE) {
+                    Some(ReferentialMatch::Simple)
+                } else {
+                    return self.expected("FULL, PARTIAL or SIMPLE after MATCH", self.peek_token());
+                }
+            } else {
+                None
+            };
+            let mut on_delete = None;
+            let mut on_update = None;
+            loop {
+                if on_delete.is_none() && self.parse_keywords(&[Keyword::ON, Keyword::DELETE]) {
+                    on_delete = Some(self.parse_referential_action()?);
+                } else if on_update.is_none()
+                    && self.parse_keywords(&[Keyword::ON, Keyword::UPDATE])
+                {
+                    on_update = Some(self.parse_referential_action()?);
+                } else {
+                    break;
+                }
+            }
+            Ok(IndexOptions::References {table, column, match_type, on_delete, on_update})
         } else {
             self.expected(
                 "alter table for index options ",
@@ -1868,22 +3539,94 @@ impl Parser {
         Ok(SqlOption { name, value })
     }
 
+    /// Parse a character set or collation name, which unlike an expression
+    /// leaves a following `COLLATE` alone
+    fn parse_charset_name(&mut self) -> Result<Expr, ParserError> {
+        match self.next_token() {
+            Token::Word(w) => Ok(Expr::Identifier(w.to_ident())),
+            Token::SingleQuotedString(s) => Ok(Expr::Value(self.single_quoted_string(s))),
+            unexpected => self.expected("character set or collation name", unexpected),
+        }
+    }
+
+    /// The value of a `'...'` literal, which prints the way it was read
+    fn single_quoted_string(&self, value: String) -> Value {
+        Value::SingleQuotedString {
+            value,
+            backslash_escapes: self.backslash_escapes,
+        }
+    }
+
+    /// Parse a column of `ALTER TABLE ... ORDER BY`, `<column> [ASC | DESC]`
+    fn parse_alter_order_by_column(&mut self) -> Result<OrderByExpr, ParserError> {
+        let expr = Expr::Identifier(self.parse_identifier()?);
+        let asc = if self.parse_keyword(Keyword::ASC) {
+            Some(true)
+        } else if self.parse_keyword(Keyword::DESC) {
+            Some(false)
+        } else {
+            None
+        };
+        Ok(OrderByExpr {
+            expr,
+            asc,
+            nulls_first: None,
+        })
+    }
+
+    /// Parse `{ CHARACTER SET | CHARSET } [=] <charset> [ COLLATE [=] <collation> ]`
+    /// following `CONVERT TO` or `DEFAULT` in `ALTER TABLE`
+    fn parse_alter_charset(&mut self, convert: bool) -> Result<AlterTableOperation, ParserError> {
+        if !self.parse_keywords(&[Keyword::CHARACTER, Keyword::SET])
+            && !self.parse_keyword(Keyword::CHARSET)
+        {
+            return self.expected("CHARACTER SET", self.peek_token());
+        }
+        let _ = self.consume_token(&Token::Eq);
+        let charset = self.parse_identifier()?;
+        let collation = if self.parse_keyword(Keyword::COLLATE) {
+            let _ = self.consume_token(&Token::Eq);
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(AlterTableOperation::ConvertCharset {
+            charset,
+            collation,
+            convert,
+        })
+    }
+
     pub fn parse_alter(&mut self) -> Result<Statement, ParserError> {
         self.expect_keyword(Keyword::TABLE)?;
         let _ = self.parse_keyword(Keyword::ONLY);
         let table_name = self.parse_object_name()?;
         let mut tmp = vec![];
         loop {
-            if self.consume_token(&Token::EOF) || self.consume_token(&Token::SemiColon) {
+            if self.peek_statement_end() {
                 break
             }
             if self.consume_token(&Token::Comma){}
 
-            let operation = if self.parse_keyword(Keyword::ADD) ||
-                self.parse_keyword(Keyword::MODIFY){
-                match self.dialect_type{
-                    DBType::MySql=>{
-                        if !self.parse_keyword(Keyword::COLUMN){
+            let operation = if self.parse_keyword(Keyword::MODIFY) {
+                let _ = self.parse_keyword(Keyword::COLUMN);
+                let mut column_def = self.parse_column_def()?;
+                let first_or_after = match column_def.options.last().map(|o| &o.option) {
+                    Some(ColumnOption::First) | Some(ColumnOption::After(_)) => {
+                        column_def.options.pop().map(|o| o.option)
+                    }
+                    _ => None,
+                };
+                AlterTableOperation::ModifyColumn {
+                    column_def,
+                    first_or_after,
+                }
+            } else if self.parse_keyword(Keyword::ADD) {
+                match self.dialect.supports_index_definitions() {
+                    true => {
+                        // `COLUMN` may be left out before a column name,
+                        // while an index starts with a reserved word
+                        if !self.parse_keyword(Keyword::COLUMN) && !self.next_is_column_without_keyword() {
                             self.parse_alter_add_index()?
                         }else {
                             //let _ = self.parse_keyword(Keyword::COLUMN);
@@ -1895,7 +3638,7 @@ impl Parser {
                         if let Some(constraint) = self.parse_optional_table_constraint()? {
                             AlterTableOperation::AddConstraint(constraint)
                         } else {
-                            if !self.parse_keyword(Keyword::COLUMN){
+                            if !self.parse_keyword(Keyword::COLUMN) && !self.next_is_column_without_keyword() {
                                 self.parse_alter_add_index()?
                             }else {
                                 //let _ = self.parse_keyword(Keyword::COLUMN);
@@ -1918,9 +3661,14 @@ impl Parser {
                     new_column_def,
                 }
             } else if self.parse_keyword(Keyword::RENAME) {
-                if self.parse_keyword(Keyword::TO) {
-                    let table_name = self.parse_identifier()?;
+                if self.parse_keyword(Keyword::TO) || self.parse_keyword(Keyword::AS) {
+                    let table_name = self.parse_object_name()?;
                     AlterTableOperation::RenameTable { table_name }
+                } else if self.parse_keyword(Keyword::INDEX) || self.parse_keyword(Keyword::KEY) {
+                    let old_name = self.parse_identifier()?;
+                    self.expect_keyword(Keyword::TO)?;
+                    let new_name = self.parse_identifier()?;
+                    AlterTableOperation::RenameIndex { old_name, new_name }
                 } else {
                     let _ = self.parse_keyword(Keyword::COLUMN);
                     let old_column_name = self.parse_identifier()?;
@@ -1932,7 +3680,13 @@ impl Parser {
                     }
                 }
             } else if self.parse_keyword(Keyword::DROP) {
-                if !self.parse_keyword(Keyword::COLUMN){
+                if self.parse_keywords(&[Keyword::FOREIGN, Keyword::KEY]) {
+                    AlterTableOperation::DropForeignKey(self.parse_identifier()?)
+                } else if self.parse_keyword(Keyword::CONSTRAINT) {
+                    AlterTableOperation::DropConstraint(self.parse_identifier()?)
+                } else if self.parse_keyword(Keyword::CHECK) {
+                    AlterTableOperation::DropCheck(self.parse_identifier()?)
+                } else if !self.parse_keyword(Keyword::COLUMN) && !self.next_is_column_without_keyword() {
                     AlterTableOperation::DropIndex { index_def: self.parse_alter_drop_index()? }
                 }else {
                     //let _ = self.parse_keyword(Keyword::COLUMN);
@@ -1946,6 +3700,58 @@ impl Parser {
                     }
                 }
 
+            } else if self.parse_keywords(&[Keyword::CONVERT, Keyword::TO]) {
+                self.parse_alter_charset(true)?
+            } else if self.parse_keyword(Keyword::DEFAULT) {
+                self.parse_alter_charset(false)?
+            } else if let Token::Word(Word { keyword: Keyword::CHARACTER, .. })
+                | Token::Word(Word { keyword: Keyword::CHARSET, .. }) = self.peek_token()
+            {
+                self.parse_alter_charset(false)?
+            } else if self.parse_keywords(&[Keyword::ENABLE, Keyword::KEYS]) {
+                AlterTableOperation::EnableKeys
+            } else if self.parse_keywords(&[Keyword::DISABLE, Keyword::KEYS]) {
+                AlterTableOperation::DisableKeys
+            } else if self.parse_keyword(Keyword::FORCE) {
+                AlterTableOperation::Force
+            } else if self.parse_keywords(&[Keyword::ORDER, Keyword::BY]) {
+                let mut columns = vec![self.parse_alter_order_by_column()?];
+                // the column list ends where the next operation starts, which
+                // is with a reserved word such as `DROP`, as in MySQL
+                loop {
+                    match self.peek_nth_token(1) {
+                        Token::Word(w)
+                            if self.peek_token() == Token::Comma
+                                && !self.dialect.is_reserved_for_column_name(&w)
+                                && (w.quote_style.is_some() || !w.keyword.is_reserved()) =>
+                        {
+                            self.next_token();
+                            columns.push(self.parse_alter_order_by_column()?);
+                        }
+                        _ => break,
+                    }
+                }
+                AlterTableOperation::OrderBy(columns)
+            } else if self.parse_keyword(Keyword::ALGORITHM) {
+                let _ = self.consume_token(&Token::Eq);
+                AlterTableOperation::Algorithm(self.parse_identifier()?)
+            } else if self.parse_keyword(Keyword::LOCK) {
+                let _ = self.consume_token(&Token::Eq);
+                AlterTableOperation::Lock(self.parse_identifier()?)
+            } else if let Token::Word(_) = self.peek_token() {
+                let mut options = vec![];
+                loop {
+                    match self.peek_token() {
+                        Token::EOF | Token::SemiColon | Token::Comma => break,
+                        // not table options, though they may follow them
+                        // without a comma
+                        Token::Word(w) if w.keyword == Keyword::ALGORITHM || w.keyword == Keyword::LOCK => {
+                            break
+                        }
+                        _ => options.push(self.parse_table_option_def()?),
+                    }
+                }
+                AlterTableOperation::SetTableOptions(options)
             } else {
                 return self.expected("ADD, RENAME, or DROP after ALTER TABLE", self.peek_token());
             };
@@ -1976,77 +3782,78 @@ impl Parser {
     /// Parse a tab separated values in
     /// COPY payload
     fn parse_tsv(&mut self) -> Result<Vec<Option<String>>, ParserError> {
-        let values = self.parse_tab_value()?;
+        // the tokenizer reads all of the data as one token
+        let values = match self.peek_token_ref() {
+            Token::CopyData(data) => Self::parse_tab_value(data),
+            _ => return Ok(vec![]),
+        };
+        self.next_token();
         Ok(values)
     }
 
-    fn parse_tab_value(&mut self) -> Result<Vec<Option<String>>, ParserError> {
+    fn parse_tab_value(data: &str) -> Vec<Option<String>> {
         let mut values = vec![];
         let mut content = String::from("");
-        while let Some(t) = self.next_token_no_skip() {
-            match t {
-                Token::Whitespace(Whitespace::Tab) => {
-                    values.push(Some(content.to_string()));
-                    content.clear();
-                }
-                Token::Whitespace(Whitespace::Newline) => {
-                    values.push(Some(content.to_string()));
+        let mut null = false;
+        let mut chars = data.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\t' | '\n' => {
+                    values.push(if null { None } else { Some(content.to_string()) });
                     content.clear();
+                    null = false;
                 }
-                Token::Backslash => {
-                    if self.consume_token(&Token::Period) {
-                        return Ok(values);
-                    }
-                    if let Token::Word(w) = self.next_token() {
-                        if w.value == "N" {
-                            values.push(None);
-                        }
-                    }
-                }
-                _ => {
-                    content.push_str(&t.to_string());
-                }
+                '\\' => match chars.next() {
+                    Some('.') | None => break,
+                    Some('N') => null = true,
+                    Some(ch) => content.push(ch),
+                },
+                _ => content.push(ch),
             }
         }
-        Ok(values)
+        values
     }
 
     /// Parse a literal value (numbers, strings, date/time, booleans)
     fn parse_value(&mut self) -> Result<Value, ParserError> {
-        match self.next_token() {
+        let token = self.next_token();
+        self.value_from_token(token)
+    }
+
+    /// The value of a literal token that was just consumed
+    fn value_from_token(&self, token: Token) -> Result<Value, ParserError> {
+        match token {
             Token::Word(w) => match w.keyword {
                 Keyword::TRUE => Ok(Value::Boolean(true)),
                 Keyword::FALSE => Ok(Value::Boolean(false)),
                 Keyword::NULL => Ok(Value::Null),
                 _ => self.expected("a concrete value", Token::Word(w)),
             },
-            // The call to n.parse() returns a bigdecimal when the
-            // bigdecimal feature is enabled, and is otherwise a no-op
-            // (i.e., it returns the input string).
-            Token::Number(ref n) => match n.parse() {
-                Ok(n) => Ok(Value::Number(n)),
-                Err(e) => parser_err!(format!("Could not parse '{}' as number: {}", n, e)),
+            // A number is kept as the string of its digits, unless the
+            // bigdecimal feature is enabled
+            #[cfg(not(feature = "bigdecimal"))]
+            Token::Number(n) => Ok(Value::Number(n)),
+            #[cfg(feature = "bigdecimal")]
+            Token::Number(n) => match n.parse() {
+                Ok(number) => Ok(Value::Number(number)),
+                Err(e) => {
+                    let message = format!("Could not parse '{}' as number: {}", n, e);
+                    parser_err!(self, SyntaxErrorKind::NumberParse { text: n }, message)
+                }
             },
-            Token::SingleQuotedString(ref s) => Ok(Value::SingleQuotedString(s.to_string())),
-            Token::NationalStringLiteral(ref s) => Ok(Value::NationalStringLiteral(s.to_string())),
-            Token::HexStringLiteral(ref s) => Ok(Value::HexStringLiteral(s.to_string())),
-            Token::VariableString(ref v) => Ok(Value::VariableName(v.to_string())),
-            Token::Char(ref c) => Ok(Value::Char(*c)),
+            Token::SingleQuotedString(s) => Ok(self.single_quoted_string(s)),
+            Token::DoubleQuotedString(s) => Ok(Value::DoubleQuotedString(s)),
+            Token::NationalStringLiteral(s) => Ok(Value::NationalStringLiteral(s)),
+            Token::HexStringLiteral(s) => Ok(Value::HexStringLiteral(s)),
+            Token::VariableString(v) => Ok(Value::VariableName(v)),
+            Token::Placeholder(s) => Ok(Value::Placeholder(s)),
             unexpected => self.expected("a value", unexpected),
         }
     }
 
     pub fn parse_number_value(&mut self) -> Result<Value, ParserError> {
         match self.parse_value()? {
-            v @ Value::Number(_) => Ok(v),
-            v @ Value::Char(_) => {
-                if v == Value::Char('?'){
-                    Ok(v)
-                }else {
-                    self.prev_token();
-                    self.expected("literal number", self.peek_token())
-                }
-            }
+            v @ Value::Number(_) | v @ Value::Placeholder(_) => Ok(v),
             _ => {
                 self.prev_token();
                 self.expected("literal number", self.peek_token())
@@ -2054,12 +3861,18 @@ impl Parser {
         }
     }
 
-    /// Parse an unsigned literal integer/long
+    /// Parse an unsigned literal integer/long. This is for sizes and counts
+    /// in DDL, such as `VARCHAR(10)`, which can't be placeholders in MySQL;
+    /// values that can, such as `LIMIT ?`, go through `parse_number_value`.
     pub fn parse_literal_uint(&mut self) -> Result<u64, ParserError> {
         match self.next_token() {
-            Token::Number(s) => s.parse::<u64>().map_err(|e| {
-                ParserError::ParserError(format!("Could not parse '{}' as u64: {}", s, e))
-            }),
+            Token::Number(s) => match s.parse::<u64>() {
+                Ok(n) => Ok(n),
+                Err(e) => {
+                    let message = format!("Could not parse '{}' as u64: {}", s, e);
+                    parser_err!(self, SyntaxErrorKind::NumberParse { text: s }, message)
+                }
+            },
             unexpected => self.expected("literal int", unexpected),
         }
     }
@@ -2067,7 +3880,7 @@ impl Parser {
     /// Parse a literal string
     pub fn parse_literal_string(&mut self) -> Result<String, ParserError> {
         match self.next_token() {
-            Token::SingleQuotedString(s) => Ok(s),
+            Token::SingleQuotedString(s) | Token::DoubleQuotedString(s) => Ok(s),
             unexpected => self.expected("literal string", unexpected),
         }
     }
@@ -2106,11 +3919,25 @@ impl Parser {
                 Keyword::UUID => Ok(DataType::Uuid),
                 Keyword::DATE => Ok(DataType::Date),
                 Keyword::TIMESTAMP => {
+                    let precision = self.parse_optional_precision()?;
                     // TBD: we throw away "with/without timezone" information
                     if self.parse_keyword(Keyword::WITH) || self.parse_keyword(Keyword::WITHOUT) {
                         self.expect_keywords(&[Keyword::TIME, Keyword::ZONE])?;
                     }
-                    Ok(DataType::Timestamp)
+                    Ok(DataType::Timestamp(precision))
+                }
+                Keyword::DATETIME => Ok(DataType::Datetime(self.parse_optional_precision()?)),
+                // without a length, as in `CAST(a AS BINARY)`, `BINARY` is
+                // left a custom type as before
+                Keyword::BINARY | Keyword::VARBINARY if self.peek_token_ref() == &Token::LParen => {
+                    self.expect_token(&Token::LParen)?;
+                    let length = self.parse_literal_uint()?;
+                    self.expect_token(&Token::RParen)?;
+                    if w.keyword == Keyword::BINARY {
+                        Ok(DataType::Binary(length))
+                    } else {
+                        Ok(DataType::Varbinary(length))
+                    }
                 }
                 Keyword::TIME => {
                     // TBD: we throw away "with/without timezone" information
@@ -2154,17 +3981,25 @@ impl Parser {
     pub fn parse_optional_alias(
         &mut self,
         reserved_kwds: &[Keyword],
+    ) -> Result<Option<Ident>, ParserError> {
+        self.parse_optional_alias_unless(&|w| reserved_kwds.contains(&w.keyword))
+    }
+
+    /// Like `parse_optional_alias`, with the words that can't be an alias
+    /// without `AS` decided by `is_reserved`
+    fn parse_optional_alias_unless(
+        &mut self,
+        is_reserved: &dyn Fn(&Word) -> bool,
     ) -> Result<Option<Ident>, ParserError> {
         let after_as = self.parse_keyword(Keyword::AS);
-        match self.next_token() {
+        let start = self.index;
+        let alias = match self.next_token() {
             // Accept any identifier after `AS` (though many dialects have restrictions on
             // keywords that may appear here). If there's no `AS`: don't parse keywords,
             // which may start a construct allowed in this position, to be parsed as aliases.
             // (For example, in `FROM t1 JOIN` the `JOIN` will always be parsed as a keyword,
             // not an alias.)
-            Token::Word(w) if after_as || !reserved_kwds.contains(&w.keyword) => {
-                Ok(Some(w.to_ident()))
-            }
+            Token::Word(w) if after_as || !is_reserved(&w) => w.to_ident(),
             // MSSQL supports single-quoted strings as aliases for columns
             // We accept them as table aliases too, although MSSQL does not.
             //
@@ -2177,15 +4012,18 @@ impl Parser {
             //    character. When it sees such a <literal>, your DBMS will
             //    ignore the <separator> and treat the multiple strings as
             //    a single <literal>."
-            Token::SingleQuotedString(s) => Ok(Some(Ident::with_quote('\'', s))),
+            Token::SingleQuotedString(s) => Ident::with_quote('\'', s),
+            Token::DoubleQuotedString(s) => Ident::with_quote('"', s),
             not_an_ident => {
                 if after_as {
                     return self.expected("an identifier after AS", not_an_ident);
                 }
                 self.prev_token();
-                Ok(None) // no alias found
+                return Ok(None); // no alias found
             }
-        }
+        };
+        self.record_span(start, &alias, |nodes| &mut nodes.idents);
+        Ok(Some(alias))
     }
 
     /// Parse `AS identifier` when the AS is describing a table-valued object,
@@ -2196,7 +4034,21 @@ impl Parser {
         &mut self,
         reserved_kwds: &[Keyword],
     ) -> Result<Option<TableAlias>, ParserError> {
-        match self.parse_optional_alias(reserved_kwds)? {
+        self.parse_optional_table_alias_unless(&|w| reserved_kwds.contains(&w.keyword))
+    }
+
+    /// Parse the alias of a table in the `FROM` clause, leaving the words
+    /// the dialect reserves for what can follow it
+    fn parse_optional_relation_alias(&mut self) -> Result<Option<TableAlias>, ParserError> {
+        let dialect = self.dialect;
+        self.parse_optional_table_alias_unless(&|w| dialect.is_reserved_for_table_alias(w))
+    }
+
+    fn parse_optional_table_alias_unless(
+        &mut self,
+        is_reserved: &dyn Fn(&Word) -> bool,
+    ) -> Result<Option<TableAlias>, ParserError> {
+        match self.parse_optional_alias_unless(is_reserved)? {
             Some(name) => {
                 let columns = self.parse_parenthesized_column_list(Optional)?;
                 Ok(Some(TableAlias { name, columns }))
@@ -2208,6 +4060,7 @@ impl Parser {
     /// Parse a possibly qualified, possibly quoted identifier, e.g.
     /// `foo` or `myschema."table"
     pub fn parse_object_name(&mut self) -> Result<ObjectName, ParserError> {
+        let start = self.index;
         let mut idents = vec![];
         loop {
             idents.push(self.parse_identifier()?);
@@ -2215,16 +4068,21 @@ impl Parser {
                 break;
             }
         }
-        Ok(ObjectName(idents))
+        let name = ObjectName(idents);
+        self.record_span(start, &name, |nodes| &mut nodes.object_names);
+        Ok(name)
     }
 
     /// Parse a simple one-word identifier (possibly quoted, possibly a keyword)
     pub fn parse_identifier(&mut self) -> Result<Ident, ParserError> {
-        match self.next_token() {
-            Token::Word(w) => Ok(w.to_ident()),
-            Token::VariableString(v) => Ok(Ident{ value: v, quote_style: None }),
-            unexpected => self.expected("identifier", unexpected),
-        }
+        let start = self.index;
+        let ident = match self.next_token() {
+            Token::Word(w) => w.to_ident(),
+            Token::VariableString(v) => Ident{ value: v, quote_style: None },
+            unexpected => return self.expected("identifier", unexpected),
+        };
+        self.record_span(start, &ident, |nodes| &mut nodes.idents);
+        Ok(ident)
     }
 
     /// Parse a parenthesized comma-separated list of unqualified, possibly quoted identifiers
@@ -2271,6 +4129,8 @@ impl Parser {
     }
 
     pub fn parse_delete(&mut self) -> Result<Statement, ParserError> {
+        let hints = self.parse_optimizer_hints();
+        let comments = self.parse_statement_comments();
         self.expect_keyword(Keyword::FROM)?;
         let table_name = self.parse_object_name()?;
         let selection = if self.parse_keyword(Keyword::WHERE) {
@@ -2280,6 +4140,8 @@ impl Parser {
         };
 
         Ok(Statement::Delete {
+            hints,
+            comments,
             table_name,
             selection,
         })
@@ -2290,6 +4152,15 @@ impl Parser {
     /// by `ORDER BY`. Unlike some other parse_... methods, this one doesn't
     /// expect the initial keyword to be already consumed
     pub fn parse_query(&mut self) -> Result<Query, ParserError> {
+        let depth = self.depth;
+        let query = self.parse_nested_query();
+        self.depth = depth;
+        query
+    }
+
+    /// Parse a query one level deeper than the caller
+    fn parse_nested_query(&mut self) -> Result<Query, ParserError> {
+        self.deepen(CHAINED_OPERATORS_PER_LEVEL)?;
         let ctes = if self.parse_keyword(Keyword::WITH) {
             // TODO: optional RECURSIVE
             self.parse_comma_separated(Parser::parse_cte)?
@@ -2324,6 +4195,14 @@ impl Parser {
 
 
         let fetch = if self.parse_keyword(Keyword::FETCH) {
+            if !self.dialect.supports_fetch() {
+                self.prev_token();
+                return self.error_at(
+                    SyntaxErrorKind::Unsupported { feature: "FETCH".to_string() },
+                    "FETCH is not supported, use LIMIT instead".to_string(),
+                    &self.peek_token(),
+                );
+            }
             Some(self.parse_fetch()?)
         } else {
             None
@@ -2396,6 +4275,8 @@ impl Parser {
             if precedence >= next_precedence {
                 break;
             }
+            // the query so far becomes an operand, one level deeper
+            self.deepen(1)?;
             self.next_token(); // skip past the set operator
             expr = SetExpr::SetOperation {
                 left: Box::new(expr),
@@ -2408,23 +4289,15 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Parse `ON DUPLICATE KEY UPDATE`, returning whether it was there. Any
+    /// other `ON ...` after `INSERT` is an error.
     fn parse_on_duplicate_key_update(&mut self) -> Result<bool, ParserError> {
-        if self.parse_keyword(Keyword::ON){
-            if self.parse_keyword(Keyword::DUPLICATE){
-                if self.parse_keyword(Keyword::KEY){
-                    if self.parse_keyword(Keyword::UPDATE){
-                        return Ok(true);
-                    }else {
-                        self.expected("on duplicate key update error", self.peek_token())
-                    }
-                }else {
-                    self.expected("on duplicate key update error", self.peek_token())
-                }
-            }else {
-                self.expected("on duplicate key update error", self.peek_token())
-            }
-        }else {
-            return Ok(false)
+        if self.parse_keywords(&[Keyword::ON, Keyword::DUPLICATE, Keyword::KEY, Keyword::UPDATE]) {
+            Ok(true)
+        } else if self.parse_keyword(Keyword::ON) {
+            self.expected("DUPLICATE KEY UPDATE after ON", self.peek_token())
+        } else {
+            Ok(false)
         }
     }
 
@@ -2440,12 +4313,30 @@ impl Parser {
     /// Parse a restricted `SELECT` statement (no CTEs / `UNION` / `ORDER BY`),
     /// assuming the initial `SELECT` was already consumed
     pub fn parse_select(&mut self) -> Result<Select, ParserError> {
-        let comment = self.parse_comment_for_select()?;
-        let distinct = self.parse_all_or_distinct()?;
-
-        let top = if self.parse_keyword(Keyword::TOP) {
-            Some(self.parse_top()?)
+        let hints = self.parse_optimizer_hints();
+        let comments = self.parse_statement_comments();
+        let distinct = self.parse_select_distinct()?;
+        let modifiers = self.parse_select_modifiers();
+
+        let top = if self.dialect.supports_top() {
+            if self.parse_keyword(Keyword::TOP) {
+                Some(self.parse_top()?)
+            } else {
+                None
+            }
         } else {
+            // `top` is a column name unless a row count follows it
+            if let Token::Word(w) = self.peek_token_ref() {
+                if w.keyword == Keyword::TOP
+                    && matches!(self.peek_nth_token_ref(1), Token::Number(..) | Token::LParen)
+                {
+                    return self.error_at(
+                        SyntaxErrorKind::Unsupported { feature: "TOP".to_string() },
+                        "TOP is not supported, use LIMIT instead".to_string(),
+                        &self.peek_token(),
+                    );
+                }
+            }
             None
         };
 
@@ -2453,8 +4344,8 @@ impl Parser {
         let projection = self.parse_comma_separated(Parser::parse_select_item)?;
         // println!("aaa");
         // Note that for keywords to be properly handled here, they need to be
-        // added to `RESERVED_FOR_COLUMN_ALIAS` / `RESERVED_FOR_TABLE_ALIAS`,
-        // otherwise they may be parsed as an alias as part of the `projection`
+        // reserved by `Dialect::is_reserved_for_column_alias` /
+        // `is_reserved_for_table_alias`, otherwise they may be parsed as an alias as part of the `projection`
         // or `from`.
 
         let from = if self.parse_keyword(Keyword::FROM) {
@@ -2470,7 +4361,7 @@ impl Parser {
         };
 
         let group_by = if self.parse_keywords(&[Keyword::GROUP, Keyword::BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
+            self.parse_comma_separated(Parser::parse_position_or_expr)?
         } else {
             vec![]
         };
@@ -2482,10 +4373,13 @@ impl Parser {
         };
 
         Ok(Select {
-            comment,
+            hints,
+            comments,
             distinct,
+            modifiers,
             top,
             projection,
+            wildcard_except: vec![],
             from,
             selection,
             group_by,
@@ -2505,14 +4399,40 @@ impl Parser {
 
     }
 
-    fn parse_comment_for_select(&mut self) -> Result<Option<Ident>, ParserError>{
-        match self.next_token_no_ignore_comment(){
-            Token::Whitespace(Whitespace::MultiLineComment(v)) => {
-                Ok(Some(Ident{ value: v.clone().replace(" ",""), quote_style: None }))
+    /// Parse MySQL optimizer hints `/*+ ... */` directly following the
+    /// SELECT/INSERT/UPDATE/DELETE keyword, keeping the text as written
+    fn parse_optimizer_hints(&mut self) -> Option<String> {
+        let mut index = self.index;
+        loop {
+            match self.tokens.get(index) {
+                Some(Token::Whitespace(Whitespace::MultiLineComment(s))) if s.starts_with('+') => {
+                    self.index = index + 1;
+                    return Some(s[1..].to_string());
+                }
+                Some(Token::Whitespace(Whitespace::Space))
+                | Some(Token::Whitespace(Whitespace::Newline))
+                | Some(Token::Whitespace(Whitespace::Tab)) => index += 1,
+                _ => return None,
             }
-            _ => {
-                self.prev_token();
-                Ok(None)
+        }
+    }
+
+    /// Parse the `/* ... */` comments directly following the statement
+    /// keyword and optimizer hints, keeping their text as written
+    fn parse_statement_comments(&mut self) -> Vec<String> {
+        let mut comments = vec![];
+        let mut index = self.index;
+        loop {
+            match self.tokens.get(index) {
+                Some(Token::Whitespace(Whitespace::MultiLineComment(s))) => {
+                    comments.push(s.clone());
+                    index += 1;
+                    self.index = index;
+                }
+                Some(Token::Whitespace(Whitespace::Space))
+                | Some(Token::Whitespace(Whitespace::Newline))
+                | Some(Token::Whitespace(Whitespace::Tab)) => index += 1,
+                _ => return comments,
             }
         }
     }
@@ -2523,7 +4443,7 @@ impl Parser {
                 Keyword::CONFIG => {
                     self.prev_token();
                     let variable = self.parse_identifier()?;
-                    if self.consume_token(&Token::EOF) {
+                    if self.peek_statement_end() {
                         Ok(Statement::ReLoad {
                             variable,
                             selection: None
@@ -2535,7 +4455,7 @@ impl Parser {
                 Keyword::USER => {
                     self.prev_token();
                     let variable = self.parse_identifier()?;
-                    if !self.consume_token(&Token::EOF) {
+                    if !self.peek_statement_end() {
                         let selection = if self.parse_keyword(Keyword::WHERE) {
                             Some(self.parse_expr()?)
                         } else {
@@ -2569,7 +4489,7 @@ impl Parser {
         let variable = self.parse_identifier()?;
         if self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::TO) {
             let value = self.parse_set_variables_value()?;
-            if !self.consume_token(&Token::EOF){
+            if !self.peek_statement_end() {
                 let selection = if self.parse_keyword(Keyword::WHERE) {
                     Some(self.parse_expr()?)
                 } else {
@@ -2587,11 +4507,11 @@ impl Parser {
                     value,
                 })
             }
-        } else if variable.value == "TRANSACTION" && modifier.is_none() {
+        } else if variable.eq_ignore_case("TRANSACTION") && modifier.is_none() {
             Ok(Statement::SetTransaction {
                 modes: self.parse_transaction_modes()?,
             })
-        } else if variable.value.to_lowercase() == "names" && modifier.is_none() {
+        } else if variable.eq_ignore_case("NAMES") && modifier.is_none() {
             Ok(Statement::SetVariable {
                 local: modifier == Some(Keyword::LOCAL),
                 variable,
@@ -2624,6 +4544,8 @@ impl Parser {
         {
             self.prev_token();
             self.parse_show_columns()
+        } else if let Some(kind) = self.parse_show_object_kind() {
+            self.parse_show_objects(kind)
         } else if self.parse_keyword(Keyword::CREATE) {
             if self.parse_keyword(Keyword::TABLE){
                 let table_name = self.parse_object_name()?;
@@ -2639,16 +4561,12 @@ impl Parser {
                 false
             };
             let variable = self.parse_identifier()?;
-            let selection = if self.parse_keyword(Keyword::WHERE) {
-                Some(self.parse_expr()?)
-            } else {
-                None
-            };
+            let filter = self.parse_show_statement_filter()?;
 
             Ok(Statement::ShowVariable {
                 variable,
                 global,
-                selection
+                filter
             })
         }
     }
@@ -2656,17 +4574,61 @@ impl Parser {
     fn parse_show_columns(&mut self) -> Result<Statement, ParserError> {
         let extended = self.parse_keyword(Keyword::EXTENDED);
         let full = self.parse_keyword(Keyword::FULL);
-        self.expect_one_of_keywords(&[Keyword::COLUMNS, Keyword::FIELDS])?;
-        self.expect_one_of_keywords(&[Keyword::FROM, Keyword::IN])?;
+        let fields = self.expect_one_of_keywords(&[Keyword::COLUMNS, Keyword::FIELDS])?
+            == Keyword::FIELDS;
+        let table_in = self.expect_one_of_keywords(&[Keyword::FROM, Keyword::IN])? == Keyword::IN;
         let table_name = self.parse_object_name()?;
-        // MySQL also supports FROM <database> here. In other words, MySQL
-        // allows both FROM <table> FROM <database> and FROM <database>.<table>,
-        // while we only support the latter for now.
+        // MySQL allows both FROM <table> FROM <database> and
+        // FROM <database>.<table>
+        let database_keyword = self.parse_one_of_keywords(&[Keyword::FROM, Keyword::IN]);
+        let database = match database_keyword {
+            Some(_) => Some(self.parse_identifier()?),
+            None => None,
+        };
+        let database_in = database_keyword == Some(Keyword::IN);
         let filter = self.parse_show_statement_filter()?;
         Ok(Statement::ShowColumns {
             extended,
             full,
+            fields,
+            table_in,
             table_name,
+            database,
+            database_in,
+            filter,
+        })
+    }
+
+    fn parse_show_object_kind(&mut self) -> Option<ShowObjectKind> {
+        if self.parse_keywords(&[Keyword::OPEN, Keyword::TABLES]) {
+            Some(ShowObjectKind::OpenTables)
+        } else if self.parse_keyword(Keyword::TRIGGERS) {
+            Some(ShowObjectKind::Triggers)
+        } else if self.parse_keyword(Keyword::EVENTS) {
+            Some(ShowObjectKind::Events)
+        } else if self.parse_keywords(&[Keyword::PROCEDURE, Keyword::STATUS]) {
+            Some(ShowObjectKind::ProcedureStatus)
+        } else if self.parse_keywords(&[Keyword::FUNCTION, Keyword::STATUS]) {
+            Some(ShowObjectKind::FunctionStatus)
+        } else {
+            None
+        }
+    }
+
+    fn parse_show_objects(&mut self, kind: ShowObjectKind) -> Result<Statement, ParserError> {
+        let database = if kind.has_database()
+            && self
+                .parse_one_of_keywords(&[Keyword::FROM, Keyword::IN])
+                .is_some()
+        {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        let filter = self.parse_show_statement_filter()?;
+        Ok(Statement::ShowObjects {
+            kind,
+            database,
             filter,
         })
     }
@@ -2687,8 +4649,8 @@ impl Parser {
         let relation = self.parse_table_factor()?;
 
         // Note that for keywords to be properly handled here, they need to be
-        // added to `RESERVED_FOR_TABLE_ALIAS`, otherwise they may be parsed as
-        // a table alias.
+        // reserved by `Dialect::is_reserved_for_table_alias`, otherwise they
+        // may be parsed as a table alias.
         let mut joins = vec![];
         loop {
             // if self.parse_keyword(Keyword::FORCE){
@@ -2762,6 +4724,16 @@ impl Parser {
 
     /// A table name or a parenthesized subquery, followed by optional `[AS] alias`
     pub fn parse_table_factor(&mut self) -> Result<TableFactor, ParserError> {
+        let depth = self.depth;
+        let table_factor = self.parse_nested_table_factor();
+        self.depth = depth;
+        table_factor
+    }
+
+    /// Parse a table factor one level deeper than the caller, as it may be
+    /// a nested join
+    fn parse_nested_table_factor(&mut self) -> Result<TableFactor, ParserError> {
+        self.deepen(CHAINED_OPERATORS_PER_LEVEL)?;
         if self.parse_keyword(Keyword::LATERAL) {
             // LATERAL must always be followed by a subquery.
             if !self.consume_token(&Token::LParen) {
@@ -2792,21 +4764,45 @@ impl Parser {
             // `parse_derived_table_factor` below will return success after parsing the
             // subquery, followed by the closing ')', and the alias of the derived table.
             // In the example above this is case (3).
-            return_ok_if_some!(
-                self.maybe_parse(|parser| parser.parse_derived_table_factor(NotLateral))
-            );
+            let start = self.index;
+            let checkpoint = self.spans_checkpoint();
+            self.rewindable += 1;
+            let derived = self.parse_derived_table_factor(NotLateral);
+            self.rewindable -= 1;
+            let derived_err = match derived {
+                Ok(derived) => return Ok(derived),
+                Err(err) => err,
+            };
             // A parsing error from `parse_derived_table_factor` indicates that the '(' we've
-            // recently consumed does not start a derived table (cases 1, 2, or 4).
-            // `maybe_parse` will ignore such an error and rewind to be after the opening '('.
+            // recently consumed does not start a derived table (cases 1, 2, or 4), so we
+            // rewind to be after the opening '('. The error is kept in case it's a broken
+            // subquery rather than a nested join: whichever attempt got further is the one
+            // the user meant, and its error points at the real failing token.
+            let derived_failed_at = self.index;
+            self.index = start;
+            self.rollback_spans(checkpoint);
 
             // Inside the parentheses we expect to find a table factor
             // followed by some joins or another level of nesting.
-            let table_and_joins = self.parse_table_and_joins()?;
-            self.expect_token(&Token::RParen)?;
+            let table_and_joins = self
+                .parse_table_and_joins()
+                .and_then(|table_and_joins| {
+                    self.expect_token(&Token::RParen)?;
+                    Ok(table_and_joins)
+                })
+                .map_err(|err| {
+                    if derived_failed_at > self.index {
+                        derived_err
+                    } else {
+                        err
+                    }
+                })?;
             // The SQL spec prohibits derived and bare tables from appearing
             // alone in parentheses. We don't enforce this as some databases
             // (e.g. Snowflake) allow such syntax.
             Ok(TableFactor::NestedJoin(Box::new(table_and_joins)))
+        } else if self.parse_keyword(Keyword::DUAL) {
+            Ok(TableFactor::Dual)
         } else {
             let name = self.parse_object_name()?;
             // Postgres, MSSQL: table-valued functions:
@@ -2820,7 +4816,7 @@ impl Parser {
             if self.parse_keyword(Keyword::FORCE){
                 force = Some(self.parse_force_for_select()?);
             }
-            let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+            let alias = self.parse_optional_relation_alias()?;
 
             // mysql force index agin
             if self.parse_keyword(Keyword::FORCE){
@@ -2853,7 +4849,7 @@ impl Parser {
     ) -> Result<TableFactor, ParserError> {
         let subquery = Box::new(self.parse_query()?);
         self.expect_token(&Token::RParen)?;
-        let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+        let alias = self.parse_optional_relation_alias()?;
         Ok(TableFactor::Derived {
             lateral: match lateral {
                 Lateral => true,
@@ -2878,8 +4874,36 @@ impl Parser {
         }
     }
 
+    /// Parse a `REPLACE` statement, which is written like `INSERT` without
+    /// `IGNORE` and `ON DUPLICATE KEY UPDATE`
+    pub fn parse_replace(&mut self) -> Result<Statement, ParserError> {
+        let hints = self.parse_optimizer_hints();
+        let comments = self.parse_statement_comments();
+        let priority = if self.parse_keyword(Keyword::LOW_PRIORITY) {
+            Some(Priority::LOW_PRIORITY)
+        } else if self.parse_keyword(Keyword::DELAYED) {
+            Some(Priority::DELAYED)
+        } else {
+            None
+        };
+        let (into, table_name) = self.parse_insert_table()?;
+        let (columns, set, source) = self.parse_insert_source()?;
+        Ok(Statement::Replace {
+            hints,
+            comments,
+            priority,
+            into,
+            table_name,
+            columns,
+            set,
+            source,
+        })
+    }
+
     /// Parse an INSERT statement
     pub fn parse_insert(&mut self) -> Result<Statement, ParserError> {
+        let hints = self.parse_optimizer_hints();
+        let comments = self.parse_statement_comments();
         let mut priority = None;
         let mut ignore = false;
         if self.parse_keyword(Keyword::LOW_PRIORITY){
@@ -2894,26 +4918,73 @@ impl Parser {
             ignore = true;
         }
 
-        if let Err(e) = self.expect_keyword(Keyword::INTO){}
-        let table_name = self.parse_object_name()?;
-        let columns = self.parse_parenthesized_column_list(Optional)?;
-        let source = Box::new(self.parse_query()?);
+        let (into, table_name) = self.parse_insert_table()?;
+        let (columns, set, source) = self.parse_insert_source()?;
         let update = if self.parse_on_duplicate_key_update()? {
             Some(self.parse_comma_separated(Parser::parse_assignment)?)
         }else {
             None
         };
         Ok(Statement::Insert {
+            hints,
+            comments,
             priority,
             ignore,
+            into,
             table_name,
             columns,
+            set,
             source,
             update
         })
     }
 
+    /// Parse `[INTO] <table>` of `INSERT` or `REPLACE`, along with whether
+    /// `INTO` was written
+    fn parse_insert_table(&mut self) -> Result<(bool, ObjectName), ParserError> {
+        let into = self.parse_keyword(Keyword::INTO);
+        // with `INTO` left out, make sure a table name follows rather than
+        // something like `INSERT SELECT ...`
+        match self.peek_token() {
+            Token::Word(w) if w.quote_style.is_some() || !w.keyword.is_reserved() => {}
+            unexpected => return self.expected("a table name", unexpected),
+        }
+        Ok((into, self.parse_object_name()?))
+    }
+
+    /// Parse what `INSERT` or `REPLACE` writes to the table, either
+    /// `[(<columns>)] <query>` or `SET <column> = <value>, ...`. The latter is
+    /// kept as the columns and one `VALUES` row, along with `true`.
+    fn parse_insert_source(&mut self) -> Result<(Vec<Ident>, bool, Box<Query>), ParserError> {
+        if !self.parse_keyword(Keyword::SET) {
+            let columns = self.parse_parenthesized_column_list(Optional)?;
+            return Ok((columns, false, Box::new(self.parse_query()?)));
+        }
+        let mut columns = vec![];
+        let mut row = vec![];
+        loop {
+            columns.push(self.parse_identifier()?);
+            self.expect_token(&Token::Eq)?;
+            row.push(self.parse_expr()?);
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        let source = Query {
+            ctes: vec![],
+            body: SetExpr::Values(Values(vec![row])),
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            update: false,
+            fetch: None,
+        };
+        Ok((columns, true, Box::new(source)))
+    }
+
     pub fn parse_update(&mut self) -> Result<Statement, ParserError> {
+        let hints = self.parse_optimizer_hints();
+        let comments = self.parse_statement_comments();
         let table_name = self.parse_object_name()?;
         self.expect_keyword(Keyword::SET)?;
         let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
@@ -2930,6 +5001,8 @@ impl Parser {
         };
 
         Ok(Statement::Update {
+            hints,
+            comments,
             table_name,
             assignments,
             selection,
@@ -2939,7 +5012,10 @@ impl Parser {
 
     /// Parse a `var = expr` assignment, used in an UPDATE statement
     pub fn parse_assignment(&mut self) -> Result<Assignment, ParserError> {
-        let id = self.parse_identifier()?;
+        let mut id = vec![self.parse_identifier()?];
+        while self.consume_token(&Token::Period) {
+            id.push(self.parse_identifier()?);
+        }
         self.expect_token(&Token::Eq)?;
         let value = self.parse_expr()?;
         Ok(Assignment { id, value })
@@ -2957,24 +5033,30 @@ impl Parser {
 
     /// Parse a comma-delimited list of projections after SELECT
     pub fn parse_select_item(&mut self) -> Result<SelectItem, ParserError> {
+        let start = self.index;
         let expr = self.parse_expr()?;
-        if let Expr::Wildcard = expr {
-            Ok(SelectItem::Wildcard)
+        let item = if let Expr::Wildcard = expr {
+            SelectItem::Wildcard
         } else if let Expr::QualifiedWildcard(prefix) = expr {
-            Ok(SelectItem::QualifiedWildcard(ObjectName(prefix)))
+            SelectItem::QualifiedWildcard(ObjectName(prefix))
         } else {
             // `expr` is a regular SQL expression and can be followed by an alias
-            if let Some(alias) = self.parse_optional_alias(keywords::RESERVED_FOR_COLUMN_ALIAS)? {
-                Ok(SelectItem::ExprWithAlias { expr, alias })
+            let dialect = self.dialect;
+            let alias =
+                self.parse_optional_alias_unless(&|w| dialect.is_reserved_for_column_alias(w))?;
+            if let Some(alias) = alias {
+                SelectItem::ExprWithAlias { expr, alias }
             } else {
-                Ok(SelectItem::UnnamedExpr(expr))
+                SelectItem::UnnamedExpr(expr)
             }
-        }
+        };
+        self.record_span(start, &item, |nodes| &mut nodes.select_items);
+        Ok(item)
     }
 
     /// Parse an expression, optionally followed by ASC or DESC (used in ORDER BY)
     pub fn parse_order_by_expr(&mut self) -> Result<OrderByExpr, ParserError> {
-        let expr = self.parse_expr()?;
+        let expr = self.parse_position_or_expr()?;
 
         let asc = if self.parse_keyword(Keyword::ASC) {
             Some(true)
@@ -2984,6 +5066,18 @@ impl Parser {
             None
         };
 
+        if !self.dialect.supports_nulls_ordering() {
+            if let Token::Word(w) = self.peek_token_ref() {
+                if w.keyword == Keyword::NULLS {
+                    return self.error_at(
+                        SyntaxErrorKind::Unsupported { feature: "NULLS FIRST/LAST".to_string() },
+                        "NULLS FIRST and NULLS LAST are not supported, order by `<expr> IS NULL` instead"
+                            .to_string(),
+                        &self.peek_token(),
+                    );
+                }
+            }
+        }
         let nulls_first = if self.parse_keywords(&[Keyword::NULLS, Keyword::FIRST]) {
             Some(true)
         } else if self.parse_keywords(&[Keyword::NULLS, Keyword::LAST]) {
@@ -2999,6 +5093,18 @@ impl Parser {
         })
     }
 
+    /// Parse an `ORDER BY` or `GROUP BY` expression, where an integer on its
+    /// own refers to a select item by position
+    pub fn parse_position_or_expr(&mut self) -> Result<Expr, ParserError> {
+        match self.parse_expr()? {
+            Expr::Value(Value::Number(n)) => match n.to_string().parse() {
+                Ok(position) => Ok(Expr::Position(position)),
+                Err(_) => Ok(Expr::Value(Value::Number(n))),
+            },
+            expr => Ok(expr),
+        }
+    }
+
     /// Parse a TOP clause, MSSQL equivalent of LIMIT,
     /// that follows after SELECT [DISTINCT].
     pub fn parse_top(&mut self) -> Result<Top, ParserError> {
@@ -3026,13 +5132,11 @@ impl Parser {
             Ok((None, None))
         } else {
             let limit_value = Some(Expr::Value(self.parse_number_value()?));
-            if self.parse_keyword(Keyword::OFFSET){
-                Ok((limit_value,Some(self.parse_offset()?)))
-            }else if self.peek_token() == Token::Comma {
-                self.next_token();
-                Ok((limit_value,Some(self.parse_offset()?)))
-            }
-            else {
+            if self.parse_keyword(Keyword::OFFSET)
+                || (self.dialect.supports_limit_comma() && self.consume_token(&Token::Comma))
+            {
+                Ok((limit_value, Some(self.parse_offset()?)))
+            } else {
                 Ok((limit_value, None))
             }
         }
@@ -3089,9 +5193,12 @@ impl Parser {
     }
 
     pub fn parse_values(&mut self) -> Result<Values, ParserError> {
-        let values = self.parse_comma_separated(|parser| {
+        let values = self.parse_long_comma_separated(|parser| {
             parser.expect_token(&Token::LParen)?;
-            let exprs = parser.parse_comma_separated(Parser::parse_expr)?;
+            let exprs = match parser.parse_literal_list()? {
+                Some(exprs) => exprs,
+                None => parser.parse_long_comma_separated(Parser::parse_expr)?,
+            };
             parser.expect_token(&Token::RParen)?;
             Ok(exprs)
         })?;
@@ -3173,6 +5280,71 @@ impl Parser {
     }
 }
 
+impl Statement {
+    /// Parse `sql` as a single statement, optionally followed by a `;`
+    pub fn parse_with(dialect: &dyn Dialect, sql: &str) -> Result<Statement, ParserError> {
+        let mut statements = Parser::parse_sql(dialect, sql)?;
+        match statements.len() {
+            1 => Ok(statements.remove(0)),
+            n => Err(ParserError::SyntaxError(SyntaxError {
+                kind: SyntaxErrorKind::Other,
+                message: format!("Expected exactly one statement, found {}", n),
+                location: None,
+            })),
+        }
+    }
+}
+
+/// Parse a single MySQL statement, see `Statement::parse_with` for other
+/// dialects:
+///
+/// ```
+/// # use sqlparser::ast::Statement;
+/// let statement: Statement = "SELECT 1".parse().unwrap();
+/// assert_eq!("SELECT 1", statement.to_string());
+///
+/// assert!("SELECT 1; SELECT 2".parse::<Statement>().is_err());
+/// ```
+impl FromStr for Statement {
+    type Err = ParserError;
+
+    fn from_str(sql: &str) -> Result<Self, Self::Err> {
+        Statement::parse_with(&MySqlDialect {}, sql)
+    }
+}
+
+impl TryFrom<&str> for Statement {
+    type Error = ParserError;
+
+    fn try_from(sql: &str) -> Result<Self, Self::Error> {
+        sql.parse()
+    }
+}
+
+/// Parse a single MySQL expression, see `Parser::parse_expr_str` for other
+/// dialects:
+///
+/// ```
+/// # use sqlparser::ast::Expr;
+/// let expr: Expr = "a + 1".parse().unwrap();
+/// assert_eq!("a + 1", expr.to_string());
+/// ```
+impl FromStr for Expr {
+    type Err = ParserError;
+
+    fn from_str(sql: &str) -> Result<Self, Self::Err> {
+        Parser::parse_expr_str(&MySqlDialect {}, sql)
+    }
+}
+
+impl TryFrom<&str> for Expr {
+    type Error = ParserError;
+
+    fn try_from(sql: &str) -> Result<Self, Self::Error> {
+        sql.parse()
+    }
+}
+
 impl Word {
     pub fn to_ident(&self) -> Ident {
         Ident {
@@ -3182,6 +5354,33 @@ impl Word {
     }
 }
 
+/// The unit of `EXTRACT` and `INTERVAL` that the keyword names, if any
+fn date_time_field(keyword: Keyword) -> Option<DateTimeField> {
+    Some(match keyword {
+        Keyword::YEAR => DateTimeField::Year,
+        Keyword::QUARTER => DateTimeField::Quarter,
+        Keyword::MONTH => DateTimeField::Month,
+        Keyword::WEEK => DateTimeField::Week,
+        Keyword::DAY => DateTimeField::Day,
+        Keyword::HOUR => DateTimeField::Hour,
+        Keyword::MINUTE => DateTimeField::Minute,
+        Keyword::SECOND => DateTimeField::Second,
+        Keyword::MICROSECOND => DateTimeField::Microsecond,
+        Keyword::YEAR_MONTH => DateTimeField::YearMonth,
+        Keyword::DAY_HOUR => DateTimeField::DayHour,
+        Keyword::DAY_MINUTE => DateTimeField::DayMinute,
+        Keyword::DAY_SECOND => DateTimeField::DaySecond,
+        Keyword::DAY_MICROSECOND => DateTimeField::DayMicrosecond,
+        Keyword::HOUR_MINUTE => DateTimeField::HourMinute,
+        Keyword::HOUR_SECOND => DateTimeField::HourSecond,
+        Keyword::HOUR_MICROSECOND => DateTimeField::HourMicrosecond,
+        Keyword::MINUTE_SECOND => DateTimeField::MinuteSecond,
+        Keyword::MINUTE_MICROSECOND => DateTimeField::MinuteMicrosecond,
+        Keyword::SECOND_MICROSECOND => DateTimeField::SecondMicrosecond,
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
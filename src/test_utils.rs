@@ -53,7 +53,7 @@ impl TestedDialects {
         self.one_of_identical_results(|dialect| {
             let mut tokenizer = Tokenizer::new(dialect, sql);
             let tokens = tokenizer.tokenize().unwrap();
-            f(&mut Parser::new(tokens, dialect.check_db_type()))
+            f(&mut Parser::new(tokens, dialect))
         })
     }
 
@@ -65,7 +65,8 @@ impl TestedDialects {
 
     /// Ensures that `sql` parses as a single statement, optionally checking
     /// that converting AST back to string equals to `canonical` (unless an
-    /// empty canonical string is provided).
+    /// empty canonical string is provided), and that the string parses back
+    /// to the same AST.
     pub fn one_statement_parses_to(&self, sql: &str, canonical: &str) -> Statement {
         let mut statements = self.parse_sql_statements(&sql).unwrap();
         assert_eq!(statements.len(), 1);
@@ -74,9 +75,26 @@ impl TestedDialects {
         if !canonical.is_empty() {
             assert_eq!(canonical, only_statement.to_string())
         }
+        self.round_trips(&only_statement);
         only_statement
     }
 
+    /// Ensures that the SQL printed for the statement parses back to the
+    /// same statement. Every statement that the tests parse goes through
+    /// this, so a new statement variant must `Display` as valid SQL.
+    pub fn round_trips(&self, statement: &Statement) {
+        let sql = statement.to_string();
+        match self.parse_sql_statements(&sql) {
+            Ok(statements) => assert_eq!(
+                vec![statement.clone()],
+                statements,
+                "{} doesn't parse back to the same statement",
+                sql
+            ),
+            Err(e) => panic!("{} doesn't parse back: {}", sql, e),
+        }
+    }
+
     /// Ensures that `sql` parses as a single [Statement], and is not modified
     /// after a serialization round-trip.
     pub fn verified_stmt(&self, query: &str) -> Statement {
@@ -104,7 +122,7 @@ impl TestedDialects {
     /// Ensures that `sql` parses as an expression, and is not modified
     /// after a serialization round-trip.
     pub fn verified_expr(&self, sql: &str) -> Expr {
-        let ast = self.run_parser_method(sql, Parser::parse_expr).unwrap();
+        let ast = self.run_parser_method(sql, |parser| parser.parse_expr()).unwrap();
         assert_eq!(sql, &ast.to_string(), "round-tripping without changes");
         ast
     }
@@ -121,6 +139,14 @@ pub fn all_dialects() -> TestedDialects {
     }
 }
 
+/// The message of a syntax error, for tests that only check the message
+pub fn syntax_error_message(error: ParserError) -> String {
+    match error {
+        ParserError::SyntaxError(e) => e.message,
+        e => panic!("expected a syntax error, got {:?}", e),
+    }
+}
+
 pub fn only<T>(v: impl IntoIterator<Item = T>) -> T {
     let mut iter = v.into_iter();
     if let (Some(item), None) = (iter.next(), iter.next()) {
@@ -140,3 +166,11 @@ pub fn expr_from_projection(item: &SelectItem) -> &Expr {
 pub fn number(n: &'static str) -> Value {
     Value::Number(n.parse().unwrap())
 }
+
+/// A `'...'` literal, as read with or without backslash escapes
+pub fn single_quoted(value: impl Into<String>, backslash_escapes: bool) -> Value {
+    Value::SingleQuotedString {
+        value: value.into(),
+        backslash_escapes,
+    }
+}
@@ -15,17 +15,36 @@
 //! The tokenizer (a.k.a. lexer) converts a string into a sequence of tokens.
 //!
 //! The tokens then form the input for the parser, which outputs an Abstract Syntax Tree (AST).
+//!
+//! The tokenizer can also be used on its own, e.g. to highlight or mask
+//! parts of a query without parsing it. `Tokenizer::iter` yields the tokens
+//! with their locations, whitespace and comments included, and this API is
+//! kept stable across releases.
 
-use std::iter::Peekable;
-use std::str::Chars;
 
-use super::dialect::keywords::{Keyword, ALL_KEYWORDS, ALL_KEYWORDS_INDEX};
+use super::ast::{escape_double_quote_string, escape_quoted_identifier, escape_single_quote_string};
+use super::dialect::keywords::Keyword;
 use super::dialect::Dialect;
-use std::fmt;
-// use serde_json::value::Value::String;
+#[cfg(not(feature = "std"))]
+use alloc::collections::VecDeque;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// SQL Token enumeration
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Token {
     /// An end-of-file marker, not a real token
     EOF,
@@ -33,12 +52,14 @@ pub enum Token {
     Word(Word),
     /// An unsigned numeric literal
     Number(String),
-    /// A character that could not be tokenized
-    Char(char),
+    /// A prepared statement placeholder: `?`
+    Placeholder(String),
     /// Environment variable name
     VariableString(String),
     /// Single quoted string: i.e: 'string'
     SingleQuotedString(String),
+    /// Double quoted string: i.e: "string", in MySQL without `ANSI_QUOTES`
+    DoubleQuotedString(String),
     /// "National" string literal: i.e: N'string'
     NationalStringLiteral(String),
     /// Hexadecimal string literal: i.e.: X'deadbeef'
@@ -83,7 +104,7 @@ pub enum Token {
     DoubleColon,
     /// SemiColon `;` used as separator for COPY and payload
     SemiColon,
-    /// Backslash `\` used in terminating the COPY payload with `\.`
+    /// Backslash `\`
     Backslash,
     /// Left bracket `[`
     LBracket,
@@ -104,7 +125,10 @@ pub enum Token {
     /// Right Displacement `>>`
     RDisplacement,
     /// Left Displacement `<<`
-    LDisplacement
+    LDisplacement,
+    /// The data of `COPY ... FROM stdin;`, from the line after it up to and
+    /// including the `\.` that ends it, as written
+    CopyData(String),
 }
 
 impl fmt::Display for Token {
@@ -113,10 +137,11 @@ impl fmt::Display for Token {
             Token::EOF => f.write_str("EOF"),
             Token::Word(ref w) => write!(f, "{}", w),
             Token::Number(ref n) => f.write_str(n),
-            Token::Char(ref c) => write!(f, "{}", c),
-            Token::SingleQuotedString(ref s) => write!(f, "'{}'", s),
-            Token::NationalStringLiteral(ref s) => write!(f, "N'{}'", s),
-            Token::HexStringLiteral(ref s) => write!(f, "X'{}'", s),
+            Token::Placeholder(ref s) => f.write_str(s),
+            Token::SingleQuotedString(ref s) => write!(f, "'{}'", escape_single_quote_string(s)),
+            Token::DoubleQuotedString(ref s) => write!(f, "\"{}\"", escape_double_quote_string(s)),
+            Token::NationalStringLiteral(ref s) => write!(f, "N'{}'", escape_single_quote_string(s)),
+            Token::HexStringLiteral(ref s) => write!(f, "X'{}'", escape_single_quote_string(s)),
             Token::VariableString(ref v) => write!(f, "{}", v),
             Token::Comma => f.write_str(","),
             Token::Whitespace(ws) => write!(f, "{}", ws),
@@ -149,6 +174,7 @@ impl fmt::Display for Token {
             Token::Negate => f.write_str("~"),
             Token::LDisplacement => f.write_str("<<"),
             Token::RDisplacement => f.write_str(">>"),
+            Token::CopyData(ref s) => f.write_str(s),
         }
     }
 }
@@ -158,13 +184,11 @@ impl Token {
         Token::make_word(keyword, None)
     }
     pub fn make_word(word: &str, quote_style: Option<char>) -> Self {
-        let word_uppercase = word.to_uppercase();
         Token::Word(Word {
             value: word.to_string(),
             quote_style,
             keyword: if quote_style == None {
-                let keyword = ALL_KEYWORDS.binary_search(&word_uppercase.as_str());
-                keyword.map_or(Keyword::NoKeyword, |x| ALL_KEYWORDS_INDEX[x])
+                Keyword::from_str_ci(word).unwrap_or(Keyword::NoKeyword)
             } else {
                 Keyword::NoKeyword
             },
@@ -174,6 +198,7 @@ impl Token {
 
 /// A keyword (like SELECT) or an optionally quoted SQL identifier
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Word {
     /// The value of the token, without the enclosing quotes, and with the
     /// escape sequences (if any) processed (TODO: escapes are not handled)
@@ -190,11 +215,11 @@ pub struct Word {
 impl fmt::Display for Word {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.quote_style {
-            Some(s) if s == '"' || s == '[' || s == '`' => {
-                write!(f, "{}{}{}", s, self.value, Word::matching_end_quote(s))
+            Some(s) => {
+                let end = Word::matching_end_quote(s);
+                write!(f, "{}{}{}", s, escape_quoted_identifier(&self.value, end), end)
             }
             None => f.write_str(&self.value),
-            _ => panic!("Unexpected quote_style!"),
         }
     }
 }
@@ -204,18 +229,24 @@ impl Word {
             '"' => '"', // ANSI and most dialects
             '[' => ']', // MS SQL
             '`' => '`', // MySQL
-            _ => panic!("unexpected quoting style!"),
+            _ => ch,
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Whitespace {
     Space,
     Newline,
     Tab,
-    SingleLineComment(String),
+    SingleLineComment { comment: String, prefix: String },
     MultiLineComment(String),
+    /// The opening `/*!` (plus optional version) of a MySQL versioned
+    /// comment, whose content is tokenized as ordinary SQL
+    VersionedCommentStart(Option<u64>),
+    /// The closing `*/` of a MySQL versioned comment
+    VersionedCommentEnd,
 }
 
 impl fmt::Display for Whitespace {
@@ -224,14 +255,238 @@ impl fmt::Display for Whitespace {
             Whitespace::Space => f.write_str(" "),
             Whitespace::Newline => f.write_str("\n"),
             Whitespace::Tab => f.write_str("\t"),
-            Whitespace::SingleLineComment(s) => write!(f, "--{}", s),
+            Whitespace::SingleLineComment { prefix, comment } => write!(f, "{}{}", prefix, comment),
             Whitespace::MultiLineComment(s) => write!(f, "/*{}*/", s),
+            Whitespace::VersionedCommentStart(Some(v)) => write!(f, "/*!{}", v),
+            Whitespace::VersionedCommentStart(None) => f.write_str("/*!"),
+            Whitespace::VersionedCommentEnd => f.write_str("*/"),
+        }
+    }
+}
+
+/// Position of a token in the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Location {
+    /// Line number, starting from 1
+    pub line: u64,
+    /// Column number in characters, starting from 1
+    pub column: u64,
+    /// Byte offset from the start of the query
+    pub offset: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location {
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line: {}, Column {}", self.line, self.column)
+    }
+}
+
+/// A token along with where it starts in the query
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TokenWithLocation {
+    pub token: Token,
+    pub location: Location,
+}
+
+/// The characters of the query being tokenized, keeping track of the
+/// location of the next one
+#[derive(Clone)]
+struct State<'a> {
+    source: &'a str,
+    /// Byte index of the next char in `source`
+    pos: usize,
+    /// Where the next char is, which is only worked out when asked for
+    cursor: Cursor,
+}
+
+/// The part of `State` that tracks locations, kept between tokens
+#[derive(Clone, Copy)]
+struct Cursor {
+    /// Where `source` starts
+    start: Location,
+    /// Line of the next char
+    line: u64,
+    /// Byte index in `source` where that line starts, and its column
+    line_start: usize,
+    line_column: u64,
+    /// How many more bytes than chars the line has up to the next char
+    wide: usize,
+}
+
+impl Cursor {
+    fn new(start: Location) -> Self {
+        Cursor {
+            start,
+            line: start.line,
+            line_start: 0,
+            line_column: start.column,
+            wide: 0,
+        }
+    }
+}
+
+impl<'a> State<'a> {
+    fn new(source: &'a str, start: Location) -> Self {
+        State {
+            source,
+            pos: 0,
+            cursor: Cursor::new(start),
+        }
+    }
+
+    /// Location of the next char
+    fn location(&self) -> Location {
+        let cursor = &self.cursor;
+        Location {
+            line: cursor.line,
+            column: cursor.line_column + (self.pos - cursor.line_start - cursor.wide) as u64,
+            offset: cursor.start.offset + self.pos,
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let &byte = self.source.as_bytes().get(self.pos)?;
+        if byte.is_ascii() {
+            self.pos += 1;
+            if byte == b'\n' {
+                self.new_line();
+            }
+            return Some(byte as char);
+        }
+        let ch = self.source[self.pos..].chars().next()?;
+        self.pos += ch.len_utf8();
+        self.cursor.wide += ch.len_utf8() - 1;
+        Some(ch)
+    }
+
+    fn peek(&self) -> Option<char> {
+        match self.source.as_bytes().get(self.pos) {
+            // most SQL is ASCII, which needn't be decoded
+            Some(&byte) if byte.is_ascii() => Some(byte as char),
+            Some(_) => self.source[self.pos..].chars().next(),
+            None => None,
+        }
+    }
+
+    /// Consume bytes while `predicate` holds for them, which must not stop
+    /// in the middle of a char, i.e. hold for all bytes above 0x7F or none
+    fn skip_bytes_while(&mut self, mut predicate: impl FnMut(u8) -> bool) {
+        let bytes = self.source.as_bytes();
+        let mut pos = self.pos;
+        while let Some(&byte) = bytes.get(pos) {
+            if !predicate(byte) {
+                break;
+            }
+            pos += 1;
+            if byte == b'\n' {
+                self.pos = pos;
+                self.new_line();
+            } else if byte & 0xC0 == 0x80 {
+                // a continuation byte of a multi-byte char
+                self.cursor.wide += 1;
+            }
         }
+        self.pos = pos;
+    }
+
+    /// Start a new line at `pos`, after a `\n`
+    fn new_line(&mut self) {
+        self.cursor.line += 1;
+        self.cursor.line_start = self.pos;
+        self.cursor.line_column = 1;
+        self.cursor.wide = 0;
+    }
+
+    /// The part of the source from byte index `start` up to the next char
+    fn slice_from(&self, start: usize) -> &'a str {
+        &self.source[start..self.pos]
+    }
+}
+
+/// The tokens of a query with their locations, see `Tokenizer::iter`
+pub struct TokenIter<'t, 'a> {
+    tokenizer: &'t mut Tokenizer<'a>,
+    query: String,
+    /// Byte index of the next char in `query`
+    pos: usize,
+    cursor: Cursor,
+    /// The tokens tokenized but not returned yet, which are more than one
+    /// for a versioned comment
+    pending: VecDeque<TokenWithLocation>,
+    /// How many tokens were tokenized, whitespace and comments included
+    count: usize,
+    done: bool,
+}
+
+impl TokenIter<'_, '_> {
+    fn next_token(&mut self) -> Result<Option<TokenWithLocation>, TokenizerError> {
+        if let Some(token) = self.pending.pop_front() {
+            return Ok(Some(token));
+        }
+        let tokenizer = &mut *self.tokenizer;
+        let mut state = State {
+            source: &self.query,
+            pos: self.pos,
+            cursor: self.cursor,
+        };
+        if self.pos == 0 {
+            tokenizer.check_input_bytes(&state)?;
+        }
+        let pending = &mut self.pending;
+        let more = tokenizer.tokenize_next(&mut state, &mut |token, location| {
+            pending.push_back(TokenWithLocation { token, location })
+        })?;
+        self.pos = state.pos;
+        self.cursor = state.cursor;
+        self.count += self.pending.len();
+        tokenizer.check_tokens(self.count)?;
+        Ok(if more { self.pending.pop_front() } else { None })
+    }
+}
+
+impl Drop for TokenIter<'_, '_> {
+    fn drop(&mut self) {
+        self.tokenizer.query = core::mem::take(&mut self.query);
+    }
+}
+
+impl Iterator for TokenIter<'_, '_> {
+    type Item = Result<TokenWithLocation, TokenizerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.next_token() {
+                Ok(Some(TokenWithLocation {
+                    token: Token::Whitespace(_),
+                    ..
+                })) if self.tokenizer.skip_whitespace => {}
+                Ok(Some(token)) => return Some(Ok(token)),
+                Ok(None) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
     }
 }
 
 /// Tokenizer error
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TokenizerError {
     pub message: String,
     pub line: u64,
@@ -244,6 +499,67 @@ pub struct Tokenizer<'a> {
     pub query: String,
     pub line: u64,
     pub col: u64,
+    /// Server version that MySQL versioned comments `/*!NNNNN ... */` are
+    /// checked against; `None` executes all of them
+    pub mysql_version: Option<u64>,
+    /// MySQL `NO_BACKSLASH_ESCAPES` SQL mode: a backslash in a string
+    /// literal is an ordinary character
+    pub no_backslash_escapes: bool,
+    /// MySQL `ANSI_QUOTES` SQL mode: `"..."` is a delimited identifier even
+    /// in dialects that otherwise read it as a string literal
+    pub ansi_quotes: bool,
+    /// The longest query accepted, in bytes
+    pub max_input_bytes: Option<usize>,
+    /// The most tokens accepted, whitespace and comments included
+    pub max_tokens: Option<usize>,
+    /// Leave out whitespace and comments, including the start and end of
+    /// versioned comments, from the tokens
+    pub skip_whitespace: bool,
+    /// Where the query starts, when it is part of a larger one
+    start: Location,
+    /// Whether `COPY ... FROM stdin;` was just tokenized, so its data follows
+    copy: CopyState,
+}
+
+/// How far the tokens are into a `COPY ... FROM stdin;` statement, which is
+/// followed by its data rather than SQL
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CopyState {
+    /// At the start of a statement
+    StatementStart,
+    /// In a statement other than `COPY`
+    Other,
+    /// In a `COPY` statement
+    Copy,
+    /// After `COPY ... FROM`
+    From,
+    /// After `COPY ... FROM stdin`
+    Stdin,
+    /// On the rest of the line of `COPY ... FROM stdin;`
+    Data,
+    /// At the start of the data of `COPY ... FROM stdin;`
+    DataLine,
+}
+
+impl CopyState {
+    /// The state after `token`, which isn't whitespace
+    fn after(self, token: &Token) -> Self {
+        match token {
+            Token::SemiColon if self == CopyState::Stdin => CopyState::Data,
+            Token::SemiColon | Token::CopyData(_) => CopyState::StatementStart,
+            _ if self == CopyState::StatementStart || self == CopyState::Other => {
+                match token {
+                    Token::Word(w) if w.keyword == Keyword::COPY => CopyState::Copy,
+                    _ => CopyState::Other,
+                }
+            }
+            Token::Word(w) if w.keyword == Keyword::FROM => CopyState::From,
+            Token::Word(w) if w.keyword == Keyword::STDIN && self == CopyState::From => {
+                CopyState::Stdin
+            }
+            _ => CopyState::Copy,
+        }
+    }
 }
 
 impl<'a> Tokenizer<'a> {
@@ -254,40 +570,261 @@ impl<'a> Tokenizer<'a> {
             query: query.to_string(),
             line: 1,
             col: 1,
+            mysql_version: None,
+            no_backslash_escapes: false,
+            ansi_quotes: false,
+            max_input_bytes: None,
+            max_tokens: None,
+            skip_whitespace: false,
+            start: Location::default(),
+            copy: CopyState::StatementStart,
         }
     }
 
+    /// Read `"..."` as a delimited identifier, as in MySQL's `ANSI_QUOTES`
+    /// SQL mode
+    pub fn with_ansi_quotes(mut self, ansi_quotes: bool) -> Self {
+        self.ansi_quotes = ansi_quotes;
+        self
+    }
+
+    /// Treat backslashes in string literals as ordinary characters, as in
+    /// MySQL's `NO_BACKSLASH_ESCAPES` SQL mode
+    pub fn with_no_backslash_escapes(mut self, no_backslash_escapes: bool) -> Self {
+        self.no_backslash_escapes = no_backslash_escapes;
+        self
+    }
+
+    /// Only execute versioned comments whose version is at most `version`,
+    /// e.g. `80023` for MySQL 8.0.23
+    pub fn with_mysql_version(mut self, version: u64) -> Self {
+        self.mysql_version = Some(version);
+        self
+    }
+
+    /// Fail on queries longer than `max_input_bytes`, before tokenizing them
+    pub fn with_max_input_bytes(mut self, max_input_bytes: usize) -> Self {
+        self.max_input_bytes = Some(max_input_bytes);
+        self
+    }
+
+    /// Fail once a query has more than `max_tokens` tokens, whitespace and
+    /// comments included
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Leave out whitespace and comments from the tokens
+    pub fn with_skip_whitespace(mut self, skip_whitespace: bool) -> Self {
+        self.skip_whitespace = skip_whitespace;
+        self
+    }
+
+    /// Report the locations of the tokens as if the query started at
+    /// `start`, e.g. when it is part of a larger script
+    pub(crate) fn with_start(mut self, start: Location) -> Self {
+        self.start = start;
+        self
+    }
+
     /// Tokenize the statement and produce a vector of tokens
     pub fn tokenize(&mut self) -> Result<Vec<Token>, TokenizerError> {
-        let mut peekable = self.query.chars().peekable();
+        let mut tokens = vec![];
+        self.tokenize_into(|token, _| tokens.push(token))?;
+        Ok(tokens)
+    }
 
-        let mut tokens: Vec<Token> = vec![];
+    /// Tokenize the statement and produce a vector of tokens with their
+    /// locations in the query
+    pub fn tokenize_with_location(&mut self) -> Result<Vec<TokenWithLocation>, TokenizerError> {
+        let mut tokens = vec![];
+        self.tokenize_into(|token, location| tokens.push(TokenWithLocation { token, location }))?;
+        Ok(tokens)
+    }
 
-        while let Some(token) = self.next_token(&mut peekable)? {
-            match &token {
-                Token::Whitespace(Whitespace::Newline) => {
-                    self.line += 1;
-                    self.col = 1;
+    /// Tokenize the whole query, passing each token and its location to
+    /// `push`, and return where the query ends. This is the loop behind
+    /// `tokenize` and the parser, which costs less per token than `iter`.
+    pub(crate) fn tokenize_into(
+        &mut self,
+        mut push: impl FnMut(Token, Location),
+    ) -> Result<Location, TokenizerError> {
+        let query = core::mem::take(&mut self.query);
+        let mut state = State::new(&query, self.start);
+        let skip_whitespace = self.skip_whitespace;
+        let mut count = 0;
+        let result = self.check_input_bytes(&state).and_then(|()| {
+            while self.tokenize_next(&mut state, &mut |token, location| {
+                count += 1;
+                if !(skip_whitespace && matches!(token, Token::Whitespace(_))) {
+                    push(token, location);
                 }
+            })? {
+                self.check_tokens(count)?;
+            }
+            Ok(state.location())
+        });
+        self.query = query;
+        result
+    }
+
+    /// Iterate over the tokens of the query and their locations, tokenizing
+    /// one token at a time. The iteration ends after the first error.
+    ///
+    /// Unless whitespace is skipped, each token's text in the query starts at
+    /// the offset of its location and runs up to the offset of the next
+    /// token, or the end of the query, so the texts of all the tokens make up
+    /// the query. `Display` of a token may spell it differently, e.g. `!=`
+    /// as `<>`.
+    ///
+    /// ```
+    /// # use sqlparser::dialect::MySqlDialect;
+    /// # use sqlparser::tokenizer::{Token, Tokenizer};
+    /// let sql = "SELECT 'secret' FROM t";
+    /// let mut tokenizer = Tokenizer::new(&MySqlDialect {}, sql).with_skip_whitespace(true);
+    /// let strings: Vec<usize> = tokenizer
+    ///     .iter()
+    ///     .map(Result::unwrap)
+    ///     .filter(|t| matches!(t.token, Token::SingleQuotedString(_)))
+    ///     .map(|t| t.location.offset)
+    ///     .collect();
+    /// assert_eq!(vec![7], strings);
+    /// ```
+    pub fn iter(&mut self) -> TokenIter<'_, 'a> {
+        TokenIter {
+            // given back when the iterator is dropped
+            query: core::mem::take(&mut self.query),
+            pos: 0,
+            cursor: Cursor::new(self.start),
+            pending: VecDeque::new(),
+            count: 0,
+            done: false,
+            tokenizer: self,
+        }
+    }
 
-                Token::Whitespace(Whitespace::Tab) => self.col += 4,
-                Token::Word(w) if w.quote_style == None => self.col += w.value.len() as u64,
-                Token::Word(w) if w.quote_style != None => self.col += w.value.len() as u64 + 2,
-                Token::Number(s) => self.col += s.len() as u64,
-                Token::SingleQuotedString(s) => self.col += s.len() as u64,
-                _ => self.col += 1,
+    /// Fail on a query longer than `max_input_bytes`, pointing at the first
+    /// character past the limit
+    fn check_input_bytes(&mut self, state: &State<'_>) -> Result<(), TokenizerError> {
+        match self.max_input_bytes {
+            Some(max) if state.source.len() > max => {
+                let mut state = state.clone();
+                while state.pos < max {
+                    state.next();
+                }
+                let location = state.location();
+                self.line = location.line;
+                self.col = location.column;
+                self.tokenizer_error(&format!("SQL longer than {} bytes", max))
             }
+            _ => Ok(()),
+        }
+    }
 
-            tokens.push(token);
+    /// Fail once `count` tokens are more than `max_tokens`
+    fn check_tokens(&self, count: usize) -> Result<(), TokenizerError> {
+        match self.max_tokens {
+            Some(max) if count > max => {
+                self.tokenizer_error(&format!("SQL with more than {} tokens", max))
+            }
+            _ => Ok(()),
         }
-        Ok(tokens)
+    }
+
+    /// Tokenize the next token of `state` and pass it to `push` along with
+    /// its location, or return `false` at the end of the query. A versioned
+    /// comment is passed as its start, the tokens of its content and its end.
+    fn tokenize_next(
+        &mut self,
+        state: &mut State<'_>,
+        push: &mut impl FnMut(Token, Location),
+    ) -> Result<bool, TokenizerError> {
+        let location = state.location();
+        self.line = location.line;
+        self.col = location.column;
+        let copy_data = match self.copy {
+            CopyState::DataLine => state.peek().is_some(),
+            // the data starts on the next line
+            CopyState::Data => !matches!(state.peek(), None | Some(' ' | '\t' | '\r' | '\n')),
+            _ => false,
+        };
+        let token = if copy_data {
+            self.tokenize_copy_data(state)
+        } else {
+            match self.next_token(state)? {
+                Some(token) => token,
+                None => {
+                    let location = state.location();
+                    self.line = location.line;
+                    self.col = location.column;
+                    return Ok(false);
+                }
+            }
+        };
+        match &token {
+            Token::Whitespace(Whitespace::MultiLineComment(comment)) => {
+                if let Some((version, body)) = self.executable_comment(comment) {
+                    let skipped = comment.len() - body.len() + 2;
+                    self.tokenize_versioned_comment(version, body, skipped, location, state, push)?;
+                    return Ok(true);
+                }
+            }
+            Token::Whitespace(Whitespace::Newline) if self.copy == CopyState::Data => {
+                self.copy = CopyState::DataLine;
+            }
+            Token::Whitespace(_) => {}
+            token => self.copy = self.copy.after(token),
+        }
+        push(token, location);
+        Ok(true)
+    }
+
+    /// Pass the start of a versioned comment at `location` to `push`, then
+    /// the tokens of `body`, its content, which starts `skipped` bytes after
+    /// `location` past `/*!` and the version, and then its end, which is
+    /// just before `state`
+    fn tokenize_versioned_comment(
+        &self,
+        version: Option<u64>,
+        body: &str,
+        skipped: usize,
+        location: Location,
+        state: &State<'_>,
+        push: &mut dyn FnMut(Token, Location),
+    ) -> Result<(), TokenizerError> {
+        push(
+            Token::Whitespace(Whitespace::VersionedCommentStart(version)),
+            location,
+        );
+        // tokenize the content of the comment as ordinary SQL
+        let mut inner = Tokenizer::new(self.dialect, body);
+        inner.mysql_version = self.mysql_version;
+        inner.no_backslash_escapes = self.no_backslash_escapes;
+        inner.ansi_quotes = self.ansi_quotes;
+        inner.start = Location {
+            line: location.line,
+            column: location.column + skipped as u64,
+            offset: location.offset + skipped,
+        };
+        inner.tokenize_into(&mut *push)?;
+        let end = state.location();
+        push(
+            Token::Whitespace(Whitespace::VersionedCommentEnd),
+            Location {
+                column: end.column - 2,
+                offset: end.offset - 2,
+                ..end
+            },
+        );
+        Ok(())
     }
 
     /// Get the next token or return None
-    fn next_token(&self, chars: &mut Peekable<Chars<'_>>) -> Result<Option<Token>, TokenizerError> {
+    fn next_token(&self, chars: &mut State<'_>) -> Result<Option<Token>, TokenizerError> {
         //println!("next_token: {:?}", chars.peek());
         match chars.peek() {
-            Some(&ch) => match ch {
+            Some(ch) => match ch {
                 ' ' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Space)),
                 '\t' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Tab)),
                 '\n' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Newline)),
@@ -312,7 +849,7 @@ impl<'a> Tokenizer<'a> {
                             // regular identifier starting with an "N"
                             // 读取常规N开头的字符串
                             let s = self.tokenize_word('N', chars);
-                            Ok(Some(Token::make_word(&s, None)))
+                            Ok(Some(Token::make_word(s, None)))
                         }
                     }
                 }
@@ -333,7 +870,7 @@ impl<'a> Tokenizer<'a> {
                             // regular identifier starting with an "X"
                             // 读取常规X开头的字符串
                             let s = self.tokenize_word(x, chars);
-                            Ok(Some(Token::make_word(&s, None)))
+                            Ok(Some(Token::make_word(s, None)))
                         }
                     }
                 }
@@ -342,7 +879,7 @@ impl<'a> Tokenizer<'a> {
                 ch if self.dialect.is_identifier_start(ch) => {
                     chars.next(); // consume the first char
                     let s = self.tokenize_word(ch, chars);
-                    Ok(Some(Token::make_word(&s, None)))
+                    Ok(Some(Token::make_word(s, None)))
                 }
 
                 '@' => {
@@ -361,31 +898,26 @@ impl<'a> Tokenizer<'a> {
                     let s = self.tokenize_single_quoted_string(chars)?;
                     Ok(Some(Token::SingleQuotedString(s)))
                 }
+                '"' if self.dialect.supports_double_quoted_strings() && !self.ansi_quotes => {
+                    let s = self.tokenize_quoted_string(chars, '"')?;
+                    Ok(Some(Token::DoubleQuotedString(s)))
+                }
                 // delimited (quoted) identifier
 
                 // mssql所用
                 quote_start if self.dialect.is_delimited_identifier_start(quote_start) => {
                     chars.next(); // consume the opening quote
                     let quote_end = Word::matching_end_quote(quote_start);
-                    let s = peeking_take_while(chars, |ch| ch != quote_end);
-                    if chars.next() == Some(quote_end) {
-                        Ok(Some(Token::make_word(&s, Some(quote_start))))
-                    } else {
-                        self.tokenizer_error(
-                            format!("Expected close delimiter '{}' before EOF.", quote_end)
-                                .as_str(),
-                        )
-                    }
+                    let s = self.tokenize_quoted_identifier(quote_end, chars)?;
+                    Ok(Some(Token::make_word(&s, Some(quote_start))))
                 }
                 // numbers
                 // 读取整型数据
                 '0'..='9' => {
                     // TODO: https://jakewheat.github.io/sql-overview/sql-2011-foundation-grammar.html#unsigned-numeric-literal
-                    let s = peeking_take_while(chars, |ch| match ch {
-                        '0'..='9' | '.' => true,
-                        _ => false,
-                    });
-                    Ok(Some(Token::Number(s)))
+                    let start = chars.pos;
+                    chars.skip_bytes_while(|byte| matches!(byte, b'0'..=b'9' | b'.'));
+                    Ok(Some(Token::Number(chars.slice_from(start).to_string())))
                 }
                 // punctuation
                 // 左括号
@@ -398,19 +930,26 @@ impl<'a> Tokenizer<'a> {
                 '-' => {
                     chars.next(); // consume the '-'
                     match chars.peek() {
-                        Some('-') => {
+                        Some('-') if self.is_double_dash_comment(chars) => {
                             chars.next(); // consume the second '-', starting a single-line comment
-                            let mut s = peeking_take_while(chars, |ch| ch != '\n');
-                            if let Some(ch) = chars.next() {
-                                assert_eq!(ch, '\n');
-                                s.push(ch);
-                            }
-                            Ok(Some(Token::Whitespace(Whitespace::SingleLineComment(s))))
+                            let comment = self.tokenize_single_line_comment(chars);
+                            Ok(Some(Token::Whitespace(Whitespace::SingleLineComment {
+                                prefix: "--".to_owned(),
+                                comment,
+                            })))
                         }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
                     }
                 }
+                '#' if self.dialect.supports_hash_comments() => {
+                    chars.next(); // consume the '#', starting a single-line comment
+                    let comment = self.tokenize_single_line_comment(chars);
+                    Ok(Some(Token::Whitespace(Whitespace::SingleLineComment {
+                        prefix: "#".to_owned(),
+                        comment,
+                    })))
+                }
                 // 读取注释内容
                 '/' => {
                     chars.next(); // consume the '/'
@@ -476,12 +1015,26 @@ impl<'a> Tokenizer<'a> {
                 '^' => self.consume_and_return(chars, Token::Caret),
                 '{' => self.consume_and_return(chars, Token::LBrace),
                 '}' => self.consume_and_return(chars, Token::RBrace),
-                other => self.consume_and_return(chars, Token::Char(other)),
+                '?' => self.consume_and_return(chars, Token::Placeholder("?".to_string())),
+                other => self.tokenizer_error(&format!("Unexpected character '{}'", other)),
             },
             None => Ok(None),
         }
     }
 
+    /// Read the data of `COPY ... FROM stdin;` up to and including the `\.`
+    /// that ends it, or up to the end of the query. It isn't SQL, so it may
+    /// have any characters.
+    fn tokenize_copy_data(&self, chars: &mut State<'_>) -> Token {
+        let start = chars.pos;
+        while let Some(ch) = chars.next() {
+            if ch == '\\' && chars.next() == Some('.') {
+                break;
+            }
+        }
+        Token::CopyData(chars.slice_from(start).to_string())
+    }
+
     fn tokenizer_error<R>(&self, message: &str) -> Result<R, TokenizerError> {
         Err(TokenizerError {
             message: message.to_string(),
@@ -491,46 +1044,52 @@ impl<'a> Tokenizer<'a> {
     }
 
     /// 读去反引号包含的内容
-    fn tokenizer_backticks_string(&self, chars: &mut Peekable<Chars<'_>>,) -> Result<String, TokenizerError>{
-        let mut st = String::from("");
-        chars.next(); // consume the first char
-        'aa: loop{
-            match chars.peek() {
-                Some(&ch) => match ch {
-                    '`' => {
+    fn tokenizer_backticks_string(&self, chars: &mut State<'_>,) -> Result<String, TokenizerError>{
+        chars.next(); // consume the opening backtick
+        self.tokenize_quoted_identifier('`', chars)
+    }
+
+    /// Read a delimited identifier up to `quote_end`, after the opening quote
+    /// has been consumed; a doubled `quote_end` stands for itself
+    fn tokenize_quoted_identifier(
+        &self,
+        quote_end: char,
+        chars: &mut State<'_>,
+    ) -> Result<String, TokenizerError> {
+        let mut s = String::new();
+        loop {
+            match chars.next() {
+                Some(ch) if ch == quote_end => {
+                    if chars.peek() == Some(quote_end) {
                         chars.next();
-                        break 'aa;
-                    }
-                    ch if self.dialect.is_identifier_start(ch) => {
-                        chars.next(); // consume the first char
-                        st = self.tokenize_word(ch, chars);
-                        continue 'aa;
-                    }
-                    _ => {
-                        return self.tokenizer_error("parser sql error");
+                        s.push(quote_end);
+                    } else {
+                        return Ok(s);
                     }
                 }
+                Some(ch) => s.push(ch),
                 None => {
-                    return self.tokenizer_error("parser sql error");
+                    return self.tokenizer_error(
+                        format!("Expected close delimiter '{}' before EOF.", quote_end).as_str(),
+                    )
                 }
             }
         }
-        return Ok(st);
     }
 
-    fn tokenizer_var(&self, chars: &mut Peekable<Chars<'_>>,) -> Result<String, TokenizerError>{
+    fn tokenizer_var(&self, chars: &mut State<'_>,) -> Result<String, TokenizerError>{
         let mut s = String::from("@");
         loop{
             chars.next(); // consume the first char
             match chars.peek() {
-                Some(&ch) => match ch {
+                Some(ch) => match ch {
                     '@' => s.push("@".parse().unwrap()),
                     ch if self.dialect.is_identifier_start(ch) => {
                         chars.next(); // consume the first char
                         let st = self.tokenize_word(ch, chars);
                         s = format!("{}{}",s, st);
                         match chars.peek() {
-                            Some(&ch) => match ch {
+                            Some(ch) => match ch {
                                 '.' => {
                                     chars.next(); // consume the first char
                                     let st = self.tokenize_word(ch, chars);
@@ -558,45 +1117,115 @@ impl<'a> Tokenizer<'a> {
     /// Tokenize an identifier or keyword, after the first char is already consumed.
     ///
     /// 读取字符串
-    fn tokenize_word(&self, first_char: char, chars: &mut Peekable<Chars<'_>>) -> String {
-        let mut s = first_char.to_string();
-        s.push_str(&peeking_take_while(chars, |ch| {
-            self.dialect.is_identifier_part(ch)
-        }));
-        s
+    fn tokenize_word<'s>(&self, first_char: char, chars: &mut State<'s>) -> &'s str {
+        let start = chars.pos - first_char.len_utf8();
+        peeking_take_while(chars, |ch| self.dialect.is_identifier_part(ch));
+        chars.slice_from(start)
     }
 
     /// Read a single quoted string, starting with the opening quote.
     fn tokenize_single_quoted_string(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
+        chars: &mut State<'_>,
+    ) -> Result<String, TokenizerError> {
+        self.tokenize_quoted_string(chars, '\'')
+    }
+
+    /// Read a string literal delimited by `quote`, starting at the opening quote
+    fn tokenize_quoted_string(
+        &self,
+        chars: &mut State<'_>,
+        quote: char,
     ) -> Result<String, TokenizerError> {
         let mut s = String::new();
+        let backslash_escapes = self.backslash_escapes();
         chars.next(); // consume the opening quote
-        while let Some(&ch) = chars.peek() {
+        while let Some(ch) = chars.peek() {
             match ch {
-                '\'' => {
+                ch if ch == quote => {
                     chars.next(); // consume
-                    let escaped_quote = chars.peek().map(|c| *c == '\'').unwrap_or(false);
+                    let escaped_quote = chars.peek() == Some(quote);
                     if escaped_quote {
-                        s.push('\'');
+                        s.push(quote);
                         chars.next();
                     } else {
                         return Ok(s);
                     }
                 }
+                '\\' if backslash_escapes => {
+                    chars.next(); // consume the backslash
+                    match chars.next() {
+                        Some(escaped) => unescape_backslash(escaped, &mut s),
+                        None => break,
+                    }
+                }
                 _ => {
-                    chars.next(); // consume
-                    s.push(ch);
+                    // copy everything up to the next quote or escape at once
+                    let start = chars.pos;
+                    let quote = quote as u8;
+                    chars.skip_bytes_while(|byte| {
+                        byte != quote && !(backslash_escapes && byte == b'\\')
+                    });
+                    s.push_str(chars.slice_from(start));
                 }
             }
         }
         self.tokenizer_error("Unterminated string literal")
     }
 
+    pub(crate) fn backslash_escapes(&self) -> bool {
+        self.dialect.supports_backslash_escapes() && !self.no_backslash_escapes
+    }
+
+    /// Split a `/*!NNNNN ... */` comment into its version and content, unless
+    /// the dialect doesn't support them or the version is too new
+    fn executable_comment<'c>(&self, comment: &'c str) -> Option<(Option<u64>, &'c str)> {
+        if !self.dialect.supports_versioned_comments() {
+            return None;
+        }
+        let body = comment.strip_prefix('!')?;
+        let digits = body.chars().take_while(|ch| ch.is_ascii_digit()).count();
+        let (version, body) = if digits == 5 || digits == 6 {
+            (Some(body[..digits].parse().unwrap()), &body[digits..])
+        } else {
+            (None, body)
+        };
+        match (version, self.mysql_version) {
+            (Some(version), Some(server)) if version > server => None,
+            _ => Some((version, body)),
+        }
+    }
+
+    /// Whether the `-` just consumed, followed by the next `-`, starts a
+    /// comment; some dialects require whitespace or a control character
+    /// after `--`, so that `a--b` means `a - (-b)`
+    fn is_double_dash_comment(&self, chars: &State<'_>) -> bool {
+        if !self.dialect.requires_whitespace_after_double_dash() {
+            return true;
+        }
+        let mut lookahead = chars.clone();
+        lookahead.next(); // skip the second '-'
+        match lookahead.peek() {
+            Some(ch) => ch.is_whitespace() || ch.is_control(),
+            None => true,
+        }
+    }
+
+    /// Read a comment up to and including the end of the line
+    fn tokenize_single_line_comment(&self, chars: &mut State<'_>) -> String {
+        let start = chars.pos;
+        chars.skip_bytes_while(|byte| byte != b'\n');
+        let mut comment = chars.slice_from(start).to_string();
+        if let Some(ch) = chars.next() {
+            assert_eq!(ch, '\n');
+            comment.push(ch);
+        }
+        comment
+    }
+
     fn tokenize_multiline_comment(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
+        chars: &mut State<'_>,
     ) -> Result<Option<Token>, TokenizerError> {
         let mut s = String::new();
         let mut maybe_closing_comment = false;
@@ -623,7 +1252,7 @@ impl<'a> Tokenizer<'a> {
 
     fn consume_and_return(
         &self,
-        chars: &mut Peekable<Chars<'_>>,
+        chars: &mut State<'_>,
         t: Token,
     ) -> Result<Option<Token>, TokenizerError> {
         chars.next();
@@ -631,31 +1260,223 @@ impl<'a> Tokenizer<'a> {
     }
 }
 
+/// Tokenizes a query one statement at a time, so that the tokens of a
+/// statement can be dropped once it is parsed
+pub(crate) struct StatementTokenizer<'a> {
+    tokenizer: Tokenizer<'a>,
+    state: State<'a>,
+}
+
+impl<'a> StatementTokenizer<'a> {
+    pub(crate) fn new(dialect: &'a dyn Dialect, query: &'a str) -> Self {
+        StatementTokenizer {
+            tokenizer: Tokenizer::new(dialect, ""),
+            state: State::new(query, Location::default()),
+        }
+    }
+
+    /// Where the tokens returned so far end, which is the end of the query
+    /// once they all are
+    pub(crate) fn location(&self) -> Location {
+        self.state.location()
+    }
+
+    /// The tokens up to and including the next `;`, or `None` at the end of
+    /// the query. After an error, tokenizing resumes after the next `;`.
+    pub(crate) fn next_statement(
+        &mut self,
+    ) -> Option<Result<Vec<TokenWithLocation>, TokenizerError>> {
+        self.state.peek()?;
+        let mut tokens = vec![];
+        loop {
+            let next = self.tokenizer.tokenize_next(&mut self.state, &mut |token, location| {
+                tokens.push(TokenWithLocation { token, location })
+            });
+            match next {
+                Ok(true) => {
+                    // `COPY ... FROM stdin;` ends after its data instead, and
+                    // a `;` in a versioned comment is followed by its end
+                    let end = match tokens.last().map(|t| &t.token) {
+                        Some(Token::SemiColon) => self.tokenizer.copy != CopyState::Data,
+                        Some(Token::CopyData(_)) => true,
+                        _ => false,
+                    };
+                    if end {
+                        break;
+                    }
+                }
+                Ok(false) => break,
+                Err(e) => {
+                    while let Some(ch) = self.state.next() {
+                        if ch == ';' {
+                            break;
+                        }
+                    }
+                    return Some(Err(e));
+                }
+            }
+        }
+        Some(Ok(tokens))
+    }
+}
+
+/// A part of a script for the `mysql` command-line client, see
+/// `split_client_delimiters`
+#[derive(Debug, PartialEq)]
+pub(crate) enum ClientChunk<'a> {
+    /// `DELIMITER <delimiter>`, which changes what ends the statements
+    /// after it
+    Delimiter(&'a str),
+    /// SQL ending at the delimiter, and where it starts
+    Sql(&'a str, Location),
+}
+
+/// Split a script at the `DELIMITER` commands of the `mysql` client, which
+/// must start a line outside of a statement, and at the delimiters they set.
+/// SQL ending with `;` is left for the parser to split, so with the default
+/// delimiter all the SQL between two `DELIMITER` commands is one chunk.
+pub(crate) fn split_client_delimiters<'a>(
+    sql: &'a str,
+    backslash_escapes: bool,
+) -> Vec<ClientChunk<'a>> {
+    let mut chunks = vec![];
+    let mut state = State::new(sql, Location::default());
+    let mut delimiter = ";";
+    let mut start = (0, state.location());
+    let mut line_start = true;
+    // whether a statement was started since the last delimiter, which
+    // makes a line starting with `delimiter` part of it
+    let mut in_statement = false;
+    let push_sql = |chunks: &mut Vec<ClientChunk<'a>>, start: (usize, Location), end| {
+        let chunk = &sql[start.0..end];
+        if !chunk.trim().is_empty() {
+            chunks.push(ClientChunk::Sql(chunk, start.1));
+        }
+    };
+    loop {
+        if line_start && !in_statement {
+            line_start = false;
+            let line = sql[state.pos..].split('\n').next().unwrap_or_default();
+            let mut words = line.split_whitespace();
+            if let (Some(command), Some(new_delimiter)) = (words.next(), words.next()) {
+                if command.eq_ignore_ascii_case("DELIMITER") {
+                    push_sql(&mut chunks, start, state.pos);
+                    chunks.push(ClientChunk::Delimiter(new_delimiter));
+                    delimiter = new_delimiter;
+                    while !matches!(state.next(), Some('\n') | None) {}
+                    start = (state.pos, state.location());
+                    line_start = true;
+                    continue;
+                }
+            }
+        }
+        if delimiter != ";" && sql[state.pos..].starts_with(delimiter) {
+            push_sql(&mut chunks, start, state.pos);
+            for _ in delimiter.chars() {
+                state.next();
+            }
+            start = (state.pos, state.location());
+            in_statement = false;
+            continue;
+        }
+        line_start = false;
+        match state.next() {
+            None => break,
+            Some('\n') => line_start = true,
+            Some(ch) if ch.is_whitespace() => {}
+            Some(';') if delimiter == ";" => in_statement = false,
+            Some(quote @ '\'') | Some(quote @ '"') | Some(quote @ '`') => {
+                in_statement = true;
+                loop {
+                    match state.next() {
+                        Some('\\') if quote != '`' && backslash_escapes => {
+                            state.next();
+                        }
+                        Some(ch) if ch == quote => break,
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+            }
+            Some('-') if is_double_dash_comment(&sql[state.pos..]) => {
+                while !matches!(state.peek(), Some('\n') | None) {
+                    state.next();
+                }
+            }
+            Some('#') => {
+                while !matches!(state.peek(), Some('\n') | None) {
+                    state.next();
+                }
+            }
+            Some('/') if state.peek() == Some('*') => {
+                state.next();
+                while !sql[state.pos..].starts_with("*/") && state.next().is_some() {}
+                state.next();
+                state.next();
+            }
+            Some(_) => in_statement = true,
+        }
+    }
+    push_sql(&mut chunks, start, sql.len());
+    chunks
+}
+
+/// Whether `rest`, which follows a `-`, makes it a `-- ` comment
+fn is_double_dash_comment(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    if chars.next() != Some('-') {
+        return false;
+    }
+    match chars.next() {
+        Some(ch) => ch.is_whitespace(),
+        None => true,
+    }
+}
+
 /// Read from `chars` until `predicate` returns `false` or EOF is hit.
-/// Return the characters read as String, and keep the first non-matching
-/// char available as `chars.next()`.
+/// Return the characters read as a slice of the query, and keep the first
+/// non-matching char available as `chars.next()`.
 ///
 /// 读取字符， 直到不是所有提供predicate所规范的字符结束
-fn peeking_take_while(
-    chars: &mut Peekable<Chars<'_>>,
+fn peeking_take_while<'a>(
+    chars: &mut State<'a>,
     mut predicate: impl FnMut(char) -> bool,
-) -> String {
-    let mut s = String::new();
-    while let Some(&ch) = chars.peek() {
+) -> &'a str {
+    let start = chars.pos;
+    while let Some(ch) = chars.peek() {
         if predicate(ch) {
             chars.next(); // consume
-            s.push(ch);
         } else {
             break;
         }
     }
-    s
+    chars.slice_from(start)
+}
+
+/// Decode the character following a backslash in a string literal, see
+/// https://dev.mysql.com/doc/refman/8.0/en/string-literals.html
+fn unescape_backslash(escaped: char, s: &mut String) {
+    match escaped {
+        '0' => s.push('\0'),
+        'b' => s.push('\u{8}'),
+        'n' => s.push('\n'),
+        'r' => s.push('\r'),
+        't' => s.push('\t'),
+        'Z' => s.push('\u{1a}'),
+        // `\%` and `\_` keep the backslash, for use in LIKE patterns
+        '%' | '_' => {
+            s.push('\\');
+            s.push(escaped);
+        }
+        _ => s.push(escaped),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::dialect::GenericDialect;
     use super::super::dialect::MsSqlDialect;
+    use super::super::dialect::MySqlDialect;
     use super::*;
 
     #[test]
@@ -802,18 +1623,14 @@ mod tests {
 
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
-        let tokens = tokenizer.tokenize().unwrap();
-
-        let expected = vec![
-            Token::Whitespace(Whitespace::Newline),
-            Token::Char('م'),
-            Token::Char('ص'),
-            Token::Char('ط'),
-            Token::Char('ف'),
-            Token::Char('ى'),
-            Token::make_word("h", None),
-        ];
-        compare(expected, tokens);
+        assert_eq!(
+            tokenizer.tokenize(),
+            Err(TokenizerError {
+                message: "Unexpected character 'م'".to_string(),
+                line: 2,
+                col: 1
+            })
+        );
     }
 
     #[test]
@@ -847,27 +1664,31 @@ mod tests {
     fn tokenize_invalid_string_cols() {
         let sql = String::from("\n\nSELECT * FROM table\tمصطفىh");
 
+        let dialect = GenericDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, &sql);
+        assert_eq!(
+            tokenizer.tokenize(),
+            Err(TokenizerError {
+                message: "Unexpected character 'م'".to_string(),
+                line: 3,
+                col: 21
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_placeholder() {
+        let sql = String::from("a = ?");
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
 
         let expected = vec![
-            Token::Whitespace(Whitespace::Newline),
-            Token::Whitespace(Whitespace::Newline),
-            Token::make_keyword("SELECT"),
-            Token::Whitespace(Whitespace::Space),
-            Token::Mult,
+            Token::make_word("a", None),
             Token::Whitespace(Whitespace::Space),
-            Token::make_keyword("FROM"),
+            Token::Eq,
             Token::Whitespace(Whitespace::Space),
-            Token::make_keyword("table"),
-            Token::Whitespace(Whitespace::Tab),
-            Token::Char('م'),
-            Token::Char('ص'),
-            Token::Char('ط'),
-            Token::Char('ف'),
-            Token::Char('ى'),
-            Token::make_word("h", None),
+            Token::Placeholder("?".to_string()),
         ];
         compare(expected, tokens);
     }
@@ -899,9 +1720,10 @@ mod tests {
         let tokens = tokenizer.tokenize().unwrap();
         let expected = vec![
             Token::Number("0".to_string()),
-            Token::Whitespace(Whitespace::SingleLineComment(
-                "this is a comment\n".to_string(),
-            )),
+            Token::Whitespace(Whitespace::SingleLineComment {
+                prefix: "--".to_string(),
+                comment: "this is a comment\n".to_string(),
+            }),
             Token::Number("1".to_string()),
         ];
         compare(expected, tokens);
@@ -914,9 +1736,60 @@ mod tests {
         let dialect = GenericDialect {};
         let mut tokenizer = Tokenizer::new(&dialect, &sql);
         let tokens = tokenizer.tokenize().unwrap();
-        let expected = vec![Token::Whitespace(Whitespace::SingleLineComment(
-            "this is a comment".to_string(),
-        ))];
+        let expected = vec![Token::Whitespace(Whitespace::SingleLineComment {
+            prefix: "--".to_string(),
+            comment: "this is a comment".to_string(),
+        })];
+        compare(expected, tokens);
+    }
+
+    #[test]
+    fn tokenize_hash_comment() {
+        let sql = String::from("SELECT 1#x\n");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::make_keyword("SELECT"),
+            Token::Whitespace(Whitespace::Space),
+            Token::Number("1".to_string()),
+            Token::Whitespace(Whitespace::SingleLineComment {
+                prefix: "#".to_string(),
+                comment: "x\n".to_string(),
+            }),
+        ];
+        compare(expected, tokens);
+    }
+
+    #[test]
+    fn tokenize_double_dash_without_whitespace() {
+        let sql = String::from("a--b -- c");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::make_word("a", None),
+            Token::Minus,
+            Token::Minus,
+            Token::make_word("b", None),
+            Token::Whitespace(Whitespace::Space),
+            Token::Whitespace(Whitespace::SingleLineComment {
+                prefix: "--".to_string(),
+                comment: " c".to_string(),
+            }),
+        ];
+        compare(expected, tokens);
+
+        // other dialects treat `--` as a comment regardless
+        let dialect = GenericDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::make_word("a", None),
+            Token::Whitespace(Whitespace::SingleLineComment {
+                prefix: "--".to_string(),
+                comment: "b -- c".to_string(),
+            }),
+        ];
         compare(expected, tokens);
     }
 
@@ -952,6 +1825,183 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_versioned_comment() {
+        let sql = String::from("/*!40101 SET a=1 */");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::Whitespace(Whitespace::VersionedCommentStart(Some(40101))),
+            Token::Whitespace(Whitespace::Space),
+            Token::make_keyword("SET"),
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("a", None),
+            Token::Eq,
+            Token::Number(String::from("1")),
+            Token::Whitespace(Whitespace::Space),
+            Token::Whitespace(Whitespace::VersionedCommentEnd),
+        ];
+        compare(expected, tokens);
+
+        // too new for the server version: an ordinary comment
+        let tokens = Tokenizer::new(&dialect, &sql)
+            .with_mysql_version(40000)
+            .tokenize()
+            .unwrap();
+        let expected = vec![Token::Whitespace(Whitespace::MultiLineComment(
+            "!40101 SET a=1 ".to_string(),
+        ))];
+        compare(expected.clone(), tokens);
+
+        // other dialects keep it as a comment
+        let dialect = GenericDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        compare(expected, tokens);
+    }
+
+    #[test]
+    fn tokenize_backslash_escapes() {
+        let sql = String::from(r"'it\'s' 'a\\b\n\%'");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::SingleQuotedString("it's".to_string()),
+            Token::Whitespace(Whitespace::Space),
+            Token::SingleQuotedString("a\\b\n\\%".to_string()),
+        ];
+        compare(expected, tokens);
+
+        let sql = String::from(r"'a\b\n'");
+        let tokens = Tokenizer::new(&dialect, &sql)
+            .with_no_backslash_escapes(true)
+            .tokenize()
+            .unwrap();
+        compare(vec![Token::SingleQuotedString(r"a\b\n".to_string())], tokens);
+    }
+
+    #[test]
+    fn tokenize_doubled_quotes() {
+        let sql = String::from("'it''s' `weird``name` \"a\"\"b\"");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::SingleQuotedString("it's".to_string()),
+            Token::Whitespace(Whitespace::Space),
+            Token::make_word("weird`name", Some('`')),
+            Token::Whitespace(Whitespace::Space),
+            Token::DoubleQuotedString("a\"b".to_string()),
+        ];
+        compare(expected, tokens);
+
+        let dialect = GenericDialect {};
+        let tokens = Tokenizer::new(&dialect, "\"a\"\"b\"").tokenize().unwrap();
+        compare(vec![Token::make_word("a\"b", Some('"'))], tokens);
+        assert_eq!(Token::make_word("weird`name", Some('`')).to_string(), "`weird``name`");
+    }
+
+    #[test]
+    fn tokenize_ansi_quotes() {
+        let sql = String::from("\"a\" 'b'");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+        let expected = vec![
+            Token::DoubleQuotedString("a".to_string()),
+            Token::Whitespace(Whitespace::Space),
+            Token::SingleQuotedString("b".to_string()),
+        ];
+        compare(expected, tokens);
+
+        let tokens = Tokenizer::new(&dialect, &sql)
+            .with_ansi_quotes(true)
+            .tokenize()
+            .unwrap();
+        let expected = vec![
+            Token::make_word("a", Some('"')),
+            Token::Whitespace(Whitespace::Space),
+            Token::SingleQuotedString("b".to_string()),
+        ];
+        compare(expected, tokens);
+    }
+
+    #[test]
+    fn tokenize_with_location() {
+        let sql = String::from("SELECT a,\n  'é' /*!1 b */");
+
+        let dialect = MySqlDialect {};
+        let tokens = Tokenizer::new(&dialect, &sql)
+            .tokenize_with_location()
+            .unwrap();
+        let location = |line, column, offset| Location {
+            line,
+            column,
+            offset,
+        };
+        let expected = vec![
+            (Token::make_keyword("SELECT"), location(1, 1, 0)),
+            (Token::Whitespace(Whitespace::Space), location(1, 7, 6)),
+            (Token::make_word("a", None), location(1, 8, 7)),
+            (Token::Comma, location(1, 9, 8)),
+            (Token::Whitespace(Whitespace::Newline), location(1, 10, 9)),
+            (Token::Whitespace(Whitespace::Space), location(2, 1, 10)),
+            (Token::Whitespace(Whitespace::Space), location(2, 2, 11)),
+            (Token::SingleQuotedString("é".to_string()), location(2, 3, 12)),
+            (Token::Whitespace(Whitespace::Space), location(2, 6, 16)),
+            (
+                Token::Whitespace(Whitespace::VersionedCommentStart(None)),
+                location(2, 7, 17),
+            ),
+            (Token::Number("1".to_string()), location(2, 10, 20)),
+            (Token::Whitespace(Whitespace::Space), location(2, 11, 21)),
+            (Token::make_word("b", None), location(2, 12, 22)),
+            (Token::Whitespace(Whitespace::Space), location(2, 13, 23)),
+            (
+                Token::Whitespace(Whitespace::VersionedCommentEnd),
+                location(2, 14, 24),
+            ),
+        ];
+        let tokens: Vec<(Token, Location)> =
+            tokens.into_iter().map(|t| (t.token, t.location)).collect();
+        assert_eq!(expected, tokens);
+    }
+
+    #[test]
+    fn tokenize_iter() {
+        let sql = "SELECT a != 'it''s',\r\n\t\"x\" # c\n/*!40101 X'af' */ -- d";
+        let dialect = MySqlDialect {};
+        let mut tokenizer = Tokenizer::new(&dialect, sql);
+        let tokens: Vec<TokenWithLocation> = tokenizer.iter().map(Result::unwrap).collect();
+        // the texts of the tokens make up the query
+        let mut text = String::new();
+        for (i, token) in tokens.iter().enumerate() {
+            let end = tokens.get(i + 1).map_or(sql.len(), |t| t.location.offset);
+            text.push_str(&sql[token.location.offset..end]);
+        }
+        assert_eq!(sql, text);
+
+        let mut tokenizer = Tokenizer::new(&dialect, sql).with_skip_whitespace(true);
+        let tokens: Vec<Token> = tokenizer.iter().map(|t| t.unwrap().token).collect();
+        let expected = vec![
+            Token::make_keyword("SELECT"),
+            Token::make_word("a", None),
+            Token::Neq,
+            Token::SingleQuotedString("it's".to_string()),
+            Token::Comma,
+            Token::DoubleQuotedString("x".to_string()),
+            Token::HexStringLiteral("af".to_string()),
+        ];
+        compare(expected, tokens);
+
+        let mut tokenizer = Tokenizer::new(&dialect, "a 'b").with_skip_whitespace(true);
+        let mut iter = tokenizer.iter();
+        assert_eq!(Token::make_word("a", None), iter.next().unwrap().unwrap().token);
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
     #[test]
     fn tokenize_mismatched_quotes() {
         let sql = String::from("\"foo");
@@ -0,0 +1,20 @@
+SELECT * FROM users WHERE id = ?;
+SELECT id, name FROM users WHERE email = ? AND deleted_at IS NULL LIMIT ?;
+SELECT * FROM orders WHERE user_id = ? ORDER BY created_at DESC LIMIT ? OFFSET ?;
+SELECT * FROM orders WHERE created_at BETWEEN ? AND ?;
+SELECT * FROM orders WHERE status IN (?, ?, ?) AND total > ?;
+SELECT COUNT(*) FROM orders WHERE user_id = ? GROUP BY status HAVING COUNT(*) > ?;
+SELECT * FROM sessions WHERE expires_at > NOW() - INTERVAL ? SECOND;
+SELECT DATE_ADD(?, INTERVAL ? DAY);
+SELECT * FROM users WHERE name LIKE ? ORDER BY name LIMIT ?;
+SELECT u.id, o.total FROM users AS u JOIN orders AS o ON o.user_id = u.id WHERE u.id = ? AND o.total >= ?;
+SELECT * FROM items WHERE id IN (SELECT item_id FROM order_items WHERE order_id = ?);
+SELECT CASE WHEN score >= ? THEN ? ELSE ? END FROM results WHERE id = ?;
+SELECT * FROM accounts WHERE id = ? FOR UPDATE;
+SELECT * FROM events WHERE JSON_EXTRACT(payload, ?) = ? LIMIT ?;
+INSERT INTO users (name, email) VALUES (?, ?);
+INSERT INTO counters (id, cnt) VALUES (?, ?), (?, ?) ON DUPLICATE KEY UPDATE cnt = cnt + VALUES(cnt);
+REPLACE INTO settings (k, v) VALUES (?, ?);
+UPDATE users SET name = ?, updated_at = NOW() WHERE id = ?;
+UPDATE jobs SET attempts = attempts + ? WHERE id = ? LIMIT ?;
+DELETE FROM sessions WHERE expires_at < ?;
@@ -0,0 +1,16 @@
+CREATE TABLE `articles` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `author_id` int DEFAULT NULL,
+  `email` varchar(255) NOT NULL,
+  `slug` varchar(64) NOT NULL,
+  `body` text,
+  `geom` geometry NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_email` (`email`),
+  UNIQUE KEY `uk_slug` (`slug`(32)) USING BTREE,
+  KEY `fk_author` (`author_id`),
+  KEY `idx_author_email` (`author_id`,`email` DESC) COMMENT 'lookups',
+  FULLTEXT KEY `ft_body` (`body`) /*!50100 WITH PARSER `ngram` */ ,
+  SPATIAL KEY `sp_geom` (`geom`),
+  CONSTRAINT `fk_author` FOREIGN KEY (`author_id`) REFERENCES `authors` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB AUTO_INCREMENT=3 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci
@@ -23,7 +23,7 @@ use matches::assert_matches;
 use sqlparser::ast::*;
 use sqlparser::dialect::keywords::ALL_KEYWORDS;
 use sqlparser::parser::{Parser, ParserError};
-use sqlparser::test_utils::{all_dialects, expr_from_projection, number, only};
+use sqlparser::test_utils::{all_dialects, expr_from_projection, number, only, single_quoted, syntax_error_message};
 
 #[test]
 fn parse_insert_values() {
@@ -90,8 +90,8 @@ fn parse_insert_invalid() {
     let sql = "INSERT public.customer (id, name, active) VALUES (1, 2, 3)";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError("Expected INTO, found: public".to_string()),
-        res.unwrap_err()
+        "Expected INTO, found: public".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -110,15 +110,15 @@ fn parse_update() {
                 assignments,
                 vec![
                     Assignment {
-                        id: "a".into(),
+                        id: vec!["a".into()],
                         value: Expr::Value(number("1")),
                     },
                     Assignment {
-                        id: "b".into(),
+                        id: vec!["b".into()],
                         value: Expr::Value(number("2")),
                     },
                     Assignment {
-                        id: "c".into(),
+                        id: vec!["c".into()],
                         value: Expr::Value(number("3")),
                     },
                 ]
@@ -133,27 +133,27 @@ fn parse_update() {
     let sql = "UPDATE t WHERE 1";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError("Expected SET, found: WHERE".to_string()),
-        res.unwrap_err()
+        "Expected SET, found: WHERE".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let sql = "UPDATE t SET a = 1 extrabadstuff";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: extrabadstuff".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: extrabadstuff".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
 #[test]
 fn parse_invalid_table_name() {
-    let ast = all_dialects().run_parser_method("db.public..customer", Parser::parse_object_name);
+    let ast = all_dialects().run_parser_method("db.public..customer", |parser| parser.parse_object_name());
     assert!(ast.is_err());
 }
 
 #[test]
 fn parse_no_table_name() {
-    let ast = all_dialects().run_parser_method("", Parser::parse_object_name);
+    let ast = all_dialects().run_parser_method("", |parser| parser.parse_object_name());
     assert!(ast.is_err());
 }
 
@@ -209,7 +209,7 @@ fn parse_top_level() {
 fn parse_simple_select() {
     let sql = "SELECT id, fname, lname FROM customer WHERE id = 1 LIMIT 5";
     let select = verified_only_select(sql);
-    assert_eq!(false, select.distinct);
+    assert_eq!(None, select.distinct);
     assert_eq!(3, select.projection.len());
     let select = verified_query(sql);
     assert_eq!(Some(Expr::Value(number("5"))), select.limit);
@@ -229,7 +229,7 @@ fn parse_limit_is_not_an_alias() {
 fn parse_select_distinct() {
     let sql = "SELECT DISTINCT name FROM customer";
     let select = verified_only_select(sql);
-    assert_eq!(true, select.distinct);
+    assert_eq!(Some(Distinct::Distinct), select.distinct);
     assert_eq!(
         &SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("name"))),
         only(&select.projection)
@@ -238,15 +238,16 @@ fn parse_select_distinct() {
 
 #[test]
 fn parse_select_all() {
-    one_statement_parses_to("SELECT ALL name FROM customer", "SELECT name FROM customer");
+    let select = verified_only_select("SELECT ALL name FROM customer");
+    assert_eq!(Some(Distinct::All), select.distinct);
 }
 
 #[test]
 fn parse_select_all_distinct() {
     let result = parse_sql_statements("SELECT ALL DISTINCT name FROM customer");
     assert_eq!(
-        ParserError::ParserError("Cannot specify both ALL and DISTINCT".to_string()),
-        result.unwrap_err(),
+        "Cannot specify both ALL and DISTINCT".to_string(),
+        syntax_error_message(result.unwrap_err())
     );
 }
 
@@ -307,14 +308,14 @@ fn parse_column_aliases() {
 fn test_eof_after_as() {
     let res = parse_sql_statements("SELECT foo AS");
     assert_eq!(
-        ParserError::ParserError("Expected an identifier after AS, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected an identifier after AS, found: EOF".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = parse_sql_statements("SELECT 1 FROM foo AS");
     assert_eq!(
-        ParserError::ParserError("Expected an identifier after AS, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected an identifier after AS, found: EOF".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -328,6 +329,7 @@ fn parse_select_count_wildcard() {
             args: vec![Expr::Wildcard],
             over: None,
             distinct: false,
+            special: false,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -346,6 +348,7 @@ fn parse_select_count_distinct() {
             }],
             over: None,
             distinct: true,
+            special: false,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -358,8 +361,8 @@ fn parse_select_count_distinct() {
     let sql = "SELECT COUNT(ALL DISTINCT + x) FROM customer";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError("Cannot specify both ALL and DISTINCT".to_string()),
-        res.unwrap_err()
+        "Cannot specify both ALL and DISTINCT".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -374,8 +377,8 @@ fn parse_not() {
 fn parse_invalid_infix_not() {
     let res = parse_sql_statements("SELECT c FROM t WHERE c NOT (");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: NOT".to_string()),
-        res.unwrap_err(),
+        "Expected end of statement, found: NOT".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -436,9 +439,7 @@ fn parse_escaped_single_quote_string_predicate() {
         Some(Expr::BinaryOp {
             left: Box::new(Expr::Identifier(Ident::new("salary"))),
             op: NotEq,
-            right: Box::new(Expr::Value(Value::SingleQuotedString(
-                "Jim's salary".to_string()
-            )))
+            right: Box::new(Expr::Value(single_quoted("Jim's salary", false)))
         }),
         ast.selection,
     );
@@ -574,9 +575,9 @@ fn parse_not_precedence() {
         Expr::UnaryOp {
             op: UnaryOperator::Not,
             expr: Box::new(Expr::BinaryOp {
-                left: Box::new(Expr::Value(Value::SingleQuotedString("a".into()))),
+                left: Box::new(Expr::Value(single_quoted("a", false))),
                 op: BinaryOperator::NotLike,
-                right: Box::new(Expr::Value(Value::SingleQuotedString("b".into()))),
+                right: Box::new(Expr::Value(single_quoted("b", false))),
             }),
         },
     );
@@ -589,7 +590,7 @@ fn parse_not_precedence() {
             op: UnaryOperator::Not,
             expr: Box::new(Expr::InList {
                 expr: Box::new(Expr::Identifier("a".into())),
-                list: vec![Expr::Value(Value::SingleQuotedString("a".into()))],
+                list: vec![Expr::Value(single_quoted("a", false))],
                 negated: true,
             }),
         },
@@ -612,7 +613,7 @@ fn parse_like() {
                 } else {
                     BinaryOperator::Like
                 },
-                right: Box::new(Expr::Value(Value::SingleQuotedString("%a".to_string()))),
+                right: Box::new(Expr::Value(single_quoted("%a", false))),
             },
             select.selection.unwrap()
         );
@@ -632,7 +633,7 @@ fn parse_like() {
                 } else {
                     BinaryOperator::Like
                 },
-                right: Box::new(Expr::Value(Value::SingleQuotedString("%a".to_string()))),
+                right: Box::new(Expr::Value(single_quoted("%a", false))),
             })),
             select.selection.unwrap()
         );
@@ -653,8 +654,8 @@ fn parse_in_list() {
             Expr::InList {
                 expr: Box::new(Expr::Identifier(Ident::new("segment"))),
                 list: vec![
-                    Expr::Value(Value::SingleQuotedString("HIGH".to_string())),
-                    Expr::Value(Value::SingleQuotedString("MED".to_string())),
+                    Expr::Value(single_quoted("HIGH", false)),
+                    Expr::Value(single_quoted("MED", false)),
                 ],
                 negated,
             },
@@ -885,7 +886,8 @@ fn parse_select_having() {
                 name: ObjectName(vec![Ident::new("COUNT")]),
                 args: vec![Expr::Wildcard],
                 over: None,
-                distinct: false
+                distinct: false,
+                special: false,
             })),
             op: BinaryOperator::Gt,
             right: Box::new(Expr::Value(number("1")))
@@ -957,8 +959,8 @@ fn parse_extract() {
 
     let res = parse_sql_statements("SELECT EXTRACT(MILLISECOND FROM d)");
     assert_eq!(
-        ParserError::ParserError("Expected date/time field, found: MILLISECOND".to_string()),
-        res.unwrap_err()
+        "Expected date/time field, found: MILLISECOND".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -977,9 +979,7 @@ fn parse_listagg() {
 
     let expr = Box::new(Expr::Identifier(Ident::new("dateid")));
     let on_overflow = Some(ListAggOnOverflow::Truncate {
-        filler: Some(Box::new(Expr::Value(Value::SingleQuotedString(
-            "%".to_string(),
-        )))),
+        filler: Some(Box::new(Expr::Value(single_quoted("%", false)))),
         with_count: false,
     });
     let within_group = vec![
@@ -1004,9 +1004,7 @@ fn parse_listagg() {
         &Expr::ListAgg(ListAgg {
             distinct: true,
             expr,
-            separator: Some(Box::new(Expr::Value(Value::SingleQuotedString(
-                ", ".to_string()
-            )))),
+            separator: Some(Box::new(Expr::Value(single_quoted(", ", false)))),
             on_overflow,
             within_group
         }),
@@ -1058,6 +1056,7 @@ fn parse_create_table() {
                             name: None,
                             option: ColumnOption::NotNull
                         }],
+                        ordinal: 1,
                     },
                     ColumnDef {
                         name: "lat".into(),
@@ -1067,12 +1066,14 @@ fn parse_create_table() {
                             name: None,
                             option: ColumnOption::Null
                         }],
+                        ordinal: 2,
                     },
                     ColumnDef {
                         name: "lng".into(),
                         data_type: DataType::Double,
                         collation: None,
                         options: vec![],
+                        ordinal: 3,
                     },
                     ColumnDef {
                         name: "constrained".into(),
@@ -1100,6 +1101,7 @@ fn parse_create_table() {
                                 option: ColumnOption::Check(verified_expr("constrained > 0")),
                             }
                         ],
+                        ordinal: 4,
                     },
                     ColumnDef {
                         name: "ref".into(),
@@ -1113,7 +1115,8 @@ fn parse_create_table() {
                                 on_delete: None,
                                 on_update: None,
                             }
-                        }]
+                        }],
+                        ordinal: 5,
                     },
                     ColumnDef {
                         name: "ref2".into(),
@@ -1127,7 +1130,8 @@ fn parse_create_table() {
                                 on_delete: Some(ReferentialAction::Cascade),
                                 on_update: Some(ReferentialAction::NoAction),
                             }
-                        },]
+                        },],
+                        ordinal: 6,
                     }
                 ]
             );
@@ -1184,7 +1188,7 @@ fn parse_assert_message() {
             message: Some(message),
         } => {
             match message {
-                Expr::Value(Value::SingleQuotedString(s)) => assert_eq!(s, "No rows in table"),
+                Expr::Value(Value::SingleQuotedString { value: s, .. }) => assert_eq!(s, "No rows in table"),
                 _ => unreachable!(),
             };
         }
@@ -1197,7 +1201,7 @@ fn parse_create_schema() {
     let sql = "CREATE SCHEMA X";
 
     match verified_stmt(sql) {
-        Statement::CreateSchema { schema_name } => {
+        Statement::CreateSchema { schema_name, .. } => {
             assert_eq!(schema_name.to_string(), "X".to_owned())
         }
         _ => unreachable!(),
@@ -1267,7 +1271,7 @@ fn parse_create_table_with_options() {
                 vec![
                     SqlOption {
                         name: "foo".into(),
-                        value: Value::SingleQuotedString("bar".into())
+                        value: single_quoted("bar", false)
                     },
                     SqlOption {
                         name: "a".into(),
@@ -1326,6 +1330,7 @@ fn parse_create_external_table() {
                             name: None,
                             option: ColumnOption::NotNull
                         }],
+                        ordinal: 1,
                     },
                     ColumnDef {
                         name: "lat".into(),
@@ -1335,12 +1340,14 @@ fn parse_create_external_table() {
                             name: None,
                             option: ColumnOption::Null
                         }],
+                        ordinal: 2,
                     },
                     ColumnDef {
                         name: "lng".into(),
                         data_type: DataType::Double,
                         collation: None,
                         options: vec![],
+                        ordinal: 3,
                     },
                 ]
             );
@@ -1426,13 +1433,13 @@ fn parse_alter_table_constraints() {
     check_one("CONSTRAINT uk_task UNIQUE (report_date, task_id)");
     check_one(
         "CONSTRAINT customer_address_id_fkey FOREIGN KEY (address_id) \
-         REFERENCES public.address(address_id)",
+         REFERENCES public.address (address_id)",
     );
     check_one("CONSTRAINT ck CHECK (rtrim(ltrim(REF_CODE)) <> '')");
 
     check_one("PRIMARY KEY (foo, bar)");
     check_one("UNIQUE (id)");
-    check_one("FOREIGN KEY (foo, bar) REFERENCES AnotherTable(foo, bar)");
+    check_one("FOREIGN KEY (foo, bar) REFERENCES AnotherTable (foo, bar)");
     check_one("CHECK (end_date > start_date OR end_date IS NULL)");
 
     fn check_one(constraint_text: &str) {
@@ -1487,16 +1494,14 @@ fn parse_alter_table_drop_column() {
 fn parse_bad_constraint() {
     let res = parse_sql_statements("ALTER TABLE tab ADD");
     assert_eq!(
-        ParserError::ParserError("Expected identifier, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected alter table index def , found: EOF".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = parse_sql_statements("CREATE TABLE tab (foo int,");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected column name or constraint definition, found: EOF".to_string()
-        ),
-        res.unwrap_err()
+        "Expected column name or constraint definition, found: EOF".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -1510,6 +1515,7 @@ fn parse_scalar_function_in_projection() {
             args: vec![Expr::Identifier(Ident::new("id"))],
             over: None,
             distinct: false,
+            special: false,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1543,6 +1549,7 @@ fn parse_window_functions() {
                 window_frame: None,
             }),
             distinct: false,
+            special: false,
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -1578,7 +1585,7 @@ fn parse_literal_string() {
     let select = verified_only_select(sql);
     assert_eq!(3, select.projection.len());
     assert_eq!(
-        &Expr::Value(Value::SingleQuotedString("one".to_string())),
+        &Expr::Value(single_quoted("one", false)),
         expr_from_projection(&select.projection[0])
     );
     assert_eq!(
@@ -1625,7 +1632,7 @@ fn parse_literal_timestamp() {
     let select = verified_only_select(sql);
     assert_eq!(
         &Expr::TypedString {
-            data_type: DataType::Timestamp,
+            data_type: DataType::Timestamp(None),
             value: "1999-01-01 01:23:34".into()
         },
         expr_from_projection(only(&select.projection)),
@@ -1714,14 +1721,14 @@ fn parse_literal_interval() {
 
     let result = parse_sql_statements("SELECT INTERVAL '1' SECOND TO SECOND");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: SECOND".to_string()),
-        result.unwrap_err(),
+        "Expected end of statement, found: SECOND".to_string(),
+        syntax_error_message(result.unwrap_err())
     );
 
     let result = parse_sql_statements("SELECT INTERVAL '10' HOUR (1) TO HOUR (2)");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: (".to_string()),
-        result.unwrap_err(),
+        "Expected end of statement, found: (".to_string(),
+        syntax_error_message(result.unwrap_err())
     );
 
     verified_only_select("SELECT INTERVAL '1' YEAR");
@@ -1793,6 +1800,7 @@ fn parse_delimited_identifiers() {
             args: vec![],
             over: None,
             distinct: false,
+            special: false,
         }),
         expr_from_projection(&select.projection[1]),
     );
@@ -1855,13 +1863,11 @@ fn parse_searched_case_expr() {
                 }
             ],
             results: vec![
-                Expr::Value(Value::SingleQuotedString("null".to_string())),
-                Expr::Value(Value::SingleQuotedString("=0".to_string())),
-                Expr::Value(Value::SingleQuotedString(">=0".to_string()))
+                Expr::Value(single_quoted("null", false)),
+                Expr::Value(single_quoted("=0", false)),
+                Expr::Value(single_quoted(">=0", false))
             ],
-            else_result: Some(Box::new(Expr::Value(Value::SingleQuotedString(
-                "<0".to_string()
-            ))))
+            else_result: Some(Box::new(Expr::Value(single_quoted("<0", false))))
         },
         expr_from_projection(only(&select.projection)),
     );
@@ -1877,10 +1883,8 @@ fn parse_simple_case_expr() {
         &Case {
             operand: Some(Box::new(Identifier(Ident::new("foo")))),
             conditions: vec![Expr::Value(number("1"))],
-            results: vec![Expr::Value(Value::SingleQuotedString("Y".to_string())),],
-            else_result: Some(Box::new(Expr::Value(Value::SingleQuotedString(
-                "N".to_string()
-            ))))
+            results: vec![Expr::Value(single_quoted("Y", false)),],
+            else_result: Some(Box::new(Expr::Value(single_quoted("N", false))))
         },
         expr_from_projection(only(&select.projection)),
     );
@@ -2122,8 +2126,8 @@ fn parse_natural_join() {
 
     let sql = "SELECT * FROM t1 natural";
     assert_eq!(
-        ParserError::ParserError("Expected a join type after NATURAL, found: EOF".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Expected a join type after NATURAL, found: EOF".to_string(),
+        syntax_error_message(parse_sql_statements(sql).unwrap_err())
     );
 }
 
@@ -2227,8 +2231,8 @@ fn parse_join_syntax_variants() {
 
     let res = parse_sql_statements("SELECT * FROM a OUTER JOIN b ON 1");
     assert_eq!(
-        ParserError::ParserError("Expected APPLY, found: JOIN".to_string()),
-        res.unwrap_err()
+        "Expected APPLY, found: JOIN".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -2411,8 +2415,8 @@ fn parse_multiple_statements() {
         // Check that forgetting the semicolon results in an error:
         let res = parse_sql_statements(&(sql1.to_owned() + " " + sql2_kw + sql2_rest));
         assert_eq!(
-            ParserError::ParserError("Expected end of statement, found: ".to_string() + sql2_kw),
-            res.unwrap_err()
+            "Expected end of statement, found: ".to_string() + sql2_kw,
+            syntax_error_message(res.unwrap_err())
         );
     }
     test_with("SELECT foo", "SELECT", " bar");
@@ -2468,18 +2472,14 @@ fn parse_exists_subquery() {
 
     let res = parse_sql_statements("SELECT EXISTS (");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT, VALUES, or a subquery in the query body, found: EOF".to_string()
-        ),
-        res.unwrap_err(),
+        "Expected SELECT, VALUES, or a subquery in the query body, found: EOF".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = parse_sql_statements("SELECT EXISTS (NULL)");
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT, VALUES, or a subquery in the query body, found: NULL".to_string()
-        ),
-        res.unwrap_err(),
+        "Expected SELECT, VALUES, or a subquery in the query body, found: NULL".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -2513,7 +2513,7 @@ fn parse_create_view_with_options() {
                 vec![
                     SqlOption {
                         name: "foo".into(),
-                        value: Value::SingleQuotedString("bar".into())
+                        value: single_quoted("bar", false)
                     },
                     SqlOption {
                         name: "a".into(),
@@ -2611,14 +2611,14 @@ fn parse_drop_table() {
 
     let sql = "DROP TABLE";
     assert_eq!(
-        ParserError::ParserError("Expected identifier, found: EOF".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Expected identifier, found: EOF".to_string(),
+        syntax_error_message(parse_sql_statements(sql).unwrap_err())
     );
 
     let sql = "DROP TABLE IF EXISTS foo, bar CASCADE RESTRICT";
     assert_eq!(
-        ParserError::ParserError("Cannot specify both CASCADE and RESTRICT in DROP".to_string()),
-        parse_sql_statements(sql).unwrap_err(),
+        "Cannot specify both CASCADE and RESTRICT in DROP".to_string(),
+        syntax_error_message(parse_sql_statements(sql).unwrap_err())
     );
 }
 
@@ -2643,8 +2643,8 @@ fn parse_drop_view() {
 fn parse_invalid_subquery_without_parens() {
     let res = parse_sql_statements("SELECT SELECT 1 FROM bar WHERE 1=1 FROM baz");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: 1".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: 1".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -2857,19 +2857,15 @@ fn lateral_derived() {
     let sql = "SELECT * FROM customer LEFT JOIN LATERAL generate_series(1, customer.id)";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Expected subquery after LATERAL, found: generate_series".to_string()
-        ),
-        res.unwrap_err()
+        "Expected subquery after LATERAL, found: generate_series".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let sql = "SELECT * FROM a LEFT JOIN LATERAL (b CROSS JOIN c)";
     let res = parse_sql_statements(sql);
     assert_eq!(
-        ParserError::ParserError(
-            "Expected SELECT, VALUES, or a subquery in the query body, found: b".to_string()
-        ),
-        res.unwrap_err()
+        "Expected SELECT, VALUES, or a subquery in the query body, found: b".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -2927,20 +2923,20 @@ fn parse_start_transaction() {
 
     let res = parse_sql_statements("START TRANSACTION ISOLATION LEVEL BAD");
     assert_eq!(
-        ParserError::ParserError("Expected isolation level, found: BAD".to_string()),
-        res.unwrap_err()
+        "Expected isolation level, found: BAD".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = parse_sql_statements("START TRANSACTION BAD");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: BAD".to_string()),
-        res.unwrap_err()
+        "Expected end of statement, found: BAD".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = parse_sql_statements("START TRANSACTION READ ONLY,");
     assert_eq!(
-        ParserError::ParserError("Expected transaction mode, found: EOF".to_string()),
-        res.unwrap_err()
+        "Expected transaction mode, found: EOF".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -3015,8 +3011,15 @@ fn ensure_multiple_dialects_are_tested() {
 
 #[test]
 fn parse_create_index() {
-    let sql = "CREATE UNIQUE INDEX IF NOT EXISTS idx_name ON test(name,age);";
-    let ident_vec = vec![Ident::new("name"), Ident::new("age")];
+    let sql = "CREATE UNIQUE INDEX IF NOT EXISTS idx_name ON test (name, age)";
+    let ident_vec: Vec<IndexKeyPart> = vec![Ident::new("name"), Ident::new("age")]
+        .into_iter()
+        .map(|name| IndexKeyPart::Column {
+            name,
+            length: None,
+            asc: None,
+        })
+        .collect();
     match verified_stmt(sql) {
         Statement::CreateIndex {
             name,
@@ -3024,6 +3027,7 @@ fn parse_create_index() {
             columns,
             unique,
             if_not_exists,
+            ..
         } => {
             assert_eq!("idx_name", name.to_string());
             assert_eq!("test", table_name.to_string());
@@ -3033,6 +3037,10 @@ fn parse_create_index() {
         }
         _ => unreachable!(),
     }
+    one_statement_parses_to(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_name ON test(name,age);",
+        sql,
+    );
 }
 #[test]
 fn parse_drop_index() {
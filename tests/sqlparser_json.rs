@@ -0,0 +1,84 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "json")]
+//! Test the JSON shape of the AST, run with `cargo test --features json`
+
+use sqlparser::ast_json::{from_json, to_json, JsonError};
+use sqlparser::dialect::MySqlDialect;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+#[test]
+fn ast_json_snapshot() {
+    // pairs of a statement and its JSON, separated by blank lines. A change
+    // here breaks readers of the JSON, see `SCHEMA_VERSION`.
+    let snapshot = include_str!("ast_json_snapshot.txt");
+    for case in snapshot.split("\n\n") {
+        let mut lines = case.lines();
+        let (sql, json) = (lines.next().unwrap(), lines.next().unwrap());
+        let statement = Parser::parse_sql(&MySqlDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(json, to_json(&statement), "the JSON of {} changed", sql);
+        assert_eq!(statement, from_json(json).unwrap());
+    }
+}
+
+#[test]
+fn ast_json_version() {
+    let json = to_json(&Parser::parse_sql(&MySqlDialect {}, "SELECT 1").unwrap()[0]);
+    let newer = json.replacen("\"version\":1", "\"version\":2", 1);
+    assert!(matches!(from_json(&newer), Err(JsonError::UnsupportedVersion(2))));
+    assert!(matches!(from_json("{}"), Err(JsonError::Json(_))));
+}
+
+#[test]
+fn ast_json_defaults() {
+    // fields with `#[serde(default)]` may be missing
+    let statement = Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (a INT, KEY k (a))")
+        .unwrap()
+        .pop()
+        .unwrap();
+    let json = to_json(&statement).replacen(",\"index_type_after_key_parts\":false", "", 1);
+    assert!(!json.contains("index_type_after_key_parts"));
+    assert_eq!(statement, from_json(&json).unwrap());
+
+    let statement = Parser::parse_sql(&MySqlDialect {}, "INSERT t (a) VALUES (1)")
+        .unwrap()
+        .pop()
+        .unwrap();
+    let json = to_json(&statement)
+        .replacen("\"comments\":[],", "", 1)
+        .replacen("\"into\":false,", "", 1)
+        .replacen("\"set\":false,", "", 1);
+    for field in &["\"comments\"", "\"into\"", "\"set\""] {
+        assert!(!json.contains(field), "{} in {}", field, json);
+    }
+    assert_eq!(statement, from_json(&json).unwrap());
+}
+
+#[test]
+fn tokens_and_errors_serialize() {
+    let tokens = Tokenizer::new(&MySqlDialect {}, "SELECT `a` -- x\nFROM t")
+        .tokenize_with_location()
+        .unwrap();
+    let json = serde_json::to_string(&tokens).unwrap();
+    assert_eq!(tokens, serde_json::from_str::<Vec<_>>(&json).unwrap());
+    assert_eq!(tokens[0].token, Token::make_keyword("SELECT"));
+
+    let error = Parser::parse_sql(&MySqlDialect {}, "SELECT * FROM").unwrap_err();
+    let json = serde_json::to_string(&error).unwrap();
+    assert!(json.starts_with("{\"SyntaxError\":"), "{}", json);
+    assert_eq!(error, serde_json::from_str::<ParserError>(&json).unwrap());
+}
@@ -15,8 +15,15 @@
 //! Test SQL syntax specific to MySQL. The parser based on the generic dialect
 //! is also tested (on the inputs it can handle).
 
+use std::convert::TryFrom;
+
 use sqlparser::ast::*;
-use sqlparser::dialect::{GenericDialect, MySqlDialect};
+use sqlparser::dialect::{
+    AnsiDialect, DBType, Dialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+};
+use sqlparser::format::{format_statement, FormatOptions};
+use sqlparser::parser::{Parser, ParserError, ParserOptions, SyntaxError, SyntaxErrorKind};
+use sqlparser::tokenizer::{Location, Token, Tokenizer};
 use sqlparser::test_utils::*;
 
 #[test]
@@ -32,7 +39,11 @@ fn parse_show_columns() {
         Statement::ShowColumns {
             extended: false,
             full: false,
+            fields: false,
+            table_in: false,
             table_name: table_name.clone(),
+            database: None,
+            database_in: false,
             filter: None,
         }
     );
@@ -41,7 +52,11 @@ fn parse_show_columns() {
         Statement::ShowColumns {
             extended: false,
             full: false,
+            fields: false,
+            table_in: false,
             table_name: ObjectName(vec![Ident::new("mydb"), Ident::new("mytable")]),
+            database: None,
+            database_in: false,
             filter: None,
         }
     );
@@ -50,7 +65,11 @@ fn parse_show_columns() {
         Statement::ShowColumns {
             extended: true,
             full: false,
+            fields: false,
+            table_in: false,
             table_name: table_name.clone(),
+            database: None,
+            database_in: false,
             filter: None,
         }
     );
@@ -59,7 +78,11 @@ fn parse_show_columns() {
         Statement::ShowColumns {
             extended: false,
             full: true,
+            fields: false,
+            table_in: false,
             table_name: table_name.clone(),
+            database: None,
+            database_in: false,
             filter: None,
         }
     );
@@ -68,7 +91,11 @@ fn parse_show_columns() {
         Statement::ShowColumns {
             extended: false,
             full: false,
+            fields: false,
+            table_in: false,
             table_name: table_name.clone(),
+            database: None,
+            database_in: false,
             filter: Some(ShowStatementFilter::Like("pattern".into())),
         }
     );
@@ -77,24 +104,4357 @@ fn parse_show_columns() {
         Statement::ShowColumns {
             extended: false,
             full: false,
+            fields: false,
+            table_in: false,
             table_name,
+            database: None,
+            database_in: false,
             filter: Some(ShowStatementFilter::Where(
                 mysql_and_generic().verified_expr("1 = 2")
             )),
         }
     );
-    mysql_and_generic()
-        .one_statement_parses_to("SHOW FIELDS FROM mytable", "SHOW COLUMNS FROM mytable");
-    mysql_and_generic()
-        .one_statement_parses_to("SHOW COLUMNS IN mytable", "SHOW COLUMNS FROM mytable");
-    mysql_and_generic()
-        .one_statement_parses_to("SHOW FIELDS IN mytable", "SHOW COLUMNS FROM mytable");
+    assert_eq!(
+        mysql_and_generic().verified_stmt("SHOW EXTENDED FULL FIELDS IN mytable IN mydb LIKE 'a%'"),
+        Statement::ShowColumns {
+            extended: true,
+            full: true,
+            fields: true,
+            table_in: true,
+            table_name: ObjectName(vec![Ident::new("mytable")]),
+            database: Some(Ident::new("mydb")),
+            database_in: true,
+            filter: Some(ShowStatementFilter::Like("a%".into())),
+        }
+    );
+    mysql_and_generic().verified_stmt("SHOW FIELDS FROM mytable");
+    mysql_and_generic().verified_stmt("SHOW COLUMNS IN mytable");
+    mysql_and_generic().verified_stmt("SHOW FIELDS IN mytable FROM mydb");
+    mysql_and_generic().verified_stmt("SHOW COLUMNS FROM mytable IN mydb WHERE 1 = 2");
+}
+
+#[test]
+fn parse_create_table_column_attributes() {
+    let sql = "CREATE TABLE t (a INT INVISIBLE, b INT NOT NULL VISIBLE COLUMN_FORMAT FIXED, c INT STORAGE DISK COLUMN_FORMAT DEFAULT, d INT COLUMN_FORMAT DYNAMIC STORAGE MEMORY)";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable { columns, .. } => {
+            let options: Vec<Vec<ColumnOption>> = columns
+                .into_iter()
+                .map(|c| c.options.into_iter().map(|o| o.option).collect())
+                .collect();
+            assert_eq!(
+                options,
+                vec![
+                    vec![ColumnOption::Invisible],
+                    vec![
+                        ColumnOption::NotNull,
+                        ColumnOption::Visible,
+                        ColumnOption::ColumnFormat(ColumnFormat::Fixed),
+                    ],
+                    vec![
+                        ColumnOption::Storage(ColumnStorage::Disk),
+                        ColumnOption::ColumnFormat(ColumnFormat::Default),
+                    ],
+                    vec![
+                        ColumnOption::ColumnFormat(ColumnFormat::Dynamic),
+                        ColumnOption::Storage(ColumnStorage::Memory),
+                    ],
+                ]
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT COLUMN_FORMAT COMPACT)")
+        .is_err());
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT STORAGE)")
+        .is_err());
+}
+
+#[test]
+fn parse_create_table_default_expr() {
+    let sql = "CREATE TABLE t (\
+        uuid BINARY(16) DEFAULT (UUID_TO_BIN(UUID())) NOT NULL, \
+        j JSON DEFAULT ('{}'), \
+        ts TIMESTAMP(3) DEFAULT CURRENT_TIMESTAMP(3), \
+        dt DATETIME(6) DEFAULT (NOW(6)), \
+        n INT DEFAULT (1 + 2))";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable { columns, .. } => {
+            let data_types: Vec<String> = columns.iter().map(|c| c.data_type.to_string()).collect();
+            assert_eq!(
+                data_types,
+                vec!["BINARY(16)", "JSON", "TIMESTAMP(3)", "DATETIME(6)", "INT"]
+            );
+            assert_eq!(columns[0].data_type, DataType::Binary(16));
+            assert_eq!(columns[2].data_type, DataType::Timestamp(Some(3)));
+            assert_eq!(columns[3].data_type, DataType::Datetime(Some(6)));
+            let options: Vec<ColumnOption> = columns
+                .into_iter()
+                .map(|c| c.options[0].option.clone())
+                .collect();
+            assert_eq!(
+                options,
+                vec![
+                    ColumnOption::DefaultExpr(mysql().verified_expr("UUID_TO_BIN(UUID())")),
+                    ColumnOption::DefaultExpr(Expr::Value(single_quoted("{}", true))),
+                    ColumnOption::Default(mysql().verified_expr("CURRENT_TIMESTAMP(3)")),
+                    ColumnOption::DefaultExpr(mysql().verified_expr("NOW(6)")),
+                    ColumnOption::DefaultExpr(mysql().verified_expr("1 + 2")),
+                ]
+            );
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("CREATE TABLE t (a VARBINARY(255), b TIMESTAMP, c DATETIME)");
+    mysql().verified_expr("CAST(a AS BINARY)");
+
+    match mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT DEFAULT -1, b INT DEFAULT (-1))",
+        "CREATE TABLE t (a INT DEFAULT - 1, b INT DEFAULT (- 1))",
+    ) {
+        Statement::CreateTable { columns, .. } => {
+            let minus_one = Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: Box::new(Expr::Value(number("1"))),
+            };
+            assert_eq!(
+                columns[0].options[0].option,
+                ColumnOption::Default(minus_one.clone())
+            );
+            assert_eq!(
+                columns[1].options[0].option,
+                ColumnOption::DefaultExpr(minus_one)
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT DEFAULT (1, b INT)")
+        .is_err());
+}
+
+#[test]
+fn parse_alter_table_column_position() {
+    fn last_option(stmt: Statement) -> ColumnOption {
+        match stmt {
+            Statement::AlterTable { mut operation, .. } => match operation.remove(0) {
+                AlterTableOperation::AddColumn { column_def } => column_def.options,
+                AlterTableOperation::ChangeColumn { new_column_def, .. } => {
+                    new_column_def.options
+                }
+                AlterTableOperation::ModifyColumn { first_or_after, .. } => {
+                    return first_or_after.unwrap()
+                }
+                op => panic!("unexpected operation: {:?}", op),
+            }
+            .pop()
+            .unwrap()
+            .option,
+            _ => unreachable!(),
+        }
+    }
+
+    assert_eq!(
+        last_option(mysql().verified_stmt("ALTER TABLE t ADD COLUMN a INT FIRST")),
+        ColumnOption::First
+    );
+    assert_eq!(
+        last_option(mysql().verified_stmt("ALTER TABLE t ADD COLUMN a INT NOT NULL AFTER b")),
+        ColumnOption::After(Expr::Identifier(Ident::new("b")))
+    );
+    assert_eq!(
+        last_option(mysql().verified_stmt("ALTER TABLE t CHANGE COLUMN a b INT FIRST")),
+        ColumnOption::First
+    );
+    assert_eq!(
+        last_option(mysql().verified_stmt("ALTER TABLE t CHANGE COLUMN a b INT AFTER c")),
+        ColumnOption::After(Expr::Identifier(Ident::new("c")))
+    );
+    assert_eq!(
+        last_option(mysql().verified_stmt("ALTER TABLE t MODIFY COLUMN a INT FIRST")),
+        ColumnOption::First
+    );
+    assert_eq!(
+        last_option(mysql().verified_stmt("ALTER TABLE t MODIFY COLUMN a INT AFTER b")),
+        ColumnOption::After(Expr::Identifier(Ident::new("b")))
+    );
+}
+
+#[test]
+fn parse_create_table_options() {
+    fn table_options(stmt: Statement) -> Vec<TableOption> {
+        match stmt {
+            Statement::CreateTable { table_options, .. } => {
+                table_options.into_iter().map(|o| o.option).collect()
+            }
+            _ => unreachable!(),
+        }
+    }
+    let ident = |s: &str| Expr::Identifier(Ident::new(s));
+    let string = |s: &str| Expr::Value(single_quoted(s, true));
+    let num = |n: &'static str| Expr::Value(number(n));
+
+    let sql = "CREATE TABLE t (a INT) ENGINE=InnoDB ROW_FORMAT=DYNAMIC KEY_BLOCK_SIZE=8 STATS_PERSISTENT=0 COMPRESSION='zlib' ENCRYPTION='Y' CHECKSUM=1 DELAY_KEY_WRITE=1 MAX_ROWS=100000 TABLESPACE ts1";
+    assert_eq!(
+        table_options(mysql().verified_stmt(sql)),
+        vec![
+            TableOption::Engine(ident("InnoDB")),
+            TableOption::RowFormat(ident("DYNAMIC")),
+            TableOption::KeyBlockSize(num("8")),
+            TableOption::StatsPersistent(num("0")),
+            TableOption::Compression(string("zlib")),
+            TableOption::Encryption(string("Y")),
+            TableOption::Checksum(num("1")),
+            TableOption::DelayKeyWrite(num("1")),
+            TableOption::MaxRows(num("100000")),
+            TableOption::Tablespace {
+                name: Ident::new("ts1"),
+                storage: None
+            },
+        ]
+    );
+
+    let sql = "CREATE TABLE t (a INT) TABLESPACE ts1 STORAGE DISK UNION=(t1, db.t2) DATA DIRECTORY='/d' INDEX DIRECTORY='/i' STATS_AUTO_RECALC=DEFAULT FOO_BAR=3";
+    assert_eq!(
+        table_options(mysql().verified_stmt(sql)),
+        vec![
+            TableOption::Tablespace {
+                name: Ident::new("ts1"),
+                storage: Some(ColumnStorage::Disk)
+            },
+            TableOption::Union(vec![
+                ObjectName(vec![Ident::new("t1")]),
+                ObjectName(vec![Ident::new("db"), Ident::new("t2")]),
+            ]),
+            TableOption::DataDirectory(string("/d")),
+            TableOption::IndexDirectory(string("/i")),
+            TableOption::StatsAutoRecalc(ident("DEFAULT")),
+            TableOption::Other {
+                name: Ident::new("FOO_BAR"),
+                value: num("3")
+            },
+        ]
+    );
+
+    // the `=` is optional
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT) ENGINE InnoDB ROW_FORMAT COMPRESSED MIN_ROWS 10 PASSWORD 'p'",
+        "CREATE TABLE t (a INT) ENGINE=InnoDB ROW_FORMAT=COMPRESSED MIN_ROWS=10 PASSWORD='p'",
+    );
+
+    // an unknown option must at least look like `name = value`
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT) ENGINE=InnoDB FOO_BAR")
+        .is_err());
+}
+
+#[test]
+fn parse_create_table_options_round_trip() {
+    mysql().verified_stmt("CREATE TABLE t (a INT) ENGINE=InnoDB AUTO_INCREMENT=42 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='users'");
+    mysql().verified_stmt("CREATE TABLE t (a INT) ENGINE=MyISAM DEFAULT CHARSET=latin1 ROW_FORMAT=FIXED");
+    mysql().verified_stmt("CREATE TABLE t (a INT) ENGINE=InnoDB DEFAULT CHARSET=utf8 STATS_PERSISTENT=0 ROW_FORMAT=COMPACT");
+
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT) ENGINE InnoDB DEFAULT CHARSET utf8mb4 COMMENT 'x'",
+        "CREATE TABLE t (a INT) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='x'",
+    );
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT) ENGINE=InnoDB, COMMENT='x', AUTO_INCREMENT=7",
+        "CREATE TABLE t (a INT) ENGINE=InnoDB COMMENT='x' AUTO_INCREMENT=7",
+    );
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT) ENGINE = InnoDB , DEFAULT CHARSET = utf8mb4 ROW_FORMAT DYNAMIC",
+        "CREATE TABLE t (a INT) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 ROW_FORMAT=DYNAMIC",
+    );
+}
+
+#[test]
+fn parse_create_table_partition_by() {
+    let sql = "CREATE TABLE t (a INT, created DATE) ENGINE=InnoDB PARTITION BY RANGE (YEAR(created)) (PARTITION p0 VALUES LESS THAN (2020), PARTITION p1 VALUES LESS THAN MAXVALUE)";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable {
+            table_options,
+            partition,
+            ..
+        } => {
+            assert_eq!(table_options.len(), 1);
+            assert_eq!(
+                partition,
+                Some(PartitionDefinition {
+                    partition_by: PartitionMethod::Range(PartitionKey::Expr(
+                        mysql().verified_expr("YEAR(created)")
+                    )),
+                    partitions: None,
+                    subpartition_by: None,
+                    subpartitions: None,
+                    definitions: vec![
+                        Partition {
+                            name: Ident::new("p0"),
+                            values: Some(PartitionValues::LessThan(vec![Expr::Value(number(
+                                "2020"
+                            ))])),
+                            options: vec![],
+                            subpartitions: vec![],
+                        },
+                        Partition {
+                            name: Ident::new("p1"),
+                            values: Some(PartitionValues::LessThanMaxValue),
+                            options: vec![],
+                            subpartitions: vec![],
+                        },
+                    ],
+                })
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    mysql().verified_stmt("CREATE TABLE t (a INT) PARTITION BY LINEAR HASH (a) PARTITIONS 4");
+    mysql().verified_stmt("CREATE TABLE t (a INT) PARTITION BY KEY ALGORITHM=2 (a) PARTITIONS 2");
+    mysql().verified_stmt("CREATE TABLE t (a INT) PARTITION BY LINEAR KEY () PARTITIONS 2");
+    mysql().verified_stmt("CREATE TABLE t (a INT, b INT) PARTITION BY LIST COLUMNS (a, b) (PARTITION p0 VALUES IN (1, 2) ENGINE=InnoDB COMMENT='x', PARTITION p1 VALUES IN (3) DATA DIRECTORY='/d' MAX_ROWS=10)");
+    mysql().verified_stmt("CREATE TABLE t (a INT, d DATE) PARTITION BY RANGE COLUMNS (d) SUBPARTITION BY HASH (TO_DAYS(d)) SUBPARTITIONS 2 (PARTITION p0 VALUES LESS THAN ('1990-01-01') (SUBPARTITION s0 ENGINE=InnoDB, SUBPARTITION s1), PARTITION p1 VALUES LESS THAN MAXVALUE (SUBPARTITION s2, SUBPARTITION s3))");
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT) PARTITION BY LIST (a) (PARTITION p0 VALUES IN (1) STORAGE ENGINE = InnoDB)",
+        "CREATE TABLE t (a INT) PARTITION BY LIST (a) (PARTITION p0 VALUES IN (1) ENGINE=InnoDB)",
+    );
+
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT) PARTITION BY LINEAR RANGE (a)")
+        .is_err());
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT) PARTITION BY RANGE (a) SUBPARTITION BY LIST (a)")
+        .is_err());
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT) PARTITION BY RANGE (a) (PARTITION p0 VALUES (1))")
+        .is_err());
+}
+
+#[test]
+fn parse_create_table_foreign_key_actions() {
+    let sql = "CREATE TABLE t (a INT, CONSTRAINT fk FOREIGN KEY fk_a (a) REFERENCES db.p (id) MATCH FULL ON DELETE CASCADE ON UPDATE SET NULL)";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable { index, .. } => {
+            assert_eq!(index[0].constraint, Some(Ident::new("fk")));
+            match &index[0].index {
+                IndexDef::ForeignKey(MysqlIndex {
+                    index_name,
+                    key_parts,
+                    index_option,
+                    ..
+                }) => {
+                    assert_eq!(index_name, &Some(Ident::new("fk_a")));
+                    assert_eq!(
+                        key_parts,
+                        &Some(vec![IndexKeyPart::Column {
+                            name: Ident::new("a"),
+                            length: None,
+                            asc: None,
+                        }])
+                    );
+                    assert_eq!(
+                        index_option,
+                        &vec![IndexOptions::References {
+                            table: ObjectName(vec![Ident::new("db"), Ident::new("p")]),
+                            column: vec![Ident::new("id")],
+                            match_type: Some(ReferentialMatch::Full),
+                            on_delete: Some(ReferentialAction::Cascade),
+                            on_update: Some(ReferentialAction::SetNull),
+                        }]
+                    );
+                }
+                index => panic!("unexpected index: {:?}", index),
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    // either order is accepted, ON DELETE is printed first
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES p (id) ON UPDATE RESTRICT ON DELETE NO ACTION)",
+        "CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES p (id) ON DELETE NO ACTION ON UPDATE RESTRICT)",
+    );
+    mysql().verified_stmt(
+        "ALTER TABLE t ADD CONSTRAINT fk FOREIGN KEY (a) REFERENCES p (id) ON DELETE SET DEFAULT",
+    );
+
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES p (id) ON DELETE NOTHING)")
+        .is_err());
+    assert!(mysql()
+        .parse_sql_statements("CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES p (id) MATCH ALL)")
+        .is_err());
+}
+
+#[test]
+fn parse_index_key_parts() {
+    let sql = "CREATE TABLE t (name TEXT, email TEXT, KEY idx_name (name(20) DESC, (LOWER(email))), PRIMARY KEY (id ASC))";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable { index, .. } => {
+            let key_parts: Vec<Vec<IndexKeyPart>> = index
+                .into_iter()
+                .map(|i| match i.index {
+                    IndexDef::Normal(i) | IndexDef::PrimaryKey(i) => i.key_parts.unwrap(),
+                    index => panic!("unexpected index: {:?}", index),
+                })
+                .collect();
+            assert_eq!(
+                key_parts,
+                vec![
+                    vec![
+                        IndexKeyPart::Column {
+                            name: Ident::new("name"),
+                            length: Some(20),
+                            asc: Some(false),
+                        },
+                        IndexKeyPart::Expr {
+                            expr: mysql().verified_expr("LOWER(email)"),
+                            asc: None,
+                        },
+                    ],
+                    vec![IndexKeyPart::Column {
+                        name: Ident::new("id"),
+                        length: None,
+                        asc: Some(true),
+                    }],
+                ]
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    mysql().verified_stmt("ALTER TABLE t ADD INDEX idx ((a + b) DESC, c(10) ASC, d)");
+    mysql().verified_stmt("ALTER TABLE t ADD PRIMARY KEY (a(8), b)");
+
+    assert!(mysql()
+        .parse_sql_statements("ALTER TABLE t ADD INDEX idx (a(x))")
+        .is_err());
+    assert!(mysql()
+        .parse_sql_statements("ALTER TABLE t ADD INDEX idx ((a + b)")
+        .is_err());
+}
+
+#[test]
+fn parse_index_options() {
+    let sql = "CREATE TABLE t (a INT, KEY k (a) USING BTREE KEY_BLOCK_SIZE=8 COMMENT 'x' INVISIBLE, KEY k2 (a) VISIBLE ENGINE_ATTRIBUTE='{}' SECONDARY_ENGINE_ATTRIBUTE='{}')";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable { index, .. } => {
+            let options: Vec<(Option<Ident>, Vec<IndexOptions>)> = index
+                .into_iter()
+                .map(|i| match i.index {
+                    IndexDef::Normal(i) => (i.index_type, i.index_option),
+                    index => panic!("unexpected index: {:?}", index),
+                })
+                .collect();
+            let string = |s: &str| Expr::Value(single_quoted(s, true));
+            assert_eq!(
+                options,
+                vec![
+                    (Some(Ident::new("BTREE")), vec![
+                        IndexOptions::KeyBlockSize(Expr::Value(number("8"))),
+                        IndexOptions::Comment(string("x")),
+                        IndexOptions::Invisible,
+                    ]),
+                    (None, vec![
+                        IndexOptions::Visible,
+                        IndexOptions::EngineAttribute(string("{}")),
+                        IndexOptions::SecondaryEngineAttribute(string("{}")),
+                    ]),
+                ]
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t ADD INDEX k (a) KEY_BLOCK_SIZE 4 INVISIBLE",
+        "ALTER TABLE t ADD INDEX k (a) KEY_BLOCK_SIZE=4 INVISIBLE",
+    );
+    assert!(mysql()
+        .parse_sql_statements("ALTER TABLE t ADD INDEX k (a) HIDDEN")
+        .is_err());
+}
+
+#[test]
+fn parse_alter_table_modify_column() {
+    match mysql().verified_stmt("ALTER TABLE t MODIFY COLUMN a BIGINT NOT NULL") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![AlterTableOperation::ModifyColumn {
+                column_def: ColumnDef {
+                    name: Ident::new("a"),
+                    data_type: DataType::BigInt,
+                    collation: None,
+                    options: vec![ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::NotNull
+                    }],
+                    ordinal: 0,
+                },
+                first_or_after: None,
+            }]
+        ),
+        _ => unreachable!(),
+    }
+
+    match mysql().one_statement_parses_to(
+        "ALTER TABLE t MODIFY a INT COMMENT 'x' AFTER b",
+        "ALTER TABLE t MODIFY COLUMN a INT COMMENT 'x' AFTER b",
+    ) {
+        Statement::AlterTable { operation, .. } => match &operation[0] {
+            AlterTableOperation::ModifyColumn {
+                column_def,
+                first_or_after,
+            } => {
+                assert_eq!(column_def.options.len(), 1);
+                assert_eq!(
+                    first_or_after,
+                    &Some(ColumnOption::After(Expr::Identifier(Ident::new("b"))))
+                );
+            }
+            op => panic!("unexpected operation: {:?}", op),
+        },
+        _ => unreachable!(),
+    }
+
+    // ADD is unaffected
+    match mysql().verified_stmt("ALTER TABLE t ADD COLUMN a INT FIRST") {
+        Statement::AlterTable { operation, .. } => match &operation[0] {
+            AlterTableOperation::AddColumn { .. } => {}
+            op => panic!("unexpected operation: {:?}", op),
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_alter_table_rename() {
+    match mysql().verified_stmt(
+        "ALTER TABLE t RENAME INDEX old_idx TO new_idx, RENAME COLUMN a TO b, RENAME TO db2.t2",
+    ) {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![
+                AlterTableOperation::RenameIndex {
+                    old_name: Ident::new("old_idx"),
+                    new_name: Ident::new("new_idx"),
+                },
+                AlterTableOperation::RenameColumn {
+                    old_column_name: Ident::new("a"),
+                    new_column_name: Ident::new("b"),
+                },
+                AlterTableOperation::RenameTable {
+                    table_name: ObjectName(vec![Ident::new("db2"), Ident::new("t2")]),
+                },
+            ]
+        ),
+        _ => unreachable!(),
+    }
+
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t RENAME KEY k1 TO k2, RENAME AS t2",
+        "ALTER TABLE t RENAME INDEX k1 TO k2, RENAME TO t2",
+    );
+    assert!(mysql()
+        .parse_sql_statements("ALTER TABLE t RENAME INDEX k1 k2")
+        .is_err());
+}
+
+#[test]
+fn parse_alter_table_convert_charset() {
+    match mysql().verified_stmt(
+        "ALTER TABLE t CONVERT TO CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci",
+    ) {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![AlterTableOperation::ConvertCharset {
+                charset: Ident::new("utf8mb4"),
+                collation: Some(Ident::new("utf8mb4_unicode_ci")),
+                convert: true,
+            }]
+        ),
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("ALTER TABLE t DEFAULT CHARACTER SET utf8mb4") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![AlterTableOperation::ConvertCharset {
+                charset: Ident::new("utf8mb4"),
+                collation: None,
+                convert: false,
+            }]
+        ),
+        _ => unreachable!(),
+    }
+
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t CONVERT TO CHARSET utf8mb4",
+        "ALTER TABLE t CONVERT TO CHARACTER SET utf8mb4",
+    );
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t CHARACTER SET = latin1 COLLATE = latin1_bin",
+        "ALTER TABLE t DEFAULT CHARACTER SET latin1 COLLATE latin1_bin",
+    );
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t DEFAULT CHARSET utf8",
+        "ALTER TABLE t DEFAULT CHARACTER SET utf8",
+    );
+    assert!(mysql()
+        .parse_sql_statements("ALTER TABLE t CONVERT TO utf8mb4")
+        .is_err());
+}
+
+#[test]
+fn parse_alter_table_drop_constraints() {
+    match mysql().verified_stmt(
+        "ALTER TABLE t DROP FOREIGN KEY fk_orders_user, DROP CONSTRAINT chk_positive, DROP CHECK chk_other",
+    ) {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![
+                AlterTableOperation::DropForeignKey(Ident::new("fk_orders_user")),
+                AlterTableOperation::DropConstraint(Ident::new("chk_positive")),
+                AlterTableOperation::DropCheck(Ident::new("chk_other")),
+            ]
+        ),
+        _ => unreachable!(),
+    }
+
+    mysql().verified_stmt("ALTER TABLE t DROP INDEX idx_a, DROP KEY idx_b, DROP PRIMARY KEY");
+    mysql().verified_stmt("ALTER TABLE t DROP COLUMN a");
+    assert!(mysql()
+        .parse_sql_statements("ALTER TABLE t DROP FOREIGN fk")
+        .is_err());
+}
+
+#[test]
+fn parse_alter_table_options_and_keys() {
+    match mysql().verified_stmt("ALTER TABLE t ENGINE=InnoDB ROW_FORMAT=COMPRESSED") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![AlterTableOperation::SetTableOptions(vec![
+                TableOptionDef {
+                    name: None,
+                    option: TableOption::Engine(Expr::Identifier(Ident::new("InnoDB"))),
+                },
+                TableOptionDef {
+                    name: None,
+                    option: TableOption::RowFormat(Expr::Identifier(Ident::new("COMPRESSED"))),
+                },
+            ])]
+        ),
+        _ => unreachable!(),
+    }
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t AUTO_INCREMENT = 1000",
+        "ALTER TABLE t AUTO_INCREMENT=1000",
+    );
+
+    match mysql().verified_stmt("ALTER TABLE t ADD COLUMN a INT, AUTO_INCREMENT=5") {
+        Statement::AlterTable { operation, .. } => {
+            assert_eq!(operation.len(), 2);
+            assert_eq!(
+                operation[1],
+                AlterTableOperation::SetTableOptions(vec![TableOptionDef {
+                    name: None,
+                    option: TableOption::Auto_Increment(5),
+                }])
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    let order_by = |columns: &[(&str, Option<bool>)]| {
+        AlterTableOperation::OrderBy(
+            columns
+                .iter()
+                .map(|(name, asc)| OrderByExpr {
+                    expr: Expr::Identifier(Ident::new(*name)),
+                    asc: *asc,
+                    nulls_first: None,
+                })
+                .collect(),
+        )
+    };
+    match mysql().verified_stmt("ALTER TABLE t DISABLE KEYS, ENABLE KEYS, FORCE, ORDER BY a, b") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![
+                AlterTableOperation::DisableKeys,
+                AlterTableOperation::EnableKeys,
+                AlterTableOperation::Force,
+                order_by(&[("a", None), ("b", None)]),
+            ]
+        ),
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("ALTER TABLE t ORDER BY a, DROP COLUMN b") {
+        Statement::AlterTable { operation, .. } => {
+            assert_eq!(operation[0], order_by(&[("a", None)]));
+        }
+        _ => unreachable!(),
+    }
+    // columns named like non-reserved keywords, and the direction of each
+    match mysql().verified_stmt("ALTER TABLE t ORDER BY a DESC, status, comment ASC") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![order_by(&[
+                ("a", Some(false)),
+                ("status", None),
+                ("comment", Some(true)),
+            ])]
+        ),
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("ALTER TABLE t ORDER BY a, `order`");
+    match mysql().verified_stmt("ALTER TABLE t ORDER BY status DESC, DROP COLUMN comment") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![
+                order_by(&[("status", Some(false))]),
+                AlterTableOperation::DropColumn {
+                    column_name: Ident::new("comment"),
+                    if_exists: false,
+                    cascade: false,
+                },
+            ]
+        ),
+        _ => unreachable!(),
+    }
+
+    match mysql().verified_stmt("ALTER TABLE t ADD COLUMN a INT, ALGORITHM=INPLACE, LOCK=NONE") {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation[1..],
+            [
+                AlterTableOperation::Algorithm(Ident::new("INPLACE")),
+                AlterTableOperation::Lock(Ident::new("NONE")),
+            ]
+        ),
+        _ => unreachable!(),
+    }
+    // also without `=`, or a comma after table options
+    match mysql().one_statement_parses_to(
+        "ALTER TABLE t ENGINE=InnoDB ALGORITHM INSTANT, LOCK DEFAULT",
+        "ALTER TABLE t ENGINE=InnoDB, ALGORITHM=INSTANT, LOCK=DEFAULT",
+    ) {
+        Statement::AlterTable { operation, .. } => {
+            assert_eq!(operation.len(), 3);
+            assert_eq!(operation[1], AlterTableOperation::Algorithm(Ident::new("INSTANT")));
+            assert_eq!(operation[2], AlterTableOperation::Lock(Ident::new("DEFAULT")));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_create_index_mysql() {
+    let sql = "CREATE UNIQUE INDEX idx USING BTREE ON db.t (a(10) DESC, (b + 1)) KEY_BLOCK_SIZE=8 COMMENT 'c' INVISIBLE ALGORITHM=INPLACE LOCK=NONE";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateIndex {
+            name,
+            table_name,
+            columns,
+            unique,
+            kind,
+            using,
+            options,
+            algorithm,
+            lock,
+            if_not_exists,
+        } => {
+            assert_eq!("idx", name.to_string());
+            assert_eq!("db.t", table_name.to_string());
+            assert_eq!(
+                columns,
+                vec![
+                    IndexKeyPart::Column {
+                        name: Ident::new("a"),
+                        length: Some(10),
+                        asc: Some(false),
+                    },
+                    IndexKeyPart::Expr {
+                        expr: Expr::BinaryOp {
+                            left: Box::new(Expr::Identifier(Ident::new("b"))),
+                            op: BinaryOperator::Plus,
+                            right: Box::new(Expr::Value(number("1"))),
+                        },
+                        asc: None,
+                    },
+                ]
+            );
+            assert!(unique);
+            assert_eq!(kind, None);
+            assert_eq!(using, Some(MysqlIndexType::Btree));
+            assert_eq!(
+                options,
+                vec![
+                    IndexOptions::KeyBlockSize(Expr::Value(number("8"))),
+                    IndexOptions::Comment(Expr::Value(single_quoted("c", true))),
+                    IndexOptions::Invisible,
+                ]
+            );
+            assert_eq!(algorithm, Some(Ident::new("INPLACE")));
+            assert_eq!(lock, Some(Ident::new("NONE")));
+            assert!(!if_not_exists);
+        }
+        _ => unreachable!(),
+    }
+
+    match mysql().verified_stmt("CREATE FULLTEXT INDEX ft ON t (body) WITH PARSER ngram") {
+        Statement::CreateIndex { kind, options, .. } => {
+            assert_eq!(kind, Some(MysqlIndexStorageType::FullText));
+            assert_eq!(options, vec![IndexOptions::WithParser(Ident::new("ngram"))]);
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("CREATE SPATIAL INDEX sp ON t (g)");
+    mysql().one_statement_parses_to(
+        "CREATE INDEX i ON t(a) ALGORITHM DEFAULT LOCK SHARED",
+        "CREATE INDEX i ON t (a) ALGORITHM=DEFAULT LOCK=SHARED",
+    );
+
+    let statements = mysql()
+        .parse_sql_statements("CREATE INDEX i ON t (a); CREATE INDEX j ON t (b)")
+        .unwrap();
+    assert_eq!(
+        statements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["CREATE INDEX i ON t (a)", "CREATE INDEX j ON t (b)"]
+    );
+}
+
+#[test]
+fn parse_drop_index_mysql() {
+    match mysql().verified_stmt("DROP INDEX i ON db.t ALGORITHM=INPLACE LOCK=NONE") {
+        Statement::Drop {
+            object_type,
+            names,
+            on_info,
+            algorithm,
+            lock,
+            ..
+        } => {
+            assert_eq!(object_type, ObjectType::Index);
+            assert_eq!(names, vec![ObjectName(vec![Ident::new("i")])]);
+            assert_eq!(on_info.to_string(), "db.t");
+            assert_eq!(algorithm, Some(Ident::new("INPLACE")));
+            assert_eq!(lock, Some(Ident::new("NONE")));
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("DROP INDEX i ON t");
+    mysql().verified_stmt("DROP TABLE IF EXISTS a, b");
+    mysql().verified_stmt("DROP TABLE a CASCADE");
+    match mysql().verified_stmt("DROP VIEW IF EXISTS v1, v2 RESTRICT") {
+        Statement::Drop {
+            object_type,
+            if_exists,
+            names,
+            cascade,
+            restrict,
+            ..
+        } => {
+            assert_eq!(object_type, ObjectType::View);
+            assert!(if_exists);
+            assert_eq!(names.len(), 2);
+            assert!(!cascade);
+            assert!(restrict);
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("DROP TABLE t RESTRICT");
+    mysql().verified_stmt("DROP INDEX i ON t RESTRICT");
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::Other,
+            message: "Cannot specify both CASCADE and RESTRICT in DROP".to_string(),
+            location: Some(Location {
+                line: 1,
+                column: 22,
+                offset: 21,
+            }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, "DROP TABLE t CASCADE RESTRICT").unwrap_err()
+    );
+    mysql().one_statement_parses_to(
+        "DROP INDEX i ON t LOCK EXCLUSIVE ALGORITHM COPY",
+        "DROP INDEX i ON t ALGORITHM=COPY LOCK=EXCLUSIVE",
+    );
+}
+
+#[test]
+fn parse_create_table_as_select() {
+    match mysql().verified_stmt("CREATE TABLE t2 AS SELECT * FROM t1") {
+        Statement::CreateTable {
+            name,
+            columns,
+            on_duplicate,
+            query,
+            ..
+        } => {
+            assert_eq!(name.to_string(), "t2");
+            assert!(columns.is_empty());
+            assert_eq!(on_duplicate, None);
+            assert_eq!(query.unwrap().to_string(), "SELECT * FROM t1");
+        }
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("CREATE TABLE t2 IGNORE AS SELECT a FROM t1") {
+        Statement::CreateTable { on_duplicate, .. } => {
+            assert_eq!(on_duplicate, Some(CreateTableOnDuplicate::Ignore));
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("CREATE TABLE t2 (a INT) ENGINE=InnoDB REPLACE AS SELECT a FROM t1");
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t2 SELECT a FROM t1",
+        "CREATE TABLE t2 AS SELECT a FROM t1",
+    );
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t2 REPLACE SELECT a FROM t1",
+        "CREATE TABLE t2 REPLACE AS SELECT a FROM t1",
+    );
+}
+
+#[test]
+fn parse_select_modifiers() {
+    let select = mysql().verified_only_select(
+        "SELECT DISTINCT HIGH_PRIORITY STRAIGHT_JOIN SQL_SMALL_RESULT SQL_BIG_RESULT SQL_BUFFER_RESULT SQL_CACHE SQL_CALC_FOUND_ROWS a FROM t",
+    );
+    assert_eq!(select.distinct, Some(Distinct::Distinct));
+    assert_eq!(
+        select.modifiers,
+        vec![
+            SelectModifier::HighPriority,
+            SelectModifier::StraightJoin,
+            SelectModifier::SqlSmallResult,
+            SelectModifier::SqlBigResult,
+            SelectModifier::SqlBufferResult,
+            SelectModifier::SqlCache,
+            SelectModifier::SqlCalcFoundRows,
+        ]
+    );
+    assert_eq!(select.projection.len(), 1);
+
+    // a column named like a modifier
+    let select = mysql().verified_only_select("SELECT sql_no_cache, sql_cache.a FROM sql_cache");
+    assert!(select.modifiers.is_empty());
+    assert_eq!(select.projection.len(), 2);
+    let select = mysql().verified_only_select("SELECT SQL_NO_CACHE sql_no_cache FROM t");
+    assert_eq!(select.modifiers, vec![SelectModifier::SqlNoCache]);
+    assert_eq!(
+        select.projection,
+        vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("sql_no_cache")))]
+    );
+}
+
+#[test]
+fn parse_versioned_comments() {
+    let sql = "/*!40101 SET @OLD_SQL_MODE=@@SQL_MODE */;\n/*!50001 CREATE VIEW v AS SELECT 1 */;\nCREATE TABLE t (a INT) /*!50100 ENGINE=InnoDB */;";
+    let statements = Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(statements.len(), 3);
+    match &statements[1] {
+        Statement::VersionedComment { version, statement } => {
+            assert_eq!(*version, Some(50001));
+            assert_eq!(statement.to_string(), "CREATE VIEW v AS SELECT 1");
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        statements[0].to_string(),
+        "/*!40101 SET @OLD_SQL_MODE = @@SQL_MODE */"
+    );
+    assert_eq!(
+        statements[2].to_string(),
+        "CREATE TABLE t (a INT) ENGINE=InnoDB"
+    );
+    mysql().verified_stmt("/*!40101 SELECT 1 */");
+    mysql().verified_stmt("/*! SELECT 1 */");
+
+    // a versioned comment in a statement is part of it, as in mysqldump's
+    // query for the rows of a table
+    let statement = mysql().one_statement_parses_to(
+        "SELECT /*!40001 SQL_NO_CACHE */ * FROM t",
+        "SELECT SQL_NO_CACHE * FROM t",
+    );
+    let select = statement.as_query().unwrap().as_select().unwrap();
+    assert_eq!(select.modifiers, vec![SelectModifier::SqlNoCache]);
+    assert!(matches!(select.projection[..], [SelectItem::Wildcard]));
+    assert_eq!("t", select.from[0].relation.to_string());
+
+    let options = ParserOptions {
+        mysql_version: Some(50700),
+        ..Default::default()
+    };
+    let statements =
+        Parser::parse_sql_with_options(&MySqlDialect {}, "/*!80023 SELECT 1 */", &options).unwrap();
+    assert!(statements.is_empty());
+}
+
+#[test]
+fn parse_optimizer_hints() {
+    let select = mysql()
+        .verified_only_select("SELECT /*+ MAX_EXECUTION_TIME(1000) NO_INDEX_MERGE(t) */ * FROM t");
+    assert_eq!(
+        select.hints,
+        Some(" MAX_EXECUTION_TIME(1000) NO_INDEX_MERGE(t) ".to_string())
+    );
+    assert!(select.comments.is_empty());
+
+    let select = mysql().verified_only_select("SELECT /*+ BKA(t1) */ /* a comment */ a FROM t1");
+    assert_eq!(select.hints, Some(" BKA(t1) ".to_string()));
+    assert_eq!(select.comments, vec![" a comment ".to_string()]);
+
+    match mysql().verified_stmt("INSERT /*+ SET_VAR(foreign_key_checks=OFF) */ INTO t VALUES (1)") {
+        Statement::Insert { hints, .. } => {
+            assert_eq!(hints, Some(" SET_VAR(foreign_key_checks=OFF) ".to_string()))
+        }
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("UPDATE /*+ NO_MERGE(t) */ t SET a = 1") {
+        Statement::Update { hints, .. } => assert_eq!(hints, Some(" NO_MERGE(t) ".to_string())),
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("DELETE /*+ MAX_EXECUTION_TIME(10) */ FROM t WHERE a = 1") {
+        Statement::Delete { hints, .. } => {
+            assert_eq!(hints, Some(" MAX_EXECUTION_TIME(10) ".to_string()))
+        }
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("DELETE FROM t") {
+        Statement::Delete { hints, .. } => assert_eq!(hints, None),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_mysql_comments() {
+    mysql().one_statement_parses_to("SELECT 1#x", "SELECT 1");
+    mysql().one_statement_parses_to("SELECT 1 -- x", "SELECT 1");
+    mysql().one_statement_parses_to("SELECT a--b FROM t", "SELECT a - - b FROM t");
+}
+
+#[test]
+fn parse_backslash_escapes() {
+    mysql().one_statement_parses_to(r"SELECT 'it\'s', 'tab\there'", "SELECT 'it''s', 'tab\there'");
+    assert_eq!(
+        mysql().verified_expr(r"'a\\b'"),
+        Expr::Value(single_quoted(r"a\b", true))
+    );
+    // `\%` keeps its backslash, which is written back as `\\`
+    assert_eq!(
+        mysql().verified_expr(r"'100\\%'"),
+        Expr::Value(single_quoted(r"100\%", true))
+    );
+    mysql().one_statement_parses_to(r"SELECT '100\%'", r"SELECT '100\\%'");
+    mysql().one_statement_parses_to(r"SELECT 'a\0b'", r"SELECT 'a\0b'");
+
+    // elsewhere a backslash is an ordinary character, and printed as one
+    let others = TestedDialects {
+        dialects: vec![Box::new(GenericDialect {}), Box::new(PostgreSqlDialect {})],
+    };
+    assert_eq!(
+        others.verified_expr(r"'a\b'"),
+        Expr::Value(single_quoted(r"a\b", false))
+    );
+    others.verified_stmt(r"SELECT 'C:\dir\', 'it''s', 'a\\b' FROM t WHERE p LIKE '100\%'");
+    assert_eq!(
+        others.verified_expr("'plain'"),
+        Expr::Value(single_quoted("plain", false))
+    );
+
+    // and so in MySQL with NO_BACKSLASH_ESCAPES
+    let options = ParserOptions {
+        no_backslash_escapes: true,
+        ..Default::default()
+    };
+    let parse = |sql: &str| Parser::parse_sql_with_options(&MySqlDialect {}, sql, &options).unwrap();
+    let sql = r"SELECT 'a\b', 'C:\dir\', 'it''s'";
+    let statements = parse(sql);
+    assert_eq!(sql, statements[0].to_string());
+    assert_eq!(statements, parse(&statements[0].to_string()));
+    let select = match &statements[0] {
+        Statement::Query(query) => query.as_select().unwrap(),
+        statement => panic!("{:?}", statement),
+    };
+    assert_eq!(
+        &Expr::Value(single_quoted(r"a\b", false)),
+        expr_from_projection(&select.projection[0])
+    );
+}
+
+#[test]
+fn parse_doubled_quotes() {
+    assert_eq!(
+        mysql().verified_expr("'it''s'"),
+        Expr::Value(single_quoted("it's", true))
+    );
+    let select = mysql().verified_only_select("SELECT `weird``name`, `my col` FROM `t``1`");
+    assert_eq!(
+        select.projection[0],
+        SelectItem::UnnamedExpr(Expr::Identifier(Ident::with_quote('`', "weird`name")))
+    );
+    assert_eq!(
+        select.projection[1],
+        SelectItem::UnnamedExpr(Expr::Identifier(Ident::with_quote('`', "my col")))
+    );
+    assert_eq!(Ident::with_quote('`', "a`b").to_string(), "`a``b`");
+    assert_eq!(Ident::with_quote('[', "a]b").to_string(), "[a]]b]");
+}
+
+#[test]
+fn parse_double_quoted_strings() {
+    let select = mysql().verified_only_select(r#"SELECT a FROM t WHERE name = "bob""#);
+    assert_eq!(
+        select.selection,
+        Some(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("name"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(Value::DoubleQuotedString("bob".to_string()))),
+        })
+    );
+    assert_eq!(
+        mysql().verified_expr(r#""say ""hi""""#),
+        Expr::Value(Value::DoubleQuotedString(r#"say "hi""#.to_string()))
+    );
+
+    let dialect = MySqlDialect {};
+    let tokens = Tokenizer::new(&dialect, r#"SELECT "a" FROM t"#)
+        .with_ansi_quotes(true)
+        .tokenize()
+        .unwrap();
+    let statement = Parser::new(tokens, &dialect)
+        .parse_statement()
+        .unwrap();
+    match statement {
+        Statement::Query(query) => match query.body {
+            SetExpr::Select(select) => assert_eq!(
+                select.projection,
+                vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::with_quote('"', "a")))]
+            ),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
 
-    // unhandled things are truly unhandled
-    match mysql_and_generic().parse_sql_statements("SHOW COLUMNS FROM mytable FROM mydb") {
-        Err(_) => {}
-        Ok(val) => panic!("unexpected successful parse: {:?}", val),
+    let options = ParserOptions {
+        ansi_quotes: true,
+        ..Default::default()
+    };
+    let sql = r#"SELECT "a" FROM t WHERE name = 'bob'"#;
+    let statements = Parser::parse_sql_with_options(&dialect, sql, &options).unwrap();
+    match &statements[0] {
+        Statement::Query(query) => match &query.body {
+            SetExpr::Select(select) => assert_eq!(
+                select.projection,
+                vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::with_quote('"', "a")))]
+            ),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
     }
+    assert_eq!(sql, statements[0].to_string());
+}
+
+#[test]
+fn parse_error_location() {
+    let sql = "SELECT a\nFROM t WHERE a = 1 1";
+    match Parser::parse_sql(&MySqlDialect {}, sql) {
+        Err(ParserError::SyntaxError(e)) => {
+            assert_eq!(e.message, "Expected end of statement, found: 1");
+            assert_eq!(
+                e.location,
+                Some(Location {
+                    line: 2,
+                    column: 20,
+                    offset: 28,
+                })
+            );
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+    // EOF is at the end of the SQL, and the message reads as before
+    let err = Parser::parse_sql(&MySqlDialect {}, "DROP TABLE\n").unwrap_err();
+    assert_eq!(
+        err,
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::UnexpectedEof { expected: "identifier".to_string() },
+            message: "Expected identifier, found: EOF".to_string(),
+            location: Some(Location {
+                line: 2,
+                column: 1,
+                offset: 11,
+            }),
+        })
+    );
+    assert_eq!(
+        err.to_string(),
+        "sql parser error: Expected identifier, found: EOF"
+    );
+}
+
+#[test]
+fn parse_located_statements() {
+    let sql = "SELECT a + 1 AS b FROM db.t WHERE c = 'x'; DELETE FROM t2";
+    let statements = Parser::parse_sql_located(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(2, statements.len());
+    let text = |span: Span| &sql[span.start..span.end];
+
+    assert_eq!("SELECT a + 1 AS b FROM db.t WHERE c = 'x'", text(statements[0].span()));
+    assert_eq!("DELETE FROM t2", text(statements[1].span()));
+    assert_eq!(
+        statements[0].statement,
+        Parser::parse_sql(&MySqlDialect {}, sql).unwrap()[0]
+    );
+
+    let nodes = &statements[0].nodes;
+    let select_items: Vec<_> = nodes.select_items.iter().map(|n| text(n.span)).collect();
+    assert_eq!(vec!["a + 1 AS b"], select_items);
+    let object_names: Vec<_> = nodes.object_names.iter().map(|n| text(n.span)).collect();
+    assert_eq!(vec!["db.t"], object_names);
+    let exprs: Vec<_> = nodes.exprs.iter().map(|n| text(n.span)).collect();
+    assert!(exprs.contains(&"a + 1"));
+    assert!(exprs.contains(&"c = 'x'"));
+    let idents: Vec<_> = nodes.idents.iter().map(|n| (n.node.value.as_str(), text(n.span))).collect();
+    assert!(idents.contains(&("b", "b")));
+    assert!(idents.contains(&("db", "db")));
+}
+
+#[test]
+fn parse_placeholders() {
+    let select = mysql().verified_only_select("SELECT ?, a FROM t WHERE b = ? AND c IN (?, ?) LIMIT ? OFFSET ?");
+    let placeholder = || Expr::Value(Value::Placeholder("?".to_string()));
+    assert_eq!(SelectItem::UnnamedExpr(placeholder()), select.projection[0]);
+    assert_eq!(
+        Some(Expr::BinaryOp {
+            left: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("b"))),
+                op: BinaryOperator::Eq,
+                right: Box::new(placeholder()),
+            }),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::InList {
+                expr: Box::new(Expr::Identifier(Ident::new("c"))),
+                list: vec![placeholder(), placeholder()],
+                negated: false,
+            }),
+        }),
+        select.selection
+    );
+
+    match mysql().verified_stmt("INSERT INTO t (a, b, c) VALUES (?, ?, ?)") {
+        Statement::Insert { source, .. } => match source.body {
+            SetExpr::Values(Values(rows)) => {
+                assert_eq!(vec![vec![placeholder(), placeholder(), placeholder()]], rows)
+            }
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+
+    assert_eq!(
+        ParserError::TokenizerError(
+            "Unexpected character '😀' at Line: 1, Column 8".to_string()
+        ),
+        Parser::parse_sql(&MySqlDialect {}, "SELECT 😀").unwrap_err()
+    );
+}
+
+#[test]
+fn parse_iter_statements() {
+    let sql = "SELECT 1; SELECT FROM WHERE; /*!40101 SET a = 1 */;\nINSERT INTO t VALUES ('a;b');;DELETE FROM t";
+    let results: Vec<_> = Parser::iter_statements(&MySqlDialect {}, sql).collect();
+    assert_eq!(5, results.len());
+
+    let statement = results[0].as_ref().unwrap();
+    assert_eq!("SELECT 1", statement.statement.to_string());
+    assert_eq!("SELECT 1", &sql[statement.span.start..statement.span.end]);
+    assert!(results[1].is_err());
+    let statement = results[2].as_ref().unwrap();
+    assert_eq!("/*!40101 SET a = 1 */", statement.statement.to_string());
+    let statement = results[3].as_ref().unwrap();
+    assert_eq!(
+        "INSERT INTO t VALUES ('a;b')",
+        &sql[statement.span.start..statement.span.end]
+    );
+
+    let statements: Vec<_> = Parser::iter_statements(&MySqlDialect {}, sql)
+        .filter_map(Result::ok)
+        .map(|s| s.statement)
+        .collect();
+    assert_eq!(
+        Parser::parse_sql(&MySqlDialect {}, "SELECT 1; /*!40101 SET a = 1 */; INSERT INTO t VALUES ('a;b'); DELETE FROM t").unwrap(),
+        statements
+    );
+
+    // a `;` inside a versioned comment doesn't hide the next statement
+    let statements: Vec<_> =
+        Parser::iter_statements(&MySqlDialect {}, "/*!40101 SET a = 1; SET b = 2 */; SELECT 1")
+            .map(|s| s.unwrap().statement.to_string())
+            .collect();
+    assert_eq!(vec!["SET a = 1", "SET b = 2", "SELECT 1"], statements);
+
+    // tokenizer errors are also skipped up to the next `;`
+    let results: Vec<_> = Parser::iter_statements(&MySqlDialect {}, "SELECT '\u{1F600}' ; SELECT \u{1F600}; SELECT 2").collect();
+    assert_eq!(3, results.len());
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert_eq!("SELECT 2", results[2].as_ref().unwrap().statement.to_string());
+}
+
+#[test]
+fn parse_random_tokens_does_not_panic() {
+    // a small xorshift generator, so that failures can be reproduced
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move |n: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % n as u64) as usize
+    };
+    let pieces = [
+        "SELECT", "FROM", "WHERE", "INSERT", "INTO", "VALUES", "UPDATE", "SET", "DELETE", "CREATE",
+        "TABLE", "INDEX", "DROP", "ALTER", "ADD", "COLUMN", "NOT", "NULL", "IN", "BETWEEN", "LIKE",
+        "IS", "AND", "OR", "AS", "ON", "JOIN", "LEFT", "UNION", "ALL", "ORDER", "BY", "GROUP",
+        "HAVING", "LIMIT", "OFFSET", "CASE", "WHEN", "THEN", "ELSE", "END", "CAST", "INTERVAL",
+        "SECOND", "TO", "EXISTS", "PRIMARY", "KEY", "UNIQUE", "DEFAULT", "AUTO_INCREMENT", "ENGINE",
+        "CHARSET", "PARTITION", "IF", "WITH", "t", "a", "`b`", "1", "1.5", "'s'", "\"d\"", "?",
+        "@v", "(", ")", ",", ";", ".", "*", "=", "<", ">", "+", "-", "/", "%", "::", "|", "&", "^",
+        "~", "<<", ">>", "/*!40101", "*/", "/*+ hint */", "-- c\n", "#c\n",
+    ];
+    let dialects: [&dyn Dialect; 2] = [&GenericDialect {}, &MySqlDialect {}];
+    for _ in 0..20_000 {
+        let len = 1 + next(12);
+        let sql: Vec<&str> = (0..len).map(|_| pieces[next(pieces.len())]).collect();
+        let sql = sql.join(" ");
+        for dialect in dialects.iter() {
+            if let Ok(statements) = Parser::parse_sql(*dialect, &sql) {
+                for statement in statements {
+                    let _ = statement.to_string();
+                }
+            }
+            for _ in Parser::iter_statements(*dialect, &sql) {}
+        }
+    }
+
+    assert!(Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (a INT) AUTO_INCREMENT = 1.5").is_err());
+    assert_eq!("NUMERIC(2)", DataType::Decimal(Some(2), None).to_string());
+    assert_eq!("NUMERIC", DataType::Decimal(None, Some(2)).to_string());
+    assert_eq!("|a|", Ident::with_quote('|', "a").to_string());
+}
+
+#[test]
+fn parse_deep_input_does_not_overflow_the_stack() {
+    // a stack overflow aborts the process, so hostile input must fail with
+    // an error before the parser, or dropping or printing the AST, recurse
+    // that deep
+    let chain = |term: &str, op: &str| vec![term; 20_000].join(op);
+    let inputs = vec![
+        format!("SELECT {}", "(".repeat(50_000)),
+        format!("SELECT {}1{}", "(".repeat(50_000), ")".repeat(50_000)),
+        format!("SELECT {}1", "NOT ".repeat(50_000)),
+        format!("SELECT {}1", "- ".repeat(50_000)),
+        format!("SELECT {}", "f(".repeat(50_000)),
+        format!("SELECT {}", "CASE WHEN ".repeat(50_000)),
+        format!("SELECT {}", "(SELECT ".repeat(10_000)),
+        format!("SELECT * FROM {}", "(SELECT * FROM ".repeat(10_000)),
+        format!("SELECT * FROM {}t", "(".repeat(50_000)),
+        format!("SELECT * FROM t WHERE {}", chain("a = 1", " OR ")),
+        format!("SELECT * FROM t WHERE {}", chain("a", " AND ")),
+        format!("SELECT {}", chain("1", " + ")),
+        chain("SELECT 1", " UNION "),
+    ];
+    for sql in &inputs {
+        let err = Parser::parse_sql(&MySqlDialect {}, sql).unwrap_err();
+        assert_eq!(err.kind(), Some(&SyntaxErrorKind::RecursionLimit));
+        assert!(Parser::parse_sql_located(&MySqlDialect {}, sql).is_err());
+        for result in Parser::iter_statements(&MySqlDialect {}, sql) {
+            assert!(result.is_err());
+        }
+    }
+
+    // just under the limit, the AST can still be printed and dropped
+    let sql = format!("SELECT {}1{}", "(".repeat(40), ")".repeat(40));
+    let statement = Parser::parse_sql(&MySqlDialect {}, &sql).unwrap().remove(0);
+    assert_eq!("SELECT 1", statement.to_string().replace(&['(', ')'][..], ""));
+    let sql = format!("SELECT * FROM t WHERE {}", vec!["a = 1"; 400].join(" OR "));
+    let statement = Parser::parse_sql(&MySqlDialect {}, &sql).unwrap().remove(0);
+    assert_eq!(sql, statement.clone().to_string());
+}
+
+#[test]
+fn parse_sql_with_recovery() {
+    let sql = "SELECT 1;\nSELECT 1 +;\nINSERT INTO t VALUES ('x; y');\nDROP TABLE;\nDELETE FROM t";
+    let (statements, errors) = Parser::parse_sql_with_recovery(&MySqlDialect {}, sql);
+    assert_eq!(
+        Parser::parse_sql(&MySqlDialect {}, "SELECT 1; INSERT INTO t VALUES ('x; y'); DELETE FROM t").unwrap(),
+        statements
+    );
+    assert_eq!(2, errors.len());
+    assert_eq!(1, errors[0].0);
+    match &errors[0].1 {
+        ParserError::SyntaxError(e) => assert_eq!(Some(2), e.location.map(|l| l.line)),
+        e => panic!("unexpected error {:?}", e),
+    }
+    assert_eq!(3, errors[1].0);
+    match &errors[1].1 {
+        ParserError::SyntaxError(e) => assert_eq!(Some(4), e.location.map(|l| l.line)),
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn parse_expr_and_query_str() {
+    let dialect = MySqlDialect {};
+    assert_eq!(
+        Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("a"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(number("1"))),
+        },
+        Parser::parse_expr_str(&dialect, "a = 1").unwrap()
+    );
+    assert_eq!(
+        "a IN (1, 2) AND `b` LIKE 'x%'",
+        Parser::parse_expr_str(&dialect, "a IN (1, 2) AND `b` LIKE 'x%'").unwrap().to_string()
+    );
+    assert_eq!(
+        "SELECT a FROM t WHERE b = 1 LIMIT 10",
+        Parser::parse_query_str(&dialect, "SELECT a FROM t WHERE b = 1 LIMIT 10").unwrap().to_string()
+    );
+
+    // trailing garbage is an error
+    assert_eq!(
+        "sql parser error: Expected end of input, found: b",
+        Parser::parse_expr_str(&dialect, "a = 1 b").unwrap_err().to_string()
+    );
+    assert_eq!(
+        "sql parser error: Expected end of input, found: )",
+        Parser::parse_expr_str(&dialect, "(a = 1))").unwrap_err().to_string()
+    );
+    assert_eq!(
+        "sql parser error: Expected end of input, found: ;",
+        Parser::parse_query_str(&dialect, "SELECT 1; DROP TABLE t").unwrap_err().to_string()
+    );
+    assert!(Parser::parse_expr_str(&dialect, "").is_err());
+}
+
+#[test]
+fn rewrite_table_names() {
+    let rename = |name: &mut ObjectName| {
+        if let Some(ident) = name.0.last_mut() {
+            // `c` is a CTE rather than a table
+            if ident.value != "c" {
+                ident.value = format!("{}_0042", ident.value);
+            }
+        }
+    };
+
+    let sql = "WITH c AS (SELECT a FROM orders) \
+        SELECT (SELECT MAX(b) FROM items), x.a \
+        FROM c JOIN (SELECT a FROM users) AS x ON x.a = c.a \
+        WHERE EXISTS (SELECT 1 FROM db.t1) AND c.a IN (SELECT a FROM t2 UNION SELECT a FROM t3)";
+    let mut statement = mysql().verified_stmt(sql);
+    statement.rewrite_table_names(rename);
+    assert_eq!(
+        "WITH c AS (SELECT a FROM orders_0042) \
+        SELECT (SELECT MAX(b) FROM items_0042), x.a \
+        FROM c JOIN (SELECT a FROM users_0042) AS x ON x.a = c.a \
+        WHERE EXISTS (SELECT 1 FROM db.t1_0042) AND c.a IN (SELECT a FROM t2_0042 UNION SELECT a FROM t3_0042)",
+        statement.to_string()
+    );
+
+    let mut statement = mysql().verified_stmt("INSERT INTO t (a) SELECT a FROM s WHERE a > (SELECT MIN(a) FROM u)");
+    statement.rewrite_table_names(rename);
+    assert_eq!(
+        "INSERT INTO t_0042 (a) SELECT a FROM s_0042 WHERE a > (SELECT MIN(a) FROM u_0042)",
+        statement.to_string()
+    );
+
+    let mut statement = mysql().verified_stmt("CREATE VIEW v AS SELECT a FROM t WHERE b IN (SELECT b FROM s)");
+    statement.rewrite_table_names(rename);
+    assert_eq!(
+        "CREATE VIEW v_0042 AS SELECT a FROM t_0042 WHERE b IN (SELECT b FROM s_0042)",
+        statement.to_string()
+    );
+
+    // function names are not table names
+    let mut statement = mysql().verified_stmt("UPDATE t SET a = COUNT(b) WHERE c = 1");
+    statement.rewrite_table_names(rename);
+    assert_eq!("UPDATE t_0042 SET a = COUNT(b) WHERE c = 1", statement.to_string());
+}
+
+#[test]
+fn visitor_mut_adds_predicate() {
+    struct AddTenant;
+
+    impl VisitorMut for AddTenant {
+        fn visit_select(&mut self, select: &mut Select) {
+            walk_select_mut(self, select);
+            let tenant = Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("tenant_id"))),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Value(number("7"))),
+            };
+            select.selection = Some(match select.selection.take() {
+                Some(selection) => Expr::BinaryOp {
+                    left: Box::new(Expr::Nested(Box::new(selection))),
+                    op: BinaryOperator::And,
+                    right: Box::new(tenant),
+                },
+                None => tenant,
+            });
+        }
+    }
+
+    let mut statement = mysql().verified_stmt("SELECT a FROM t WHERE b = 1 OR c IN (SELECT c FROM s)");
+    AddTenant.visit_statement(&mut statement);
+    assert_eq!(
+        "SELECT a FROM t WHERE (b = 1 OR c IN (SELECT c FROM s WHERE tenant_id = 7)) AND tenant_id = 7",
+        statement.to_string()
+    );
+}
+
+#[test]
+fn table_references() {
+    let references = |sql: &str| -> Vec<(String, TableAccess, bool)> {
+        mysql()
+            .verified_stmt(sql)
+            .table_references()
+            .into_iter()
+            .map(|r| (r.name.to_string(), r.access, r.cte))
+            .collect()
+    };
+    use TableAccess::{Read, Write};
+
+    // `orders` inside the CTE is the real table, the outer ones are the CTE
+    assert_eq!(
+        vec![
+            ("orders".to_string(), Read, false),
+            ("orders".to_string(), Read, true),
+            ("items".to_string(), Read, false),
+            ("orders".to_string(), Read, true),
+        ],
+        references(
+            "WITH orders AS (SELECT a FROM orders) \
+            SELECT a FROM orders JOIN (SELECT b FROM items WHERE b IN (SELECT a FROM orders)) AS x ON x.b = a"
+        )
+    );
+    // a CTE doesn't shadow a qualified name, nor a table outside its query
+    assert_eq!(
+        vec![
+            ("db.c".to_string(), Read, false),
+            ("c".to_string(), Read, true),
+            ("c".to_string(), Read, false),
+        ],
+        references(
+            "SELECT * FROM (WITH c AS (SELECT 1) SELECT * FROM db.c, c) AS x, c"
+        )
+    );
+    assert_eq!(
+        vec![
+            ("t".to_string(), Write, false),
+            ("s".to_string(), Read, false),
+            ("u".to_string(), Read, false),
+        ],
+        references("INSERT INTO t (a) SELECT a FROM s WHERE a > (SELECT MIN(a) FROM u)")
+    );
+    assert_eq!(
+        vec![
+            ("t".to_string(), Write, false),
+            ("s".to_string(), Read, false),
+        ],
+        references("UPDATE t SET a = 1 WHERE b IN (SELECT b FROM s)")
+    );
+    assert_eq!(
+        vec![("db.t".to_string(), Write, false), ("s".to_string(), Read, false)],
+        references("CREATE TABLE db.t AS SELECT * FROM s")
+    );
+    assert_eq!(
+        vec![("v".to_string(), Write, false), ("s".to_string(), Read, false)],
+        references("CREATE VIEW v AS SELECT * FROM s")
+    );
+    assert_eq!(
+        vec![("t".to_string(), Write, false)],
+        references("DELETE FROM t WHERE a = 1")
+    );
+}
+
+#[test]
+fn statement_kind() {
+    use StatementKind::*;
+    let cases = [
+        ("SELECT * FROM t", Query, true),
+        ("WITH c AS (SELECT 1) SELECT * FROM c", Query, true),
+        ("SELECT a FROM t UNION SELECT a FROM s", Query, true),
+        ("SELECT a FROM t WHERE id = 1 FOR UPDATE", Query, false),
+        ("SELECT * FROM (SELECT a FROM t FOR UPDATE) AS d", Query, false),
+        ("SELECT a FROM s WHERE b IN (SELECT b FROM t FOR UPDATE)", Query, false),
+        ("WITH c AS (SELECT a FROM t FOR UPDATE) SELECT * FROM c", Query, false),
+        ("SELECT * FROM (SELECT a FROM t) AS d WHERE a IN (SELECT a FROM s)", Query, true),
+        ("INSERT INTO t (a) VALUES (1)", Dml, false),
+        ("INSERT INTO t (a) SELECT a FROM s", Dml, false),
+        ("REPLACE INTO t (a) VALUES (1)", Dml, false),
+        ("UPDATE t SET a = 1 WHERE b = 2", Dml, false),
+        ("DELETE FROM t WHERE a = 1", Dml, false),
+        ("CALL p(1)", Dml, false),
+        ("CREATE TABLE t (a INT)", Ddl, false),
+        ("CREATE TABLE t AS SELECT * FROM s", Ddl, false),
+        ("CREATE VIEW v AS SELECT * FROM t", Ddl, false),
+        ("CREATE INDEX i ON t (a)", Ddl, false),
+        ("ALTER TABLE t ADD COLUMN b INT", Ddl, false),
+        ("DROP TABLE t", Ddl, false),
+        ("DROP INDEX i ON t", Ddl, false),
+        ("CREATE SCHEMA s", Ddl, false),
+        ("START TRANSACTION", TransactionControl, false),
+        ("START TRANSACTION READ ONLY", TransactionControl, true),
+        ("START TRANSACTION READ WRITE", TransactionControl, false),
+        ("SET TRANSACTION ISOLATION LEVEL READ COMMITTED", TransactionControl, false),
+        ("COMMIT", TransactionControl, false),
+        ("ROLLBACK", TransactionControl, false),
+        ("LOCK TABLES t READ", TransactionControl, false),
+        ("LOCK TABLES t WRITE", TransactionControl, false),
+        ("UNLOCK TABLES", TransactionControl, false),
+        ("SHOW COLUMNS FROM t", Show, true),
+        ("SHOW CREATE TABLE t", Show, true),
+        ("DESC t", Show, true),
+        ("EXPLAIN SELECT * FROM t", Show, true),
+        ("EXPLAIN DELETE FROM t", Show, true),
+        ("EXPLAIN ANALYZE SELECT * FROM t", Show, true),
+        ("EXPLAIN ANALYZE DELETE FROM t", Show, false),
+        ("SET autocommit = 0", Session, true),
+        ("SET NAMES utf8", Session, true),
+        ("SET @a = 1", Session, true),
+        ("SET @@session.sql_mode = 'ANSI'", Session, true),
+        ("SET @@GLOBAL.max_connections = 1000", Session, false),
+        ("SET @@persist.max_connections = 1000", Session, false),
+        ("USE db", Session, true),
+        ("/*!40101 SET NAMES utf8 */", Session, true),
+        ("/*!40000 ALTER TABLE t DISABLE KEYS */", Ddl, false),
+    ];
+    for (sql, kind, read_only) in cases.iter() {
+        let statement = Parser::parse_sql(&MySqlDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(*kind, statement.statement_kind(), "{}", sql);
+        assert_eq!(*read_only, statement.is_read_only(), "{}", sql);
+        assert_eq!(*kind == Dml, statement.is_dml(), "{}", sql);
+        assert_eq!(*kind == Ddl, statement.is_ddl(), "{}", sql);
+        assert_eq!(
+            *kind == TransactionControl,
+            statement.is_transaction_control(),
+            "{}",
+            sql
+        );
+    }
+
+    let object_type = |sql: &str| {
+        Parser::parse_sql(&MySqlDialect {}, sql).unwrap()[0].object_type()
+    };
+    assert_eq!(Some(ObjectType::Table), object_type("UPDATE t SET a = 1"));
+    assert_eq!(Some(ObjectType::View), object_type("CREATE VIEW v AS SELECT 1"));
+    assert_eq!(Some(ObjectType::Index), object_type("DROP INDEX i ON t"));
+    assert_eq!(None, object_type("SELECT 1"));
+}
+
+#[test]
+fn normalize_and_fingerprint() {
+    let cases = [
+        (
+            "SELECT * FROM t WHERE a = 1 AND b = 'x'",
+            "select * from t where a = ? and b = ?",
+            "select * from t where a = ? and b = ?",
+        ),
+        (
+            "SELECT a FROM t WHERE id IN (1, 2, 3)",
+            "select a from t where id in (?, ?, ?)",
+            "select a from t where id in(?+)",
+        ),
+        (
+            "INSERT INTO t (a, b) VALUES (1, 'x'), (2, 'y')",
+            "insert into t (a, b) values (?, ?), (?, ?)",
+            "insert into t (a, b) values(?+)",
+        ),
+        (
+            "SELECT * FROM t WHERE a > -5 LIMIT 10",
+            "select * from t where a > ? limit ?",
+            "select * from t where a > ? limit ?",
+        ),
+        (
+            "UPDATE t SET a = 'x' WHERE id = 3",
+            "update t set a = ? where id = ?",
+            "update t set a = ? where id = ?",
+        ),
+        (
+            "SELECT /*+ BKA(t) */ a FROM t WHERE d = DATE '2020-01-01' AND e IN (SELECT e FROM s WHERE f IN (1, 2))",
+            "select a from t where d = ? and e in (select e from s where f in (?, ?))",
+            "select a from t where d = ? and e in (select e from s where f in(?+))",
+        ),
+    ];
+    // the third column is the output of pt-fingerprint, which differs from
+    // the collapsed form only in whitespace
+    let squash = |s: &str| -> String { s.chars().filter(|c| !c.is_whitespace()).collect() };
+    for (sql, normalized, fingerprint) in cases.iter() {
+        let statement = Parser::parse_sql(&MySqlDialect {}, sql).unwrap().pop().unwrap();
+        assert_eq!(*normalized, statement.normalize(false));
+        assert_eq!(squash(fingerprint), squash(&statement.normalize(true)));
+    }
+
+    let hash = |sql: &str| {
+        Parser::parse_sql(&MySqlDialect {}, sql).unwrap()[0].fingerprint_hash()
+    };
+    assert_eq!(
+        hash("SELECT a FROM t WHERE b = 1 AND c IN (1, 2)"),
+        hash("SELECT a FROM t WHERE b = 'x' AND c IN (3)")
+    );
+    assert_eq!(
+        hash("INSERT INTO t (a) VALUES (1)"),
+        hash("INSERT INTO t (a) VALUES (2), (3)")
+    );
+    assert_ne!(
+        hash("SELECT a FROM t WHERE b = 1"),
+        hash("SELECT a FROM t WHERE c = 1")
+    );
+
+    // names spelled like a keyword keep their case
+    let statement = mysql().verified_stmt("SELECT STATUS, COUNT(*) FROM USER WHERE `KEY` = 1");
+    assert_eq!("select STATUS, count(*) from USER where `KEY` = ?", statement.normalize(false));
+    let statement = mysql()
+        .verified_stmt("/*!50700 ALTER TABLE t ROW_FORMAT=DYNAMIC, ALGORITHM=INPLACE, LOCK=NONE */");
+    assert_eq!(
+        "alter table t row_format=DYNAMIC, algorithm=INPLACE, lock=NONE",
+        statement.normalize(false)
+    );
+}
+
+#[test]
+fn format_statements() {
+    let format = |sql: &str, options: &FormatOptions| {
+        format_statement(&mysql().verified_stmt(sql), options)
+    };
+
+    let sql = "WITH recent AS (SELECT customer_id, MAX(created_at) AS last_order FROM orders GROUP BY customer_id) \
+        SELECT c.id, c.name, r.last_order, (SELECT COUNT(*) FROM tickets AS t WHERE t.customer_id = c.id) AS tickets \
+        FROM customers AS c JOIN recent AS r ON r.customer_id = c.id LEFT JOIN regions AS g ON g.id = c.region_id \
+        WHERE c.active = 1 AND r.last_order > '2020-01-01' AND c.id IN (SELECT customer_id FROM vip) \
+        ORDER BY r.last_order DESC LIMIT 10";
+    assert_eq!(
+        "\
+WITH recent AS (
+  SELECT customer_id, MAX(created_at) AS last_order
+  FROM orders
+  GROUP BY customer_id
+)
+SELECT
+  c.id,
+  c.name,
+  r.last_order,
+  (
+    SELECT COUNT(*)
+    FROM tickets AS t
+    WHERE t.customer_id = c.id
+  ) AS tickets
+FROM customers AS c
+  JOIN recent AS r ON r.customer_id = c.id
+  LEFT JOIN regions AS g ON g.id = c.region_id
+WHERE c.active = 1
+  AND r.last_order > '2020-01-01'
+  AND c.id IN (
+    SELECT customer_id
+    FROM vip
+  )
+ORDER BY r.last_order DESC
+LIMIT 10",
+        format(sql, &FormatOptions::default())
+    );
+
+    let options = FormatOptions {
+        indent: 4,
+        uppercase_keywords: false,
+        quote_identifiers: true,
+        max_line_width: 30,
+    };
+    assert_eq!(
+        "\
+select `a`, COUNT(*) as `n`
+from `t`
+union all
+select `a`, `b`
+from (
+    select `a`, `b`
+    from `s`
+    where `b` = 'SELECT'
+) as `x`",
+        format(
+            "SELECT a, COUNT(*) AS n FROM t UNION ALL SELECT a, b FROM (SELECT a, b FROM s WHERE b = 'SELECT') AS x",
+            &options
+        )
+    );
+
+    let sql = "CREATE TABLE orders (id BIGINT NOT NULL AUTO_INCREMENT, customer_id INT NOT NULL, \
+        note TEXT, PRIMARY KEY (id)) ENGINE=InnoDB";
+    assert_eq!(
+        "\
+CREATE TABLE orders (
+  id BIGINT NOT NULL AUTO_INCREMENT,
+  customer_id INT NOT NULL,
+  note TEXT,
+  PRIMARY KEY (id)
+) ENGINE=InnoDB",
+        format(sql, &FormatOptions::default())
+    );
+    assert_eq!(
+        "\
+create table `orders` (
+    `id` bigint not null auto_increment,
+    `customer_id` int not null,
+    `note` text,
+    primary key (`id`)
+) engine=InnoDB",
+        format(sql, &options)
+    );
+
+    // names spelled like a keyword keep their case
+    let options = FormatOptions {
+        uppercase_keywords: false,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        "\
+select USER.STATUS, COUNT(*) as TOTAL
+from USER
+where STATUS in ('ACTIVE', `KEY`)
+group by USER.STATUS",
+        format(
+            "SELECT USER.STATUS, COUNT(*) AS TOTAL FROM USER WHERE STATUS IN ('ACTIVE', `KEY`) \
+            GROUP BY USER.STATUS",
+            &options
+        )
+    );
+    assert_eq!(
+        "update STATUS\nset VALUE = DEFAULT, `COMMENT` = null\nwhere ID = 1",
+        format("UPDATE STATUS SET VALUE = DEFAULT, `COMMENT` = NULL WHERE ID = 1", &options)
+    );
+
+    assert_eq!(
+        "\
+INSERT INTO t (a, b)
+SELECT a, b
+FROM s
+WHERE b > 1",
+        format(
+            "INSERT INTO t (a, b) SELECT a, b FROM s WHERE b > 1",
+            &FormatOptions::default()
+        )
+    );
+}
+
+#[test]
+fn format_lowercase_keywords_parses_back_the_same() {
+    let options = FormatOptions {
+        uppercase_keywords: false,
+        ..FormatOptions::default()
+    };
+    let parse = |sql: &str| Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    let mut statements = parse(include_str!("queries/mysql/dump.sql"));
+    statements.extend(parse(include_str!("queries/mysql/parameterized.sql")));
+    statements.push(Statement::parse_with(
+        &MySqlDialect {},
+        include_str!("queries/mysql/show_create_table.sql"),
+    )
+    .unwrap());
+    // words the statement holds as written, spelled like keywords
+    statements.extend(parse(
+        "CREATE TABLE t (a INT, `b` INT, KEY USING BTREE (a), INDEX KEY_A (b)) ROW_FORMAT=DYNAMIC; \
+         ALTER TABLE t ADD COLUMN c INT, ALGORITHM=INPLACE, LOCK=NONE; \
+         SELECT COUNT(*), CURRENT_TIMESTAMP, @@SESSION.AUTOCOMMIT FROM t FORCE INDEX (PRIMARY); \
+         UPDATE t SET a = DEFAULT WHERE b = CAST(c AS UNSIGNED); \
+         /*!50700 CREATE TABLE u (a INT) ENGINE=InnoDB ROW_FORMAT=COMPACT */",
+    ));
+    for statement in statements {
+        let formatted = format_statement(&statement, &options);
+        assert!(!formatted.contains('\u{E000}'), "{}", formatted);
+        assert_eq!(vec![statement], parse(&formatted), "{}", formatted);
+    }
+}
+
+#[test]
+fn quote_identifiers_on_output() {
+    let minimal = QuoteOptions::default();
+    let always = QuoteOptions {
+        policy: QuotePolicy::Always,
+        quote: '"',
+    };
+
+    // identifiers that are safe unquoted stay unquoted
+    let sql = "SELECT a, `b`, status, t.`select` FROM db.t AS x WHERE c = 1 AND d = CURRENT_TIMESTAMP";
+    let statement = mysql().verified_stmt(sql);
+    assert_eq!(sql, statement.to_sql_with(&minimal));
+    assert_eq!(
+        "SELECT \"a\", \"b\", \"status\", \"t\".\"select\" FROM \"db\".\"t\" AS \"x\" \
+         WHERE \"c\" = 1 AND \"d\" = CURRENT_TIMESTAMP",
+        statement.to_sql_with(&always)
+    );
+
+    // names built without quotes get them when needed
+    let mut statement = mysql().verified_stmt("INSERT INTO t (a, b) VALUES (1, DEFAULT)");
+    statement.rewrite_table_names(|name| name.0 = vec![Ident::new("order")]);
+    if let Statement::Insert { columns, .. } = &mut statement {
+        columns[1] = Ident::new("my col");
+    }
+    assert_eq!(
+        "INSERT INTO order (a, my col) VALUES (1, DEFAULT)",
+        statement.to_string()
+    );
+    assert_eq!(
+        "INSERT INTO `order` (a, `my col`) VALUES (1, DEFAULT)",
+        statement.to_sql_with(&minimal)
+    );
+
+    assert_eq!(
+        "CREATE TABLE `key` (id INT, `1st` INT, `group` INT, PRIMARY KEY (id))",
+        mysql()
+            .one_statement_parses_to(
+                "CREATE TABLE `key` (id INT, `1st` INT, `group` INT, PRIMARY KEY (id))",
+                ""
+            )
+            .to_sql_with(&minimal)
+    );
+}
+
+#[test]
+fn round_trip_statements() {
+    // each statement is printed, parsed again and compared with the first
+    // parse by `one_statement_parses_to`, as well as with the input
+    let statements = [
+        "SELECT a, b FROM t WHERE a = 1",
+        "EXPLAIN SELECT * FROM t",
+        "EXPLAIN ANALYZE SELECT * FROM t",
+        "EXPLAIN FORMAT=JSON SELECT * FROM t",
+        "EXPLAIN ANALYZE FORMAT=TREE DELETE FROM t WHERE a = 1",
+        "EXPLAIN UPDATE t SET a = 1",
+        "EXPLAIN FOR CONNECTION 42",
+        "CALL p",
+        "CALL p()",
+        "CALL p(1, 'a', b + 1)",
+        "CALL db.p(1)",
+        "SET a = 1",
+        "SET LOCAL a = 1",
+        "SET NAMES utf8",
+        "SET a = 1 WHERE platform = 'x'",
+        "RELOAD CONFIG",
+        "RELOAD USER",
+        "RELOAD USER WHERE platform = 'x'",
+        "SHOW a",
+        "SHOW GLOBAL a",
+        "SHOW GLOBAL a WHERE b = 1",
+        "SHOW COLUMNS FROM t",
+        "SHOW FULL COLUMNS FROM t LIKE 'a%'",
+        "SHOW CREATE TABLE t",
+        "DESC t",
+        "USE db",
+        "START TRANSACTION",
+        "START TRANSACTION READ ONLY",
+        "SET TRANSACTION ISOLATION LEVEL READ COMMITTED",
+        "COMMIT",
+        "ROLLBACK",
+        "LOCK TABLES t READ, s WRITE",
+        "UNLOCK TABLES",
+        "INSERT INTO t (a) VALUES (1) ON DUPLICATE KEY UPDATE a = 2",
+        "INSERT IGNORE INTO t (a) SELECT a FROM s",
+        "REPLACE INTO t (a) VALUES (1)",
+        "UPDATE t SET a = 1 WHERE b = 2 LIMIT 3",
+        "DELETE FROM t WHERE a = 1",
+        "CREATE TABLE t (a INT NOT NULL, b CHAR(10) DEFAULT 'x', PRIMARY KEY (a)) ENGINE=InnoDB",
+        "CREATE TABLE t AS SELECT * FROM s",
+        "CREATE VIEW v AS SELECT * FROM t",
+        "CREATE INDEX i ON t (a)",
+        "CREATE UNIQUE INDEX i ON t (a, b)",
+        "ALTER TABLE t ADD COLUMN c INT",
+        "ALTER TABLE t DROP COLUMN c",
+        "ALTER TABLE t RENAME COLUMN a TO b",
+        "DROP TABLE t",
+        "DROP TABLE IF EXISTS t, s",
+        "DROP INDEX i ON t",
+        "CREATE SCHEMA s",
+        "/*!40101 SET NAMES utf8 */",
+    ];
+    for sql in statements.iter() {
+        mysql().verified_stmt(sql);
+    }
+}
+
+#[test]
+fn create_table_elements_round_trip() {
+    // columns, indexes and constraints print as one comma separated list
+    let statements = [
+        "CREATE TABLE t (a INT, b INT)",
+        "CREATE TABLE t (a INT, KEY k (a))",
+        "CREATE TABLE t (a INT, KEY k (a), KEY l (a, b)) ENGINE=InnoDB COMMENT='t'",
+    ];
+    for sql in statements.iter() {
+        mysql().verified_stmt(sql);
+    }
+    // only the generic dialect parses table constraints
+    let generic = TestedDialects {
+        dialects: vec![Box::new(GenericDialect {})],
+    };
+    generic.verified_stmt("CREATE TABLE t (a INT, CONSTRAINT c CHECK (a > 0))");
+    generic.verified_stmt("CREATE TABLE t (a INT, b INT, UNIQUE (a, b))");
+
+    let constraint = match generic.verified_stmt("CREATE TABLE t (a INT, CHECK (a > 0))") {
+        Statement::CreateTable { constraints, .. } => constraints,
+        _ => unreachable!(),
+    };
+    let mut statement = mysql().verified_stmt("CREATE TABLE t (a INT, KEY k (a)) ENGINE=InnoDB");
+    if let Statement::CreateTable { constraints, .. } = &mut statement {
+        *constraints = constraint;
+    }
+    assert_eq!(
+        "CREATE TABLE t (a INT, KEY k (a), CHECK (a > 0)) ENGINE=InnoDB",
+        statement.to_string()
+    );
+}
+
+#[test]
+fn custom_dialect_hooks() {
+    // the generic dialect, but reading MySQL index definitions
+    #[derive(Debug)]
+    struct IndexDialect;
+    impl Dialect for IndexDialect {
+        fn is_identifier_start(&self, ch: char) -> bool {
+            GenericDialect {}.is_identifier_start(ch)
+        }
+        fn is_identifier_part(&self, ch: char) -> bool {
+            GenericDialect {}.is_identifier_part(ch)
+        }
+        fn supports_index_definitions(&self) -> bool {
+            true
+        }
+    }
+
+    let sql = "CREATE TABLE t (a INT, KEY k (a))";
+    assert!(Parser::parse_sql(&GenericDialect {}, sql).is_err());
+    let statements = Parser::parse_sql(&IndexDialect, sql).unwrap();
+    match &statements[0] {
+        Statement::CreateTable { index, .. } => assert_eq!(1, index.len()),
+        _ => unreachable!(),
+    }
+    assert_eq!(sql, statements[0].to_string());
+    // while `COLLATE` is still an operator
+    assert!(matches!(
+        Parser::parse_expr_str(&IndexDialect, "a COLLATE utf8mb4_bin").unwrap(),
+        Expr::Collate { .. }
+    ));
+}
+
+#[test]
+fn parse_with_tokens() {
+    let sql = "-- first\nSELECT  a,\n  b FROM t /* inline */ ;\n\n/*!40101 SET NAMES utf8 */;\t# last\nDELETE FROM t WHERE a = 'x'\n";
+    let mut parsed = Parser::parse_with_tokens(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(sql, parsed.to_source());
+    assert_eq!(
+        parsed.tokens.iter().map(|t| t.token.to_string()).collect::<String>(),
+        sql
+    );
+
+    let spans: Vec<&str> = parsed
+        .statements
+        .iter()
+        .map(|s| &sql[s.span.start..s.span.end])
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            "SELECT  a,\n  b FROM t",
+            "/*!40101 SET NAMES utf8 */",
+            "DELETE FROM t WHERE a = 'x'",
+        ]
+    );
+    let trivia: Vec<&str> = parsed
+        .statements
+        .iter()
+        .map(|s| parsed.leading_trivia(s))
+        .collect();
+    assert_eq!(trivia, vec!["-- first\n", "\n\n", "\t# last\n"]);
+
+    // a changed statement is printed anew, the rest is kept as written
+    parsed.statements[2].statement = mysql().verified_stmt("DELETE FROM t WHERE a = 'y'");
+    assert!(parsed.statements[2].is_modified() && !parsed.statements[0].is_modified());
+    assert_eq!(parsed.to_source(), sql.replace("'x'", "'y'"));
+    parsed.statements[0].statement = mysql().verified_stmt("SELECT c FROM t");
+    assert!(parsed.to_source().starts_with("-- first\nSELECT c FROM t /* inline */ ;"));
+}
+
+#[test]
+fn name_comparison() {
+    let ident = Ident::with_quote('`', "Col");
+    assert!(ident.eq_ignore_case("col") && ident.eq_ignore_case("COL"));
+    assert!(!ident.eq_ignore_case("co"));
+    assert_eq!(Ident::new("col"), ident.normalized(false));
+    assert_eq!(Ident::new("Col"), ident.normalized(true));
+
+    let name = ObjectName(vec![Ident::new("Db"), Ident::with_quote('`', "T")]);
+    assert_eq!(Some(&Ident::new("Db")), name.schema());
+    assert_eq!(Some(&Ident::with_quote('`', "T")), name.name());
+    assert!(name.matches(Some("Db"), "T", true));
+    assert!(name.matches(None, "T", true));
+    assert!(!name.matches(Some("db"), "T", true));
+    assert!(name.matches(Some("db"), "t", false));
+    assert!(!name.matches(Some("db"), "u", false));
+    assert_eq!("db.t", name.normalized(false).to_string());
+
+    let unqualified = ObjectName(vec![Ident::new("t")]);
+    assert_eq!(None, unqualified.schema());
+    assert!(unqualified.matches(None, "T", false));
+    assert!(!unqualified.matches(Some("db"), "t", false));
+
+    // `DROP INDEX i` without `ON` has an empty `on_info`
+    match mysql().verified_stmt("DROP INDEX i") {
+        Statement::Drop { on_info, .. } => {
+            assert_eq!(None, on_info.name());
+            assert!(!on_info.matches(None, "i", false));
+        }
+        _ => unreachable!(),
+    }
+
+    // keywords parsed as identifiers compare in any case
+    mysql().one_statement_parses_to(
+        "set transaction isolation level read committed",
+        "SET TRANSACTION ISOLATION LEVEL READ COMMITTED",
+    );
+    mysql().one_statement_parses_to("set names utf8", "SET names utf8");
+}
+
+#[test]
+fn statement_builders() {
+    let t = || ObjectName(vec![Ident::new("t")]);
+    let column = |name: &str| Expr::Identifier(Ident::new(name));
+    let num = |n: &'static str| Expr::Value(number(n));
+    let eq = |left: Expr, right: Expr| Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::Eq,
+        right: Box::new(right),
+    };
+
+    let query = SelectBuilder::new()
+        .column(column("a"))
+        .column_as(column("b"), Ident::new("c"))
+        .from(t())
+        .filter(eq(column("a"), num("1")))
+        .filter(eq(column("b"), num("2")))
+        .order_by(column("a"), Some(false))
+        .limit(num("10"))
+        .build();
+    assert_eq!(
+        Statement::Query(Box::new(query)),
+        mysql().verified_stmt("SELECT a, b AS c FROM t WHERE a = 1 AND b = 2 ORDER BY a DESC LIMIT 10")
+    );
+
+    let insert = InsertBuilder::new(t())
+        .columns(vec![Ident::new("a")])
+        .values(vec![num("1")])
+        .values(vec![num("2")])
+        .on_duplicate_key_update(Ident::new("a"), num("3"))
+        .build();
+    assert_eq!(
+        insert,
+        mysql().verified_stmt("INSERT INTO t (a) VALUES (1), (2) ON DUPLICATE KEY UPDATE a = 3")
+    );
+    let insert_select = InsertBuilder::new(t())
+        .ignore()
+        .query(SelectBuilder::new().wildcard().from(ObjectName(vec![Ident::new("s")])).build())
+        .build();
+    assert_eq!(insert_select, mysql().verified_stmt("INSERT IGNORE INTO t SELECT * FROM s"));
+
+    let update = UpdateBuilder::new(t())
+        .set(Ident::new("a"), num("1"))
+        .filter(eq(column("b"), num("2")))
+        .build();
+    assert_eq!(update, mysql().verified_stmt("UPDATE t SET a = 1 WHERE b = 2"));
+
+    let delete = DeleteBuilder::new(t()).filter(eq(column("a"), num("1"))).build();
+    assert_eq!(delete, mysql().verified_stmt("DELETE FROM t WHERE a = 1"));
+
+    // conditions keep their meaning when combined
+    let binary = |left: Expr, op: BinaryOperator, right: Expr| Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    };
+    let a_or_b = binary(
+        eq(column("a"), num("1")),
+        BinaryOperator::Or,
+        eq(column("b"), num("2")),
+    );
+    let query = SelectBuilder::new()
+        .wildcard()
+        .from(t())
+        .filter(a_or_b.clone())
+        .filter(eq(column("c"), num("3")))
+        .having(eq(column("c"), num("3")))
+        .having(a_or_b.clone())
+        .build();
+    assert_eq!(
+        Statement::Query(Box::new(query)),
+        mysql().verified_stmt(
+            "SELECT * FROM t WHERE (a = 1 OR b = 2) AND c = 3 HAVING c = 3 AND (a = 1 OR b = 2)"
+        )
+    );
+    let update = UpdateBuilder::new(t())
+        .set(Ident::new("a"), num("1"))
+        .filter(a_or_b)
+        .filter(eq(column("c"), num("3")))
+        .build();
+    assert_eq!(update, mysql().verified_stmt("UPDATE t SET a = 1 WHERE (a = 1 OR b = 2) AND c = 3"));
+    let delete = DeleteBuilder::new(t())
+        .filter(eq(column("c"), num("3")))
+        .filter(binary(eq(column("a"), num("1")), BinaryOperator::And, column("b")))
+        .build();
+    assert_eq!(delete, mysql().verified_stmt("DELETE FROM t WHERE c = 3 AND (a = 1 AND b)"));
+}
+
+#[test]
+fn parse_sql_with_locations() {
+    let sql = "SELECT 1 ;;\n -- comment\n;/*!40101 SET NAMES utf8 */ ;\nDELETE FROM t\n";
+    let statements = Parser::parse_sql_with_locations(&MySqlDialect {}, sql).unwrap();
+    let texts: Vec<&str> = statements.iter().map(|(_, range)| &sql[range.clone()]).collect();
+    assert_eq!(
+        texts,
+        vec!["SELECT 1 ;", "/*!40101 SET NAMES utf8 */ ;", "DELETE FROM t"]
+    );
+    assert_eq!(
+        statements.into_iter().map(|(s, _)| s).collect::<Vec<_>>(),
+        Parser::parse_sql(&MySqlDialect {}, sql).unwrap()
+    );
+    assert!(Parser::parse_sql_with_locations(&MySqlDialect {}, " ; ").unwrap().is_empty());
+}
+
+#[test]
+fn statement_spans_agree() {
+    // a statement that is the whole of a versioned comment spans its
+    // markers, as it prints them, whichever way it's parsed
+    let sql = "/*!40101 SET x = 1 */;\nSELECT 1 ;";
+    let dialect = MySqlDialect {};
+    let spans = vec![Span { start: 0, end: 21 }, Span { start: 23, end: 31 }];
+    let texts: Vec<&str> = spans.iter().map(|span| &sql[span.start..span.end]).collect();
+    assert_eq!(texts, vec!["/*!40101 SET x = 1 */", "SELECT 1"]);
+
+    let located = Parser::parse_sql_located(&dialect, sql).unwrap();
+    assert_eq!(spans, located.iter().map(|s| s.span).collect::<Vec<_>>());
+    let iterated: Vec<Span> = Parser::iter_statements(&dialect, sql)
+        .map(|s| s.unwrap().span)
+        .collect();
+    assert_eq!(spans, iterated);
+    let source = Parser::parse_with_tokens(&dialect, sql).unwrap();
+    assert_eq!(spans, source.statements.iter().map(|s| s.span).collect::<Vec<_>>());
+    // along with the `;`
+    let ranges: Vec<_> = Parser::parse_sql_with_locations(&dialect, sql)
+        .unwrap()
+        .into_iter()
+        .map(|(_, range)| range)
+        .collect();
+    assert_eq!(ranges, vec![0..22, 23..33]);
+}
+
+#[test]
+fn parse_explain_statements() {
+    let statements = [
+        "EXPLAIN INSERT INTO t VALUES (1)",
+        "EXPLAIN FORMAT=JSON INSERT INTO t (a) SELECT a FROM s",
+        "EXPLAIN REPLACE INTO t VALUES (1)",
+        "EXPLAIN FORMAT=JSON DELETE FROM t WHERE id = 1",
+        "EXPLAIN EXTENDED SELECT * FROM t",
+        "EXPLAIN PARTITIONS FORMAT=TRADITIONAL SELECT * FROM t",
+        "EXPLAIN ANALYZE FORMAT=TREE UPDATE t SET a = 1",
+        "EXPLAIN (SELECT 1)",
+        "EXPLAIN t",
+        "EXPLAIN db.t",
+        "EXPLAIN FOR CONNECTION 42",
+        "EXPLAIN FORMAT=JSON FOR CONNECTION 42",
+        "EXPLAIN FORMAT=TRADITIONAL FOR CONNECTION ?",
+    ];
+    for sql in statements.iter() {
+        mysql().verified_stmt(sql);
+    }
+
+    match mysql().verified_stmt("EXPLAIN EXTENDED SELECT 1") {
+        Statement::Explain { modifier, .. } => assert_eq!(Some(ExplainModifier::Extended), modifier),
+        _ => unreachable!(),
+    }
+    let describe = mysql().verified_stmt("EXPLAIN db.t");
+    match &describe {
+        Statement::Explain { body, .. } => assert_eq!(
+            &ExplainStmt::TableDescribe(ObjectName(vec![Ident::new("db"), Ident::new("t")])),
+            body
+        ),
+        _ => unreachable!(),
+    }
+    assert!(describe.is_read_only());
+    assert_eq!(1, describe.table_references().len());
+    // a plan can't be asked of a table
+    assert!(Parser::parse_sql(&MySqlDialect {}, "EXPLAIN FORMAT=JSON t").is_err());
+
+    match mysql().verified_stmt("EXPLAIN FORMAT=JSON FOR CONNECTION 42") {
+        Statement::Explain { format_type, body, .. } => {
+            assert_eq!(Some(ExplainFormat::JSON), format_type);
+            assert_eq!(ExplainStmt::Connection(number("42")), body);
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::UnexpectedToken {
+                expected: "a connection id".to_string(),
+                found: Box::new(Token::SingleQuotedString("abc".to_string())),
+            },
+            message: "Expected a connection id, found: 'abc'".to_string(),
+            location: Some(Location { line: 1, column: 24, offset: 23 }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, "EXPLAIN FOR CONNECTION 'abc'").unwrap_err()
+    );
+    assert!(Parser::parse_sql(&MySqlDialect {}, "EXPLAIN FOR CONNECTION 1.5").is_err());
+    assert!(Parser::parse_sql(&MySqlDialect {}, "EXPLAIN FOR 42").is_err());
+}
+
+#[test]
+fn parse_statement_comments() {
+    let select = mysql().verified_only_select("SELECT /* trace_id=abc-123 span=7 */ /* b */ a FROM t");
+    assert_eq!(
+        select.comments,
+        vec![" trace_id=abc-123 span=7 ".to_string(), " b ".to_string()]
+    );
+
+    match mysql().verified_stmt("INSERT /*+ SET_VAR(a=1) */ /* trace_id=abc-123 span=7 */ INTO t VALUES (1)") {
+        Statement::Insert { hints, comments, .. } => {
+            assert_eq!(hints, Some(" SET_VAR(a=1) ".to_string()));
+            assert_eq!(comments, vec![" trace_id=abc-123 span=7 ".to_string()]);
+        }
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("UPDATE /* a */ /* b */ t SET a = 1") {
+        Statement::Update { comments, .. } => {
+            assert_eq!(comments, vec![" a ".to_string(), " b ".to_string()])
+        }
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("DELETE /*trace*/ FROM t WHERE a = 1") {
+        Statement::Delete { comments, .. } => assert_eq!(comments, vec!["trace".to_string()]),
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("REPLACE /* trace */ INTO t VALUES (1)") {
+        Statement::Replace { comments, .. } => assert_eq!(comments, vec![" trace ".to_string()]),
+        _ => unreachable!(),
+    }
+    mysql().one_statement_parses_to("INSERT /* a */\n INTO t VALUES (1)", "INSERT /* a */ INTO t VALUES (1)");
+}
+
+#[test]
+fn parse_order_by_position_and_collate() {
+    let query = mysql().verified_query(
+        "SELECT a, b FROM t GROUP BY 1, b ORDER BY 2 DESC, name COLLATE utf8mb4_bin DESC, 1.5",
+    );
+    let select = match query.body {
+        SetExpr::Select(select) => select,
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        select.group_by,
+        vec![Expr::Position(1), Expr::Identifier(Ident::new("b"))]
+    );
+    assert_eq!(
+        query.order_by,
+        vec![
+            OrderByExpr {
+                expr: Expr::Position(2),
+                asc: Some(false),
+                nulls_first: None,
+            },
+            OrderByExpr {
+                expr: Expr::Collate {
+                    expr: Box::new(Expr::Identifier(Ident::new("name"))),
+                    collation: ObjectName(vec![Ident::new("utf8mb4_bin")]),
+                },
+                asc: Some(false),
+                nulls_first: None,
+            },
+            OrderByExpr {
+                expr: Expr::Value(number("1.5")),
+                asc: None,
+                nulls_first: None,
+            },
+        ]
+    );
+
+    // only a whole item is a position, and it isn't normalized like a literal
+    let select = mysql().verified_only_select("SELECT a FROM t GROUP BY a + 1");
+    assert_eq!(select.group_by[0].to_string(), "a + 1");
+    let statement = mysql().verified_stmt("SELECT a FROM t WHERE b = 3 ORDER BY 1");
+    assert_eq!("select a from t where b = ? order by 1", statement.normalize(false));
+
+    // a column's CHARACTER SET is still followed by its own COLLATE
+    match mysql().verified_stmt("CREATE TABLE t (a CHAR(10) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin)") {
+        Statement::CreateTable { columns, .. } => {
+            let options: Vec<_> = columns[0].options.iter().map(|o| o.option.clone()).collect();
+            assert_eq!(
+                options,
+                vec![
+                    ColumnOption::Character(Expr::Identifier(Ident::new("utf8mb4"))),
+                    ColumnOption::Collate(Expr::Identifier(Ident::new("utf8mb4_bin"))),
+                ]
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_in_parenthesized_subquery() {
+    let select = mysql().verified_only_select(
+        "SELECT * FROM t WHERE id IN ((SELECT id FROM a) UNION (SELECT id FROM b))",
+    );
+    match select.selection {
+        Some(Expr::InSubquery { subquery, negated: false, .. }) => {
+            assert!(matches!(subquery.body, SetExpr::SetOperation { op: SetOperator::Union, .. }))
+        }
+        _ => unreachable!(),
+    }
+    let select = mysql().verified_only_select("SELECT * FROM t WHERE id NOT IN ((SELECT id FROM a))");
+    assert!(matches!(select.selection, Some(Expr::InSubquery { negated: true, .. })));
+
+    // parenthesized values, or a subquery as one of several values, stay a list
+    let select = mysql().verified_only_select("SELECT * FROM t WHERE id IN ((1), (2))");
+    match select.selection {
+        Some(Expr::InList { list, .. }) => assert_eq!(
+            list,
+            vec![
+                Expr::Nested(Box::new(Expr::Value(number("1")))),
+                Expr::Nested(Box::new(Expr::Value(number("2")))),
+            ]
+        ),
+        _ => unreachable!(),
+    }
+    let select = mysql().verified_only_select("SELECT * FROM t WHERE id IN ((SELECT 1), 2)");
+    match select.selection {
+        Some(Expr::InList { list, .. }) => assert!(matches!(list[0], Expr::Subquery(_))),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_insert_optional_into() {
+    match mysql().verified_stmt("INSERT INTO t (x) VALUES (1)") {
+        Statement::Insert { into, table_name, .. } => {
+            assert!(into);
+            assert_eq!(table_name.to_string(), "t");
+        }
+        _ => unreachable!(),
+    }
+    match mysql().verified_stmt("INSERT IGNORE aa (x) VALUES (1)") {
+        Statement::Insert { into, ignore, table_name, .. } => {
+            assert!(!into);
+            assert!(ignore);
+            assert_eq!(table_name.to_string(), "aa");
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("INSERT `select` VALUES (1)");
+
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::UnexpectedToken {
+                expected: "a table name".to_string(),
+                found: Box::new(Token::make_keyword("SELECT")),
+            },
+            message: "Expected a table name, found: SELECT".to_string(),
+            location: Some(Location { line: 1, column: 8, offset: 7 }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, "INSERT SELECT * FROM t").unwrap_err()
+    );
+    assert!(Parser::parse_sql(&MySqlDialect {}, "INSERT INTO VALUES (1)").is_err());
+}
+
+#[test]
+fn parse_replace() {
+    let replace = |sql: &str| {
+        let statement = mysql().verified_stmt(sql);
+        let replace = statement.as_replace().unwrap();
+        (
+            replace.hints.clone(),
+            replace.priority.clone(),
+            *replace.into,
+            replace.columns.len(),
+            *replace.set,
+        )
+    };
+    assert_eq!(replace("REPLACE INTO t (a) VALUES (1)"), (None, None, true, 1, false));
+    assert_eq!(replace("REPLACE t (a) VALUES (1)"), (None, None, false, 1, false));
+    assert_eq!(
+        replace("REPLACE DELAYED INTO t (a) VALUES (1)"),
+        (None, Some(Priority::DELAYED), true, 1, false)
+    );
+    assert_eq!(
+        replace("REPLACE LOW_PRIORITY t SELECT * FROM s"),
+        (None, Some(Priority::LOW_PRIORITY), false, 0, false)
+    );
+    assert_eq!(
+        replace("REPLACE /*+ MAX_EXECUTION_TIME(10) */ INTO t VALUES (1)"),
+        (Some(" MAX_EXECUTION_TIME(10) ".to_string()), None, true, 0, false)
+    );
+    assert_eq!(replace("REPLACE INTO t SET a = 1, b = 'x'"), (None, None, true, 2, true));
+
+    // `HIGH_PRIORITY`, `IGNORE` and `ON DUPLICATE KEY UPDATE` are for `INSERT` only
+    assert!(mysql().parse_sql_statements("REPLACE HIGH_PRIORITY INTO t VALUES (1)").is_err());
+    assert!(mysql().parse_sql_statements("REPLACE IGNORE INTO t VALUES (1)").is_err());
+    assert!(mysql()
+        .parse_sql_statements("REPLACE INTO t VALUES (1) ON DUPLICATE KEY UPDATE a = 1")
+        .is_err());
+}
+
+#[test]
+fn parse_insert_set() {
+    let sql = "INSERT INTO t SET a = 1, b = DEFAULT ON DUPLICATE KEY UPDATE a = 2";
+    match mysql().verified_stmt(sql) {
+        Statement::Insert {
+            columns,
+            set,
+            source,
+            ..
+        } => {
+            assert_eq!(columns, vec![Ident::new("a"), Ident::new("b")]);
+            assert!(set);
+            assert_eq!("VALUES (1, DEFAULT)", source.to_string());
+        }
+        _ => unreachable!(),
+    }
+    assert!(mysql().parse_sql_statements("INSERT INTO t SET").is_err());
+    assert!(mysql().parse_sql_statements("INSERT INTO t SET a").is_err());
+}
+
+#[test]
+fn parse_on_duplicate_key_update() {
+    let sql = "INSERT INTO t (id, cnt) VALUES (1, 1) ON DUPLICATE KEY UPDATE cnt = cnt + VALUES(cnt)";
+    match mysql().verified_stmt(sql) {
+        Statement::Insert { update: Some(update), .. } => assert_eq!(
+            update,
+            vec![Assignment {
+                id: vec![Ident::new("cnt")],
+                value: Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("cnt"))),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Function(Function {
+                        name: ObjectName(vec![Ident::new("VALUES")]),
+                        args: vec![Expr::Identifier(Ident::new("cnt"))],
+                        over: None,
+                        distinct: false,
+                        special: false,
+                    })),
+                },
+            }]
+        ),
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("INSERT INTO t SELECT * FROM s ON DUPLICATE KEY UPDATE a = VALUES(a), b = b + 1");
+
+    for sql in &[
+        "INSERT INTO t VALUES (1) ON cnt = 1",
+        "INSERT INTO t VALUES (1) ON DUPLICATE cnt = 1",
+        "INSERT INTO t VALUES (1) ON DUPLICATE KEY cnt = 1",
+    ] {
+        match Parser::parse_sql(&MySqlDialect {}, sql) {
+            Err(ParserError::SyntaxError(e)) => assert!(
+                e.message.starts_with("Expected DUPLICATE KEY UPDATE after ON"),
+                "{}",
+                e.message
+            ),
+            other => panic!("{:?}", other),
+        }
+    }
+}
+
+#[test]
+fn parse_charset_spellings() {
+    // every spelling is accepted, and written back as `CHARSET=` for tables
+    // and `CHARACTER SET` for columns
+    for default in &["", "DEFAULT "] {
+        for charset in &["CHARSET", "CHARACTER SET"] {
+            for eq in &["=", " = ", " "] {
+                let sql = format!(
+                    "CREATE TABLE t (a INT) {}{}{}utf8mb4 {}COLLATE{}utf8mb4_bin",
+                    default, charset, eq, default, eq
+                );
+                let canonical = format!(
+                    "CREATE TABLE t (a INT) {}CHARSET=utf8mb4 {}COLLATE=utf8mb4_bin",
+                    default, default
+                );
+                mysql().one_statement_parses_to(&sql, &canonical);
+            }
+        }
+    }
+    mysql().verified_stmt("CREATE TABLE t (a INT) COLLATE=utf8mb4_bin");
+
+    mysql().one_statement_parses_to(
+        "CREATE TABLE t (a CHAR(10) CHARSET utf8mb4 COLLATE utf8mb4_bin)",
+        "CREATE TABLE t (a CHAR(10) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin)",
+    );
+    mysql().verified_stmt("CREATE TABLE t (a TEXT CHARACTER SET latin1)");
+
+    assert!(Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (a INT) DEFAULT ENGINE=InnoDB").is_err());
+}
+
+#[test]
+fn parse_column_ordinal_and_default() {
+    let columns = |sql: &str| match Parser::parse_sql(&MySqlDialect {}, sql).unwrap().remove(0) {
+        Statement::CreateTable { columns, .. } => columns,
+        _ => unreachable!(),
+    };
+    let old = columns("CREATE TABLE t (id INT NOT NULL, KEY k (id), name TEXT, note TEXT DEFAULT '')");
+    let new = columns("CREATE TABLE t (id INT NOT NULL, name TEXT DEFAULT NULL, note TEXT DEFAULT '')");
+    assert_eq!(
+        new.iter().map(|c| c.ordinal).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(old[2].ordinal, 3);
+
+    assert_eq!(old[1].explicit_default(), None);
+    assert_eq!(new[1].explicit_default(), Some(&Expr::Value(Value::Null)));
+    assert_eq!(
+        new[2].explicit_default(),
+        Some(&Expr::Value(single_quoted("", true)))
+    );
+
+    // the only difference between the two tables is `name` gaining DEFAULT NULL
+    let changed: Vec<_> = old
+        .iter()
+        .filter(|c| {
+            let other = new.iter().find(|n| n.name == c.name).unwrap();
+            other.explicit_default() != c.explicit_default() || other.options != c.options
+        })
+        .map(|c| c.name.to_string())
+        .collect();
+    assert_eq!(changed, vec!["name"]);
+
+    match mysql().verified_stmt("ALTER TABLE t ADD COLUMN a INT") {
+        Statement::AlterTable { operation, .. } => {
+            assert!(matches!(
+                operation.as_slice(),
+                [AlterTableOperation::AddColumn { column_def }] if column_def.ordinal == 0
+            ))
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_keyword_index_names() {
+    let sql = "CREATE TABLE t (id INT COMMENT 'amount (in cents)', \
+               KEY `key` (id), INDEX `order` (id), UNIQUE KEY `primary` (id), UNIQUE `order` (id))";
+    match mysql().verified_stmt(sql) {
+        Statement::CreateTable { index, .. } => {
+            let names: Vec<_> = index
+                .iter()
+                .map(|info| match &info.index {
+                    IndexDef::Normal(i) | IndexDef::Unique(i) => i.index_name.clone().unwrap(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            assert_eq!(
+                names,
+                vec![
+                    Ident::with_quote('`', "key"),
+                    Ident::with_quote('`', "order"),
+                    Ident::with_quote('`', "primary"),
+                    Ident::with_quote('`', "order"),
+                ]
+            );
+            match &index[3].index {
+                IndexDef::Unique(i) => assert_eq!(i.name, None),
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("ALTER TABLE t ADD KEY `order` (id)");
+    mysql().verified_stmt("ALTER TABLE t ADD UNIQUE INDEX `key` (id)");
+    mysql().verified_stmt("ALTER TABLE t DROP INDEX `primary`");
+}
+
+#[test]
+fn parse_non_reserved_keywords_as_names() {
+    mysql().verified_stmt("SELECT status, comment, timestamp FROM t");
+    mysql().verified_stmt("SELECT count FROM stats");
+    mysql().verified_stmt("SELECT a AS value, b AS status, c AS comment FROM t AS date");
+
+    match mysql().verified_stmt("CREATE TABLE t (comment INT, status TEXT, `key` INT, value INT)") {
+        Statement::CreateTable { columns, .. } => assert_eq!(
+            columns.iter().map(|c| c.name.clone()).collect::<Vec<_>>(),
+            vec![
+                Ident::new("comment"),
+                Ident::new("status"),
+                Ident::with_quote('`', "key"),
+                Ident::new("value"),
+            ]
+        ),
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::UnexpectedToken {
+                expected: "column name".to_string(),
+                found: Box::new(Token::make_word("order", None)),
+            },
+            message: "Expected column name, found: order".to_string(),
+            location: Some(Location { line: 1, column: 30, offset: 29 }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (comment INT, order INT)").unwrap_err()
+    );
+    assert!(Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (select INT)").is_err());
+
+    // `COLUMN` is optional before a column name in ALTER TABLE
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t ADD comment INT, DROP status",
+        "ALTER TABLE t ADD COLUMN comment INT, DROP COLUMN status",
+    );
+    mysql().verified_stmt("ALTER TABLE t ADD KEY status (status), DROP INDEX comment");
+
+    // other dialects don't reserve MySQL's words for column names, and read
+    // ADD and DROP without `COLUMN` as an index as before
+    for dialect in [&GenericDialect {} as &dyn Dialect, &PostgreSqlDialect {}] {
+        let sql = "CREATE TABLE t (key INT, rank INT, sql INT)";
+        match Parser::parse_sql(dialect, sql).unwrap().pop().unwrap() {
+            Statement::CreateTable { columns, .. } => assert_eq!(
+                columns.iter().map(|c| c.name.clone()).collect::<Vec<_>>(),
+                vec![Ident::new("key"), Ident::new("rank"), Ident::new("sql")]
+            ),
+            _ => unreachable!(),
+        }
+        let sql = "ALTER TABLE t ADD COLUMN key INT, DROP COLUMN rank, DROP INDEX i";
+        match Parser::parse_sql(dialect, sql).unwrap().pop().unwrap() {
+            Statement::AlterTable { operation, .. } => {
+                assert!(matches!(operation[0], AlterTableOperation::AddColumn { .. }));
+                assert!(matches!(operation[1], AlterTableOperation::DropColumn { .. }));
+                assert!(matches!(operation[2], AlterTableOperation::DropIndex { .. }));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn parse_call() {
+    match mysql().verified_stmt("CALL db.proc(1, a + 1)") {
+        Statement::Call { name, parameter } => {
+            assert_eq!(name, ObjectName(vec![Ident::new("db"), Ident::new("proc")]));
+            assert_eq!(parameter.map(|p| p.len()), Some(2));
+        }
+        _ => unreachable!(),
+    }
+    let statements = Parser::parse_sql(&MySqlDialect {}, "CALL proc(); CALL db.proc; CALL proc").unwrap();
+    assert_eq!(
+        statements.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec!["CALL proc()", "CALL db.proc", "CALL proc"]
+    );
+    assert!(Parser::parse_sql(&MySqlDialect {}, "CALL proc(1").is_err());
+}
+
+#[test]
+fn parse_use() {
+    match mysql().verified_stmt("USE `my-db`") {
+        Statement::ChangeDatabase { database } => {
+            assert_eq!(database, Ident::with_quote('`', "my-db"))
+        }
+        _ => unreachable!(),
+    }
+    let statements = Parser::parse_sql(&MySqlDialect {}, "USE db; SELECT 1;").unwrap();
+    assert_eq!(
+        statements.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec!["USE db", "SELECT 1"]
+    );
+    assert!(Parser::parse_sql(&MySqlDialect {}, "USE db other").is_err());
+}
+
+#[test]
+fn parse_from_dual() {
+    let select = mysql().verified_only_select("SELECT 1 FROM DUAL");
+    assert_eq!(select.from[0].relation, TableFactor::Dual);
+    mysql().one_statement_parses_to("SELECT * FROM dual WHERE 1", "SELECT * FROM DUAL WHERE 1");
+    mysql().verified_stmt("SELECT DATABASE(), CURRENT_USER(), @@version_comment");
+
+    // DUAL isn't a table that's read, while a quoted `dual` is
+    assert!(mysql().verified_stmt("SELECT 1 FROM DUAL").table_references().is_empty());
+    let select = mysql().verified_only_select("SELECT 1 FROM `dual`");
+    assert!(matches!(select.from[0].relation, TableFactor::Table { .. }));
+}
+
+#[test]
+fn parse_parameterized_queries() {
+    // the shapes applications send, with every literal a placeholder
+    let sql = include_str!("queries/mysql/parameterized.sql");
+    for query in sql.split(";\n").map(str::trim).filter(|q| !q.is_empty()) {
+        mysql().verified_stmt(query);
+    }
+
+    let select = mysql().verified_only_select("SELECT DATE_ADD(?, INTERVAL 1 + ? DAY)");
+    match &select.projection[0] {
+        SelectItem::UnnamedExpr(Expr::Function(function)) => assert_eq!(
+            function.args[1],
+            Expr::Interval {
+                value: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Value(number("1"))),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Value(Value::Placeholder("?".into()))),
+                }),
+                unit: DateTimeField::Day,
+            }
+        ),
+        _ => unreachable!(),
+    }
+    mysql().verified_stmt("SELECT INTERVAL '1' DAY");
+
+    // elsewhere an interval is only a string literal
+    for dialect in [&GenericDialect {} as &dyn Dialect, &PostgreSqlDialect {}] {
+        for sql in ["SELECT INTERVAL 5 DAY", "SELECT INTERVAL ? DAY"] {
+            assert!(Parser::parse_sql(dialect, sql).is_err(), "{}", sql);
+        }
+        Parser::parse_sql(dialect, "SELECT INTERVAL '5' DAY").unwrap();
+    }
+}
+
+#[test]
+fn parse_derived_union_with_limits() {
+    let sql = "SELECT * FROM ((SELECT a FROM t1 LIMIT 10) UNION ALL (SELECT a FROM t2 LIMIT 10)) AS u ORDER BY a";
+    let select = mysql().verified_only_select(sql);
+    match &select.from[0].relation {
+        TableFactor::Derived { subquery, alias: Some(alias), .. } => {
+            assert_eq!(alias.name, Ident::new("u"));
+            match &subquery.body {
+                SetExpr::SetOperation { op: SetOperator::Union, all: true, left, right } => {
+                    assert!(matches!(**left, SetExpr::Query(ref q) if q.limit == Some(Expr::Value(number("10")))));
+                    assert!(matches!(**right, SetExpr::Query(ref q) if q.limit == Some(Expr::Value(number("10")))));
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    // a broken subquery reports its own error rather than the nested join's
+    let err = |sql| match Parser::parse_sql(&MySqlDialect {}, sql).unwrap_err() {
+        ParserError::SyntaxError(e) => (e.message, e.location.map(|l| l.column)),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        ("Expected an expression, found: )".to_string(), Some(38)),
+        err("SELECT * FROM (SELECT a FROM t1 WHERE) AS u")
+    );
+    assert_eq!(
+        ("Expected a value, found: )".to_string(), Some(78)),
+        err("SELECT * FROM ((SELECT a FROM t1 LIMIT 10) UNION ALL (SELECT a FROM t2 LIMIT)) AS u")
+    );
+    assert_eq!(
+        ("Expected an expression, found: )".to_string(), Some(29)),
+        err("SELECT * FROM (t1 JOIN t2 ON) AS u")
+    );
+}
+
+#[test]
+fn parse_leading_comments() {
+    let sql = "-- +migrate Up\n-- disable checks\nSET FOREIGN_KEY_CHECKS=0;\n/* tables */\nDROP TABLE a;\n# last one\nDROP TABLE b; DROP TABLE c";
+    let parsed = Parser::parse_with_tokens(&MySqlDialect {}, sql).unwrap();
+    let comments: Vec<Vec<String>> = parsed
+        .statements
+        .iter()
+        .map(|s| parsed.leading_comments(s))
+        .collect();
+    assert_eq!(
+        comments,
+        vec![
+            vec!["+migrate Up".to_string(), "disable checks".to_string()],
+            vec!["tables".to_string()],
+            vec!["last one".to_string()],
+            vec![],
+        ]
+    );
+    assert_eq!(
+        parsed.statements[1].statement,
+        mysql().verified_stmt("DROP TABLE a")
+    );
+}
+
+#[test]
+fn simplify_exprs() {
+    let cases = [
+        ("1 = 1 AND id = 5", "id = 5"),
+        ("id = 5 AND 1 = 1", "id = 5"),
+        ("TRUE AND a", "a"),
+        ("FALSE AND a", "false"),
+        ("FALSE OR a", "a"),
+        ("a OR TRUE", "true"),
+        ("1 = 2 OR (2 > 1 AND b IS NULL)", "(b IS NULL)"),
+        ("NOT TRUE", "false"),
+        ("NOT 1 <> 1", "true"),
+        ("NOT NOT a", "a"),
+        ("((a))", "a"),
+        ("((a + b))", "(a + b)"),
+        ("(1 + 2) * 3", "9"),
+        ("id = 2 * 3 - 10 % 4", "id = 4"),
+        ("-(1 + 2)", "-3"),
+        ("a + 1 + 2", "a + 1 + 2"),
+        ("f(1 + 1) = 2", "f(1 + 1) = 2"),
+        // NULL, division, overflow and non-integers are left alone
+        ("NULL AND FALSE", "false"),
+        ("NULL AND TRUE", "NULL"),
+        ("NULL = NULL", "NULL = NULL"),
+        ("1 / 2", "1 / 2"),
+        ("1 % 0", "1 % 0"),
+        ("9223372036854775807 + 1", "9223372036854775807 + 1"),
+        ("1.5 + 1", "1.5 + 1"),
+        ("'a' = 'a'", "'a' = 'a'"),
+    ];
+    for (before, after) in cases.iter() {
+        let expr = Parser::parse_expr_str(&MySqlDialect {}, before).unwrap();
+        assert_eq!(*after, simplify(expr).to_string(), "simplifying {}", before);
+    }
+}
+
+#[test]
+fn select_predicates() {
+    let predicates = |condition: &str| -> Vec<(String, PredicateOp, Vec<String>, bool)> {
+        let select = mysql().verified_only_select(&format!("SELECT * FROM t WHERE {}", condition));
+        select
+            .predicates()
+            .into_iter()
+            .map(|p| {
+                let column = p.column.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(".");
+                let values = p.values.iter().map(|v| v.to_string()).collect();
+                (column, p.op, values, p.sargable)
+            })
+            .collect()
+    };
+    let p = |column: &str, op, values: &[&str], sargable| {
+        (column.to_string(), op, values.iter().map(|v| v.to_string()).collect(), sargable)
+    };
+
+    assert_eq!(
+        predicates("id = 5 AND t.created_at >= ? AND (status IN ('a', 'b') AND 10 > id)"),
+        vec![
+            p("id", PredicateOp::Eq, &["5"], true),
+            p("t.created_at", PredicateOp::GtEq, &["?"], true),
+            p("status", PredicateOp::In, &["'a'", "'b'"], true),
+            p("id", PredicateOp::Lt, &["10"], true),
+        ]
+    );
+    assert_eq!(
+        predicates("price BETWEEN 1 AND 9 AND name LIKE 'a%' AND deleted_at IS NULL"),
+        vec![
+            p("price", PredicateOp::Between, &["1", "9"], true),
+            p("name", PredicateOp::Like, &["'a%'"], true),
+            p("deleted_at", PredicateOp::IsNull, &[], true),
+        ]
+    );
+    // OR-connected and negated conditions can't be used on their own
+    assert_eq!(
+        predicates("a = 1 AND (b = 2 OR b = 3) AND NOT (c > 4) AND d NOT IN (5) AND e <> 6"),
+        vec![
+            p("a", PredicateOp::Eq, &["1"], true),
+            p("b", PredicateOp::Eq, &["2"], false),
+            p("b", PredicateOp::Eq, &["3"], false),
+            p("c", PredicateOp::Gt, &["4"], false),
+            p("d", PredicateOp::NotIn, &["5"], false),
+            p("e", PredicateOp::NotEq, &["6"], false),
+        ]
+    );
+    // comparisons with anything but literals and placeholders are skipped
+    assert_eq!(
+        predicates("a = b AND f(a) = 1 AND a IN (1, b) AND a BETWEEN 1 AND b AND a IN (SELECT 1)"),
+        vec![]
+    );
+    assert!(mysql().verified_only_select("SELECT 1").predicates().is_empty());
+}
+
+#[test]
+fn parameterize_and_bind() {
+    let mut statement = mysql().verified_stmt("SELECT a FROM t WHERE b = 'x' AND c IN (1, 2) LIMIT 10");
+    let values = statement.parameterize();
+    assert_eq!(statement.to_string(), "SELECT a FROM t WHERE b = ? AND c IN (?, ?) LIMIT ?");
+    assert_eq!(
+        values,
+        vec![
+            single_quoted("x", true),
+            number("1"),
+            number("2"),
+            number("10"),
+        ]
+    );
+
+    // every statement parameterizes to valid SQL that binds back to the
+    // original
+    let statements = [
+        "SELECT a, 1 + 2 AS b FROM t1 JOIN t2 ON t1.id = t2.id AND t2.kind = 'k' WHERE x > - 5 GROUP BY a HAVING COUNT(*) > 3 ORDER BY a LIMIT 10 OFFSET 20",
+        "SELECT * FROM t WHERE d > NOW() - INTERVAL 1 DAY AND e BETWEEN 0.5 AND 1.5 AND f IS NULL AND g = NULL",
+        "SELECT * FROM t WHERE a = true AND b = X'ff' AND c = N'n' AND d = \"dq\" AND e LIKE 'a%'",
+        "SELECT * FROM (SELECT a FROM t WHERE b = 1) AS s WHERE a IN (SELECT a FROM u WHERE c = 'c')",
+        "INSERT INTO t (a, b) VALUES (1, 'x'), (2, 'y') ON DUPLICATE KEY UPDATE b = 'z'",
+        "UPDATE t SET a = 1, b = CONCAT(b, 'x') WHERE c = 2 LIMIT 5",
+        "DELETE FROM t WHERE a = 1 OR b <> 'x'",
+        "CREATE TABLE t (a INT DEFAULT 1, b TEXT DEFAULT 'x')",
+    ];
+    for sql in statements.iter() {
+        let original = mysql().verified_stmt(sql);
+        let mut parameterized = original.clone();
+        let values = parameterized.parameterize();
+        assert!(!values.is_empty(), "{}", sql);
+        let template = parameterized.to_string();
+        assert_eq!(template.matches('?').count(), values.len(), "{}", template);
+        assert_eq!(parameterized, mysql().verified_stmt(&template));
+        assert_eq!(values.len(), parameterized.bind(&values));
+        assert_eq!(original, parameterized, "{}", sql);
+    }
+
+    let options = ParameterizeOptions {
+        keep_limit: true,
+        keep_ddl: true,
+    };
+    let mut statement = mysql().verified_stmt("SELECT a FROM t WHERE b = 1 LIMIT 10 OFFSET 5");
+    assert_eq!(statement.parameterize_with(options), vec![number("1")]);
+    assert_eq!(statement.to_string(), "SELECT a FROM t WHERE b = ? LIMIT 10 OFFSET 5");
+    let mut statement = mysql().verified_stmt("UPDATE t SET a = 1 LIMIT 5");
+    assert_eq!(statement.parameterize_with(options), vec![number("1")]);
+    assert_eq!(statement.to_string(), "UPDATE t SET a = ? LIMIT 5");
+    let mut statement = mysql().verified_stmt("CREATE TABLE t (a INT DEFAULT 1)");
+    assert!(statement.parameterize_with(options).is_empty());
+    assert_eq!(statement.to_string(), "CREATE TABLE t (a INT DEFAULT 1)");
+}
+
+#[test]
+fn qualify_tables() {
+    let qualify = |sql: &str| {
+        let mut statement = mysql().verified_stmt(sql);
+        statement.qualify_tables("db");
+        statement.to_string()
+    };
+    assert_eq!(
+        qualify("SELECT t.a, u.b FROM t JOIN other.u ON t.id = u.id WHERE t.c IN (SELECT c FROM v)"),
+        "SELECT t.a, u.b FROM db.t JOIN other.u ON t.id = u.id WHERE t.c IN (SELECT c FROM db.v)"
+    );
+    assert_eq!(
+        qualify("INSERT INTO t (a) SELECT a FROM u"),
+        "INSERT INTO db.t (a) SELECT a FROM db.u"
+    );
+    assert_eq!(qualify("UPDATE t SET a = 1"), "UPDATE db.t SET a = 1");
+    assert_eq!(qualify("DELETE FROM t WHERE a = 1"), "DELETE FROM db.t WHERE a = 1");
+    assert_eq!(
+        qualify("CREATE TABLE t (a INT, FOREIGN KEY (a) REFERENCES p (id))"),
+        "CREATE TABLE db.t (a INT, FOREIGN KEY (a) REFERENCES db.p (id))"
+    );
+    assert_eq!(
+        qualify("CREATE VIEW v AS SELECT a FROM t"),
+        "CREATE VIEW db.v AS SELECT a FROM db.t"
+    );
+    assert_eq!(qualify("ALTER TABLE t ADD COLUMN a INT"), "ALTER TABLE db.t ADD COLUMN a INT");
+    assert_eq!(
+        qualify("ALTER TABLE t ADD FOREIGN KEY (a) REFERENCES p (id)"),
+        "ALTER TABLE db.t ADD FOREIGN KEY (a) REFERENCES db.p (id)"
+    );
+    assert_eq!(qualify("DROP TABLE t, other.u"), "DROP TABLE db.t, other.u");
+    assert_eq!(qualify("DROP INDEX i ON t"), "DROP INDEX i ON db.t");
+
+    // a CTE hides the table of the same name, but only within its query and
+    // the CTEs after it
+    assert_eq!(
+        qualify("WITH t AS (SELECT a FROM t), u AS (SELECT a FROM t) SELECT * FROM t JOIN u ON t.a = u.a WHERE t.a IN (SELECT a FROM t)"),
+        "WITH t AS (SELECT a FROM db.t), u AS (SELECT a FROM t) SELECT * FROM t JOIN u ON t.a = u.a WHERE t.a IN (SELECT a FROM t)"
+    );
+    assert_eq!(
+        qualify("SELECT * FROM (WITH t AS (SELECT 1) SELECT * FROM t) AS s, t"),
+        "SELECT * FROM (WITH t AS (SELECT 1) SELECT * FROM t) AS s, db.t"
+    );
+}
+
+#[test]
+fn query_output_columns() {
+    // each column as `name: source, ...`, or `*: table, ...`
+    let columns = |sql: &str| -> Vec<String> {
+        let query = match mysql().verified_stmt(sql) {
+            Statement::Query(query) => query,
+            _ => unreachable!(),
+        };
+        let join = |names: Vec<String>| names.join(", ");
+        query
+            .output_columns()
+            .into_iter()
+            .map(|column| match column {
+                OutputColumn::Expr { name, sources } => format!(
+                    "{}: {}",
+                    name,
+                    join(sources.iter().map(|s| ObjectName(s.clone()).to_string()).collect())
+                ),
+                OutputColumn::Wildcard { from, .. } => {
+                    format!("*: {}", join(from.iter().map(|t| t.to_string()).collect()))
+                }
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        columns("SELECT id, o.total * 2 AS double_total, COUNT(*) FROM db.orders AS o"),
+        vec!["id: db.orders.id", "double_total: db.orders.total", "COUNT(*): "]
+    );
+    assert_eq!(
+        columns("SELECT o.id, c.name, UPPER(note), c.* FROM orders AS o JOIN customers AS c ON o.customer_id = c.id"),
+        vec![
+            "id: orders.id",
+            "name: customers.name",
+            "UPPER(note): note",
+            "*: customers",
+        ]
+    );
+    assert_eq!(
+        columns("SELECT *, a FROM t, (SELECT x + y AS s FROM u) AS d WHERE a IN (SELECT b FROM v)"),
+        vec!["*: t, d", "a: a"]
+    );
+    // derived tables and CTEs are resolved to the columns they're computed from
+    assert_eq!(
+        columns("SELECT d.s, total FROM (SELECT x + y AS s, u.z AS total FROM u) AS d"),
+        vec!["s: u.x, u.y", "total: u.z"]
+    );
+    assert_eq!(
+        columns("WITH c (n) AS (SELECT a + 1 FROM t) SELECT c.n, m FROM c JOIN (SELECT b AS m FROM u) AS d ON c.n = d.m"),
+        vec!["n: t.a", "m: u.b"]
+    );
+    // the first query of a UNION names the columns
+    assert_eq!(
+        columns("SELECT a, b AS x FROM t UNION ALL SELECT c, d + a FROM u UNION SELECT 1, 2"),
+        vec!["a: t.a, u.c", "x: t.b, u.d, u.a"]
+    );
+}
+
+#[test]
+fn parse_client_delimiters() {
+    let sql = "SET @a = 1;\nDELIMITER $$\nSELECT 'a$$b' /* $$ */; SELECT 2$$\n-- $$\nSELECT 3 $$\ndelimiter ;\nSELECT 4;\n";
+    let statements = Parser::parse_sql_with_delimiters(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(
+        statements.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+        vec![
+            "SET @a = 1",
+            "DELIMITER $$",
+            "SELECT 'a$$b'",
+            "SELECT 2",
+            "SELECT 3",
+            "DELIMITER ;",
+            "SELECT 4",
+        ]
+    );
+    assert_eq!(
+        statements[1],
+        Statement::ClientDelimiter {
+            delimiter: "$$".to_string()
+        }
+    );
+
+    // only a line outside of a statement can be a `DELIMITER` command
+    let sql = "SELECT a,\n  delimiter AS d FROM t;\nDELIMITER //\nSELECT 1,\ndelimiter x//";
+    let statements = Parser::parse_sql_with_delimiters(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(
+        statements.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+        vec!["SELECT a, delimiter AS d FROM t", "DELIMITER //", "SELECT 1, delimiter AS x"]
+    );
+
+    // with NO_BACKSLASH_ESCAPES, a backslash doesn't escape a quote
+    let sql = "DELIMITER //\nSELECT 'a\\'//\nSELECT 2//";
+    assert!(Parser::parse_sql_with_delimiters(&MySqlDialect {}, sql).is_err());
+    let options = ParserOptions {
+        no_backslash_escapes: true,
+        ..Default::default()
+    };
+    let statements =
+        Parser::parse_sql_with_delimiters_and_options(&MySqlDialect {}, sql, &options).unwrap();
+    assert_eq!(3, statements.len());
+    assert_eq!("SELECT 'a\\'", statements[1].to_string());
+    assert_eq!("SELECT 2", statements[2].to_string());
+
+    // errors are located in the whole script
+    let sql = "DELIMITER //\nSELECT 1//\nSELECT 1 2//";
+    match Parser::parse_sql_with_delimiters(&MySqlDialect {}, sql).unwrap_err() {
+        ParserError::SyntaxError(e) => {
+            assert_eq!(e.location.map(|l| (l.line, l.column)), Some((3, 10)))
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn parse_with_limits() {
+    let parse = |sql: &str, options: ParserOptions| {
+        Parser::parse_sql_with_options(&MySqlDialect {}, sql, &options)
+    };
+    let sql = "SELECT 1;\nSELECT 2; SELECT 3";
+    assert_eq!(3, parse(sql, ParserOptions::default()).unwrap().len());
+    let options = ParserOptions {
+        max_input_bytes: Some(sql.len()),
+        max_tokens: Some(14),
+        max_statements: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(3, parse(sql, options).unwrap().len());
+
+    assert_eq!(
+        parse(sql, ParserOptions { max_input_bytes: Some(12), ..Default::default() }).unwrap_err(),
+        ParserError::TokenizerError("SQL longer than 12 bytes at Line: 2, Column 3".to_string())
+    );
+    assert_eq!(
+        parse(&",".repeat(1000), ParserOptions { max_tokens: Some(3), ..Default::default() })
+            .unwrap_err(),
+        ParserError::TokenizerError("SQL with more than 3 tokens at Line: 1, Column 4".to_string())
+    );
+    assert_eq!(
+        parse(sql, ParserOptions { max_statements: Some(2), ..Default::default() }).unwrap_err(),
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::TooManyStatements,
+            message: "More than 2 statements".to_string(),
+            location: Some(Location { line: 2, column: 11, offset: 20 }),
+        })
+    );
+
+    // the other entry points take the same options
+    let dialect = MySqlDialect {};
+    let options = ParserOptions { max_statements: Some(2), ..Default::default() };
+    let too_many = |e: ParserError| match e {
+        ParserError::SyntaxError(e) => e.kind == SyntaxErrorKind::TooManyStatements,
+        _ => false,
+    };
+    assert!(too_many(Parser::parse_sql_located_with_options(&dialect, sql, &options).unwrap_err()));
+    assert!(too_many(Parser::parse_with_tokens_and_options(&dialect, sql, &options).unwrap_err()));
+    assert!(too_many(
+        Parser::parse_sql_with_locations_and_options(&dialect, sql, &options).unwrap_err()
+    ));
+    let script = "SELECT 1;\nDELIMITER //\nSELECT 2//\nSELECT 3//\n";
+    assert_eq!(4, Parser::parse_sql_with_delimiters(&dialect, script).unwrap().len());
+    assert!(too_many(
+        Parser::parse_sql_with_delimiters_and_options(&dialect, script, &options).unwrap_err()
+    ));
+    let options = ParserOptions { max_input_bytes: Some(12), ..Default::default() };
+    assert_eq!(
+        Parser::parse_sql_with_delimiters_and_options(&dialect, script, &options).unwrap_err(),
+        ParserError::TokenizerError("SQL longer than 12 bytes at Line: 2, Column 3".to_string())
+    );
+    let options = ParserOptions { mysql_version: Some(50700), ..Default::default() };
+    assert_eq!(
+        "SELECT 1",
+        parse("SELECT 1 /*!80000 + 1 */", options).unwrap()[0].to_string()
+    );
+
+    let mut tokenizer = Tokenizer::new(&MySqlDialect {}, "a b").with_max_tokens(2);
+    assert!(tokenizer.tokenize().is_err());
+    let mut tokenizer = Tokenizer::new(&MySqlDialect {}, "a b").with_max_input_bytes(3);
+    assert_eq!(3, tokenizer.tokenize().unwrap().len());
+}
+
+#[test]
+fn parse_aliases_of_non_reserved_words() {
+    mysql().one_statement_parses_to("SELECT x offset FROM t", "SELECT x AS offset FROM t");
+    mysql().one_statement_parses_to("SELECT a status, b top FROM t", "SELECT a AS status, b AS top FROM t");
+    mysql().one_statement_parses_to("SELECT * FROM t offset", "SELECT * FROM t AS offset");
+    mysql().verified_stmt("SELECT COUNT(*) AS rows, a AS rank FROM t AS `full`");
+    mysql().verified_stmt("SELECT `rank` FROM t");
+
+    // reserved words end the select item or table instead
+    for sql in &[
+        "SELECT a rank FROM t",
+        "SELECT a rows FROM t",
+        "SELECT a window FROM t",
+        "SELECT * FROM t rank",
+        "SELECT * FROM t full",
+    ] {
+        assert!(mysql().parse_sql_statements(sql).is_err(), "{}", sql);
+    }
+    mysql().verified_stmt("SELECT a FROM t LIMIT 1 OFFSET 2");
+}
+
+#[test]
+fn parse_statements_around_comments() {
+    let parse = |sql: &str| Parser::parse_sql(&MySqlDialect {}, sql).unwrap();
+    let statements = parse(include_str!("queries/mysql/dump.sql"));
+    assert_eq!(10, statements.len());
+    assert_eq!(
+        "CREATE TABLE `t` (`id` INT NOT NULL, PRIMARY KEY (`id`)) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4",
+        statements[3].to_string()
+    );
+    assert_eq!("UNLOCK TABLES", statements[8].to_string());
+
+    assert_eq!(2, parse(";; SELECT 1;; ; SELECT 2 -- last\n").len());
+    assert_eq!(2, parse("CREATE TABLE t (a INT); SELECT 1 # last").len());
+    assert_eq!(2, parse("ALTER TABLE t ADD COLUMN b INT; SELECT 1 /* last */").len());
+    assert!(parse("-- nothing\n/* but */ # comments\n;").is_empty());
+    assert!(parse("").is_empty());
+}
+
+#[test]
+fn string_literals_round_trip() {
+    let parse_value = |sql: &str| match mysql().verified_only_select(sql).projection.as_slice() {
+        [SelectItem::UnnamedExpr(Expr::Value(value))] => value.clone(),
+        projection => panic!("{:?}", projection),
+    };
+    let pieces = [
+        "", "'", "''", "\"", "\\", "\\'", "\\%", "\n\r\t", "\0\u{1a}", "é字😀", "*/", "a",
+    ];
+    for a in &pieces {
+        for b in &pieces {
+            let s = format!("{}{}", a, b);
+            for value in [
+                single_quoted(s.clone(), true),
+                Value::DoubleQuotedString(s.clone()),
+                Value::NationalStringLiteral(s.clone()),
+                Value::HexStringLiteral(s.clone()),
+            ]
+            .iter()
+            {
+                let value = value.clone();
+                let sql = format!("SELECT {}", value);
+                let parsed = parse_value(&sql);
+                assert_eq!(value, parsed, "{}", sql);
+                assert_eq!(value, parse_value(&format!("SELECT {}", parsed)));
+                let statement = mysql().verified_stmt(&sql);
+                let formatted = format_statement(&statement, &FormatOptions::default());
+                assert_eq!(value, parse_value(&formatted), "{}", formatted);
+            }
+        }
+    }
+
+    // a comment or hint can't end early
+    let mut select = mysql().verified_only_select("SELECT /*+ BKA(t) */ /* c */ a FROM t");
+    select.hints = Some(" x */ DROP TABLE t; /*".to_string());
+    select.comments = vec!["*/ b, /*".to_string()];
+    let reparsed = mysql().verified_only_select(&select.to_string());
+    assert_eq!(select.projection, reparsed.projection);
+    assert_eq!(Some(" x * / DROP TABLE t; /*".to_string()), reparsed.hints);
+    assert_eq!(vec!["* / b, /*".to_string()], reparsed.comments);
+    let formatted = format_statement(
+        &Statement::Query(Box::new(Query {
+            body: SetExpr::Select(Box::new(select)),
+            ..mysql().verified_query("SELECT 1")
+        })),
+        &FormatOptions::default(),
+    );
+    assert_eq!(reparsed, mysql().verified_only_select(&formatted.replace('\n', " ")));
+
+    for sql in &["UPDATE /*+ x */ /* c */ t SET a = 1", "DELETE /*+ x */ /* c */ FROM t"] {
+        let mut statement = mysql().verified_stmt(sql);
+        match &mut statement {
+            Statement::Update {
+                hints, comments, ..
+            }
+            | Statement::Delete {
+                hints, comments, ..
+            } => {
+                *hints = Some(" x */ DROP TABLE t; /*".to_string());
+                *comments = vec!["*/ b, /*".to_string()];
+            }
+            _ => unreachable!(),
+        }
+        let formatted = format_statement(&statement, &FormatOptions::default());
+        match Parser::parse_sql(&MySqlDialect {}, &formatted).unwrap().as_slice() {
+            [Statement::Update {
+                hints, comments, ..
+            }]
+            | [Statement::Delete {
+                hints, comments, ..
+            }] => {
+                assert_eq!(&Some(" x * / DROP TABLE t; /*".to_string()), hints);
+                assert_eq!(&vec!["* / b, /*".to_string()], comments);
+            }
+            statements => panic!("{}: {:?}", formatted, statements),
+        }
+    }
+}
+
+#[test]
+fn parse_create_schema_if_not_exists() {
+    match mysql_and_generic().verified_stmt("CREATE SCHEMA IF NOT EXISTS app_db") {
+        Statement::CreateSchema {
+            schema_name,
+            if_not_exists,
+        } => {
+            assert_eq!("app_db", schema_name.to_string());
+            assert!(if_not_exists);
+        }
+        _ => unreachable!(),
+    }
+    mysql().one_statement_parses_to(
+        "CREATE DATABASE IF NOT EXISTS `app_db`",
+        "CREATE SCHEMA IF NOT EXISTS `app_db`",
+    );
+    mysql().verified_stmt("CREATE SCHEMA app_db");
+}
+
+#[test]
+fn parse_date_time_units() {
+    let select = mysql().verified_only_select(
+        "SELECT EXTRACT(WEEK FROM d), EXTRACT(MICROSECOND FROM t), d + INTERVAL 1 QUARTER",
+    );
+    assert_eq!(
+        &Expr::Extract {
+            field: DateTimeField::Week,
+            expr: Box::new(Expr::Identifier(Ident::new("d"))),
+        },
+        expr_from_projection(&select.projection[0])
+    );
+    match expr_from_projection(&select.projection[2]) {
+        Expr::BinaryOp { right, .. } => match right.as_ref() {
+            Expr::Interval { unit, .. } => assert_eq!(&DateTimeField::Quarter, unit),
+            expr => panic!("{:?}", expr),
+        },
+        expr => panic!("{:?}", expr),
+    }
+
+    for unit in &[
+        "YEAR_MONTH",
+        "DAY_HOUR",
+        "DAY_MINUTE",
+        "DAY_SECOND",
+        "DAY_MICROSECOND",
+        "HOUR_MINUTE",
+        "HOUR_SECOND",
+        "HOUR_MICROSECOND",
+        "MINUTE_SECOND",
+        "MINUTE_MICROSECOND",
+        "SECOND_MICROSECOND",
+    ] {
+        mysql().verified_stmt(&format!("SELECT DATE_ADD(d, INTERVAL '1 12' {})", unit));
+        mysql().verified_stmt(&format!("SELECT EXTRACT({} FROM d)", unit));
+    }
+    // the units are still function names and aliases
+    mysql().verified_stmt("SELECT WEEK(d), QUARTER(d) AS quarter, week FROM t");
+}
+
+#[test]
+fn parse_niladic_and_keyword_functions() {
+    let select = mysql().verified_only_select(
+        "SELECT CURRENT_TIMESTAMP, CURRENT_TIMESTAMP(3), NOW(), `CURRENT_DATE`, UTC_DATE",
+    );
+    let function = |name: &str, args, special| {
+        Expr::Function(Function {
+            name: ObjectName(vec![Ident::new(name)]),
+            args,
+            over: None,
+            distinct: false,
+            special,
+        })
+    };
+    assert_eq!(
+        vec![
+            function("CURRENT_TIMESTAMP", vec![], true),
+            function("CURRENT_TIMESTAMP", vec![Expr::Value(number("3"))], false),
+            function("NOW", vec![], false),
+            Expr::Identifier(Ident::with_quote('`', "CURRENT_DATE")),
+            function("UTC_DATE", vec![], true),
+        ],
+        select
+            .projection
+            .iter()
+            .map(|item| expr_from_projection(item).clone())
+            .collect::<Vec<_>>()
+    );
+    mysql().verified_stmt(
+        "SELECT a FROM t WHERE d > CURRENT_DATE - INTERVAL 1 DAY AND u = CURRENT_USER",
+    );
+    mysql().verified_stmt("CREATE TABLE t (a TIMESTAMP DEFAULT CURRENT_TIMESTAMP)");
+
+    // function names that are keywords
+    mysql().verified_stmt(
+        "SELECT IF(x, 1, 0), LEFT(a, 2), RIGHT(a, 2), MOD(a, 2), DATABASE(), REPLACE(a, 'x', 'y')",
+    );
+}
+
+#[test]
+fn parse_literal_lists() {
+    // rows and lists of literals alone, and with other expressions among them
+    mysql().verified_stmt(
+        "INSERT INTO t VALUES (1, 'a', NULL, true), (2.5, \"b\", a, false), (X'0A', N'c', now(), 1 + 2)",
+    );
+    mysql().verified_stmt("SELECT * FROM t WHERE a IN (1, 'a', NULL) AND b NOT IN (1, c)");
+
+    // the literals are kept in the tokens handed back
+    let sql = "INSERT INTO t VALUES (1, 'a', NULL), (2, 'b', TRUE); SELECT 1 IN (1, 2)";
+    let parsed = Parser::parse_with_tokens(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(
+        parsed.tokens.iter().map(|t| t.token.to_string()).collect::<String>(),
+        sql
+    );
+    assert_eq!(
+        "INSERT INTO t VALUES (1, 'a', NULL), (2, 'b', true)",
+        parsed.statements[0].statement.to_string()
+    );
+}
+
+#[test]
+fn parse_malformed_in_lists() {
+    let error = |kind, message: &str, column, offset| {
+        ParserError::SyntaxError(SyntaxError {
+            kind,
+            message: message.to_string(),
+            location: Some(Location { line: 1, column, offset }),
+        })
+    };
+    assert_eq!(
+        mysql().parse_sql_statements("SELECT a FROM t WHERE a IN ()").unwrap_err(),
+        error(SyntaxErrorKind::EmptyInList, "IN list may not be empty", 29, 28)
+    );
+    assert_eq!(
+        mysql().parse_sql_statements("SELECT a FROM t WHERE a NOT IN (1, 2, )").unwrap_err(),
+        error(SyntaxErrorKind::TrailingCommaInInList, "Trailing comma in IN list", 37, 36)
+    );
+    // a query is still required after the parenthesis
+    assert!(mysql().parse_sql_statements("SELECT a FROM t WHERE a IN (SELECT)").is_err());
+
+    let options = ParserOptions {
+        allow_trailing_comma_in_in_list: true,
+        ..Default::default()
+    };
+    let statements =
+        Parser::parse_sql_with_options(&MySqlDialect {}, "SELECT 1 IN (1, 2,)", &options).unwrap();
+    assert_eq!("SELECT 1 IN (1, 2)", statements[0].to_string());
+
+    let (statements, errors) = Parser::parse_sql_with_recovery(
+        &MySqlDialect {},
+        "SELECT 1 IN (); SELECT 2 IN (1,); SELECT 3 IN (3)",
+    );
+    assert_eq!(1, statements.len());
+    assert_eq!("SELECT 3 IN (3)", statements[0].to_string());
+    assert_eq!(vec![0, 1], errors.iter().map(|(index, _)| *index).collect::<Vec<_>>());
+    assert_eq!(
+        vec![Some(&SyntaxErrorKind::EmptyInList), Some(&SyntaxErrorKind::TrailingCommaInInList)],
+        errors.iter().map(|(_, error)| error.kind()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn parse_between_bounds() {
+    let between = |sql: &str| match mysql().verified_only_select(sql).selection {
+        Some(Expr::Between {
+            negated, low, high, ..
+        }) => (negated, *low, *high),
+        selection => panic!("{:?}", selection),
+    };
+    let date = |value: &str| Expr::TypedString {
+        data_type: DataType::Date,
+        value: value.to_string(),
+    };
+    assert_eq!(
+        (false, date("2024-01-01"), date("2024-02-01")),
+        between("SELECT a FROM t WHERE d BETWEEN DATE '2024-01-01' AND DATE '2024-02-01'")
+    );
+    let placeholder = || Expr::Value(Value::Placeholder("?".to_string()));
+    assert_eq!(
+        (true, placeholder(), placeholder()),
+        between("SELECT a FROM t WHERE x NOT BETWEEN ? AND ?")
+    );
+    // columns named like types
+    let column = |name: &str| Expr::Identifier(Ident::new(name));
+    assert_eq!(
+        (false, column("date"), column("d2")),
+        between("SELECT a FROM t WHERE x BETWEEN date AND d2")
+    );
+    assert_eq!(
+        (false, column("time"), column("text")),
+        between("SELECT a FROM t WHERE date BETWEEN time AND text")
+    );
+    // only a type followed by a string literal is a typed string
+    let (_, low, high) = between("SELECT a FROM t WHERE x BETWEEN date(d) AND NUMERIC(10,2) '1.5'");
+    assert!(matches!(low, Expr::Function(_)), "{:?}", low);
+    assert_eq!(
+        Expr::TypedString {
+            data_type: DataType::Decimal(Some(10), Some(2)),
+            value: "1.5".to_string(),
+        },
+        high
+    );
+
+    // NOT BETWEEN binds tighter than AND and looser than +
+    match mysql()
+        .verified_only_select("SELECT a FROM t WHERE x NOT BETWEEN 1 AND y + 2 AND z")
+        .selection
+    {
+        Some(Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        }) => {
+            assert_eq!("x NOT BETWEEN 1 AND y + 2", left.to_string());
+            assert_eq!(column("z"), *right);
+        }
+        selection => panic!("{:?}", selection),
+    }
+}
+
+#[test]
+fn parse_show_variables_like() {
+    let sql = "SHOW VARIABLES LIKE 'wait_timeout'";
+    let stmt = mysql().verified_stmt(sql);
+    assert_eq!(
+        Statement::ShowVariable {
+            variable: Ident::new("VARIABLES"),
+            global: false,
+            filter: Some(ShowStatementFilter::Like("wait_timeout".into())),
+        },
+        stmt
+    );
+    assert_eq!(vec![stmt.clone()], mysql().parse_sql_statements(&stmt.to_string()).unwrap());
+
+    mysql().verified_stmt("SHOW GLOBAL STATUS LIKE 'Threads%'");
+    mysql().verified_stmt("SHOW GLOBAL VARIABLES WHERE Variable_name = 'max_connections'");
+}
+
+#[test]
+fn parse_show_objects() {
+    assert_eq!(
+        mysql().verified_stmt("SHOW OPEN TABLES FROM db LIKE 't%'"),
+        Statement::ShowObjects {
+            kind: ShowObjectKind::OpenTables,
+            database: Some(Ident::new("db")),
+            filter: Some(ShowStatementFilter::Like("t%".into())),
+        }
+    );
+    assert_eq!(
+        mysql().verified_stmt("SHOW PROCEDURE STATUS LIKE 'p%'"),
+        Statement::ShowObjects {
+            kind: ShowObjectKind::ProcedureStatus,
+            database: None,
+            filter: Some(ShowStatementFilter::Like("p%".into())),
+        }
+    );
+    mysql().verified_stmt("SHOW TRIGGERS FROM db WHERE `Table` = 't'");
+    mysql().verified_stmt("SHOW EVENTS");
+    mysql().verified_stmt("SHOW FUNCTION STATUS");
+    mysql().verified_stmt("SHOW FUNCTION STATUS WHERE Db = 'db'");
+    mysql().one_statement_parses_to("SHOW TRIGGERS IN db", "SHOW TRIGGERS FROM db");
+
+    // routines are listed across databases
+    assert!(mysql()
+        .parse_sql_statements("SHOW PROCEDURE STATUS FROM db")
+        .is_err());
+}
+
+#[test]
+fn statement_accessors() {
+    let mut stmt = mysql().verified_stmt("(SELECT a FROM t)");
+    let select = stmt.as_query_mut().and_then(Query::as_select_mut).unwrap();
+    select.distinct = Some(Distinct::Distinct);
+    assert_eq!("(SELECT DISTINCT a FROM t)", stmt.to_string());
+
+    assert!(mysql().verified_stmt("VALUES (1)").as_query().unwrap().as_select().is_none());
+    assert!(mysql().verified_stmt("DELETE FROM t").as_query().is_none());
+}
+
+#[test]
+fn parse_from_str() {
+    let stmt: Statement = "SELECT a FROM t;".parse().unwrap();
+    assert_eq!(stmt, mysql().verified_stmt("SELECT a FROM t"));
+    assert_eq!(
+        stmt,
+        Statement::parse_with(&GenericDialect {}, "SELECT a FROM t").unwrap()
+    );
+    assert_eq!(
+        Statement::try_from("SELECT 1; SELECT 2"),
+        Err(ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::Other,
+            message: "Expected exactly one statement, found 2".to_string(),
+            location: None,
+        }))
+    );
+    assert!("".parse::<Statement>().is_err());
+
+    let expr: Expr = "a BETWEEN 1 AND 2".parse().unwrap();
+    assert_eq!(expr, mysql().verified_expr("a BETWEEN 1 AND 2"));
+    assert!(Expr::try_from("a b c").is_err());
+}
+
+#[test]
+fn normalized_statements() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let normalized = |sql: &str| {
+        let statement = Statement::parse_with(&MySqlDialect {}, sql).unwrap();
+        statement.normalized(false)
+    };
+    let hash = |statement: &Statement| {
+        let mut hasher = DefaultHasher::new();
+        statement.hash(&mut hasher);
+        hasher.finish()
+    };
+    let equivalent = [
+        (
+            "select a from t where b = 1",
+            "SELECT /* x */ `A` FROM `T` WHERE (B = (1))",
+        ),
+        ("SELECT a AS x FROM t AS u", "SELECT (a) x FROM t u"),
+        (
+            "SELECT count(*) FROM t WHERE (a + b) * 2 > 0",
+            "SELECT COUNT((*)) FROM t WHERE ((a + b)) * (2) > 0",
+        ),
+        (
+            "UPDATE t SET a = a + 1 WHERE id IN (SELECT id FROM u)",
+            "UPDATE /* x */ T SET A = (a + 1) WHERE (id) IN ((SELECT (id) FROM U))",
+        ),
+    ];
+    for (sql, other) in equivalent.iter() {
+        assert_eq!(normalized(sql), normalized(other), "{} vs {}", sql, other);
+        assert_eq!(hash(&normalized(sql)), hash(&normalized(other)));
+    }
+
+    let different = [
+        ("SELECT a FROM t WHERE (a + b) * 2 > 0", "SELECT a FROM t WHERE a + b * 2 > 0"),
+        ("SELECT a FROM t WHERE NOT (a AND b)", "SELECT a FROM t WHERE NOT a AND b"),
+        ("SELECT a FROM t WHERE b = 1", "SELECT a FROM t WHERE b = 2"),
+        ("SELECT a FROM t", "SELECT /*+ NO_ICP(t) */ a FROM t"),
+    ];
+    for (sql, other) in different.iter() {
+        assert_ne!(normalized(sql), normalized(other), "{} vs {}", sql, other);
+    }
+
+    // names that need quotes keep them, and case is kept if asked to
+    let statement = mysql().verified_stmt("SELECT `Select`, `my col`, Col FROM T");
+    assert_eq!(
+        "SELECT `select`, `my col`, col FROM t",
+        statement.normalized(false).to_string()
+    );
+    assert_eq!(
+        "SELECT `Select`, `my col`, Col FROM T",
+        statement.normalized(true).to_string()
+    );
+}
+
+#[test]
+fn validate_statements() {
+    let rules = |sql: &str| -> Vec<ValidationRule> {
+        mysql()
+            .parse_sql_statements(sql)
+            .unwrap()
+            .iter()
+            .flat_map(|statement| statement.validate(&ValidateOptions::default()))
+            .map(|error| error.rule)
+            .collect()
+    };
+    for sql in [
+        "SELECT * FROM (SELECT 1) AS x",
+        "SELECT SUM(a), COUNT(*) FROM t",
+        "SELECT SUM((SELECT COUNT(*) FROM u)) FROM t",
+        "SELECT SUM(COUNT(a)) OVER () FROM t GROUP BY b",
+        "SELECT a FROM t WHERE a IN (SELECT b FROM u)",
+        "UPDATE t SET a = 1 WHERE b IN (SELECT b FROM u)",
+        "UPDATE t SET a = 1 WHERE b IN (SELECT b FROM (SELECT b FROM t) AS x)",
+        "DELETE FROM t WHERE a IN (SELECT a FROM db.u)",
+    ]
+    .iter()
+    {
+        assert_eq!(rules(sql), vec![], "{}", sql);
+    }
+
+    let cases = [
+        ("SELECT * FROM (SELECT 1)", vec![ValidationRule::DerivedTableAlias]),
+        (
+            "SELECT MAX(SUM(a)), COUNT(MIN(b)) FROM t",
+            vec![ValidationRule::NestedAggregate, ValidationRule::NestedAggregate],
+        ),
+        (
+            "SELECT a FROM t WHERE a IN (SELECT b FROM u LIMIT 1)",
+            vec![ValidationRule::LimitInSubquery],
+        ),
+        (
+            "UPDATE t SET a = (SELECT MAX(a) FROM T) WHERE b = 1",
+            vec![ValidationRule::TargetTableInSubquery],
+        ),
+        (
+            "DELETE FROM db.t WHERE EXISTS (SELECT 1 FROM t AS x WHERE x.a = 1)",
+            vec![ValidationRule::TargetTableInSubquery],
+        ),
+        (
+            "SELECT * FROM (SELECT a FROM (SELECT a FROM t)) AS x",
+            vec![ValidationRule::DerivedTableAlias],
+        ),
+    ];
+    for (sql, expected) in cases.iter() {
+        assert_eq!(rules(sql), *expected, "{}", sql);
+    }
+
+    let statement = mysql().verified_stmt("UPDATE t SET a = 1 WHERE b IN (SELECT b FROM t)");
+    assert_eq!(
+        statement.validate(&ValidateOptions::default()),
+        vec![ValidationError {
+            rule: ValidationRule::TargetTableInSubquery,
+            message: "You can't specify target table 't' for update in FROM clause".to_string(),
+            span: None,
+        }]
+    );
+    let options = ValidateOptions {
+        skip: vec![ValidationRule::TargetTableInSubquery],
+    };
+    assert_eq!(statement.validate(&options), vec![]);
+
+    // with the span of the expression at fault
+    let sql = "SELECT a FROM t WHERE SUM(COUNT(b)) > 1";
+    let located = Parser::parse_sql_located(&MySqlDialect {}, sql).unwrap();
+    let errors = located[0].validate(&ValidateOptions::default());
+    let span = errors[0].span.unwrap();
+    assert_eq!("COUNT(b)", &sql[span.start..span.end]);
+}
+
+#[test]
+fn parse_index_spellings() {
+    let sql = include_str!("queries/mysql/show_create_table.sql");
+    let statement = Statement::parse_with(&MySqlDialect {}, sql).unwrap();
+    assert_eq!(statement, mysql().verified_stmt(&statement.to_string()));
+    let index = match &statement {
+        Statement::CreateTable { index, .. } => index,
+        statement => panic!("{:?}", statement),
+    };
+    // printed as SHOW CREATE TABLE does, up to the space after commas and
+    // the versioned comment
+    let printed: Vec<String> = index.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        printed,
+        vec![
+            "PRIMARY KEY (`id`)",
+            "UNIQUE KEY `uk_email` (`email`)",
+            "UNIQUE KEY `uk_slug` (`slug`(32)) USING BTREE",
+            "KEY `fk_author` (`author_id`)",
+            "KEY `idx_author_email` (`author_id`, `email` DESC) COMMENT 'lookups'",
+            "FULLTEXT KEY `ft_body` (`body`) WITH PARSER `ngram`",
+            "SPATIAL KEY `sp_geom` (`geom`)",
+            "CONSTRAINT `fk_author` FOREIGN KEY (`author_id`) REFERENCES `authors` (`id`) ON DELETE CASCADE",
+        ]
+    );
+
+    let index_def = |sql: &str| match mysql().verified_stmt(sql) {
+        Statement::CreateTable { mut index, .. } => index.remove(0),
+        statement => panic!("{:?}", statement),
+    };
+    let mysql_index = |name: Option<&str>, index_name: Option<&str>| MysqlIndex {
+        name: name.map(Ident::new),
+        index_name: index_name.map(Ident::new),
+        index_type: None,
+        index_type_after_key_parts: false,
+        key_parts: Some(vec![IndexKeyPart::Column {
+            name: Ident::new("a"),
+            length: None,
+            asc: None,
+        }]),
+        index_option: vec![],
+    };
+    let cases = [
+        ("UNIQUE KEY uk (a)", IndexDef::Unique(mysql_index(Some("KEY"), Some("uk")))),
+        ("UNIQUE INDEX uk (a)", IndexDef::Unique(mysql_index(Some("INDEX"), Some("uk")))),
+        ("UNIQUE uk (a)", IndexDef::Unique(mysql_index(None, Some("uk")))),
+        ("UNIQUE (a)", IndexDef::Unique(mysql_index(None, None))),
+        ("KEY k (a)", IndexDef::Normal(mysql_index(Some("KEY"), Some("k")))),
+        ("INDEX (a)", IndexDef::Normal(mysql_index(Some("INDEX"), None))),
+        ("FULLTEXT KEY ft (a)", IndexDef::Normal(mysql_index(Some("KEY"), Some("ft")))),
+        ("FULLTEXT ft (a)", IndexDef::Normal(mysql_index(None, Some("ft")))),
+        ("SPATIAL INDEX sp (a)", IndexDef::Normal(mysql_index(Some("INDEX"), Some("sp")))),
+        ("SPATIAL (a)", IndexDef::Normal(mysql_index(None, None))),
+    ];
+    for (index, expected) in cases.iter() {
+        let sql = format!("CREATE TABLE t (a INT, {})", index);
+        assert_eq!(index_def(&sql).index, *expected, "{}", index);
+    }
+    let with_type = index_def("CREATE TABLE t (a INT, UNIQUE KEY uk USING HASH (a))");
+    assert_eq!(Some(Ident::new("HASH")), match with_type.index {
+        IndexDef::Unique(index) => index.index_type,
+        index => panic!("{:?}", index),
+    });
+    mysql().verified_stmt("ALTER TABLE t ADD FULLTEXT (a), ADD SPATIAL KEY sp (g), DROP PRIMARY KEY");
+}
+
+#[test]
+fn index_accessors() {
+    let sql = include_str!("queries/mysql/show_create_table.sql");
+    let index = match Statement::parse_with(&MySqlDialect {}, sql).unwrap() {
+        Statement::CreateTable { index, .. } => index,
+        statement => panic!("{:?}", statement),
+    };
+    let summary: Vec<(IndexKind, bool, Option<String>, Vec<String>)> = index
+        .iter()
+        .map(|index| {
+            (
+                index.kind(),
+                index.is_unique(),
+                index.name().map(|name| name.value.clone()),
+                index.columns().iter().map(ToString::to_string).collect(),
+            )
+        })
+        .collect();
+    let name = |name: &str| Some(name.to_string());
+    let columns = |columns: &[&str]| columns.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        summary,
+        vec![
+            (IndexKind::Primary, true, None, columns(&["`id`"])),
+            (IndexKind::Unique, true, name("uk_email"), columns(&["`email`"])),
+            (IndexKind::Unique, true, name("uk_slug"), columns(&["`slug`(32)"])),
+            (IndexKind::Normal, false, name("fk_author"), columns(&["`author_id`"])),
+            (
+                IndexKind::Normal,
+                false,
+                name("idx_author_email"),
+                columns(&["`author_id`", "`email` DESC"])
+            ),
+            (IndexKind::Fulltext, false, name("ft_body"), columns(&["`body`"])),
+            (IndexKind::Spatial, false, name("sp_geom"), columns(&["`geom`"])),
+            (IndexKind::Foreign, false, name("fk_author"), columns(&["`author_id`"])),
+        ]
+    );
+
+    // the constraint names a unique key without a name of its own
+    match mysql().verified_stmt("ALTER TABLE t ADD CONSTRAINT c UNIQUE (a)") {
+        Statement::AlterTable { operation, .. } => match operation.as_slice() {
+            [AlterTableOperation::AddIndex { index_def }] => {
+                assert_eq!(Some(&Ident::new("c")), index_def.name());
+            }
+            operation => panic!("{:?}", operation),
+        },
+        statement => panic!("{:?}", statement),
+    }
+}
+
+#[test]
+fn parse_quoted_compound_identifiers() {
+    let sql = "SELECT `my db`.`weird.table`.`col name`, `select`.* FROM `my db`.`weird.table` AS `select` \
+        WHERE `select`.`from` = 1 ORDER BY `my db`.`weird.table`.`col name`";
+    let select = mysql().verified_only_select(sql);
+    assert_eq!(
+        select.projection[0],
+        SelectItem::UnnamedExpr(Expr::CompoundIdentifier(vec![
+            Ident::with_quote('`', "my db"),
+            Ident::with_quote('`', "weird.table"),
+            Ident::with_quote('`', "col name"),
+        ]))
+    );
+    assert_eq!(
+        select.projection[1],
+        SelectItem::QualifiedWildcard(ObjectName(vec![Ident::with_quote('`', "select")]))
+    );
+    mysql().verified_stmt("SELECT `t`.* FROM `t`");
+    mysql().one_statement_parses_to("SELECT `t` . * FROM t", "SELECT `t`.* FROM t");
+
+    let sql = "UPDATE `my db`.`a b` SET `a b`.`c.d` = 1, `order` = 2 WHERE `a b`.`e f` = 3";
+    match mysql().verified_stmt(sql) {
+        Statement::Update { table_name, assignments, .. } => {
+            assert_eq!(
+                table_name,
+                ObjectName(vec![Ident::with_quote('`', "my db"), Ident::with_quote('`', "a b")])
+            );
+            assert_eq!(
+                assignments[0].id,
+                vec![Ident::with_quote('`', "a b"), Ident::with_quote('`', "c.d")]
+            );
+            assert_eq!(assignments[1].id, vec![Ident::with_quote('`', "order")]);
+        }
+        statement => panic!("{:?}", statement),
+    }
+    mysql().verified_stmt("INSERT INTO `my db`.`a b` (`c d`) VALUES (1) ON DUPLICATE KEY UPDATE `a b`.`c d` = 2");
+}
+
+#[test]
+fn parse_wildcards() {
+    let select = mysql().verified_only_select("SELECT COUNT(t.*), COUNT(DISTINCT `t`.*) FROM t");
+    assert_eq!(
+        select.projection[0],
+        SelectItem::UnnamedExpr(Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("COUNT")]),
+            args: vec![Expr::QualifiedWildcard(vec![Ident::new("t")])],
+            over: None,
+            distinct: false,
+            special: false,
+        }))
+    );
+
+    let builder = SelectBuilder::new()
+        .wildcard_except(vec![Ident::new("password")])
+        .from(ObjectName(vec![Ident::new("users")]));
+    assert_eq!(
+        Err(BuildError::WildcardExcept(vec![Ident::new("password")])),
+        builder.try_to_sql()
+    );
+    let query = builder.build();
+    assert_eq!(
+        "SELECT * /* EXCEPT password */ FROM users",
+        query.to_string()
+    );
+    assert_eq!(
+        "SELECT * /* EXCEPT password */\nFROM users",
+        format_statement(&Statement::Query(Box::new(query.clone())), &FormatOptions::default())
+    );
+    assert_eq!(
+        Ok("SELECT * FROM users".to_string()),
+        SelectBuilder::new()
+            .wildcard()
+            .from(ObjectName(vec![Ident::new("users")]))
+            .try_to_sql()
+    );
+    let columns = query.output_columns();
+    assert!(matches!(
+        &columns[..],
+        [OutputColumn::Wildcard { except, .. }] if except == &[Ident::new("password")]
+    ));
+}
+
+#[test]
+fn parse_nulls_ordering() {
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::Unsupported { feature: "NULLS FIRST/LAST".to_string() },
+            message: "NULLS FIRST and NULLS LAST are not supported, order by `<expr> IS NULL` instead"
+                .to_string(),
+            location: Some(Location { line: 1, column: 33, offset: 32 }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, "SELECT a FROM t ORDER BY a DESC NULLS LAST").unwrap_err()
+    );
+    let sql = "SELECT a FROM t ORDER BY a NULLS LAST, b DESC NULLS FIRST, c DESC NULLS LAST, d NULLS FIRST";
+    let query = match Parser::parse_sql(&GenericDialect {}, sql).unwrap().pop().unwrap() {
+        Statement::Query(query) => query,
+        statement => panic!("{:?}", statement),
+    };
+    let order_by: Vec<String> = query
+        .order_by
+        .iter()
+        .flat_map(OrderByExpr::desugar_nulls_ordering)
+        .map(|item| item.to_string())
+        .collect();
+    assert_eq!(order_by, vec!["a IS NULL", "a", "b IS NULL DESC", "b DESC", "c DESC", "d"]);
+}
+
+#[test]
+fn parse_select_distinct_keywords() {
+    let cases = [
+        ("SELECT a FROM t", None),
+        ("SELECT ALL a FROM t", Some(Distinct::All)),
+        ("SELECT DISTINCT a FROM t", Some(Distinct::Distinct)),
+        ("SELECT /*+ NO_ICP(t) */ DISTINCTROW a FROM t", Some(Distinct::DistinctRow)),
+    ];
+    for (sql, distinct) in cases.iter() {
+        let select = mysql().verified_only_select(sql);
+        assert_eq!(*distinct, select.distinct);
+        assert_eq!(distinct.is_some() && *distinct != Some(Distinct::All), select.is_distinct());
+    }
+    assert!(Parser::parse_sql(&MySqlDialect {}, "SELECT ALL DISTINCTROW a FROM t").is_err());
+    // functions only tell whether DISTINCT was written
+    mysql().one_statement_parses_to("SELECT COUNT(ALL a) FROM t", "SELECT COUNT(a) FROM t");
+}
+
+#[test]
+fn parse_top_and_fetch() {
+    let sql = "SELECT TOP 5 * FROM t LIMIT 10";
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::Unsupported { feature: "TOP".to_string() },
+            message: "TOP is not supported, use LIMIT instead".to_string(),
+            location: Some(Location { line: 1, column: 8, offset: 7 }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, sql).unwrap_err()
+    );
+    assert_eq!(
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::Unsupported { feature: "FETCH".to_string() },
+            message: "FETCH is not supported, use LIMIT instead".to_string(),
+            location: Some(Location { line: 1, column: 17, offset: 16 }),
+        }),
+        Parser::parse_sql(&MySqlDialect {}, "SELECT * FROM t FETCH FIRST 1 ROWS ONLY").unwrap_err()
+    );
+    // without a row count, `top` is a column
+    mysql().verified_stmt("SELECT top FROM t");
+
+    let statement = Parser::parse_sql(&GenericDialect {}, sql).unwrap().pop().unwrap();
+    let errors = statement.validate(&ValidateOptions::default());
+    assert_eq!(
+        errors,
+        vec![ValidationError {
+            rule: ValidationRule::ConflictingRowLimits,
+            message: "A query can't combine TOP and LIMIT".to_string(),
+            span: None,
+        }]
+    );
+    assert_eq!(
+        Some(vec!["TOP", "LIMIT"]),
+        statement.as_query().unwrap().conflicting_row_limits()
+    );
+}
+
+#[test]
+fn parse_with_db_type() {
+    let sql = "CREATE TABLE t (a INT, b INT, PRIMARY KEY (a), UNIQUE (b))";
+    let mut parsed = Parser::parse_sql_with_db_type(&MySqlDialect {}, sql).unwrap();
+    parsed.extend(Parser::parse_sql_with_db_type(&PostgreSqlDialect {}, sql).unwrap());
+    // callers tell the shapes apart by the dialect of each statement
+    let keys: Vec<(DBType, usize, usize)> = parsed
+        .iter()
+        .map(|parsed| match &parsed.statement {
+            Statement::CreateTable { index, constraints, .. } => {
+                (parsed.dialect, index.len(), constraints.len())
+            }
+            statement => panic!("{:?}", statement),
+        })
+        .collect();
+    assert_eq!(keys, vec![(DBType::MySql, 2, 0), (DBType::PostgreSql, 0, 2)]);
+
+    let tokens = Tokenizer::new(&MySqlDialect {}, sql).tokenize().unwrap();
+    assert_eq!(DBType::MySql, Parser::new(tokens, &MySqlDialect {}).dialect_type());
+
+    // every built-in dialect has a database
+    let dialects: Vec<(&dyn Dialect, DBType)> = vec![
+        (&AnsiDialect {}, DBType::Generic),
+        (&GenericDialect {}, DBType::Generic),
+        (&MsSqlDialect {}, DBType::MsSql),
+        (&MySqlDialect {}, DBType::MySql),
+        (&PostgreSqlDialect {}, DBType::PostgreSql),
+    ];
+    for (dialect, db_type) in dialects {
+        let parsed = Parser::parse_sql_with_db_type(dialect, "SELECT 1").unwrap();
+        assert_eq!(parsed[0].dialect, db_type, "{:?}", dialect);
+        let tokens = Tokenizer::new(dialect, "SELECT 1").tokenize().unwrap();
+        assert_eq!(Parser::new(tokens, dialect).dialect_type(), db_type, "{:?}", dialect);
+    }
+}
+
+#[test]
+fn parse_long_lists() {
+    let rows: Vec<String> = (0..1000)
+        .map(|i| format!("({}, CONCAT('a', 'b'), (1 + {}), NULL)", i, i))
+        .collect();
+    let sql = format!(
+        "INSERT INTO t VALUES {} ON DUPLICATE KEY UPDATE a = 1, b = 2",
+        rows.join(", ")
+    );
+    match mysql().verified_stmt(&sql) {
+        Statement::Insert { source, .. } => match &source.body {
+            SetExpr::Values(Values(values)) => {
+                assert_eq!(values.len(), 1000);
+                assert!(values.iter().all(|row| row.len() == 4));
+            }
+            body => panic!("{:?}", body),
+        },
+        statement => panic!("{:?}", statement),
+    }
+
+    let ids: Vec<String> = (0..1000).map(|i| i.to_string()).collect();
+    let sql = format!("SELECT * FROM t WHERE id IN ({}) AND b IN (1, (2), 3)", ids.join(", "));
+    let select = mysql().verified_only_select(&sql);
+    match select.selection {
+        Some(Expr::BinaryOp { left, .. }) => match *left {
+            Expr::InList { list, .. } => assert_eq!(list.len(), 1000),
+            expr => panic!("{:?}", expr),
+        },
+        expr => panic!("{:?}", expr),
+    }
+}
+
+#[test]
+fn parse_change_column_full_definition() {
+    let sql = "ALTER TABLE t CHANGE COLUMN old_name new_name VARCHAR(255) CHARACTER SET utf8mb4 \
+               COLLATE utf8mb4_bin NOT NULL DEFAULT '' COMMENT 'x' AFTER other_col";
+    let canonical = "ALTER TABLE t CHANGE COLUMN old_name new_name CHARACTER VARYING(255) \
+                     CHARACTER SET utf8mb4 COLLATE utf8mb4_bin NOT NULL DEFAULT '' COMMENT 'x' \
+                     AFTER other_col";
+    let option = |option| ColumnOptionDef { name: None, option };
+    match mysql().one_statement_parses_to(sql, canonical) {
+        Statement::AlterTable { operation, .. } => assert_eq!(
+            operation,
+            vec![AlterTableOperation::ChangeColumn {
+                old_column_name: Ident::new("old_name"),
+                new_column_def: ColumnDef {
+                    name: Ident::new("new_name"),
+                    data_type: DataType::Varchar(Some(255)),
+                    collation: None,
+                    options: vec![
+                        option(ColumnOption::Character(Expr::Identifier(Ident::new("utf8mb4")))),
+                        option(ColumnOption::Collate(Expr::Identifier(Ident::new("utf8mb4_bin")))),
+                        option(ColumnOption::NotNull),
+                        option(ColumnOption::Default(Expr::Value(single_quoted("", true)))),
+                        option(ColumnOption::Comment(Expr::Value(single_quoted("x", true)))),
+                        option(ColumnOption::After(Expr::Identifier(Ident::new("other_col")))),
+                    ],
+                    ordinal: 0,
+                },
+            }]
+        ),
+        statement => panic!("{:?}", statement),
+    }
+    mysql().verified_stmt(canonical);
+
+    // COLLATE may come first, right after the data type
+    mysql().verified_stmt(
+        "ALTER TABLE t CHANGE COLUMN a b TEXT COLLATE utf8mb4_bin CHARACTER SET utf8mb4 NOT NULL FIRST, \
+         ADD COLUMN c INT",
+    );
+    match mysql().verified_stmt("CREATE TABLE t (a TEXT COLLATE utf8mb4_bin CHARACTER SET utf8mb4)") {
+        Statement::CreateTable { columns, .. } => {
+            assert_eq!(columns[0].collation, Some(ObjectName(vec![Ident::new("utf8mb4_bin")])));
+            assert_eq!(
+                columns[0].options,
+                vec![option(ColumnOption::Character(Expr::Identifier(Ident::new("utf8mb4"))))]
+            );
+        }
+        statement => panic!("{:?}", statement),
+    }
+
+    // the position ends the definition
+    assert_eq!(
+        Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (a INT FIRST NOT NULL)").unwrap_err(),
+        ParserError::SyntaxError(SyntaxError {
+            kind: SyntaxErrorKind::UnexpectedToken {
+                expected: "',' or ')' after column definition".to_string(),
+                found: Box::new(Token::make_keyword("NOT")),
+            },
+            message: "Expected ',' or ')' after column definition, found: NOT".to_string(),
+            location: Some(Location { line: 1, column: 29, offset: 28 }),
+        })
+    );
+}
+
+#[test]
+fn parse_index_type_positions() {
+    // SHOW CREATE TABLE of MySQL 5.0 prints the index type before the key
+    // parts, 5.5 and later after them
+    let mysql_50 = "CREATE TABLE `t1` (
+  `id` int(11) NOT NULL,
+  `a` varchar(32) default NULL,
+  PRIMARY KEY  USING BTREE (`id`),
+  UNIQUE KEY `uk_a` USING HASH (`a`)
+) ENGINE=MEMORY DEFAULT CHARSET=latin1";
+    let mysql_80 = "CREATE TABLE `t1` (
+  `id` int NOT NULL,
+  `a` varchar(32) DEFAULT NULL,
+  PRIMARY KEY (`id`) USING BTREE,
+  UNIQUE KEY `uk_a` (`a`) USING HASH KEY_BLOCK_SIZE=8 COMMENT 'lookups'
+) ENGINE=MEMORY DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci";
+    let indexes = |sql: &str| match Statement::parse_with(&MySqlDialect {}, sql).unwrap() {
+        Statement::CreateTable { index, .. } => index
+            .into_iter()
+            .map(|index| match index.index {
+                IndexDef::PrimaryKey(index) | IndexDef::Unique(index) => index,
+                index => panic!("{:?}", index),
+            })
+            .collect::<Vec<_>>(),
+        statement => panic!("{:?}", statement),
+    };
+    let summary = |indexes: &[MysqlIndex]| {
+        indexes
+            .iter()
+            .map(|index| {
+                (
+                    index.index_type.as_ref().map(|t| t.value.clone()),
+                    index.index_type_after_key_parts,
+                    index.index_option.len(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let before = indexes(mysql_50);
+    assert_eq!(
+        summary(&before),
+        vec![(Some("BTREE".to_string()), false, 0), (Some("HASH".to_string()), false, 0)]
+    );
+    let after = indexes(mysql_80);
+    assert_eq!(
+        summary(&after),
+        vec![(Some("BTREE".to_string()), true, 0), (Some("HASH".to_string()), true, 2)]
+    );
+    assert_eq!(
+        after[1].index_option,
+        vec![
+            IndexOptions::KeyBlockSize(Expr::Value(number("8"))),
+            IndexOptions::Comment(Expr::Value(single_quoted("lookups", true))),
+        ]
+    );
+
+    // each is printed where it was written
+    let printed = |indexes: &[MysqlIndex]| {
+        indexes.iter().map(ToString::to_string).collect::<Vec<_>>()
+    };
+    assert_eq!(printed(&before), vec!["USING BTREE (`id`)", "KEY `uk_a` USING HASH (`a`)"]);
+    assert_eq!(
+        printed(&after),
+        vec!["(`id`) USING BTREE", "KEY `uk_a` (`a`) USING HASH KEY_BLOCK_SIZE=8 COMMENT 'lookups'"]
+    );
+    mysql().verified_stmt(
+        "ALTER TABLE t ADD PRIMARY KEY USING BTREE (id) COMMENT 'x', \
+         ADD UNIQUE KEY uk (a) USING HASH KEY_BLOCK_SIZE=8",
+    );
+    // later among the options, it moves up to the key parts
+    mysql().one_statement_parses_to(
+        "ALTER TABLE t ADD KEY k (b) COMMENT 'y' USING BTREE",
+        "ALTER TABLE t ADD KEY k (b) USING BTREE COMMENT 'y'",
+    );
+}
+
+#[test]
+fn parse_error_kinds() {
+    let kind = |sql: &str| Parser::parse_sql(&MySqlDialect {}, sql).unwrap_err().kind().cloned();
+    assert_eq!(
+        kind("SELEC 1"),
+        Some(SyntaxErrorKind::UnexpectedToken {
+            expected: "an SQL statement".to_string(),
+            found: Box::new(Token::make_word("SELEC", None)),
+        })
+    );
+    assert_eq!(
+        kind("SELECT a FROM"),
+        Some(SyntaxErrorKind::UnexpectedEof { expected: "identifier".to_string() })
+    );
+    assert_eq!(
+        kind("SELECT a FROM t ORDER BY a NULLS FIRST"),
+        Some(SyntaxErrorKind::Unsupported { feature: "NULLS FIRST/LAST".to_string() })
+    );
+    assert_eq!(
+        kind("CREATE TABLE t (a VARCHAR(99999999999999999999))"),
+        Some(SyntaxErrorKind::NumberParse { text: "99999999999999999999".to_string() })
+    );
+    assert_eq!(kind("DROP TABLE t CASCADE RESTRICT"), Some(SyntaxErrorKind::Other));
+    // an unterminated string is found by the tokenizer
+    assert_eq!(kind("SELECT 'a"), None);
+    // nesting too deep for the stack fails rather than overflowing it
+    assert_eq!(
+        kind(&format!("SELECT {}", "(".repeat(50_000))),
+        Some(SyntaxErrorKind::RecursionLimit)
+    );
+
+    // the message is unchanged
+    let err = Parser::parse_sql(&MySqlDialect {}, "CREATE TABLE t (a VARCHAR(99999999999999999999))")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "sql parser error: Could not parse '99999999999999999999' as u64: number too large to fit in target type"
+    );
+}
+
+#[test]
+fn parse_max_depth() {
+    let nested = |depth: usize| format!("SELECT {}1{}", "(".repeat(depth), ")".repeat(depth));
+    let options = ParserOptions {
+        max_depth: 10,
+        ..Default::default()
+    };
+    assert!(Parser::parse_sql_with_options(&MySqlDialect {}, &nested(5), &options).is_ok());
+    let err = Parser::parse_sql_with_options(&MySqlDialect {}, &nested(10), &options).unwrap_err();
+    assert_eq!(err.kind(), Some(&SyntaxErrorKind::RecursionLimit));
+    assert_eq!(err.to_string(), "sql parser error: Nested deeper than 10 levels");
+
+    // chained operators count a tenth of a level each
+    let chain = |terms: usize| {
+        let terms: Vec<String> = (0..terms).map(|i| format!("a = {}", i)).collect();
+        format!("SELECT * FROM t WHERE {}", terms.join(" OR "))
+    };
+    assert!(Parser::parse_sql(&MySqlDialect {}, &chain(300)).is_ok());
+    let err = Parser::parse_sql(&MySqlDialect {}, &chain(100_000)).unwrap_err();
+    assert_eq!(err.kind(), Some(&SyntaxErrorKind::RecursionLimit));
 }
 
 fn mysql() -> TestedDialects {
@@ -59,6 +59,7 @@ fn parse_create_table_with_defaults() {
                                 pg().verified_expr("nextval(public.customer_customer_id_seq)")
                             )
                         }],
+                        ordinal: 1,
                     },
                     ColumnDef {
                         name: "store_id".into(),
@@ -68,6 +69,7 @@ fn parse_create_table_with_defaults() {
                             name: None,
                             option: ColumnOption::NotNull,
                         }],
+                        ordinal: 2,
                     },
                     ColumnDef {
                         name: "first_name".into(),
@@ -77,6 +79,7 @@ fn parse_create_table_with_defaults() {
                             name: None,
                             option: ColumnOption::NotNull,
                         }],
+                        ordinal: 3,
                     },
                     ColumnDef {
                         name: "last_name".into(),
@@ -86,12 +89,14 @@ fn parse_create_table_with_defaults() {
                             name: None,
                             option: ColumnOption::NotNull,
                         }],
+                        ordinal: 4,
                     },
                     ColumnDef {
                         name: "email".into(),
                         data_type: DataType::Varchar(Some(50)),
                         collation: None,
                         options: vec![],
+                        ordinal: 5,
                     },
                     ColumnDef {
                         name: "address_id".into(),
@@ -101,6 +106,7 @@ fn parse_create_table_with_defaults() {
                             name: None,
                             option: ColumnOption::NotNull
                         }],
+                        ordinal: 6,
                     },
                     ColumnDef {
                         name: "activebool".into(),
@@ -116,6 +122,7 @@ fn parse_create_table_with_defaults() {
                                 option: ColumnOption::NotNull,
                             }
                         ],
+                        ordinal: 7,
                     },
                     ColumnDef {
                         name: "create_date".into(),
@@ -133,10 +140,11 @@ fn parse_create_table_with_defaults() {
                                 option: ColumnOption::NotNull,
                             }
                         ],
+                        ordinal: 8,
                     },
                     ColumnDef {
                         name: "last_update".into(),
-                        data_type: DataType::Timestamp,
+                        data_type: DataType::Timestamp(None),
                         collation: None,
                         options: vec![
                             ColumnOptionDef {
@@ -148,6 +156,7 @@ fn parse_create_table_with_defaults() {
                                 option: ColumnOption::NotNull,
                             }
                         ],
+                        ordinal: 9,
                     },
                     ColumnDef {
                         name: "active".into(),
@@ -157,6 +166,7 @@ fn parse_create_table_with_defaults() {
                             name: None,
                             option: ColumnOption::NotNull
                         }],
+                        ordinal: 10,
                     },
                 ]
             );
@@ -277,26 +287,26 @@ fn parse_create_table_if_not_exists() {
 fn parse_bad_if_not_exists() {
     let res = pg().parse_sql_statements("CREATE TABLE NOT EXISTS uk_cities ()");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: EXISTS".to_string()),
-        res.unwrap_err()
+        "Expected table option, found: EXISTS".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = pg().parse_sql_statements("CREATE TABLE IF EXISTS uk_cities ()");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: EXISTS".to_string()),
-        res.unwrap_err()
+        "Expected table option, found: EXISTS".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = pg().parse_sql_statements("CREATE TABLE IF uk_cities ()");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: uk_cities".to_string()),
-        res.unwrap_err()
+        "Expected table option, found: uk_cities".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 
     let res = pg().parse_sql_statements("CREATE TABLE IF NOT uk_cities ()");
     assert_eq!(
-        ParserError::ParserError("Expected end of statement, found: NOT".to_string()),
-        res.unwrap_err()
+        "Expected table option, found: NOT".to_string(),
+        syntax_error_message(res.unwrap_err())
     );
 }
 
@@ -327,6 +337,35 @@ PHP	₱ USD $
     //assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_copy_data() {
+    // the data isn't SQL, and ends at `\.`
+    let sql = "COPY t (a, b) FROM stdin; \n1\t₱ 'x\t\\N\t{\"y\"}\n\\.";
+    match pg_and_generic().verified_stmt(sql) {
+        Statement::Copy { values, .. } => assert_eq!(
+            vec![
+                Some("1".to_string()),
+                Some("₱ 'x".to_string()),
+                None,
+                Some("{\"y\"}".to_string())
+            ],
+            values
+        ),
+        stmt => panic!("{:?}", stmt),
+    }
+    let statements = pg_and_generic()
+        .parse_sql_statements("COPY t FROM stdin;\n1;2\n\\.\nSELECT 1")
+        .unwrap();
+    assert_eq!(2, statements.len());
+    assert_eq!("SELECT 1", statements[1].to_string());
+
+    // outside the data, an unknown character is still an error
+    assert_eq!(
+        ParserError::TokenizerError("Unexpected character '₱' at Line: 1, Column 8".to_string()),
+        pg_and_generic().parse_sql_statements("SELECT ₱").unwrap_err()
+    );
+}
+
 #[test]
 fn parse_set() {
     let stmt = pg_and_generic().verified_stmt("SET a = b");
@@ -345,7 +384,7 @@ fn parse_set() {
         Statement::SetVariable {
             local: false,
             variable: "a".into(),
-            value: SetVariableValue::Literal(Value::SingleQuotedString("b".into())),
+            value: SetVariableValue::Literal(single_quoted("b", false)),
         }
     );
 
@@ -383,24 +422,18 @@ fn parse_set() {
     pg_and_generic().one_statement_parses_to("SET SESSION a = b", "SET a = b");
 
     assert_eq!(
-        pg_and_generic().parse_sql_statements("SET"),
-        Err(ParserError::ParserError(
-            "Expected identifier, found: EOF".to_string()
-        )),
+        "Expected identifier, found: EOF".to_string(),
+        syntax_error_message(pg_and_generic().parse_sql_statements("SET").unwrap_err())
     );
 
     assert_eq!(
-        pg_and_generic().parse_sql_statements("SET a b"),
-        Err(ParserError::ParserError(
-            "Expected equals sign or TO, found: b".to_string()
-        )),
+        "Expected equals sign or TO, found: b".to_string(),
+        syntax_error_message(pg_and_generic().parse_sql_statements("SET a b").unwrap_err())
     );
 
     assert_eq!(
-        pg_and_generic().parse_sql_statements("SET a ="),
-        Err(ParserError::ParserError(
-            "Expected variable value, found: EOF".to_string()
-        )),
+        "Expected variable value, found: EOF".to_string(),
+        syntax_error_message(pg_and_generic().parse_sql_statements("SET a =").unwrap_err())
     );
 }
 
@@ -410,7 +443,9 @@ fn parse_show() {
     assert_eq!(
         stmt,
         Statement::ShowVariable {
-            variable: "a".into()
+            variable: "a".into(),
+            global: false,
+            filter: None,
         }
     );
 
@@ -418,7 +453,9 @@ fn parse_show() {
     assert_eq!(
         stmt,
         Statement::ShowVariable {
-            variable: "ALL".into()
+            variable: "ALL".into(),
+            global: false,
+            filter: None,
         }
     )
 }